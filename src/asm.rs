@@ -0,0 +1,463 @@
+use crate::csr::CSR_METADATA;
+use crate::disasm::ABI_NAMES;
+use crate::instructions::Instruction;
+use crate::instructions::Instruction::*;
+use crate::register::Register;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+use std::ops::RangeInclusive;
+
+// Opcodes of the base integer ISA and the M extension.
+const OP_LUI: u32 = 0b0110111;
+const OP_AUIPC: u32 = 0b0010111;
+const OP_JAL: u32 = 0b1101111;
+const OP_JALR: u32 = 0b1100111;
+const OP_BRANCH: u32 = 0b1100011;
+const OP_LOAD: u32 = 0b0000011;
+const OP_STORE: u32 = 0b0100011;
+const OP_IMM: u32 = 0b0010011;
+const OP: u32 = 0b0110011;
+const OP_MISC_MEM: u32 = 0b0001111;
+const OP_SYSTEM: u32 = 0b1110011;
+const OP_IMM_32: u32 = 0b0011011;
+const OP_32: u32 = 0b0111011;
+
+// `func7` of `sub`, `sra` and `srai` and of the M extension.
+const FUNC7_ALT: u32 = 0b0100000;
+const FUNC7_MULDIV: u32 = 0b0000001;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    UnknownMnemonic(String),
+    InvalidRegister(String),
+    InvalidImmediate(String),
+    ImmediateOutOfRange(i64),
+    /// The mnemonic takes the first count of operands but got the second.
+    OperandCount(usize, usize),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for ParseError {}
+
+impl Instruction {
+    /// Assemble one line of RV64I, Zicsr or M assembly, as printed by
+    /// `disasm::disassemble`, into the instruction at `pc`. Registers may
+    /// be given by ABI name or as `x0`-`x31`. Branch and `jal` targets are
+    /// absolute addresses, and loads, stores and `jalr` take `imm(rs1)`.
+    pub fn from_assembly(asm: &str, pc: u64) -> std::result::Result<Instruction, ParseError> {
+        let asm = asm.trim();
+        let (mnemonic, rest) = asm.split_once(char::is_whitespace).unwrap_or((asm, ""));
+        let operands: Vec<&str> = match rest.trim() {
+            "" => vec![],
+            rest => rest.split(',').map(str::trim).collect(),
+        };
+        let ops = Operands { ops: operands, pc };
+
+        let instruction = match mnemonic {
+            "lui" | "auipc" => {
+                ops.count(2)?;
+                let (rd, imm) = (ops.reg(0)?, ops.imm(1, 0..=0xfffff)? << 12);
+                if mnemonic == "lui" { Lui { rd, imm } } else { Auipc { rd, imm } }
+            },
+            "jal" => match ops.ops.len() {
+                1 => Jal { rd: Register::X1, imm: ops.target(0, 21)? },
+                _ => {
+                    ops.count(2)?;
+                    Jal { rd: ops.reg(0)?, imm: ops.target(1, 21)? }
+                },
+            },
+            "jalr" => match ops.ops.len() {
+                1 => Jalr { rd: Register::X1, rs1: ops.reg(0)?, imm: 0 },
+                _ => {
+                    ops.count(2)?;
+                    let (imm, rs1) = ops.mem(1)?;
+                    Jalr { rd: ops.reg(0)?, rs1, imm }
+                },
+            },
+            "beq" | "bne" | "blt" | "bge" | "bltu" | "bgeu" => {
+                ops.count(3)?;
+                let (rd, rs1, rs2, imm) = (Register::X0, ops.reg(0)?, ops.reg(1)?, ops.target(2, 13)?);
+                match mnemonic {
+                    "beq" => Beq { rd, rs1, rs2, imm, func3: 0b000 },
+                    "bne" => Bne { rd, rs1, rs2, imm, func3: 0b001 },
+                    "blt" => Blt { rd, rs1, rs2, imm, func3: 0b100 },
+                    "bge" => Bge { rd, rs1, rs2, imm, func3: 0b101 },
+                    "bltu" => Bltu { rd, rs1, rs2, imm, func3: 0b110 },
+                    _ => Bgeu { rd, rs1, rs2, imm, func3: 0b111 },
+                }
+            },
+            "lb" | "lh" | "lw" | "ld" | "lbu" | "lhu" | "lwu" => {
+                ops.count(2)?;
+                let rd = ops.reg(0)?;
+                let (imm, rs1) = ops.mem(1)?;
+                match mnemonic {
+                    "lb" => Lb { rd, rs1, imm, func3: 0b000 },
+                    "lh" => Lh { rd, rs1, imm, func3: 0b001 },
+                    "lw" => Lw { rd, rs1, imm, func3: 0b010 },
+                    "ld" => Ld { rd, rs1, imm, func3: 0b011 },
+                    "lbu" => Lbu { rd, rs1, imm, func3: 0b100 },
+                    "lhu" => Lhu { rd, rs1, imm, func3: 0b101 },
+                    _ => Lwu { rd, rs1, imm, func3: 0b110 },
+                }
+            },
+            "sb" | "sh" | "sw" | "sd" => {
+                ops.count(2)?;
+                let rs2 = ops.reg(0)?;
+                let (imm, rs1) = ops.mem(1)?;
+                match mnemonic {
+                    "sb" => Sb { rs1, rs2, imm, func3: 0b000 },
+                    "sh" => Sh { rs1, rs2, imm, func3: 0b001 },
+                    "sw" => Sw { rs1, rs2, imm, func3: 0b010 },
+                    _ => Sd { rs1, rs2, imm, func3: 0b011 },
+                }
+            },
+            "addi" | "slti" | "sltiu" | "xori" | "ori" | "andi" | "addiw" => {
+                ops.count(3)?;
+                let (rd, rs1, imm) = (ops.reg(0)?, ops.reg(1)?, ops.imm(2, -2048..=2047)?);
+                match mnemonic {
+                    "addi" => Addi { rd, rs1, imm, func3: 0b000 },
+                    "slti" => Slti { rd, rs1, imm, func3: 0b010 },
+                    "sltiu" => Sltiu { rd, rs1, imm, func3: 0b011 },
+                    "xori" => Xori { rd, rs1, imm, func3: 0b100 },
+                    "ori" => Ori { rd, rs1, imm, func3: 0b110 },
+                    "andi" => Andi { rd, rs1, imm, func3: 0b111 },
+                    _ => Addiw { rd, rs1, imm, func3: 0b000 },
+                }
+            },
+            "slli" | "srli" | "srai" | "slliw" | "srliw" | "sraiw" => {
+                ops.count(3)?;
+                let max = if mnemonic.ends_with('w') { 31 } else { 63 };
+                let (rd, rs1, shamt) = (ops.reg(0)?, ops.reg(1)?, ops.imm(2, 0..=max)? as u32);
+                match mnemonic {
+                    "slli" => Slli { rd, rs1, shamt, func3: 0b001, func7: 0 },
+                    "srli" => Srli { rd, rs1, shamt, func3: 0b101, func7: 0 },
+                    "srai" => Srai { rd, rs1, shamt, func3: 0b101, func7: FUNC7_ALT },
+                    "slliw" => Slliw { rd, rs1, shamt, func3: 0b001, func7: 0 },
+                    "srliw" => Srliw { rd, rs1, shamt, func3: 0b101, func7: 0 },
+                    _ => Sraiw { rd, rs1, shamt, func3: 0b101, func7: FUNC7_ALT },
+                }
+            },
+            "add" | "sub" | "sll" | "slt" | "sltu" | "xor" | "srl" | "sra" | "or" | "and" | "addw" | "subw" |
+            "sllw" | "srlw" | "sraw" | "mul" | "mulh" | "mulhsu" | "mulhu" | "div" | "divu" | "rem" | "remu" |
+            "mulw" | "divw" | "divuw" | "remw" | "remuw" => {
+                ops.count(3)?;
+                let (rd, rs1, rs2) = (ops.reg(0)?, ops.reg(1)?, ops.reg(2)?);
+                match mnemonic {
+                    "add" => Add { rd, rs1, rs2, func3: 0b000, func7: 0 },
+                    "sub" => Sub { rd, rs1, rs2, func3: 0b000, func7: FUNC7_ALT },
+                    "sll" => Sll { rd, rs1, rs2, func3: 0b001, func7: 0 },
+                    "slt" => Slt { rd, rs1, rs2, func3: 0b010, func7: 0 },
+                    "sltu" => Sltu { rd, rs1, rs2, func3: 0b011, func7: 0 },
+                    "xor" => Xor { rd, rs1, rs2, func3: 0b100, func7: 0 },
+                    "srl" => Srl { rd, rs1, rs2, func3: 0b101, func7: 0 },
+                    "sra" => Sra { rd, rs1, rs2, func3: 0b101, func7: FUNC7_ALT },
+                    "or" => Or { rd, rs1, rs2, func3: 0b110, func7: 0 },
+                    "and" => And { rd, rs1, rs2, func3: 0b111, func7: 0 },
+                    "addw" => Addw { rd, rs1, rs2, func3: 0b000, func7: 0 },
+                    "subw" => Subw { rd, rs1, rs2, func3: 0b000, func7: FUNC7_ALT },
+                    "sllw" => Sllw { rd, rs1, rs2, func3: 0b001, func7: 0 },
+                    "srlw" => Srlw { rd, rs1, rs2, func3: 0b101, func7: 0 },
+                    "sraw" => Sraw { rd, rs1, rs2, func3: 0b101, func7: FUNC7_ALT },
+                    "mul" => Mul { rd, rs1, rs2, func3: 0b000, func7: FUNC7_MULDIV },
+                    "mulh" => Mulh { rd, rs1, rs2, func3: 0b001, func7: FUNC7_MULDIV },
+                    "mulhsu" => Mulhsu { rd, rs1, rs2, func3: 0b010, func7: FUNC7_MULDIV },
+                    "mulhu" => Mulhu { rd, rs1, rs2, func3: 0b011, func7: FUNC7_MULDIV },
+                    "div" => Div { rd, rs1, rs2, func3: 0b100, func7: FUNC7_MULDIV },
+                    "divu" => Divu { rd, rs1, rs2, func3: 0b101, func7: FUNC7_MULDIV },
+                    "rem" => Rem { rd, rs1, rs2, func3: 0b110, func7: FUNC7_MULDIV },
+                    "remu" => Remu { rd, rs1, rs2, func3: 0b111, func7: FUNC7_MULDIV },
+                    "mulw" => Mulw { rd, rs1, rs2, func3: 0b000, func7: FUNC7_MULDIV },
+                    "divw" => Divw { rd, rs1, rs2, func3: 0b100, func7: FUNC7_MULDIV },
+                    "divuw" => Divuw { rd, rs1, rs2, func3: 0b101, func7: FUNC7_MULDIV },
+                    "remw" => Remw { rd, rs1, rs2, func3: 0b110, func7: FUNC7_MULDIV },
+                    _ => RemuW { rd, rs1, rs2, func3: 0b111, func7: FUNC7_MULDIV },
+                }
+            },
+            "fence" | "fence.tso" => {
+                let (fm, pred, succ) = match ops.ops.len() {
+                    0 if mnemonic == "fence" => (0, 0b1111, 0b1111),
+                    0 => (0b1000, 0b0011, 0b0011),
+                    _ => {
+                        ops.count(2)?;
+                        (0, ops.fence_set(0)?, ops.fence_set(1)?)
+                    },
+                };
+                Fence { rd: Register::X0, rs1: Register::X0, fm, pred, succ, func3: 0b000 }
+            },
+            "fence.i" => {
+                ops.count(0)?;
+                FenceI { rd: Register::X0, rs1: Register::X0, imm: 0, func3: 0b001 }
+            },
+            "sfence.vma" => {
+                let (rs1, rs2) = match ops.ops.len() {
+                    0 => (Register::X0, Register::X0),
+                    1 => (ops.reg(0)?, Register::X0),
+                    _ => {
+                        ops.count(2)?;
+                        (ops.reg(0)?, ops.reg(1)?)
+                    },
+                };
+                SfenceVma { rs1, rs2 }
+            },
+            "ecall" | "ebreak" | "mret" | "sret" | "wfi" => {
+                ops.count(0)?;
+                match mnemonic {
+                    "ecall" => ECall,
+                    "ebreak" => EBreak,
+                    "mret" => Mret,
+                    "sret" => Sret,
+                    _ => Wfi,
+                }
+            },
+            "csrrw" | "csrrs" | "csrrc" => {
+                ops.count(3)?;
+                let (rd, csr, rs1) = (ops.reg(0)?, ops.csr(1)?, ops.reg(2)?);
+                match mnemonic {
+                    "csrrw" => Csrrw { rd, rs1, csr, func3: 0b001 },
+                    "csrrs" => Csrrs { rd, rs1, csr, func3: 0b010 },
+                    _ => Csrrc { rd, rs1, csr, func3: 0b011 },
+                }
+            },
+            "csrrwi" | "csrrsi" | "csrrci" => {
+                ops.count(3)?;
+                let (rd, csr, uimm) = (ops.reg(0)?, ops.csr(1)?, ops.imm(2, 0..=31)? as u32);
+                match mnemonic {
+                    "csrrwi" => Csrrwi { rd, uimm, csr, func3: 0b101 },
+                    "csrrsi" => Csrrsi { rd, uimm, csr, func3: 0b110 },
+                    _ => Csrrci { rd, uimm, csr, func3: 0b111 },
+                }
+            },
+            _ => return Err(ParseError::UnknownMnemonic(mnemonic.to_string())),
+        };
+
+        Ok(instruction)
+    }
+
+    /// The standard 32 bit encoding of an instruction `from_assembly`
+    /// accepts, or `None` for the other extensions and `Undefined`.
+    /// Immediates are truncated to their field, and the `func3` and
+    /// `func7` fields are ignored in favour of those of the variant.
+    pub fn encode(&self) -> Option<u32> {
+        let inst = match *self {
+            Lui { rd, imm } => u_type(OP_LUI, rd, imm),
+            Auipc { rd, imm } => u_type(OP_AUIPC, rd, imm),
+            Jal { rd, imm } => j_type(rd, imm),
+            Jalr { rd, rs1, imm } => i_type(OP_JALR, 0b000, rd, rs1, imm),
+            Beq { rs1, rs2, imm, .. } => b_type(0b000, rs1, rs2, imm),
+            Bne { rs1, rs2, imm, .. } => b_type(0b001, rs1, rs2, imm),
+            Blt { rs1, rs2, imm, .. } => b_type(0b100, rs1, rs2, imm),
+            Bge { rs1, rs2, imm, .. } => b_type(0b101, rs1, rs2, imm),
+            Bltu { rs1, rs2, imm, .. } => b_type(0b110, rs1, rs2, imm),
+            Bgeu { rs1, rs2, imm, .. } => b_type(0b111, rs1, rs2, imm),
+            Lb { rd, rs1, imm, .. } => i_type(OP_LOAD, 0b000, rd, rs1, imm),
+            Lh { rd, rs1, imm, .. } => i_type(OP_LOAD, 0b001, rd, rs1, imm),
+            Lw { rd, rs1, imm, .. } => i_type(OP_LOAD, 0b010, rd, rs1, imm),
+            Ld { rd, rs1, imm, .. } => i_type(OP_LOAD, 0b011, rd, rs1, imm),
+            Lbu { rd, rs1, imm, .. } => i_type(OP_LOAD, 0b100, rd, rs1, imm),
+            Lhu { rd, rs1, imm, .. } => i_type(OP_LOAD, 0b101, rd, rs1, imm),
+            Lwu { rd, rs1, imm, .. } => i_type(OP_LOAD, 0b110, rd, rs1, imm),
+            Sb { rs1, rs2, imm, .. } => s_type(0b000, rs1, rs2, imm),
+            Sh { rs1, rs2, imm, .. } => s_type(0b001, rs1, rs2, imm),
+            Sw { rs1, rs2, imm, .. } => s_type(0b010, rs1, rs2, imm),
+            Sd { rs1, rs2, imm, .. } => s_type(0b011, rs1, rs2, imm),
+            Addi { rd, rs1, imm, .. } => i_type(OP_IMM, 0b000, rd, rs1, imm),
+            Slti { rd, rs1, imm, .. } => i_type(OP_IMM, 0b010, rd, rs1, imm),
+            Sltiu { rd, rs1, imm, .. } => i_type(OP_IMM, 0b011, rd, rs1, imm),
+            Xori { rd, rs1, imm, .. } => i_type(OP_IMM, 0b100, rd, rs1, imm),
+            Ori { rd, rs1, imm, .. } => i_type(OP_IMM, 0b110, rd, rs1, imm),
+            Andi { rd, rs1, imm, .. } => i_type(OP_IMM, 0b111, rd, rs1, imm),
+            Addiw { rd, rs1, imm, .. } => i_type(OP_IMM_32, 0b000, rd, rs1, imm),
+            Slli { rd, rs1, shamt, .. } => shift(OP_IMM, 0b001, 0, rd, rs1, shamt & 0b111111),
+            Srli { rd, rs1, shamt, .. } => shift(OP_IMM, 0b101, 0, rd, rs1, shamt & 0b111111),
+            Srai { rd, rs1, shamt, .. } => shift(OP_IMM, 0b101, FUNC7_ALT, rd, rs1, shamt & 0b111111),
+            Slliw { rd, rs1, shamt, .. } => shift(OP_IMM_32, 0b001, 0, rd, rs1, shamt & 0b11111),
+            Srliw { rd, rs1, shamt, .. } => shift(OP_IMM_32, 0b101, 0, rd, rs1, shamt & 0b11111),
+            Sraiw { rd, rs1, shamt, .. } => shift(OP_IMM_32, 0b101, FUNC7_ALT, rd, rs1, shamt & 0b11111),
+            Add { rd, rs1, rs2, .. } => r_type(OP, 0b000, 0, rd, rs1, rs2),
+            Sub { rd, rs1, rs2, .. } => r_type(OP, 0b000, FUNC7_ALT, rd, rs1, rs2),
+            Sll { rd, rs1, rs2, .. } => r_type(OP, 0b001, 0, rd, rs1, rs2),
+            Slt { rd, rs1, rs2, .. } => r_type(OP, 0b010, 0, rd, rs1, rs2),
+            Sltu { rd, rs1, rs2, .. } => r_type(OP, 0b011, 0, rd, rs1, rs2),
+            Xor { rd, rs1, rs2, .. } => r_type(OP, 0b100, 0, rd, rs1, rs2),
+            Srl { rd, rs1, rs2, .. } => r_type(OP, 0b101, 0, rd, rs1, rs2),
+            Sra { rd, rs1, rs2, .. } => r_type(OP, 0b101, FUNC7_ALT, rd, rs1, rs2),
+            Or { rd, rs1, rs2, .. } => r_type(OP, 0b110, 0, rd, rs1, rs2),
+            And { rd, rs1, rs2, .. } => r_type(OP, 0b111, 0, rd, rs1, rs2),
+            Addw { rd, rs1, rs2, .. } => r_type(OP_32, 0b000, 0, rd, rs1, rs2),
+            Subw { rd, rs1, rs2, .. } => r_type(OP_32, 0b000, FUNC7_ALT, rd, rs1, rs2),
+            Sllw { rd, rs1, rs2, .. } => r_type(OP_32, 0b001, 0, rd, rs1, rs2),
+            Srlw { rd, rs1, rs2, .. } => r_type(OP_32, 0b101, 0, rd, rs1, rs2),
+            Sraw { rd, rs1, rs2, .. } => r_type(OP_32, 0b101, FUNC7_ALT, rd, rs1, rs2),
+            Mul { rd, rs1, rs2, .. } => r_type(OP, 0b000, FUNC7_MULDIV, rd, rs1, rs2),
+            Mulh { rd, rs1, rs2, .. } => r_type(OP, 0b001, FUNC7_MULDIV, rd, rs1, rs2),
+            Mulhsu { rd, rs1, rs2, .. } => r_type(OP, 0b010, FUNC7_MULDIV, rd, rs1, rs2),
+            Mulhu { rd, rs1, rs2, .. } => r_type(OP, 0b011, FUNC7_MULDIV, rd, rs1, rs2),
+            Div { rd, rs1, rs2, .. } => r_type(OP, 0b100, FUNC7_MULDIV, rd, rs1, rs2),
+            Divu { rd, rs1, rs2, .. } => r_type(OP, 0b101, FUNC7_MULDIV, rd, rs1, rs2),
+            Rem { rd, rs1, rs2, .. } => r_type(OP, 0b110, FUNC7_MULDIV, rd, rs1, rs2),
+            Remu { rd, rs1, rs2, .. } => r_type(OP, 0b111, FUNC7_MULDIV, rd, rs1, rs2),
+            Mulw { rd, rs1, rs2, .. } => r_type(OP_32, 0b000, FUNC7_MULDIV, rd, rs1, rs2),
+            Divw { rd, rs1, rs2, .. } => r_type(OP_32, 0b100, FUNC7_MULDIV, rd, rs1, rs2),
+            Divuw { rd, rs1, rs2, .. } => r_type(OP_32, 0b101, FUNC7_MULDIV, rd, rs1, rs2),
+            Remw { rd, rs1, rs2, .. } => r_type(OP_32, 0b110, FUNC7_MULDIV, rd, rs1, rs2),
+            RemuW { rd, rs1, rs2, .. } => r_type(OP_32, 0b111, FUNC7_MULDIV, rd, rs1, rs2),
+            Fence { rd, rs1, fm, pred, succ, .. } => {
+                let imm = ((fm & 0b1111) << 8 | (pred & 0b1111) << 4 | (succ & 0b1111)) as i32;
+                i_type(OP_MISC_MEM, 0b000, rd, rs1, imm)
+            },
+            FenceI { rd, rs1, imm, .. } => i_type(OP_MISC_MEM, 0b001, rd, rs1, imm),
+            ECall => OP_SYSTEM,
+            EBreak => 1 << 20 | OP_SYSTEM,
+            Mret => 0x302 << 20 | OP_SYSTEM,
+            Sret => 0x102 << 20 | OP_SYSTEM,
+            Wfi => 0x105 << 20 | OP_SYSTEM,
+            SfenceVma { rs1, rs2 } => r_type(OP_SYSTEM, 0b000, 0b0001001, Register::X0, rs1, rs2),
+            Csrrw { rd, rs1, csr, .. } => i_type(OP_SYSTEM, 0b001, rd, rs1, csr),
+            Csrrs { rd, rs1, csr, .. } => i_type(OP_SYSTEM, 0b010, rd, rs1, csr),
+            Csrrc { rd, rs1, csr, .. } => i_type(OP_SYSTEM, 0b011, rd, rs1, csr),
+            Csrrwi { rd, uimm, csr, .. } => i_type(OP_SYSTEM, 0b101, rd, (uimm as usize & 0b11111).into(), csr),
+            Csrrsi { rd, uimm, csr, .. } => i_type(OP_SYSTEM, 0b110, rd, (uimm as usize & 0b11111).into(), csr),
+            Csrrci { rd, uimm, csr, .. } => i_type(OP_SYSTEM, 0b111, rd, (uimm as usize & 0b11111).into(), csr),
+            _ => return None,
+        };
+
+        Some(inst)
+    }
+}
+
+fn reg(reg: Register) -> u32 {
+    reg as u32
+}
+
+fn r_type(opcode: u32, func3: u32, func7: u32, rd: Register, rs1: Register, rs2: Register) -> u32 {
+    func7 << 25 | reg(rs2) << 20 | reg(rs1) << 15 | func3 << 12 | reg(rd) << 7 | opcode
+}
+
+fn i_type(opcode: u32, func3: u32, rd: Register, rs1: Register, imm: i32) -> u32 {
+    (imm as u32 & 0xfff) << 20 | reg(rs1) << 15 | func3 << 12 | reg(rd) << 7 | opcode
+}
+
+// An immediate shift, whose `func7` loses its low bit to bit 5 of `shamt`
+// on RV64.
+fn shift(opcode: u32, func3: u32, func7: u32, rd: Register, rs1: Register, shamt: u32) -> u32 {
+    func7 << 25 | shamt << 20 | reg(rs1) << 15 | func3 << 12 | reg(rd) << 7 | opcode
+}
+
+fn s_type(func3: u32, rs1: Register, rs2: Register, imm: i32) -> u32 {
+    let imm = imm as u32;
+    (imm >> 5 & 0x7f) << 25 | reg(rs2) << 20 | reg(rs1) << 15 | func3 << 12 | (imm & 0x1f) << 7 | OP_STORE
+}
+
+fn b_type(func3: u32, rs1: Register, rs2: Register, imm: i32) -> u32 {
+    let imm = imm as u32;
+    (imm >> 12 & 1) << 31 | (imm >> 5 & 0x3f) << 25 | reg(rs2) << 20 | reg(rs1) << 15 | func3 << 12 |
+        (imm >> 1 & 0xf) << 8 | (imm >> 11 & 1) << 7 | OP_BRANCH
+}
+
+fn u_type(opcode: u32, rd: Register, imm: i32) -> u32 {
+    imm as u32 & 0xfffff000 | reg(rd) << 7 | opcode
+}
+
+fn j_type(rd: Register, imm: i32) -> u32 {
+    let imm = imm as u32;
+    (imm >> 20 & 1) << 31 | (imm >> 1 & 0x3ff) << 21 | (imm >> 11 & 1) << 20 | (imm >> 12 & 0xff) << 12 |
+        reg(rd) << 7 | OP_JAL
+}
+
+struct Operands<'a> {
+    ops: Vec<&'a str>,
+    pc: u64,
+}
+
+impl Operands<'_> {
+    fn count(&self, expected: usize) -> std::result::Result<(), ParseError> {
+        if self.ops.len() != expected {
+            return Err(ParseError::OperandCount(expected, self.ops.len()));
+        }
+        Ok(())
+    }
+
+    fn reg(&self, idx: usize) -> std::result::Result<Register, ParseError> {
+        parse_reg(self.ops[idx])
+    }
+
+    fn imm(&self, idx: usize, range: RangeInclusive<i64>) -> std::result::Result<i32, ParseError> {
+        let imm = parse_imm(self.ops[idx])?;
+        if !range.contains(&imm) {
+            return Err(ParseError::ImmediateOutOfRange(imm));
+        }
+        Ok(imm as i32)
+    }
+
+    // The offset from the pc of the absolute target at `idx`, which must
+    // be even and fit in a signed immediate of `bits`.
+    fn target(&self, idx: usize, bits: u32) -> std::result::Result<i32, ParseError> {
+        let offset = (parse_imm(self.ops[idx])? as u64).wrapping_sub(self.pc) as i64;
+        let max = 1i64 << (bits - 1);
+        if offset % 2 != 0 || !(-max..max).contains(&offset) {
+            return Err(ParseError::ImmediateOutOfRange(offset));
+        }
+        Ok(offset as i32)
+    }
+
+    // An `imm(rs1)` memory operand. The immediate may be left out.
+    fn mem(&self, idx: usize) -> std::result::Result<(i32, Register), ParseError> {
+        let op = self.ops[idx];
+        let (imm, rest) = op.split_once('(').ok_or_else(|| ParseError::InvalidRegister(op.to_string()))?;
+        let rs1 = rest.strip_suffix(')').ok_or_else(|| ParseError::InvalidRegister(op.to_string()))?;
+        let imm = if imm.trim().is_empty() { 0 } else { parse_imm(imm.trim())? };
+        if !(-2048..=2047).contains(&imm) {
+            return Err(ParseError::ImmediateOutOfRange(imm));
+        }
+        Ok((imm as i32, parse_reg(rs1.trim())?))
+    }
+
+    fn csr(&self, idx: usize) -> std::result::Result<i32, ParseError> {
+        let op = self.ops[idx];
+        if let Some((addr, _)) = CSR_METADATA.iter().find(|(_, meta)| meta.name == op) {
+            return Ok(*addr as i32);
+        }
+        self.imm(idx, 0..=0xfff)
+    }
+
+    // A fence `pred` or `succ` set such as `rw` or `iorw`.
+    fn fence_set(&self, idx: usize) -> std::result::Result<u32, ParseError> {
+        let op = self.ops[idx];
+        op.chars().try_fold(0, |set, c| match c {
+            'i' => Ok(set | 0b1000),
+            'o' => Ok(set | 0b0100),
+            'r' => Ok(set | 0b0010),
+            'w' => Ok(set | 0b0001),
+            _ => Err(ParseError::InvalidImmediate(op.to_string())),
+        })
+    }
+}
+
+fn parse_reg(op: &str) -> std::result::Result<Register, ParseError> {
+    let idx = match op {
+        "fp" => Some(8),
+        _ => ABI_NAMES.iter().position(|name| *name == op)
+            .or_else(|| op.strip_prefix('x').and_then(|num| num.parse::<usize>().ok()).filter(|num| *num < 32)),
+    };
+    idx.map(Register::from).ok_or_else(|| ParseError::InvalidRegister(op.to_string()))
+}
+
+// A decimal or `0x` prefixed hex immediate, either of which may be
+// negative.
+fn parse_imm(op: &str) -> std::result::Result<i64, ParseError> {
+    let (negative, digits) = match op.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, op),
+    };
+    let val = match digits.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => digits.parse::<i64>(),
+    };
+    let val = val.map_err(|_| ParseError::InvalidImmediate(op.to_string()))?;
+    Ok(if negative { -val } else { val })
+}
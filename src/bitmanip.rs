@@ -0,0 +1,106 @@
+use crate::encoding_types::Inst;
+use crate::instructions::Instruction;
+use crate::instructions::Instruction::*;
+use crate::instructions::SEVEN_BIT_MASK;
+use crate::register::Register;
+
+const OP_IMM: u32 = 0b0010011;
+const OP_IMM_32: u32 = 0b0011011;
+const OP: u32 = 0b0110011;
+const OP_32: u32 = 0b0111011;
+
+// The whole immediates of `orc.b` and, on RV64, `rev8`.
+const IMM_ORC_B: u32 = 0x287;
+const IMM_REV8: u32 = 0x6b8;
+
+fn reg(inst: Inst, lo: u32) -> Register {
+    Register::from(((inst >> lo) & 0b11111) as usize)
+}
+
+impl Instruction {
+    /// Decode an instruction of the Zba, Zbb, Zbc or Zbs extensions, or
+    /// None if `inst` is not one. Where RV32 and RV64 encodings differ,
+    /// as for `zext.h` and `rev8`, the RV64 one is decoded.
+    pub fn decode_bitmanip(inst: Inst) -> Option<Instruction> {
+        let rd = reg(inst, 7);
+        let rs1 = reg(inst, 15);
+        let rs2 = reg(inst, 20);
+        let func3 = (inst >> 12) & 0b111;
+        let func6 = inst >> 26;
+        let func7 = inst >> 25;
+        // rs2 of the unary instructions, which select between them.
+        let select = (inst >> 20) & 0b11111;
+        let instruction = match (inst & SEVEN_BIT_MASK, func7, func3) {
+            (OP, 0b0010000, 0b010) => Sh1add { rd, rs1, rs2 },
+            (OP, 0b0010000, 0b100) => Sh2add { rd, rs1, rs2 },
+            (OP, 0b0010000, 0b110) => Sh3add { rd, rs1, rs2 },
+            (OP, 0b0100000, 0b111) => Andn { rd, rs1, rs2 },
+            (OP, 0b0100000, 0b110) => Orn { rd, rs1, rs2 },
+            (OP, 0b0100000, 0b100) => Xnor { rd, rs1, rs2 },
+            (OP, 0b0000101, 0b001) => Clmul { rd, rs1, rs2 },
+            (OP, 0b0000101, 0b010) => Clmulr { rd, rs1, rs2 },
+            (OP, 0b0000101, 0b011) => Clmulh { rd, rs1, rs2 },
+            (OP, 0b0000101, 0b100) => Min { rd, rs1, rs2 },
+            (OP, 0b0000101, 0b101) => Minu { rd, rs1, rs2 },
+            (OP, 0b0000101, 0b110) => Max { rd, rs1, rs2 },
+            (OP, 0b0000101, 0b111) => Maxu { rd, rs1, rs2 },
+            (OP, 0b0110000, 0b001) => Rol { rd, rs1, rs2 },
+            (OP, 0b0110000, 0b101) => Ror { rd, rs1, rs2 },
+            (OP, 0b0100100, 0b001) => Bclr { rd, rs1, rs2 },
+            (OP, 0b0100100, 0b101) => Bext { rd, rs1, rs2 },
+            (OP, 0b0110100, 0b001) => Binv { rd, rs1, rs2 },
+            (OP, 0b0010100, 0b001) => Bset { rd, rs1, rs2 },
+            (OP_32, 0b0000100, 0b000) => AddUw { rd, rs1, rs2 },
+            (OP_32, 0b0000100, 0b100) if select == 0 => ZextH { rd, rs1 },
+            (OP_32, 0b0010000, 0b010) => Sh1addUw { rd, rs1, rs2 },
+            (OP_32, 0b0010000, 0b100) => Sh2addUw { rd, rs1, rs2 },
+            (OP_32, 0b0010000, 0b110) => Sh3addUw { rd, rs1, rs2 },
+            (OP_32, 0b0110000, 0b001) => Rolw { rd, rs1, rs2 },
+            (OP_32, 0b0110000, 0b101) => Rorw { rd, rs1, rs2 },
+            (OP_IMM, 0b0110000, 0b001) => match select {
+                0b00000 => Clz { rd, rs1 },
+                0b00001 => Ctz { rd, rs1 },
+                0b00010 => Cpop { rd, rs1 },
+                0b00100 => SextB { rd, rs1 },
+                0b00101 => SextH { rd, rs1 },
+                _ => return None,
+            },
+            (OP_IMM, _, 0b001 | 0b101) => {
+                let shamt = (inst >> 20) & 0b111111;
+                match (inst >> 20, func6, func3) {
+                    (IMM_ORC_B, _, 0b101) => OrcB { rd, rs1 },
+                    (IMM_REV8, _, 0b101) => Rev8 { rd, rs1 },
+                    (_, 0b011000, 0b101) => Rori { rd, rs1, shamt },
+                    (_, 0b010010, 0b001) => Bclri { rd, rs1, shamt },
+                    (_, 0b010010, 0b101) => Bexti { rd, rs1, shamt },
+                    (_, 0b011010, 0b001) => Binvi { rd, rs1, shamt },
+                    (_, 0b001010, 0b001) => Bseti { rd, rs1, shamt },
+                    _ => return None,
+                }
+            },
+            (OP_IMM_32, 0b0110000, 0b001) => match select {
+                0b00000 => Clzw { rd, rs1 },
+                0b00001 => Ctzw { rd, rs1 },
+                0b00010 => Cpopw { rd, rs1 },
+                _ => return None,
+            },
+            (OP_IMM_32, 0b0110000, 0b101) => Roriw { rd, rs1, shamt: select },
+            (OP_IMM_32, _, 0b001) if func6 == 0b000010 => SlliUw { rd, rs1, shamt: (inst >> 20) & 0b111111 },
+            _ => return None,
+        };
+        Some(instruction)
+    }
+}
+
+/// The 128 bit carry-less product of `a` and `b`. `clmul` keeps its low
+/// 64 bits, `clmulh` its high 64 and `clmulr` bits 126 to 63.
+pub fn clmul(a: u64, b: u64) -> u128 {
+    (0..64).filter(|bit| b >> bit & 1 != 0).fold(0, |product, bit| product ^ (a as u128) << bit)
+}
+
+/// `orc.b`: each byte of `val` becomes 0xff if any of its bits are set,
+/// otherwise 0.
+pub fn orc_b(val: u64) -> u64 {
+    let bytes = val.to_le_bytes().map(|byte| if byte != 0 { 0xff } else { 0 });
+    u64::from_le_bytes(bytes)
+}
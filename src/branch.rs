@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BranchType {
+    Beq,
+    Bne,
+    Blt,
+    Bge,
+    Bltu,
+    Bgeu,
+    Jal,
+    Jalr,
+}
+
+/// Counts of taken and not taken branches, overall and per branch type.
+/// `by_opcode` maps each type to its `(taken, not_taken)` counts. Jumps
+/// are always taken.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BranchStats {
+    pub total_branches: u64,
+    pub taken: u64,
+    pub not_taken: u64,
+    pub by_opcode: HashMap<BranchType, (u64, u64)>,
+}
+
+impl BranchStats {
+    pub fn record(&mut self, kind: BranchType, taken: bool) {
+        let counts = self.by_opcode.entry(kind).or_insert((0, 0));
+        self.total_branches += 1;
+        if taken {
+            self.taken += 1;
+            counts.0 += 1;
+        } else {
+            self.not_taken += 1;
+            counts.1 += 1;
+        }
+    }
+
+    /// The fraction of branches that were taken, or 0 if there were none.
+    pub fn taken_rate(&self) -> f64 {
+        rate(self.taken, self.total_branches)
+    }
+
+    /// The fraction of branches of each type seen that were taken.
+    pub fn by_type(&self) -> impl Iterator<Item = (&BranchType, f64)> {
+        self.by_opcode.iter().map(|(kind, (taken, not_taken))| (kind, rate(*taken, taken + not_taken)))
+    }
+}
+
+fn rate(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
@@ -0,0 +1,42 @@
+use crate::memory::Dram;
+use crate::soft::SoftThread;
+use std::fmt::{Debug, Formatter};
+
+// `ebreak`, which a breakpoint patches over the instruction it replaces.
+pub const EBREAK: u32 = 0x0010_0073;
+
+pub type BreakpointCondition<M = Dram> = Box<dyn Fn(&SoftThread<u64, f64, M>) -> bool>;
+
+/// A breakpoint that only stops execution when `condition` holds for the
+/// hart as it reaches `addr`. `saved_inst` is the instruction the `ebreak`
+/// at `addr` replaced.
+pub struct ConditionalBreakpoint<M = Dram> {
+    pub addr: u64,
+    pub condition: BreakpointCondition<M>,
+    pub saved_inst: u32,
+}
+
+impl<M> Debug for ConditionalBreakpoint<M> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("ConditionalBreakpoint")
+            .field("addr", &self.addr)
+            .field("saved_inst", &self.saved_inst)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Identifies a breakpoint set with `SoftThread::set_breakpoint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BreakpointId(pub u64);
+
+pub type DebuggerCallback<M = Dram> = Box<dyn FnMut(&mut SoftThread<u64, f64, M>)>;
+
+/// The callback `SoftThread::attach_debugger` runs for each `ebreak` the
+/// program executes.
+pub struct DebuggerHook<M = Dram>(pub DebuggerCallback<M>);
+
+impl<M> Debug for DebuggerHook<M> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("DebuggerHook").finish_non_exhaustive()
+    }
+}
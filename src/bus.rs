@@ -1,35 +1,250 @@
-use crate::memory::{Memory, ReadOnlyMemory};
-use crate::{clint::Clint, plic::Plic, uart::Uart, vio:::Vio};
-
-#[derive(Clone, Debug)]
-pub struct Bus<C, P, U, I, M, R>
-where
-    C: Clone + Clint,
-    P: Clone + Plic,
-    U: Clone + Uart,
-    I: Clone + Vio,
-    M: Clone + Memory,
-    R: Clone + ReadOnlyMemory
-{
-    pub clint: C,
-    pub plic: P,
-    pub uart: U,
-    pub io: I,
-    pub dram: M,
-    rom: R,
+use crate::consts::INDEX_SHIFTS;
+use crate::exceptions::Exception;
+use crate::memory::{self, MemError, Memory};
+use crate::peripheral::Peripheral;
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Memory mapped at `size` bytes of physical address space from
+/// `base_addr`, such as DRAM or a peripheral. A `Bus` passes it addresses
+/// relative to `base_addr`.
+pub trait Device: Memory<RegValue = u64, Bytes = Vec<u8>, Error = MemError> + Any + Debug {
+    fn base_addr(&self) -> u64;
+    fn size(&self) -> u64;
+
+    fn contains(&self, addr: u64) -> bool {
+        addr.wrapping_sub(self.base_addr()) < self.size()
+    }
+
+    /// Another handle on this device, for a device that several buses
+    /// share. Devices that belong to one bus return None.
+    fn share(&self) -> Option<Box<dyn Device>> {
+        None
+    }
+}
+
+/// A device that several buses share, such as the CLINT of the harts of
+/// a `Cpu`. Each bus ticks it, so it advances with any of them.
+impl<D: Peripheral> Peripheral for Rc<RefCell<D>> {
+    fn tick(&mut self, cycles: u64) {
+        Peripheral::tick(&mut *self.borrow_mut(), cycles);
+    }
+
+    fn read(&mut self, offset: u32, size: u8) -> u64 {
+        Peripheral::read(&mut *self.borrow_mut(), offset, size)
+    }
+
+    fn write(&mut self, offset: u32, val: u64, size: u8) {
+        Peripheral::write(&mut *self.borrow_mut(), offset, val, size);
+    }
+
+    fn interrupts(&self, hart_id: u64) -> (u64, u64) {
+        Peripheral::interrupts(&*self.borrow(), hart_id)
+    }
+}
+
+impl<D: Device + Peripheral> Device for Rc<RefCell<D>> {
+    fn base_addr(&self) -> u64 {
+        self.borrow().base_addr()
+    }
+
+    fn size(&self) -> u64 {
+        self.borrow().size()
+    }
+
+    fn share(&self) -> Option<Box<dyn Device>> {
+        Some(Box::new(Rc::clone(self)))
+    }
+}
+
+/// The devices mapped into a physical address space, as one `Memory` that
+/// sends each access to the device whose range holds its address. An
+/// access nothing is mapped at is an access fault. Ranges should not
+/// overlap; if they do, the device attached first wins.
+#[derive(Debug, Default)]
+pub struct Bus {
+    devices: Vec<Box<dyn Device>>,
 }
 
+impl Bus {
+    pub fn new() -> Bus {
+        Bus::default()
+    }
+
+    /// Map `device` at its range, returning its index.
+    pub fn attach<D: Device + 'static>(&mut self, device: D) -> usize {
+        self.devices.push(Box::new(device));
+        self.devices.len() - 1
+    }
+
+    /// The device attached at `idx`, if it is a `D`.
+    pub fn get_mut<D: Device + 'static>(&mut self, idx: usize) -> Option<&mut D> {
+        let device: &mut dyn Any = self.devices.get_mut(idx)?.as_mut();
+        device.downcast_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// True if a device is mapped at `addr`.
+    pub fn contains(&self, addr: u64) -> bool {
+        self.devices.iter().any(|device| device.contains(addr))
+    }
+
+    /// A bus with the devices of this one that can be shared, see
+    /// `Device::share`. The others are left out.
+    pub fn fork(&self) -> Bus {
+        Bus { devices: self.devices.iter().filter_map(|device| device.share()).collect() }
+    }
+
+    // The device mapped at `addr` and the address relative to its base.
+    fn find(&self, addr: u64) -> Option<(&dyn Device, u64)> {
+        let device = self.devices.iter().find(|device| device.contains(addr))?;
+        Some((device.as_ref(), addr - device.base_addr()))
+    }
+
+    fn find_mut(&mut self, addr: u64) -> Option<(&mut dyn Device, u64)> {
+        let device = self.devices.iter_mut().find(|device| device.contains(addr))?;
+        let offset = addr - device.base_addr();
+        Some((device.as_mut(), offset))
+    }
+}
+
+impl Memory for Bus {
+    type RegValue = u64;
+    type Bytes = Vec<u8>;
+    type Error = Exception;
+
+    fn init(&mut self, addr: u64, size: u64, flags: u8, source: Option<Vec<u8>>, offset: u64) -> Result<(), Exception> {
+        let (device, addr) = self.find_mut(addr).ok_or(Exception::StoreAMOAccessFault)?;
+        device.init(addr, size, flags, source, offset).map_err(|_| Exception::StoreAMOAccessFault)
+    }
+
+    // Flags are kept per `INDEX_SIZE` bytes, so `index` names the device
+    // mapped at `index << INDEX_SHIFTS`.
+    fn get_flag(&mut self, index: u64) -> Result<u8, Exception> {
+        let (device, addr) = self.find_mut(index << INDEX_SHIFTS).ok_or(Exception::LoadAccessFault)?;
+        device.get_flag(addr >> INDEX_SHIFTS).map_err(|_| Exception::LoadAccessFault)
+    }
+
+    fn set_flag(&mut self, index: u64, flag: u8) -> Result<(), Exception> {
+        let (device, addr) = self.find_mut(index << INDEX_SHIFTS).ok_or(Exception::StoreAMOAccessFault)?;
+        device.set_flag(addr >> INDEX_SHIFTS, flag).map_err(|_| Exception::StoreAMOAccessFault)
+    }
+
+    fn clear_flag(&mut self, index: u64, flag: u8) -> Result<(), Exception> {
+        let (device, addr) = self.find_mut(index << INDEX_SHIFTS).ok_or(Exception::StoreAMOAccessFault)?;
+        device.clear_flag(addr >> INDEX_SHIFTS, flag).map_err(|_| Exception::StoreAMOAccessFault)
+    }
+
+    fn get_indices(&self, addr: u64, size: u64) -> Result<(u64, u64), Exception> {
+        let (device, offset) = self.find(addr).ok_or(Exception::LoadAccessFault)?;
+        let (start, end) = device.get_indices(offset, size).map_err(|_| Exception::LoadAccessFault)?;
+        let base = device.base_addr() >> INDEX_SHIFTS;
+        Ok((start + base, end + base))
+    }
+
+    fn execute_readhw(&mut self, addr: u64) -> u64 {
+        self.readhw(&addr)
+    }
+
+    fn execute_readw(&mut self, addr: u64) -> u64 {
+        self.readw(&addr)
+    }
+
+    fn read(&self, addr: &u64, size: u8) -> Result<u64, Exception> {
+        let (device, addr) = self.find(*addr).ok_or(Exception::LoadAccessFault)?;
+        device.read(&addr, size).map_err(|_| Exception::LoadAccessFault)
+    }
+
+    fn load(&mut self, addr: &u64, size: u8) -> Result<u64, Exception> {
+        let (device, addr) = self.find_mut(*addr).ok_or(Exception::LoadAccessFault)?;
+        device.load(&addr, size).map_err(|_| Exception::LoadAccessFault)
+    }
+
+    // The fixed size reads cannot fail, so an unmapped address reads as 0.
+    fn readb(&self, addr: &u64) -> u64 {
+        self.read(addr, memory::BYTE).unwrap_or(0)
+    }
+
+    fn readhw(&self, addr: &u64) -> u64 {
+        self.read(addr, memory::HALFWORD).unwrap_or(0)
+    }
+
+    fn readw(&self, addr: &u64) -> u64 {
+        self.read(addr, memory::WORD).unwrap_or(0)
+    }
+
+    fn readdw(&self, addr: &u64) -> u64 {
+        self.read(addr, memory::DOUBLEWORD).unwrap_or(0)
+    }
+
+    fn write_array(&mut self, addr: u64, val: Vec<u8>) -> Result<(), Exception> {
+        let (device, addr) = self.find_mut(addr).ok_or(Exception::StoreAMOAccessFault)?;
+        device.write_array(addr, val).map_err(|_| Exception::StoreAMOAccessFault)
+    }
+
+    fn read_array(&self, addr: u64, buf: &mut [u8]) -> Result<(), Exception> {
+        let (device, addr) = self.find(addr).ok_or(Exception::LoadAccessFault)?;
+        device.read_array(addr, buf).map_err(|_| Exception::LoadAccessFault)
+    }
+
+    fn write(&mut self, addr: u64, value: u64, size: u8) -> Result<(), Exception> {
+        let (device, addr) = self.find_mut(addr).ok_or(Exception::StoreAMOAccessFault)?;
+        device.write(addr, value, size).map_err(|_| Exception::StoreAMOAccessFault)
+    }
+
+    // Likewise a fixed size write to an unmapped address is dropped.
+    fn writeb(&mut self, addr: u64, val: u64) {
+        let _ = self.write(addr, val, memory::BYTE);
+    }
+
+    fn writehw(&mut self, addr: u64, val: u64) {
+        let _ = self.write(addr, val, memory::HALFWORD);
+    }
 
-impl<C, P, U, I, M, R> Bus<C, P, U, I, M, R> {
-    pub fn new() -> Self {
-        Bus {
-            clint: C::default(),
-            plic: P::default(),
-            uart: U::default(),
-            io: I::default(),
-            dram: M::default(),
-            rom: R::default()
+    fn writew(&mut self, addr: u64, val: u64) {
+        let _ = self.write(addr, val, memory::WORD);
+    }
+
+    fn writedw(&mut self, addr: u64, val: u64) {
+        let _ = self.write(addr, val, memory::DOUBLEWORD);
+    }
+
+    fn into_u64(&self, val: &u64) -> u64 {
+        *val
+    }
+
+    fn into_i64(&self, val: &u64) -> i64 {
+        *val as i64
+    }
+
+    fn into_u32(&self, val: &u64) -> u32 {
+        *val as u32
+    }
+
+    fn into_i32(&self, val: &u64) -> i32 {
+        *val as i32
+    }
+
+    /// Advance every device by `cycles`, in the order they were attached.
+    fn tick(&mut self, cycles: u64) {
+        for device in self.devices.iter_mut() {
+            device.tick(cycles);
         }
     }
-} 
 
+    fn interrupts(&self, hart_id: u64) -> (u64, u64) {
+        self.devices.iter().fold((0, 0), |(mask, pending), device| {
+            let (driven, set) = device.interrupts(hart_id);
+            (mask | driven, pending | set)
+        })
+    }
+}
@@ -0,0 +1,126 @@
+// Checkpoints of a hart as serde data, so that the state at a failure can
+// be saved on one machine and reproduced on another. Only the
+// architectural state is kept: registers, CSRs, the program and where it
+// was loaded, the reservation set and DRAM. Hooks, traces and other host
+// side settings are left at their defaults when a checkpoint is loaded.
+
+use crate::consts::{INDEX_SIZE, MAX_MEM};
+use crate::memory::{Dram, DramBacking, MEM_SIZE};
+use crate::privilege::PrivilegeLevel;
+use crate::soft::SoftThread;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::Range;
+
+#[derive(Serialize, Deserialize)]
+struct DramState {
+    // The length of the memory. `mem` leaves out the zero bytes at the
+    // end of it.
+    len: usize,
+    size: u64,
+    mem: String,
+    flags: String,
+}
+
+impl Serialize for Dram {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let used = self.mem.iter().rposition(|byte| *byte != 0).map_or(0, |last| last + 1);
+        DramState {
+            len: self.mem.len(),
+            size: self.size,
+            mem: STANDARD.encode(&self.mem[..used]),
+            flags: STANDARD.encode(&self.flags),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dram {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Dram, D::Error> {
+        let state = DramState::deserialize(deserializer)?;
+        if state.len > MAX_MEM.max(MEM_SIZE as usize) {
+            return Err(D::Error::custom("memory is longer than the largest DRAM"));
+        }
+        if state.size > state.len as u64 {
+            return Err(D::Error::custom("program is longer than the memory"));
+        }
+        let mut mem = STANDARD.decode(&state.mem).map_err(D::Error::custom)?;
+        if mem.len() > state.len {
+            return Err(D::Error::custom("memory is longer than its length"));
+        }
+        mem.resize(state.len, 0);
+
+        let mut dram = Dram::new();
+        dram.mem = DramBacking::Heap(mem);
+        dram.flags = STANDARD.decode(&state.flags).map_err(D::Error::custom)?;
+        if dram.flags.len() != state.len.div_ceil(INDEX_SIZE) {
+            return Err(D::Error::custom("flags do not cover the memory"));
+        }
+        dram.size = state.size;
+        Ok(dram)
+    }
+}
+
+// Generic over the DRAM so that serializing can borrow it.
+#[derive(Serialize, Deserialize)]
+struct SoftThreadState<B> {
+    registers: Vec<u64>,
+    // As bits, which keeps NaN payloads.
+    f_registers: Vec<u64>,
+    pc: u64,
+    program: Vec<u8>,
+    remainder: u32,
+    eq_flag: bool,
+    csr: Vec<u64>,
+    res: Vec<u64>,
+    priv_level: u64,
+    // The addresses `load_image` put code at.
+    image: Range<u64>,
+    bus: B,
+}
+
+impl Serialize for SoftThread<u64, f64, Dram> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SoftThreadState {
+            registers: self.registers.to_vec(),
+            f_registers: self.f_registers.iter().map(|val| val.to_bits()).collect(),
+            pc: self.pc,
+            program: self.program.clone(),
+            remainder: self.remainder,
+            eq_flag: self.eq_flag,
+            csr: self.csr.to_vec(),
+            res: self.res.clone(),
+            priv_level: self.priv_level.into(),
+            image: self.image.clone(),
+            bus: &self.bus,
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SoftThread<u64, f64, Dram> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SoftThread<u64, f64, Dram>, D::Error> {
+        let state = SoftThreadState::<Dram>::deserialize(deserializer)?;
+        let mut soft = SoftThread::default();
+        soft.registers = state.registers.try_into().map_err(|_| D::Error::custom("expected 33 registers"))?;
+        let f_registers: [u64; 33] = state.f_registers.try_into().map_err(|_| D::Error::custom("expected 33 float registers"))?;
+        soft.f_registers = f_registers.map(f64::from_bits);
+        if state.csr.len() != soft.csr.len() {
+            return Err(D::Error::custom("expected 4096 CSRs"));
+        }
+        // Through `write_csr_raw`, so that the PMP entries follow.
+        for (addr, val) in state.csr.into_iter().enumerate() {
+            soft.write_csr_raw(addr as u16, val);
+        }
+
+        soft.pc = state.pc;
+        soft.program = state.program;
+        soft.remainder = state.remainder;
+        soft.eq_flag = state.eq_flag;
+        soft.res = state.res;
+        soft.priv_level = PrivilegeLevel::from(state.priv_level);
+        soft.image = state.image;
+        soft.bus = state.bus;
+        Ok(soft)
+    }
+}
@@ -0,0 +1,201 @@
+use crate::encoding_types::Inst;
+use crate::instructions::Instruction;
+use crate::instructions::Instruction::*;
+use crate::register::Register;
+use crate::soft::{COMPRESSED_INST_LEN, INST_LEN};
+
+// `func7` of `sub`, `sra` and `srai`.
+const FUNC7_ALT: u32 = 0b0100000;
+
+/// The length in bytes of the instruction `inst` starts with: 2 when its
+/// low bits are not 0b11, otherwise 4.
+pub fn inst_len(inst: Inst) -> u64 {
+    match inst & 0b11 {
+        0b11 => INST_LEN,
+        _ => COMPRESSED_INST_LEN,
+    }
+}
+
+// Bits `hi..=lo` of `inst`.
+fn bits(inst: u16, hi: u32, lo: u32) -> u32 {
+    (inst as u32 >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+// Bit `from` of `inst`, moved to bit `to`.
+fn bit(inst: u16, from: u32, to: u32) -> u32 {
+    bits(inst, from, from) << to
+}
+
+// The low `width` bits of `val`, sign extended.
+fn sign_extend(val: u32, width: u32) -> i32 {
+    ((val << (32 - width)) as i32) >> (32 - width)
+}
+
+// A full register field at bits 11:7 or 6:2.
+fn reg(inst: u16, lo: u32) -> Register {
+    Register::from(bits(inst, lo + 4, lo) as usize)
+}
+
+// One of x8-x15 from a three bit field at bits 9:7 or 4:2.
+fn reg_prime(inst: u16, lo: u32) -> Register {
+    Register::from(8 + bits(inst, lo + 2, lo) as usize)
+}
+
+// The six bit immediate of `c.addi`, `c.li` and `c.andi` and the shift
+// amounts, from bit 12 and bits 6:2.
+fn imm6(inst: u16) -> u32 {
+    bit(inst, 12, 5) | bits(inst, 6, 2)
+}
+
+impl Instruction {
+    /// Decode a 16 bit instruction of the C extension. Register numbers
+    /// and immediates are those of the expanded instruction: `rd'` fields
+    /// name x8-x15 and offsets are in bytes. Reserved and illegal
+    /// encodings, including the all zero one, decode as `Undefined`. Where
+    /// RV32 and RV64 differ, the RV64 instruction is decoded.
+    pub fn decode_compressed(inst: u16) -> Instruction {
+        let rd = reg(inst, 7);
+        let rs2 = reg(inst, 2);
+        match (inst & 0b11, bits(inst, 15, 13)) {
+            (0b00, 0b000) => {
+                let imm = bits(inst, 12, 11) << 4 | bits(inst, 10, 7) << 6 | bit(inst, 6, 2) | bit(inst, 5, 3);
+                match imm {
+                    0 => Undefined,
+                    _ => CAddi4spn { rd: reg_prime(inst, 2), imm: imm as i32 },
+                }
+            },
+            (0b00, funct3) => {
+                let rs1 = reg_prime(inst, 7);
+                let rd = reg_prime(inst, 2);
+                // Doubleword offsets put bits 6:5 at 7:6, word offsets put
+                // bit 6 at 2 and bit 5 at 6.
+                let double = (bits(inst, 12, 10) << 3 | bits(inst, 6, 5) << 6) as i32;
+                let word = (bits(inst, 12, 10) << 3 | bit(inst, 6, 2) | bit(inst, 5, 6)) as i32;
+                match funct3 {
+                    0b001 => CFld { rd, rs1, imm: double },
+                    0b010 => CLw { rd, rs1, imm: word },
+                    0b011 => CLd { rd, rs1, imm: double },
+                    0b101 => CFsd { rs1, rs2: rd, imm: double },
+                    0b110 => CSw { rs1, rs2: rd, imm: word },
+                    0b111 => CSd { rs1, rs2: rd, imm: double },
+                    _ => Undefined,
+                }
+            },
+            (0b01, 0b000) => match rd {
+                Register::X0 => CNop,
+                _ => CAddi { rd, imm: sign_extend(imm6(inst), 6) },
+            },
+            (0b01, 0b001) => match rd {
+                Register::X0 => Undefined,
+                _ => CAddiw { rd, imm: sign_extend(imm6(inst), 6) },
+            },
+            (0b01, 0b010) => CLi { rd, imm: sign_extend(imm6(inst), 6) },
+            (0b01, 0b011) if rd == Register::X2 => {
+                let imm = bit(inst, 12, 9) | bit(inst, 6, 4) | bit(inst, 5, 6) | bits(inst, 4, 3) << 7 | bit(inst, 2, 5);
+                match imm {
+                    0 => Undefined,
+                    _ => CAddi16sp { imm: sign_extend(imm, 10) },
+                }
+            },
+            (0b01, 0b011) => match imm6(inst) {
+                0 => Undefined,
+                imm => CLui { rd, imm: sign_extend(imm << 12, 18) },
+            },
+            (0b01, 0b100) => {
+                let rd = reg_prime(inst, 7);
+                let rs2 = reg_prime(inst, 2);
+                match (bits(inst, 11, 10), bits(inst, 12, 12), bits(inst, 6, 5)) {
+                    (0b00, _, _) => CSrli { rd, shamt: imm6(inst) },
+                    (0b01, _, _) => CSrai { rd, shamt: imm6(inst) },
+                    (0b10, _, _) => CAndi { rd, imm: sign_extend(imm6(inst), 6) },
+                    (_, 0, 0b00) => CSub { rd, rs2 },
+                    (_, 0, 0b01) => CXor { rd, rs2 },
+                    (_, 0, 0b10) => COr { rd, rs2 },
+                    (_, 0, _) => CAnd { rd, rs2 },
+                    (_, _, 0b00) => CSubw { rd, rs2 },
+                    (_, _, 0b01) => CAddw { rd, rs2 },
+                    _ => Undefined,
+                }
+            },
+            (0b01, 0b101) => {
+                let imm = bit(inst, 12, 11) | bit(inst, 11, 4) | bits(inst, 10, 9) << 8 | bit(inst, 8, 10) |
+                    bit(inst, 7, 6) | bit(inst, 6, 7) | bits(inst, 5, 3) << 1 | bit(inst, 2, 5);
+                CJ { imm: sign_extend(imm, 12) }
+            },
+            (0b01, funct3) => {
+                let rs1 = reg_prime(inst, 7);
+                let imm = bit(inst, 12, 8) | bits(inst, 11, 10) << 3 | bits(inst, 6, 5) << 6 |
+                    bits(inst, 4, 3) << 1 | bit(inst, 2, 5);
+                let imm = sign_extend(imm, 9);
+                match funct3 {
+                    0b110 => CBeqz { rs1, imm },
+                    _ => CBnez { rs1, imm },
+                }
+            },
+            (0b10, 0b000) => CSlli { rd, shamt: imm6(inst) },
+            (0b10, 0b001) => CFldsp { rd, imm: (bit(inst, 12, 5) | bits(inst, 6, 5) << 3 | bits(inst, 4, 2) << 6) as i32 },
+            (0b10, 0b010 | 0b011) if rd == Register::X0 => Undefined,
+            (0b10, 0b010) => CLwsp { rd, imm: (bit(inst, 12, 5) | bits(inst, 6, 4) << 2 | bits(inst, 3, 2) << 6) as i32 },
+            (0b10, 0b011) => CLdsp { rd, imm: (bit(inst, 12, 5) | bits(inst, 6, 5) << 3 | bits(inst, 4, 2) << 6) as i32 },
+            (0b10, 0b100) => match (bits(inst, 12, 12), rd, rs2) {
+                (0, Register::X0, Register::X0) => Undefined,
+                (0, rs1, Register::X0) => CJr { rs1 },
+                (0, _, _) => CMv { rd, rs2 },
+                (_, Register::X0, Register::X0) => CEbreak,
+                (_, rs1, Register::X0) => CJalr { rs1 },
+                _ => CAdd { rd, rs2 },
+            },
+            (0b10, 0b101) => CFsdsp { rs2, imm: (bits(inst, 12, 10) << 3 | bits(inst, 9, 7) << 6) as i32 },
+            (0b10, 0b110) => CSwsp { rs2, imm: (bits(inst, 12, 9) << 2 | bits(inst, 8, 7) << 6) as i32 },
+            (0b10, 0b111) => CSdsp { rs2, imm: (bits(inst, 12, 10) << 3 | bits(inst, 9, 7) << 6) as i32 },
+            _ => Undefined,
+        }
+    }
+
+    /// The 32 bit instruction a C extension instruction stands for, which
+    /// is how it is executed. Other instructions are returned as they are.
+    pub fn expand(self) -> Instruction {
+        let sp = Register::X2;
+        let zero = Register::X0;
+        match self {
+            CAddi4spn { rd, imm } => Addi { rd, rs1: sp, imm, func3: 0b000 },
+            CFld { rd, rs1, imm } => Fld { rd, rs1, imm },
+            CLw { rd, rs1, imm } => Lw { rd, rs1, imm, func3: 0b010 },
+            CLd { rd, rs1, imm } => Ld { rd, rs1, imm, func3: 0b011 },
+            CFsd { rs1, rs2, imm } => Fsd { rs1, rs2, imm },
+            CSw { rs1, rs2, imm } => Sw { rs1, rs2, imm, func3: 0b010 },
+            CSd { rs1, rs2, imm } => Sd { rs1, rs2, imm, func3: 0b011 },
+            CNop => Addi { rd: zero, rs1: zero, imm: 0, func3: 0b000 },
+            CAddi { rd, imm } => Addi { rd, rs1: rd, imm, func3: 0b000 },
+            CAddiw { rd, imm } => Addiw { rd, rs1: rd, imm, func3: 0b000 },
+            CLi { rd, imm } => Addi { rd, rs1: zero, imm, func3: 0b000 },
+            CAddi16sp { imm } => Addi { rd: sp, rs1: sp, imm, func3: 0b000 },
+            CLui { rd, imm } => Lui { rd, imm },
+            CSrli { rd, shamt } => Srli { rd, rs1: rd, shamt, func3: 0b101, func7: 0 },
+            CSrai { rd, shamt } => Srai { rd, rs1: rd, shamt, func3: 0b101, func7: FUNC7_ALT },
+            CAndi { rd, imm } => Andi { rd, rs1: rd, imm, func3: 0b111 },
+            CSub { rd, rs2 } => Sub { rd, rs1: rd, rs2, func3: 0b000, func7: FUNC7_ALT },
+            CXor { rd, rs2 } => Xor { rd, rs1: rd, rs2, func3: 0b100, func7: 0 },
+            COr { rd, rs2 } => Or { rd, rs1: rd, rs2, func3: 0b110, func7: 0 },
+            CAnd { rd, rs2 } => And { rd, rs1: rd, rs2, func3: 0b111, func7: 0 },
+            CSubw { rd, rs2 } => Subw { rd, rs1: rd, rs2, func3: 0b000, func7: FUNC7_ALT },
+            CAddw { rd, rs2 } => Addw { rd, rs1: rd, rs2, func3: 0b000, func7: 0 },
+            CJ { imm } => Jal { rd: zero, imm },
+            CBeqz { rs1, imm } => Beq { rd: zero, rs1, rs2: zero, imm, func3: 0b000 },
+            CBnez { rs1, imm } => Bne { rd: zero, rs1, rs2: zero, imm, func3: 0b001 },
+            CSlli { rd, shamt } => Slli { rd, rs1: rd, shamt, func3: 0b001, func7: 0 },
+            CFldsp { rd, imm } => Fld { rd, rs1: sp, imm },
+            CLwsp { rd, imm } => Lw { rd, rs1: sp, imm, func3: 0b010 },
+            CLdsp { rd, imm } => Ld { rd, rs1: sp, imm, func3: 0b011 },
+            CJr { rs1 } => Jalr { rd: zero, rs1, imm: 0 },
+            CMv { rd, rs2 } => Add { rd, rs1: zero, rs2, func3: 0b000, func7: 0 },
+            CEbreak => EBreak,
+            CJalr { rs1 } => Jalr { rd: Register::X1, rs1, imm: 0 },
+            CAdd { rd, rs2 } => Add { rd, rs1: rd, rs2, func3: 0b000, func7: 0 },
+            CFsdsp { rs2, imm } => Fsd { rs1: sp, rs2, imm },
+            CSwsp { rs2, imm } => Sw { rs1: sp, rs2, imm, func3: 0b010 },
+            CSdsp { rs2, imm } => Sd { rs1: sp, rs2, imm, func3: 0b011 },
+            instruction => instruction,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::disasm;
+use crate::instructions::Instruction;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::mem::Discriminant;
+use strum::IntoEnumIterator;
+
+/// Mnemonics of the instructions `SoftThread::execute` decodes but cannot
+/// execute yet.
+pub const UNIMPLEMENTED: [&str; 0] = [];
+
+/// How many times each instruction type has executed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InstructionHistogram {
+    counts: HashMap<Discriminant<Instruction>, u64>,
+    pub total: u64,
+}
+
+impl InstructionHistogram {
+    pub fn record(&mut self, instruction: &Instruction) {
+        *self.counts.entry(std::mem::discriminant(instruction)).or_insert(0) += 1;
+        self.total += 1;
+    }
+
+    /// The executions of the type of `instruction`, whatever its operands.
+    pub fn count(&self, instruction: &Instruction) -> u64 {
+        self.counts.get(&std::mem::discriminant(instruction)).copied().unwrap_or(0)
+    }
+
+    /// The fraction of instruction types that have executed at least once.
+    /// `Undefined` is not a type.
+    pub fn coverage(&self) -> f64 {
+        let types: Vec<Instruction> = Self::types().collect();
+        let seen = types.iter().filter(|inst| self.count(inst) > 0).count();
+        seen as f64 / types.len() as f64
+    }
+
+    /// Write a markdown table of every instruction type, most executed
+    /// first, followed by how many of the types executed. Types listed in
+    /// `UNIMPLEMENTED` are marked as such.
+    pub fn write_report(&self, output: &mut dyn Write) -> io::Result<()> {
+        let mut rows: Vec<(String, u64)> = Self::types().map(|inst| (disasm::mnemonic(&inst), self.count(&inst))).collect();
+        rows.sort_by_key(|(_, count)| Reverse(*count));
+
+        writeln!(output, "| Instruction | Count | % |")?;
+        writeln!(output, "|---|---:|---:|")?;
+        for (name, count) in rows.iter() {
+            let note = if UNIMPLEMENTED.contains(&name.as_str()) { " (unimplemented)" } else { "" };
+            writeln!(output, "| {}{} | {} | {:.1}% |", name, note, count, percent(*count, self.total))?;
+        }
+
+        let seen = rows.iter().filter(|(_, count)| *count > 0).count();
+        writeln!(output)?;
+        writeln!(output, "Total: {} / {} instruction types executed ({:.1}%)", seen, rows.len(),
+            percent(seen as u64, rows.len() as u64))
+    }
+
+    fn types() -> impl Iterator<Item = Instruction> {
+        Instruction::iter().filter(|inst| *inst != Instruction::Undefined)
+    }
+}
+
+fn percent(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        100.0 * count as f64 / total as f64
+    }
+}
@@ -0,0 +1,353 @@
+use crate::extensions::{Base, Extension};
+use crate::privilege::PrivilegeLevel;
+
+// Addresses of the control and status registers the hart gives meaning to.
+// Other known addresses in the 12 bit CSR space are plain storage.
+pub const CSR_FFLAGS: u16 = 0x001;
+pub const CSR_FRM: u16 = 0x002;
+pub const CSR_FCSR: u16 = 0x003;
+pub const CSR_SSTATUS: u16 = 0x100;
+pub const CSR_STVEC: u16 = 0x105;
+pub const CSR_SEPC: u16 = 0x141;
+pub const CSR_SCAUSE: u16 = 0x142;
+pub const CSR_SATP: u16 = 0x180;
+pub const CSR_MHARTID: u16 = 0xf14;
+pub const CSR_MSTATUS: u16 = 0x300;
+pub const CSR_MISA: u16 = 0x301;
+pub const CSR_MIE: u16 = 0x304;
+pub const CSR_MTVEC: u16 = 0x305;
+pub const CSR_MSCRATCH: u16 = 0x340;
+pub const CSR_MEPC: u16 = 0x341;
+pub const CSR_MCAUSE: u16 = 0x342;
+pub const CSR_MTVAL: u16 = 0x343;
+pub const CSR_MIP: u16 = 0x344;
+pub const CSR_PMPCFG0: u16 = 0x3a0;
+pub const CSR_PMPADDR0: u16 = 0x3b0;
+pub const CSR_MCYCLE: u16 = 0xb00;
+pub const CSR_MINSTRET: u16 = 0xb02;
+pub const CSR_CYCLE: u16 = 0xc00;
+pub const CSR_INSTRET: u16 = 0xc02;
+pub const CSR_CYCLEH: u16 = 0xc80;
+pub const CSR_INSTRETH: u16 = 0xc82;
+
+// mstatus fields used on trap entry and exit.
+pub const MSTATUS_SIE: u64 = 1 << 1;
+pub const MSTATUS_MIE: u64 = 1 << 3;
+pub const MSTATUS_SPIE: u64 = 1 << 5;
+pub const MSTATUS_MPIE: u64 = 1 << 7;
+pub const MSTATUS_SPP: u64 = 1 << 8;
+pub const MSTATUS_MPP_SHIFT: u64 = 11;
+pub const MSTATUS_MPP: u64 = 0b11 << MSTATUS_MPP_SHIFT;
+pub const MSTATUS_FS_SHIFT: u64 = 13;
+pub const MSTATUS_FS: u64 = 0b11 << MSTATUS_FS_SHIFT;
+pub const MSTATUS_MPRV: u64 = 1 << 17;
+pub const MSTATUS_TSR: u64 = 1 << 22;
+pub const MSTATUS_SD: u64 = 1 << 63;
+
+// mstatus.FS once the floating point state has been written.
+pub const FS_DIRTY: u64 = 0b11;
+
+// satp fields for RV64.
+pub const SATP_MODE_SHIFT: u64 = 60;
+pub const SATP_PPN: u64 = (1 << 44) - 1;
+
+// The address matching mode of a PMP entry, in each byte of a pmpcfg CSR.
+// An entry is off when it is zero.
+pub const PMPCFG_A: u64 = 0b11 << 3;
+pub const PMP_ENTRIES: u16 = 64;
+
+// Inclusive ranges of CSR addresses that are assigned to a register by the
+// privileged spec. Accesses outside of these are to reserved addresses.
+pub const KNOWN_CSRS: [(u16, u16); 29] = [
+    (0x001, 0x003), // fflags, frm, fcsr
+    (0xc00, 0xc1f), // cycle, time, instret, hpmcounter3-31
+    (0xc80, 0xc9f), // cycleh, timeh, instreth, hpmcounter3h-31h
+    (0x100, 0x100), // sstatus
+    (0x104, 0x106), // sie, stvec, scounteren
+    (0x10a, 0x10a), // senvcfg
+    (0x140, 0x144), // sscratch, sepc, scause, stval, sip
+    (0x180, 0x180), // satp
+    (0x5a8, 0x5a8), // scontext
+    (0xf11, 0xf15), // mvendorid, marchid, mimpid, mhartid, mconfigptr
+    (0x300, 0x306), // mstatus, misa, medeleg, mideleg, mie, mtvec, mcounteren
+    (0x30a, 0x30a), // menvcfg
+    (0x310, 0x310), // mstatush
+    (0x31a, 0x31a), // menvcfgh
+    (0x320, 0x320), // mcountinhibit
+    (0x323, 0x33f), // mhpmevent3-31
+    (0x340, 0x344), // mscratch, mepc, mcause, mtval, mip
+    (0x34a, 0x34b), // mtinst, mtval2
+    (0x3a0, 0x3af), // pmpcfg0-15
+    (0x3b0, 0x3ef), // pmpaddr0-63
+    (0x747, 0x747), // mseccfg
+    (0x757, 0x757), // mseccfgh
+    (0xb00, 0xb00), // mcycle
+    (0xb02, 0xb1f), // minstret, mhpmcounter3-31
+    (0xb80, 0xb80), // mcycleh
+    (0xb82, 0xb9f), // minstreth, mhpmcounter3h-31h
+    (0x7a0, 0x7a3), // tselect, tdata1-3
+    (0x7a8, 0x7a8), // mcontext
+    (0x7b0, 0x7b3), // dcsr, dpc, dscratch0-1
+];
+
+pub fn is_known(addr: u16) -> bool {
+    KNOWN_CSRS.iter().any(|(start, end)| (*start..=*end).contains(&addr))
+}
+
+// Bits [11:10] of the address being 0b11 marks a CSR as read-only.
+pub fn is_read_only(addr: u16) -> bool {
+    (addr >> 10) & 0b11 == 0b11
+}
+
+// Bits [9:8] of the address encode the lowest privilege level that may
+// access the CSR (0 = U, 1 = S, 2 = H, 3 = M).
+pub fn min_privilege(addr: u16) -> u64 {
+    ((addr >> 8) & 0b11) as u64
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    Plain,
+    /// A privilege level, shown with its letter.
+    Privilege,
+    /// One bit per extension letter, shown as the letters that are set.
+    Extensions,
+}
+
+/// A bit field of a CSR, `width` bits wide starting at bit `shift`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldDef {
+    pub name: &'static str,
+    pub shift: u32,
+    pub width: u32,
+    pub kind: FieldKind,
+}
+
+const fn field(name: &'static str, shift: u32, width: u32) -> FieldDef {
+    FieldDef { name, shift, width, kind: FieldKind::Plain }
+}
+
+const fn privilege(name: &'static str, shift: u32, width: u32) -> FieldDef {
+    FieldDef { name, shift, width, kind: FieldKind::Privilege }
+}
+
+impl FieldDef {
+    pub fn extract(&self, val: u64) -> u64 {
+        (val >> self.shift) & (u64::MAX >> (64 - self.width))
+    }
+}
+
+/// The name of a CSR and the fields `dump` breaks its value into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CsrMeta {
+    pub name: &'static str,
+    pub fields: &'static [FieldDef],
+}
+
+const fn meta(name: &'static str) -> CsrMeta {
+    CsrMeta { name, fields: &[] }
+}
+
+const MSTATUS_FIELDS: [FieldDef; 19] = [
+    privilege("MPP", 11, 2), field("MPIE", 7, 1), field("MIE", 3, 1), privilege("SPP", 8, 1),
+    field("SPIE", 5, 1), field("SIE", 1, 1), field("UBE", 6, 1), field("VS", 9, 2), field("FS", 13, 2),
+    field("XS", 15, 2), field("MPRV", 17, 1), field("SUM", 18, 1), field("MXR", 19, 1), field("TVM", 20, 1),
+    field("TW", 21, 1), field("TSR", 22, 1), field("UXL", 32, 2), field("SXL", 34, 2), field("SD", 63, 1),
+];
+
+const SSTATUS_FIELDS: [FieldDef; 10] = [
+    privilege("SPP", 8, 1), field("SPIE", 5, 1), field("SIE", 1, 1), field("UBE", 6, 1), field("FS", 13, 2),
+    field("XS", 15, 2), field("SUM", 18, 1), field("MXR", 19, 1), field("UXL", 32, 2), field("SD", 63, 1),
+];
+
+const MISA_FIELDS: [FieldDef; 2] = [
+    field("MXL", 62, 2), FieldDef { name: "EXT", shift: 0, width: 26, kind: FieldKind::Extensions },
+];
+
+const MIP_FIELDS: [FieldDef; 6] = [
+    field("MEIP", 11, 1), field("MTIP", 7, 1), field("MSIP", 3, 1),
+    field("SEIP", 9, 1), field("STIP", 5, 1), field("SSIP", 1, 1),
+];
+
+const MIE_FIELDS: [FieldDef; 6] = [
+    field("MEIE", 11, 1), field("MTIE", 7, 1), field("MSIE", 3, 1),
+    field("SEIE", 9, 1), field("STIE", 5, 1), field("SSIE", 1, 1),
+];
+
+// The CSRs `dump` knows by name.
+pub const CSR_METADATA: [(u16, CsrMeta); 35] = [
+    (CSR_FFLAGS, meta("fflags")), (CSR_FRM, meta("frm")), (CSR_FCSR, meta("fcsr")),
+    (0xc00, meta("cycle")), (0xc01, meta("time")), (0xc02, meta("instret")),
+    (CSR_SSTATUS, CsrMeta { name: "sstatus", fields: &SSTATUS_FIELDS }),
+    (0x104, meta("sie")), (CSR_STVEC, meta("stvec")), (0x106, meta("scounteren")), (0x10a, meta("senvcfg")),
+    (0x140, meta("sscratch")), (CSR_SEPC, meta("sepc")), (CSR_SCAUSE, meta("scause")), (0x143, meta("stval")),
+    (0x144, meta("sip")), (CSR_SATP, meta("satp")),
+    (CSR_MSTATUS, CsrMeta { name: "mstatus", fields: &MSTATUS_FIELDS }),
+    (CSR_MISA, CsrMeta { name: "misa", fields: &MISA_FIELDS }),
+    (0x302, meta("medeleg")), (0x303, meta("mideleg")),
+    (CSR_MIE, CsrMeta { name: "mie", fields: &MIE_FIELDS }),
+    (CSR_MTVEC, meta("mtvec")), (0x306, meta("mcounteren")),
+    (CSR_MSCRATCH, meta("mscratch")), (CSR_MEPC, meta("mepc")), (CSR_MCAUSE, meta("mcause")),
+    (CSR_MTVAL, meta("mtval")),
+    (CSR_MIP, CsrMeta { name: "mip", fields: &MIP_FIELDS }),
+    (CSR_PMPCFG0, meta("pmpcfg0")), (CSR_PMPADDR0, meta("pmpaddr0")),
+    (0xf11, meta("mvendorid")), (0xf12, meta("marchid")), (0xf13, meta("mimpid")), (CSR_MHARTID, meta("mhartid")),
+];
+
+pub fn metadata(addr: u16) -> Option<&'static CsrMeta> {
+    CSR_METADATA.iter().find(|(csr, _)| *csr == addr).map(|(_, meta)| meta)
+}
+
+/// Format `val` as the value of the CSR at `addr`, e.g.
+/// `mstatus (0x300) = 0x0000000000001800 [MPP=3 (M), MPIE=0, ...]`.
+pub fn dump(addr: u16, val: u64) -> String {
+    let meta = match metadata(addr) {
+        Some(meta) => meta,
+        None => return format!("csr[{:#05x}] = {:#018x}", addr, val),
+    };
+
+    let mut line = format!("{} ({:#05x}) = {:#018x}", meta.name, addr, val);
+    if meta.fields.is_empty() {
+        return line;
+    }
+
+    let fields: Vec<String> = meta.fields.iter().map(|field| {
+        let bits = field.extract(val);
+        match field.kind {
+            FieldKind::Plain => format!("{}={}", field.name, bits),
+            FieldKind::Privilege => {
+                let level = ["U", "S", "?", "M"][bits as usize];
+                format!("{}={} ({})", field.name, bits, level)
+            },
+            FieldKind::Extensions => {
+                let letters: String = (0..field.width).filter(|bit| bits & (1 << bit) != 0)
+                    .map(|bit| (b'A' + bit as u8) as char)
+                    .collect();
+                format!("{}={}", field.name, letters)
+            },
+        }
+    }).collect();
+    line.push_str(&format!(" [{}]", fields.join(", ")));
+    line
+}
+
+/// The `misa` of a hart that implements `ext` on `base`, along with S-
+/// and U-mode: MXL in the top two bits and a bit per extension letter.
+/// G stands for IMAFD and, since its harts decode them, C and B as well.
+/// B means all of Zba, Zbb and Zbs, so a hart with just one of them, or
+/// with Zbc, reports no bit for it.
+pub fn misa(base: Base, ext: Extension) -> u64 {
+    let mxl = match base {
+        Base::I32 => 1 << 30,
+        Base::I64 => 2 << 62,
+    };
+    let letters = match ext {
+        Extension::I => "I",
+        Extension::M => "IM",
+        Extension::A => "IA",
+        Extension::F => "IF",
+        Extension::D => "IFD",
+        Extension::C => "IC",
+        Extension::Zba | Extension::Zbb | Extension::Zbc | Extension::Zbs => "I",
+        Extension::G => "IMAFDCB",
+    };
+    letters.bytes().chain("SU".bytes()).fold(mxl, |misa, letter| misa | 1 << (letter - b'A'))
+}
+
+/// The fields of an `mstatus` value. `SD` is not stored separately: it is
+/// set whenever `FS` is Dirty.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mstatus(pub u64);
+
+impl Mstatus {
+    fn bit(&self, mask: u64) -> bool {
+        self.0 & mask != 0
+    }
+
+    fn set_bit(&mut self, mask: u64, on: bool) {
+        if on {
+            self.0 |= mask;
+        } else {
+            self.0 &= !mask;
+        }
+    }
+
+    pub fn mie(&self) -> bool {
+        self.bit(MSTATUS_MIE)
+    }
+
+    pub fn set_mie(&mut self, on: bool) {
+        self.set_bit(MSTATUS_MIE, on);
+    }
+
+    pub fn mpie(&self) -> bool {
+        self.bit(MSTATUS_MPIE)
+    }
+
+    pub fn set_mpie(&mut self, on: bool) {
+        self.set_bit(MSTATUS_MPIE, on);
+    }
+
+    pub fn mpp(&self) -> PrivilegeLevel {
+        PrivilegeLevel::from((self.0 & MSTATUS_MPP) >> MSTATUS_MPP_SHIFT)
+    }
+
+    pub fn set_mpp(&mut self, level: PrivilegeLevel) {
+        self.0 = (self.0 & !MSTATUS_MPP) | u64::from(level) << MSTATUS_MPP_SHIFT;
+    }
+
+    pub fn sie(&self) -> bool {
+        self.bit(MSTATUS_SIE)
+    }
+
+    pub fn set_sie(&mut self, on: bool) {
+        self.set_bit(MSTATUS_SIE, on);
+    }
+
+    pub fn spie(&self) -> bool {
+        self.bit(MSTATUS_SPIE)
+    }
+
+    pub fn set_spie(&mut self, on: bool) {
+        self.set_bit(MSTATUS_SPIE, on);
+    }
+
+    /// The level `sret` returns to, which can only be U- or S-mode.
+    pub fn spp(&self) -> PrivilegeLevel {
+        if self.bit(MSTATUS_SPP) { PrivilegeLevel::Supervisor } else { PrivilegeLevel::User }
+    }
+
+    pub fn set_spp(&mut self, level: PrivilegeLevel) {
+        self.set_bit(MSTATUS_SPP, level != PrivilegeLevel::User);
+    }
+
+    pub fn mprv(&self) -> bool {
+        self.bit(MSTATUS_MPRV)
+    }
+
+    pub fn set_mprv(&mut self, on: bool) {
+        self.set_bit(MSTATUS_MPRV, on);
+    }
+
+    /// Trap SRET: `sret` in S-mode is an illegal instruction.
+    pub fn tsr(&self) -> bool {
+        self.bit(MSTATUS_TSR)
+    }
+
+    pub fn set_tsr(&mut self, on: bool) {
+        self.set_bit(MSTATUS_TSR, on);
+    }
+
+    pub fn fs(&self) -> u64 {
+        (self.0 & MSTATUS_FS) >> MSTATUS_FS_SHIFT
+    }
+
+    pub fn set_fs(&mut self, fs: u64) {
+        self.0 = (self.0 & !MSTATUS_FS) | (fs & 0b11) << MSTATUS_FS_SHIFT;
+        self.set_bit(MSTATUS_SD, fs & 0b11 == FS_DIRTY);
+    }
+
+    pub fn sd(&self) -> bool {
+        self.bit(MSTATUS_SD)
+    }
+}
@@ -0,0 +1,167 @@
+use crate::instructions::Instruction;
+use crate::instructions::Instruction::*;
+use crate::register::Register;
+
+pub(crate) const ABI_NAMES: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4", "a5",
+    "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4", "t5", "t6",
+];
+
+fn x(reg: Register) -> String {
+    ABI_NAMES.get(reg as usize).map_or_else(|| format!("x{}", reg as usize), |name| name.to_string())
+}
+
+fn f(reg: Register) -> String {
+    format!("f{}", reg as usize)
+}
+
+/// The assembler mnemonic of `instruction`, derived from its variant name:
+/// `FcvtWUS` is `fcvt.wu.s` and `AmoaddW` is `amoadd.w`.
+pub fn mnemonic(instruction: &Instruction) -> String {
+    match instruction {
+        Undefined => return "unknown".to_string(),
+        ECall => return "ecall".to_string(),
+        EBreak => return "ebreak".to_string(),
+        RemuW { .. } => return "remuw".to_string(),
+        _ => {},
+    }
+
+    let debug = format!("{:?}", instruction);
+    let name = debug.split(|c: char| !c.is_alphanumeric()).next().unwrap_or_default();
+    let mut parts: Vec<String> = vec![];
+    for c in name.chars() {
+        match parts.last_mut() {
+            // A lone U belongs to the type before it, as in `wu` or `lu`.
+            Some(last) if c == 'U' && last.len() == 1 => last.push('u'),
+            Some(last) if !c.is_uppercase() => last.push(c),
+            _ => parts.push(c.to_ascii_lowercase().to_string()),
+        }
+    }
+    parts.join(".")
+}
+
+/// Render `instruction`, located at `pc`, as assembly. Branch and `jal`
+/// targets are shown as absolute addresses.
+pub fn disassemble(instruction: &Instruction, pc: u64) -> String {
+    let target = |imm: i32| pc.wrapping_add(imm as i64 as u64);
+    let operands = match *instruction {
+        Lui { rd, imm } | Auipc { rd, imm } => format!("{}, {:#x}", x(rd), (imm as u32) >> 12),
+        Jal { rd, imm } => format!("{}, {:#x}", x(rd), target(imm)),
+        Jalr { rd, rs1, imm } => format!("{}, {}({})", x(rd), imm, x(rs1)),
+        Beq { rs1, rs2, imm, .. } | Bne { rs1, rs2, imm, .. } | Blt { rs1, rs2, imm, .. } |
+        Bge { rs1, rs2, imm, .. } | Bltu { rs1, rs2, imm, .. } | Bgeu { rs1, rs2, imm, .. } => {
+            format!("{}, {}, {:#x}", x(rs1), x(rs2), target(imm))
+        },
+        Lb { rd, rs1, imm, .. } | Lh { rd, rs1, imm, .. } | Lw { rd, rs1, imm, .. } | Lbu { rd, rs1, imm, .. } |
+        Lhu { rd, rs1, imm, .. } | Lwu { rd, rs1, imm, .. } | Ld { rd, rs1, imm, .. } => {
+            format!("{}, {}({})", x(rd), imm, x(rs1))
+        },
+        Flw { rd, rs1, imm } | Fld { rd, rs1, imm } | Flq { rd, rs1, imm } => format!("{}, {}({})", f(rd), imm, x(rs1)),
+        Sb { rs1, rs2, imm, .. } | Sh { rs1, rs2, imm, .. } | Sw { rs1, rs2, imm, .. } | Sd { rs1, rs2, imm, .. } => {
+            format!("{}, {}({})", x(rs2), imm, x(rs1))
+        },
+        Fsw { rs1, rs2, imm } | Fsd { rs1, rs2, imm } | Fsq { rs1, rs2, imm } => format!("{}, {}({})", f(rs2), imm, x(rs1)),
+        Addi { rd, rs1, imm, .. } | Slti { rd, rs1, imm, .. } | Sltiu { rd, rs1, imm, .. } | Xori { rd, rs1, imm, .. } |
+        Ori { rd, rs1, imm, .. } | Andi { rd, rs1, imm, .. } | Addiw { rd, rs1, imm, .. } => {
+            format!("{}, {}, {}", x(rd), x(rs1), imm)
+        },
+        Slli { rd, rs1, shamt, .. } | Srli { rd, rs1, shamt, .. } | Srai { rd, rs1, shamt, .. } |
+        Slliw { rd, rs1, shamt, .. } | Srliw { rd, rs1, shamt, .. } | Sraiw { rd, rs1, shamt, .. } => {
+            format!("{}, {}, {}", x(rd), x(rs1), shamt)
+        },
+        Add { rd, rs1, rs2, .. } | Sub { rd, rs1, rs2, .. } | Sll { rd, rs1, rs2, .. } | Slt { rd, rs1, rs2, .. } |
+        Sltu { rd, rs1, rs2, .. } | Xor { rd, rs1, rs2, .. } | Srl { rd, rs1, rs2, .. } | Sra { rd, rs1, rs2, .. } |
+        Or { rd, rs1, rs2, .. } | And { rd, rs1, rs2, .. } | Addw { rd, rs1, rs2, .. } | Subw { rd, rs1, rs2, .. } |
+        Sllw { rd, rs1, rs2, .. } | Srlw { rd, rs1, rs2, .. } | Sraw { rd, rs1, rs2, .. } | Mul { rd, rs1, rs2, .. } |
+        Mulh { rd, rs1, rs2, .. } | Mulhsu { rd, rs1, rs2, .. } | Mulhu { rd, rs1, rs2, .. } | Div { rd, rs1, rs2, .. } |
+        Divu { rd, rs1, rs2, .. } | Rem { rd, rs1, rs2, .. } | Remu { rd, rs1, rs2, .. } | Mulw { rd, rs1, rs2, .. } |
+        Divw { rd, rs1, rs2, .. } | Divuw { rd, rs1, rs2, .. } | Remw { rd, rs1, rs2, .. } | RemuW { rd, rs1, rs2, .. } => {
+            format!("{}, {}, {}", x(rd), x(rs1), x(rs2))
+        },
+        Csrrw { rd, rs1, csr, .. } | Csrrs { rd, rs1, csr, .. } | Csrrc { rd, rs1, csr, .. } => {
+            format!("{}, {:#x}, {}", x(rd), csr, x(rs1))
+        },
+        Csrrwi { rd, uimm, csr, .. } | Csrrsi { rd, uimm, csr, .. } | Csrrci { rd, uimm, csr, .. } => {
+            format!("{}, {:#x}, {}", x(rd), csr, uimm)
+        },
+        LrW { rd, rs1, .. } | LrD { rd, rs1, .. } => format!("{}, ({})", x(rd), x(rs1)),
+        ScW { rd, rs1, rs2, .. } | AmoswapW { rd, rs1, rs2, .. } | AmoaddW { rd, rs1, rs2, .. } |
+        AmoxorW { rd, rs1, rs2, .. } | AmoandW { rd, rs1, rs2, .. } | AmoorW { rd, rs1, rs2, .. } |
+        AmominW { rd, rs1, rs2, .. } | AmomaxW { rd, rs1, rs2, .. } | AmominuW { rd, rs1, rs2, .. } |
+        AmomaxuW { rd, rs1, rs2, .. } | ScD { rd, rs1, rs2, .. } | AmoswapD { rd, rs1, rs2, .. } |
+        AmoaddD { rd, rs1, rs2, .. } | AmoxorD { rd, rs1, rs2, .. } | AmoandD { rd, rs1, rs2, .. } |
+        AmoorD { rd, rs1, rs2, .. } | AmominD { rd, rs1, rs2, .. } | AmomaxD { rd, rs1, rs2, .. } |
+        AmominuD { rd, rs1, rs2, .. } | AmomaxuD { rd, rs1, rs2, .. } => format!("{}, {}, ({})", x(rd), x(rs2), x(rs1)),
+        FmaddS { rd, rs1, rs2, rs3, .. } | FmsubS { rd, rs1, rs2, rs3, .. } | FnmsubS { rd, rs1, rs2, rs3, .. } |
+        FnmaddS { rd, rs1, rs2, rs3, .. } | FmaddD { rd, rs1, rs2, rs3, .. } | FmsubD { rd, rs1, rs2, rs3, .. } |
+        FnmsubD { rd, rs1, rs2, rs3, .. } | FnmaddD { rd, rs1, rs2, rs3, .. } | FmaddQ { rd, rs1, rs2, rs3, .. } |
+        FmsubQ { rd, rs1, rs2, rs3, .. } | FnmsubQ { rd, rs1, rs2, rs3, .. } | FnmaddQ { rd, rs1, rs2, rs3, .. } => {
+            format!("{}, {}, {}, {}", f(rd), f(rs1), f(rs2), f(rs3))
+        },
+        FaddS { rd, rs1, rs2, .. } | FsubS { rd, rs1, rs2, .. } | FmulS { rd, rs1, rs2, .. } | FdivS { rd, rs1, rs2, .. } |
+        FsgnjS { rd, rs1, rs2 } | FsgnjnS { rd, rs1, rs2 } | FsgnjxS { rd, rs1, rs2 } | FminS { rd, rs1, rs2 } |
+        FmaxS { rd, rs1, rs2 } | FaddD { rd, rs1, rs2, .. } | FsubD { rd, rs1, rs2, .. } | FmulD { rd, rs1, rs2, .. } |
+        FdivD { rd, rs1, rs2, .. } | FsgnjD { rd, rs1, rs2 } | FsgnjnD { rd, rs1, rs2 } | FsgnjxD { rd, rs1, rs2 } |
+        FminD { rd, rs1, rs2 } | FmaxD { rd, rs1, rs2 } | FaddQ { rd, rs1, rs2, .. } | FsubQ { rd, rs1, rs2, .. } |
+        FmulQ { rd, rs1, rs2, .. } | FdivQ { rd, rs1, rs2, .. } | FsgnjQ { rd, rs1, rs2 } | FsgnjnQ { rd, rs1, rs2 } |
+        FsgnjxQ { rd, rs1, rs2 } | FminQ { rd, rs1, rs2 } | FmaxQ { rd, rs1, rs2 } => {
+            format!("{}, {}, {}", f(rd), f(rs1), f(rs2))
+        },
+        FeqS { rd, rs1, rs2 } | FltS { rd, rs1, rs2 } | FleS { rd, rs1, rs2 } | FeqD { rd, rs1, rs2 } |
+        FltD { rd, rs1, rs2 } | FleD { rd, rs1, rs2 } | FeqQ { rd, rs1, rs2 } | FltQ { rd, rs1, rs2 } |
+        FleQ { rd, rs1, rs2 } => format!("{}, {}, {}", x(rd), f(rs1), f(rs2)),
+        FsqrtS { rd, rs1, .. } | FsqrtD { rd, rs1, .. } | FsqrtQ { rd, rs1, .. } | FcvtSD { rd, rs1, .. } |
+        FcvtDS { rd, rs1, .. } | FcvtSQ { rd, rs1, .. } | FcvtQS { rd, rs1, .. } | FcvtDQ { rd, rs1, .. } |
+        FcvtQD { rd, rs1, .. } => format!("{}, {}", f(rd), f(rs1)),
+        FcvtWS { rd, rs1, .. } | FcvtWUS { rd, rs1, .. } | FcvtLS { rd, rs1, .. } | FcvtLUS { rd, rs1, .. } |
+        FcvtWD { rd, rs1, .. } | FcvtWUD { rd, rs1, .. } | FcvtLD { rd, rs1, .. } | FcvtLUD { rd, rs1, .. } |
+        FcvtWQ { rd, rs1, .. } | FcvtWUQ { rd, rs1, .. } | FcvtLQ { rd, rs1, .. } | FcvtLUQ { rd, rs1, .. } |
+        FmvXW { rd, rs1 } | FmvXD { rd, rs1 } | FclassS { rd, rs1 } | FclassD { rd, rs1 } | FclassQ { rd, rs1 } => {
+            format!("{}, {}", x(rd), f(rs1))
+        },
+        FcvtSW { rd, rs1, .. } | FcvtSWU { rd, rs1, .. } | FcvtSL { rd, rs1, .. } | FcvtSLU { rd, rs1, .. } |
+        FcvtDW { rd, rs1, .. } | FcvtDWU { rd, rs1, .. } | FcvtDL { rd, rs1, .. } | FcvtDLU { rd, rs1, .. } |
+        FcvtQW { rd, rs1, .. } | FcvtQWU { rd, rs1, .. } | FcvtQL { rd, rs1, .. } | FcvtQLU { rd, rs1, .. } |
+        FmvWX { rd, rs1 } | FmvDX { rd, rs1 } => format!("{}, {}", f(rd), x(rs1)),
+        SfenceVma { rs1, rs2 } => format!("{}, {}", x(rs1), x(rs2)),
+        CAddi4spn { rd, imm } => format!("{}, sp, {}", x(rd), imm),
+        CLw { rd, rs1, imm } | CLd { rd, rs1, imm } => format!("{}, {}({})", x(rd), imm, x(rs1)),
+        CFld { rd, rs1, imm } => format!("{}, {}({})", f(rd), imm, x(rs1)),
+        CSw { rs1, rs2, imm } | CSd { rs1, rs2, imm } => format!("{}, {}({})", x(rs2), imm, x(rs1)),
+        CFsd { rs1, rs2, imm } => format!("{}, {}({})", f(rs2), imm, x(rs1)),
+        CAddi { rd, imm } | CAddiw { rd, imm } | CLi { rd, imm } | CAndi { rd, imm } => format!("{}, {}", x(rd), imm),
+        CAddi16sp { imm } => format!("sp, {}", imm),
+        CLui { rd, imm } => format!("{}, {:#x}", x(rd), (imm as u32 >> 12) & 0xfffff),
+        CSrli { rd, shamt } | CSrai { rd, shamt } | CSlli { rd, shamt } => format!("{}, {}", x(rd), shamt),
+        CSub { rd, rs2 } | CXor { rd, rs2 } | COr { rd, rs2 } | CAnd { rd, rs2 } | CSubw { rd, rs2 } |
+        CAddw { rd, rs2 } | CMv { rd, rs2 } | CAdd { rd, rs2 } => format!("{}, {}", x(rd), x(rs2)),
+        CJ { imm } => format!("{:#x}", target(imm)),
+        CBeqz { rs1, imm } | CBnez { rs1, imm } => format!("{}, {:#x}", x(rs1), target(imm)),
+        CLwsp { rd, imm } | CLdsp { rd, imm } => format!("{}, {}(sp)", x(rd), imm),
+        CFldsp { rd, imm } => format!("{}, {}(sp)", f(rd), imm),
+        CSwsp { rs2, imm } | CSdsp { rs2, imm } => format!("{}, {}(sp)", x(rs2), imm),
+        CFsdsp { rs2, imm } => format!("{}, {}(sp)", f(rs2), imm),
+        CJr { rs1 } | CJalr { rs1 } => x(rs1),
+        Sh1add { rd, rs1, rs2 } | Sh2add { rd, rs1, rs2 } | Sh3add { rd, rs1, rs2 } | AddUw { rd, rs1, rs2 } |
+        Sh1addUw { rd, rs1, rs2 } | Sh2addUw { rd, rs1, rs2 } | Sh3addUw { rd, rs1, rs2 } | Andn { rd, rs1, rs2 } |
+        Orn { rd, rs1, rs2 } | Xnor { rd, rs1, rs2 } | Max { rd, rs1, rs2 } | Maxu { rd, rs1, rs2 } |
+        Min { rd, rs1, rs2 } | Minu { rd, rs1, rs2 } | Rol { rd, rs1, rs2 } | Ror { rd, rs1, rs2 } |
+        Rolw { rd, rs1, rs2 } | Rorw { rd, rs1, rs2 } | Clmul { rd, rs1, rs2 } | Clmulh { rd, rs1, rs2 } |
+        Clmulr { rd, rs1, rs2 } | Bclr { rd, rs1, rs2 } | Bext { rd, rs1, rs2 } | Binv { rd, rs1, rs2 } |
+        Bset { rd, rs1, rs2 } => format!("{}, {}, {}", x(rd), x(rs1), x(rs2)),
+        Clz { rd, rs1 } | Ctz { rd, rs1 } | Cpop { rd, rs1 } | Clzw { rd, rs1 } | Ctzw { rd, rs1 } |
+        Cpopw { rd, rs1 } | SextB { rd, rs1 } | SextH { rd, rs1 } | ZextH { rd, rs1 } | OrcB { rd, rs1 } |
+        Rev8 { rd, rs1 } => format!("{}, {}", x(rd), x(rs1)),
+        SlliUw { rd, rs1, shamt } | Rori { rd, rs1, shamt } | Roriw { rd, rs1, shamt } | Bclri { rd, rs1, shamt } |
+        Bexti { rd, rs1, shamt } | Binvi { rd, rs1, shamt } | Bseti { rd, rs1, shamt } => {
+            format!("{}, {}, {}", x(rd), x(rs1), shamt)
+        },
+        Undefined | Fence { .. } | ECall | EBreak | Mret | Sret | Wfi | FenceI { .. } | CNop | CEbreak => String::new(),
+    };
+
+    if operands.is_empty() {
+        mnemonic(instruction)
+    } else {
+        format!("{} {}", mnemonic(instruction), operands)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::exceptions::Exception;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+use std::io;
+
+// Flattened device trees start with this magic, stored big endian.
+pub const DTB_MAGIC: u32 = 0xd00d_feed;
+
+#[derive(Debug)]
+pub enum DtbError {
+    BadMagic,
+    OverlapsCode,
+    OutOfBounds,
+    Io(io::Error),
+}
+
+impl Display for DtbError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for DtbError {}
+
+impl From<io::Error> for DtbError {
+    fn from(e: io::Error) -> DtbError {
+        DtbError::Io(e)
+    }
+}
+
+impl From<Exception> for DtbError {
+    fn from(_: Exception) -> DtbError {
+        DtbError::OutOfBounds
+    }
+}
+
+pub fn has_magic(dtb: &[u8]) -> bool {
+    dtb.len() >= 4 && u32::from_be_bytes([dtb[0], dtb[1], dtb[2], dtb[3]]) == DTB_MAGIC
+}
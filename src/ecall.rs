@@ -0,0 +1,132 @@
+use crate::exceptions::Exception;
+use crate::linux::ENOSYS;
+use crate::memory::Dram;
+use crate::soft::SoftThread;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+/// A Linux error number, returned to the program negated in `a0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Errno(pub i64);
+
+/// The outcome of a syscall. `return_value` is written to `a0`, unless
+/// `error` is set, in which case `a0` gets the negated error number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EcallResult {
+    pub return_value: i64,
+    pub error: Option<Errno>,
+}
+
+impl EcallResult {
+    pub fn ok(return_value: i64) -> EcallResult {
+        EcallResult { return_value, error: None }
+    }
+
+    pub fn err(errno: Errno) -> EcallResult {
+        EcallResult { return_value: -1, error: Some(errno) }
+    }
+
+    /// The value the program sees in `a0`.
+    pub fn a0(&self) -> i64 {
+        self.error.map_or(self.return_value, |errno| -errno.0)
+    }
+}
+
+pub type EcallHandler<M = Dram> = Box<dyn FnMut(&mut SoftThread<u64, f64, M>) -> EcallResult>;
+
+/// Services every syscall without an `EcallHandler` of its own, given the
+/// number from `a7`. It finds the arguments in `a0`-`a5` and leaves its
+/// result in `a0`.
+pub type SyscallHandler<M = Dram> = Box<dyn FnMut(&mut SoftThread<u64, f64, M>, u64) -> Result<(), Exception>>;
+
+/// The outcome of an SBI call. `error` is written to `a0` and `value` to
+/// `a1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SbiResult {
+    pub error: i64,
+    pub value: i64,
+}
+
+/// The SBI error for a call the handler does not implement.
+pub const SBI_ERR_NOT_SUPPORTED: i64 = -2;
+
+impl SbiResult {
+    pub fn ok(value: i64) -> SbiResult {
+        SbiResult { error: 0, value }
+    }
+
+    pub fn err(error: i64) -> SbiResult {
+        SbiResult { error, value: 0 }
+    }
+}
+
+/// Services an SBI call made by an `ecall` from supervisor mode, given
+/// the extension id from `a7` and the function id from `a6`. It finds
+/// the arguments in `a0`-`a5`.
+pub type SbiCallback<M = Dram> = Box<dyn FnMut(&mut SoftThread<u64, f64, M>, u64, u64) -> SbiResult>;
+
+/// The callback `SoftThread::set_sbi_handler` installs.
+pub struct SbiHook<M = Dram>(pub SbiCallback<M>);
+
+impl<M> Debug for SbiHook<M> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("SbiHook").finish_non_exhaustive()
+    }
+}
+
+/// Handlers for individual syscalls, by the number the program puts in
+/// `a7`, and the `fallback` for the rest.
+pub struct SyscallRouter<M = Dram> {
+    handlers: HashMap<u64, EcallHandler<M>>,
+    pub fallback: Option<SyscallHandler<M>>,
+}
+
+impl<M> SyscallRouter<M> {
+    pub fn new() -> SyscallRouter<M> {
+        SyscallRouter { handlers: HashMap::new(), fallback: None }
+    }
+
+    /// Replace the handler for syscall `number`, or add one.
+    pub fn register(&mut self, number: u64, handler: EcallHandler<M>) {
+        self.handlers.insert(number, handler);
+    }
+
+    /// Take over the handlers of `other`, replacing any for the same
+    /// syscalls.
+    pub fn extend(&mut self, other: SyscallRouter<M>) {
+        self.handlers.extend(other.handlers);
+        if other.fallback.is_some() {
+            self.fallback = other.fallback;
+        }
+    }
+
+    pub fn handles(&self, number: u64) -> bool {
+        self.handlers.contains_key(&number)
+    }
+
+    /// Run the handler for syscall `number` on `soft`. Numbers without a
+    /// handler fail with `ENOSYS`.
+    pub fn dispatch(&mut self, number: u64, soft: &mut SoftThread<u64, f64, M>) -> EcallResult {
+        match self.handlers.get_mut(&number) {
+            Some(handler) => handler(soft),
+            None => EcallResult::err(Errno(ENOSYS)),
+        }
+    }
+}
+
+impl<M> Default for SyscallRouter<M> {
+    fn default() -> SyscallRouter<M> {
+        SyscallRouter::new()
+    }
+}
+
+impl<M> Debug for SyscallRouter<M> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let mut numbers: Vec<&u64> = self.handlers.keys().collect();
+        numbers.sort_unstable();
+        f.debug_struct("SyscallRouter")
+            .field("handlers", &numbers)
+            .field("fallback", &self.fallback.is_some())
+            .finish()
+    }
+}
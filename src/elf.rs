@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+use std::io::{self, Write};
+
+pub const ELF_MAGIC: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+pub const ELFCLASS64: u8 = 2;
+pub const ELFDATA2LSB: u8 = 1;
+pub const EM_RISCV: u16 = 243;
+pub const ET_CORE: u16 = 4;
+pub const EV_CURRENT: u8 = 1;
+pub const EF_RISCV_FLOAT_ABI_DOUBLE: u32 = 0x4;
+pub const PT_LOAD: u32 = 1;
+pub const PT_NOTE: u32 = 4;
+pub const SHT_SYMTAB: u32 = 2;
+
+// Symbol types in the low nibble of `st_info`.
+pub const STT_NOTYPE: u8 = 0;
+pub const STT_OBJECT: u8 = 1;
+pub const STT_FUNC: u8 = 2;
+
+// Segment permission bits in `p_flags`.
+pub const PF_X: u32 = 1 << 0;
+pub const PF_W: u32 = 1 << 1;
+pub const PF_R: u32 = 1 << 2;
+
+// Note types of a core file.
+pub const NT_PRSTATUS: u32 = 1;
+pub const NT_PRFPREG: u32 = 2;
+
+// Layout of the RV64 Linux `elf_prstatus`, whose `pr_reg` holds the pc
+// followed by x1-x31, and `__riscv_d_ext_state`, which holds f0-f31 then
+// fcsr padded to a doubleword.
+pub const PRSTATUS_SIZE: usize = 376;
+pub const PRSTATUS_PID: usize = 32;
+pub const PRSTATUS_REG: usize = 112;
+pub const PRFPREG_SIZE: usize = 264;
+
+const EHDR_SIZE: usize = 64;
+const PHDR_SIZE: usize = 56;
+const SHDR_SIZE: usize = 64;
+const SYM_SIZE: usize = 24;
+
+#[derive(Debug, PartialEq)]
+pub enum ElfError {
+    BadMagic,
+    UnsupportedClass,
+    UnsupportedEndianness,
+    UnsupportedMachine(u16),
+    Truncated,
+}
+
+impl Display for ElfError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for ElfError {}
+
+/// A loadable segment of an ELF file. `data` holds the `p_filesz` bytes
+/// from the file; the remaining `memsz - data.len()` bytes are zero.
+#[derive(Debug, PartialEq)]
+pub struct Segment {
+    pub vaddr: u64,
+    pub memsz: u64,
+    pub flags: u32,
+    pub data: Vec<u8>,
+}
+
+/// The parts of a little endian RV64 ELF executable needed to load and
+/// start it. `symbols` maps the addresses of the named functions and
+/// objects in `.symtab` to their names, and is empty for a stripped file.
+#[derive(Debug, PartialEq)]
+pub struct Elf {
+    pub entry: u64,
+    pub segments: Vec<Segment>,
+    pub symbols: HashMap<u64, String>,
+}
+
+impl Elf {
+    pub fn parse(bytes: &[u8]) -> std::result::Result<Elf, ElfError> {
+        if bytes.len() < EHDR_SIZE {
+            return Err(ElfError::Truncated);
+        }
+
+        if bytes[..4] != ELF_MAGIC {
+            return Err(ElfError::BadMagic);
+        }
+
+        if bytes[4] != ELFCLASS64 {
+            return Err(ElfError::UnsupportedClass);
+        }
+
+        if bytes[5] != ELFDATA2LSB {
+            return Err(ElfError::UnsupportedEndianness);
+        }
+
+        let machine = read_u16(bytes, 18)?;
+        if machine != EM_RISCV {
+            return Err(ElfError::UnsupportedMachine(machine));
+        }
+
+        let entry = read_u64(bytes, 24)?;
+        let phoff = read_u64(bytes, 32)? as usize;
+        let phentsize = read_u16(bytes, 54)? as usize;
+        let phnum = read_u16(bytes, 56)? as usize;
+
+        let mut segments = vec![];
+        for idx in 0..phnum {
+            let phdr = entry_at(phoff, idx, phentsize.max(PHDR_SIZE))?;
+            if read_u32(bytes, phdr)? != PT_LOAD {
+                continue;
+            }
+
+            let flags = read_u32(bytes, phdr + 4)?;
+            let offset = read_u64(bytes, phdr + 8)? as usize;
+            let vaddr = read_u64(bytes, phdr + 16)?;
+            let filesz = read_u64(bytes, phdr + 32)? as usize;
+            let memsz = read_u64(bytes, phdr + 40)?;
+            let data = field(bytes, offset, filesz)?;
+
+            segments.push(Segment { vaddr, memsz, flags, data: data.to_vec() });
+        }
+
+        let symbols = parse_symbols(bytes);
+
+        Ok(Elf { entry, segments, symbols })
+    }
+}
+
+/// A note of a `PT_NOTE` segment.
+#[derive(Debug, PartialEq)]
+pub struct Note {
+    pub name: String,
+    pub kind: u32,
+    pub desc: Vec<u8>,
+}
+
+/// The notes of every `PT_NOTE` segment of `bytes`, in file order.
+pub fn parse_notes(bytes: &[u8]) -> std::result::Result<Vec<Note>, ElfError> {
+    let phoff = read_u64(bytes, 32)? as usize;
+    let phentsize = read_u16(bytes, 54)? as usize;
+    let phnum = read_u16(bytes, 56)? as usize;
+
+    let mut notes = vec![];
+    for idx in 0..phnum {
+        let phdr = entry_at(phoff, idx, phentsize.max(PHDR_SIZE))?;
+        if read_u32(bytes, phdr)? != PT_NOTE {
+            continue;
+        }
+
+        let mut at = read_u64(bytes, phdr + 8)? as usize;
+        let end = at.checked_add(read_u64(bytes, phdr + 32)? as usize).ok_or(ElfError::Truncated)?;
+        while at < end {
+            let namesz = read_u32(bytes, at)? as usize;
+            let descsz = read_u32(bytes, at + 4)? as usize;
+            let kind = read_u32(bytes, at + 8)?;
+            let name = read_str(bytes, at + 12)?;
+            let desc = at + 12 + align4(namesz);
+            let desc = field(bytes, desc, descsz)?.to_vec();
+
+            notes.push(Note { name, kind, desc });
+            at += 12 + align4(namesz) + align4(descsz);
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Memory written to a core file by `write_core`.
+#[derive(Debug)]
+pub struct CoreSegment<'a> {
+    pub vaddr: u64,
+    pub flags: u32,
+    pub data: &'a [u8],
+}
+
+/// Write an RV64 Linux style core file: a `PT_NOTE` segment with an
+/// `NT_PRSTATUS` note for `gregs`, the pc followed by x1-x31, and an
+/// `NT_PRFPREG` note for `fregs` and `fcsr`, then a `PT_LOAD` segment for
+/// each of `segments`.
+pub fn write_core<W: Write>(out: &mut W, gregs: &[u64; 32], fregs: &[u64; 32], fcsr: u32, segments: &[CoreSegment]) -> io::Result<()> {
+    let mut prstatus = vec![0u8; PRSTATUS_SIZE];
+    prstatus[PRSTATUS_PID..PRSTATUS_PID + 4].copy_from_slice(&1u32.to_le_bytes());
+    for (idx, reg) in gregs.iter().enumerate() {
+        let at = PRSTATUS_REG + idx * 8;
+        prstatus[at..at + 8].copy_from_slice(&reg.to_le_bytes());
+    }
+
+    let mut prfpreg = vec![0u8; PRFPREG_SIZE];
+    for (idx, reg) in fregs.iter().enumerate() {
+        prfpreg[idx * 8..idx * 8 + 8].copy_from_slice(&reg.to_le_bytes());
+    }
+    prfpreg[256..260].copy_from_slice(&fcsr.to_le_bytes());
+
+    let mut notes = vec![];
+    for (kind, desc) in [(NT_PRSTATUS, prstatus), (NT_PRFPREG, prfpreg)] {
+        notes.extend_from_slice(&5u32.to_le_bytes());
+        notes.extend_from_slice(&(desc.len() as u32).to_le_bytes());
+        notes.extend_from_slice(&kind.to_le_bytes());
+        notes.extend_from_slice(b"CORE\0\0\0\0");
+        notes.extend_from_slice(&desc);
+        notes.resize(align4(notes.len()), 0);
+    }
+
+    let phnum = 1 + segments.len();
+    let mut offset = (EHDR_SIZE + phnum * PHDR_SIZE) as u64;
+    let mut ehdr = [0u8; EHDR_SIZE];
+    ehdr[..4].copy_from_slice(&ELF_MAGIC);
+    ehdr[4] = ELFCLASS64;
+    ehdr[5] = ELFDATA2LSB;
+    ehdr[6] = EV_CURRENT;
+    ehdr[16..18].copy_from_slice(&ET_CORE.to_le_bytes());
+    ehdr[18..20].copy_from_slice(&EM_RISCV.to_le_bytes());
+    ehdr[20..24].copy_from_slice(&(EV_CURRENT as u32).to_le_bytes());
+    ehdr[32..40].copy_from_slice(&(EHDR_SIZE as u64).to_le_bytes());
+    ehdr[48..52].copy_from_slice(&EF_RISCV_FLOAT_ABI_DOUBLE.to_le_bytes());
+    ehdr[52..54].copy_from_slice(&(EHDR_SIZE as u16).to_le_bytes());
+    ehdr[54..56].copy_from_slice(&(PHDR_SIZE as u16).to_le_bytes());
+    ehdr[56..58].copy_from_slice(&(phnum as u16).to_le_bytes());
+    out.write_all(&ehdr)?;
+
+    out.write_all(&phdr(PT_NOTE, 0, offset, 0, notes.len() as u64, 4))?;
+    offset += notes.len() as u64;
+    for segment in segments {
+        let len = segment.data.len() as u64;
+        out.write_all(&phdr(PT_LOAD, segment.flags, offset, segment.vaddr, len, 1))?;
+        offset += len;
+    }
+
+    out.write_all(&notes)?;
+    for segment in segments {
+        out.write_all(segment.data)?;
+    }
+
+    Ok(())
+}
+
+fn phdr(kind: u32, flags: u32, offset: u64, vaddr: u64, size: u64, align: u64) -> [u8; PHDR_SIZE] {
+    let mut phdr = [0u8; PHDR_SIZE];
+    phdr[0..4].copy_from_slice(&kind.to_le_bytes());
+    phdr[4..8].copy_from_slice(&flags.to_le_bytes());
+    for (at, val) in [(8, offset), (16, vaddr), (24, vaddr), (32, size), (40, size), (48, align)] {
+        phdr[at..at + 8].copy_from_slice(&val.to_le_bytes());
+    }
+    phdr
+}
+
+fn align4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+// Collect the defined function, object and untyped symbols of every
+// symbol table section. Assembler mapping symbols such as `$x` are skipped
+// and the first name seen for an address wins. Symbols only help with
+// disassembly, so a malformed section header table yields none instead of
+// failing the load.
+fn parse_symbols(bytes: &[u8]) -> HashMap<u64, String> {
+    let mut symbols = HashMap::new();
+    if read_symbols(bytes, &mut symbols).is_err() {
+        symbols.clear();
+    }
+    symbols
+}
+
+fn read_symbols(bytes: &[u8], symbols: &mut HashMap<u64, String>) -> std::result::Result<(), ElfError> {
+    let shoff = read_u64(bytes, 40)? as usize;
+    let shentsize = read_u16(bytes, 58)? as usize;
+    let shnum = read_u16(bytes, 60)? as usize;
+    if shoff == 0 {
+        return Ok(());
+    }
+
+    let section = |idx: usize| field(bytes, entry_at(shoff, idx, shentsize.max(SHDR_SIZE))?, SHDR_SIZE);
+    for idx in 0..shnum {
+        let shdr = section(idx)?;
+        if read_u32(shdr, 4)? != SHT_SYMTAB {
+            continue;
+        }
+
+        let offset = read_u64(shdr, 24)? as usize;
+        let size = read_u64(shdr, 32)? as usize;
+        let strtab = read_u64(section(read_u32(shdr, 40)? as usize)?, 24)? as usize;
+
+        for sym in (offset..offset.checked_add(size).ok_or(ElfError::Truncated)?).step_by(SYM_SIZE) {
+            let sym = field(bytes, sym, SYM_SIZE)?;
+            let info = sym[4];
+            let shndx = read_u16(sym, 6)?;
+            let value = read_u64(sym, 8)?;
+            if shndx == 0 || ![STT_NOTYPE, STT_OBJECT, STT_FUNC].contains(&(info & 0xf)) {
+                continue;
+            }
+
+            let name = read_str(bytes, strtab.checked_add(read_u32(sym, 0)? as usize).ok_or(ElfError::Truncated)?)?;
+            if !name.is_empty() && !name.starts_with('$') {
+                symbols.entry(value).or_insert(name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn read_str(bytes: &[u8], at: usize) -> std::result::Result<String, ElfError> {
+    let b = bytes.get(at..).ok_or(ElfError::Truncated)?;
+    let len = b.iter().position(|&c| c == 0).ok_or(ElfError::Truncated)?;
+    Ok(String::from_utf8_lossy(&b[..len]).into_owned())
+}
+
+// The offset of entry `idx` of a table at `offset` with entries of
+// `size` bytes.
+fn entry_at(offset: usize, idx: usize, size: usize) -> std::result::Result<usize, ElfError> {
+    idx.checked_mul(size).and_then(|at| offset.checked_add(at)).ok_or(ElfError::Truncated)
+}
+
+// The `len` bytes of `bytes` at `at`.
+fn field(bytes: &[u8], at: usize, len: usize) -> std::result::Result<&[u8], ElfError> {
+    at.checked_add(len).and_then(|end| bytes.get(at..end)).ok_or(ElfError::Truncated)
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> std::result::Result<u16, ElfError> {
+    let b = field(bytes, at, 2)?;
+    Ok(u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> std::result::Result<u32, ElfError> {
+    let b = field(bytes, at, 4)?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> std::result::Result<u64, ElfError> {
+    let b = field(bytes, at, 8)?;
+    Ok(u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+}
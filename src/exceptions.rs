@@ -1,40 +1,143 @@
-use std::error::Error;
-use std::fmt::{Display, Formatter, Result};
-
-#[derive(Debug, PartialEq)]
-pub enum Exception {
-    AddressMisaligned,
-    AccessFault,
-    Invalid(u64),
-    Breakpoint,
-    LoadAddressMisaligned,
-    LoadAccessFault,
-    StoreAMOAccessFault,
-    StoreAMOAddressMisaligned,
-    EnvironmentCallFromUMode,
-    EnvironmentCallFromSMode,
-    EnvironmentCallFromMMode,
-    InstructionPageFault(u64),
-    LoadPageFault(u64),
-    StoreAMOPageFault(u64),
-    StackSizeExceeded,
-    InvalidAddr,
-    LoadFromBuffer,
-    General,
-}
-
-#[derive(Debug)]
-pub enum Trap {
-    Contained,
-    Requested,
-    Invisible,
-    Fatal
-}
-
-impl Display for Exception {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-impl Error for Exception {}
+use crate::sanitizer::SanitizerKind;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, PartialEq)]
+pub enum Exception {
+    AddressMisaligned,
+    AccessFault,
+    Invalid(u64),
+    Breakpoint,
+    LoadAddressMisaligned,
+    LoadAccessFault,
+    StoreAMOAccessFault,
+    StoreAMOAddressMisaligned,
+    EnvironmentCallFromUMode,
+    EnvironmentCallFromSMode,
+    EnvironmentCallFromMMode,
+    InstructionPageFault(u64),
+    LoadPageFault(u64),
+    StoreAMOPageFault(u64),
+    StackSizeExceeded,
+    InvalidAddr,
+    AddressInUse,
+    LoadFromBuffer,
+    SanitizerError(SanitizerKind),
+    StackOverflow,
+    BarrierNotReached,
+    TestFailed(u64),
+    StepLimitExceeded,
+    Timeout,
+    NopSledDetected { start_pc: u64 },
+    General,
+}
+
+#[derive(Debug)]
+pub enum Trap {
+    Contained,
+    Requested,
+    Invisible,
+    Fatal
+}
+
+impl Exception {
+    // The value written to `mcause` when the exception is delivered as
+    // a trap. Emulator-only errors have no architectural cause.
+    pub fn cause(&self) -> Option<u64> {
+        match self {
+            Exception::AddressMisaligned => Some(0),
+            Exception::AccessFault => Some(1),
+            Exception::Invalid(_) => Some(2),
+            Exception::Breakpoint => Some(3),
+            Exception::LoadAddressMisaligned => Some(4),
+            Exception::LoadAccessFault => Some(5),
+            Exception::StoreAMOAddressMisaligned => Some(6),
+            Exception::StoreAMOAccessFault => Some(7),
+            Exception::EnvironmentCallFromUMode => Some(8),
+            Exception::EnvironmentCallFromSMode => Some(9),
+            Exception::EnvironmentCallFromMMode => Some(11),
+            Exception::InstructionPageFault(_) => Some(12),
+            Exception::LoadPageFault(_) => Some(13),
+            Exception::StoreAMOPageFault(_) => Some(15),
+            _ => None
+        }
+    }
+
+    // The value written to `mtval` alongside the cause, i.e. the raw
+    // instruction for illegal instructions or the faulting address.
+    pub fn tval(&self) -> u64 {
+        match self {
+            Exception::Invalid(val) => *val,
+            Exception::InstructionPageFault(addr) => *addr,
+            Exception::LoadPageFault(addr) => *addr,
+            Exception::StoreAMOPageFault(addr) => *addr,
+            _ => 0
+        }
+    }
+}
+
+impl Display for Exception {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let description = match self {
+            Exception::AddressMisaligned => "instruction address misaligned",
+            Exception::AccessFault => "instruction access fault",
+            Exception::Invalid(_) => "illegal instruction",
+            Exception::Breakpoint => "breakpoint",
+            Exception::LoadAddressMisaligned => "load address misaligned",
+            Exception::LoadAccessFault => "load access fault",
+            Exception::StoreAMOAccessFault => "store/AMO access fault",
+            Exception::StoreAMOAddressMisaligned => "store/AMO address misaligned",
+            Exception::EnvironmentCallFromUMode => "environment call from U-mode",
+            Exception::EnvironmentCallFromSMode => "environment call from S-mode",
+            Exception::EnvironmentCallFromMMode => "environment call from M-mode",
+            Exception::InstructionPageFault(_) => "instruction page fault",
+            Exception::LoadPageFault(_) => "load page fault",
+            Exception::StoreAMOPageFault(_) => "store/AMO page fault",
+            Exception::StackSizeExceeded => "program too large",
+            Exception::InvalidAddr => "address outside of memory",
+            Exception::AddressInUse => "address in use",
+            Exception::LoadFromBuffer => "load from program buffer",
+            Exception::SanitizerError(kind) => return write!(f, "sanitizer error: {:?}", kind),
+            Exception::StackOverflow => "stack overflow",
+            Exception::BarrierNotReached => "hart stopped before reaching the barrier",
+            Exception::TestFailed(test) => return write!(f, "test {} failed", test),
+            Exception::StepLimitExceeded => "step limit exceeded",
+            Exception::Timeout => "timed out",
+            Exception::NopSledDetected { start_pc } => return write!(f, "NOP sled starting at {:#x}", start_pc),
+            Exception::General => "general error",
+        };
+
+        match self.cause() {
+            Some(cause) => write!(f, "{} (cause={}, tval={:#x})", description, cause, self.tval()),
+            None => write!(f, "{}", description),
+        }
+    }
+}
+
+impl Error for Exception {}
+
+/// Why `SoftThread::execute_until_pc` stopped short of its target.
+#[derive(Debug, PartialEq)]
+pub enum StepError {
+    StepLimitReached,
+    MisalignedTarget,
+    Exception(Exception),
+}
+
+impl Display for StepError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            StepError::StepLimitReached => write!(f, "step limit reached"),
+            StepError::MisalignedTarget => write!(f, "target address misaligned"),
+            StepError::Exception(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for StepError {}
+
+impl From<Exception> for StepError {
+    fn from(e: Exception) -> StepError {
+        StepError::Exception(e)
+    }
+}
@@ -0,0 +1,430 @@
+// Floating point helpers for the semantics RISC-V specifies where they
+// differ from what Rust's `f64` methods provide.
+
+use std::num::FpCategory;
+
+// Exception flags accrued in `fflags`.
+pub const FFLAGS_NX: u8 = 1 << 0;
+pub const FFLAGS_UF: u8 = 1 << 1;
+pub const FFLAGS_OF: u8 = 1 << 2;
+pub const FFLAGS_DZ: u8 = 1 << 3;
+pub const FFLAGS_NV: u8 = 1 << 4;
+
+// The canonical NaN. Narrowing it to `f32` gives the single precision
+// canonical NaN `0x7fc0_0000`.
+pub const CANONICAL_NAN: u64 = 0x7ff8_0000_0000_0000;
+
+pub fn canonical_nan_f64() -> f64 {
+    f64::from_bits(CANONICAL_NAN)
+}
+
+pub fn canonical_nan_f32() -> f32 {
+    f32::from_bits(0x7fc0_0000)
+}
+
+// The result of an arithmetic instruction, which is never a NaN other
+// than the canonical one, whatever payload the host's arithmetic left.
+pub fn canonicalize(val: f64) -> f64 {
+    if val.is_nan() { canonical_nan_f64() } else { val }
+}
+
+pub fn canonicalize_f32(val: f32) -> f32 {
+    if val.is_nan() { canonical_nan_f32() } else { val }
+}
+
+// The upper half of a register holding a single precision value.
+pub const NAN_BOX: u64 = 0xffff_ffff_0000_0000;
+
+const QUIET_BIT: u64 = 1 << 51;
+const QUIET_BIT_F32: u32 = 1 << 22;
+
+// Rounding modes of the `rm` field. `RM_DYN` takes the mode from `frm`.
+pub const RM_RNE: u32 = 0;
+pub const RM_RTZ: u32 = 1;
+pub const RM_RDN: u32 = 2;
+pub const RM_RUP: u32 = 3;
+pub const RM_RMM: u32 = 4;
+pub const RM_DYN: u32 = 7;
+
+pub fn is_signaling_nan(val: f64) -> bool {
+    val.is_nan() && val.to_bits() & QUIET_BIT == 0
+}
+
+pub fn is_signaling_nan_f32(val: f32) -> bool {
+    val.is_nan() && val.to_bits() & QUIET_BIT_F32 == 0
+}
+
+// The bit FCLASS sets in `rd` for each class of value.
+pub const FCLASS_NEG_INF: u64 = 1 << 0;
+pub const FCLASS_NEG_NORMAL: u64 = 1 << 1;
+pub const FCLASS_NEG_SUBNORMAL: u64 = 1 << 2;
+pub const FCLASS_NEG_ZERO: u64 = 1 << 3;
+pub const FCLASS_POS_ZERO: u64 = 1 << 4;
+pub const FCLASS_POS_SUBNORMAL: u64 = 1 << 5;
+pub const FCLASS_POS_NORMAL: u64 = 1 << 6;
+pub const FCLASS_POS_INF: u64 = 1 << 7;
+pub const FCLASS_SIGNALING_NAN: u64 = 1 << 8;
+pub const FCLASS_QUIET_NAN: u64 = 1 << 9;
+
+// The FCLASS result for `val`, which has exactly one bit set. NaNs are
+// told apart by the top mantissa bit, which is set for a quiet NaN.
+pub fn classify_f64(val: f64) -> u64 {
+    class_bit(val.classify(), val.is_sign_negative(), is_signaling_nan(val))
+}
+
+pub fn classify_f32(val: f32) -> u64 {
+    class_bit(val.classify(), val.is_sign_negative(), is_signaling_nan_f32(val))
+}
+
+fn class_bit(category: FpCategory, negative: bool, signaling: bool) -> u64 {
+    use std::num::FpCategory::*;
+
+    match (category, negative) {
+        (Nan, _) if signaling => FCLASS_SIGNALING_NAN,
+        (Nan, _) => FCLASS_QUIET_NAN,
+        (Infinite, true) => FCLASS_NEG_INF,
+        (Infinite, false) => FCLASS_POS_INF,
+        (Normal, true) => FCLASS_NEG_NORMAL,
+        (Normal, false) => FCLASS_POS_NORMAL,
+        (Subnormal, true) => FCLASS_NEG_SUBNORMAL,
+        (Subnormal, false) => FCLASS_POS_SUBNORMAL,
+        (Zero, true) => FCLASS_NEG_ZERO,
+        (Zero, false) => FCLASS_POS_ZERO,
+    }
+}
+
+// `val` NaN-boxed into a 64 bit float register, which is how every
+// single precision instruction leaves its result.
+pub fn box_f32(val: f32) -> f64 {
+    f64::from_bits(NAN_BOX | val.to_bits() as u64)
+}
+
+// The single precision value a float register holds. A value that is not
+// NaN-boxed, such as a double, reads as the canonical NaN.
+pub fn unbox_f32(val: f64) -> f32 {
+    let bits = val.to_bits();
+    if bits & NAN_BOX == NAN_BOX {
+        return f32::from_bits(bits as u32);
+    }
+    canonical_nan_f32()
+}
+
+// Narrow `val` to single precision, rounding by `rm`, which must already
+// have `RM_DYN` resolved. `as` rounds to nearest even, so the other modes
+// step that result by one ulp when it went the wrong way.
+pub fn narrow_f32(val: f64, rm: u32) -> f32 {
+    let near = val as f32;
+    if val.is_nan() || near as f64 == val {
+        return near;
+    }
+
+    let (below, above) = if (near as f64) < val { (near, near.next_up()) } else { (near.next_down(), near) };
+    match rm {
+        RM_RTZ => if val > 0.0 { below } else { above },
+        RM_RDN => below,
+        RM_RUP => above,
+        RM_RMM if val - below as f64 == above as f64 - val => if val > 0.0 { above } else { below },
+        _ => near,
+    }
+}
+
+// Round `val` to an integer by `rm`, which must already have `RM_DYN`
+// resolved.
+pub fn round_rm(val: f64, rm: u32) -> f64 {
+    match rm {
+        RM_RTZ => val.trunc(),
+        RM_RDN => val.floor(),
+        RM_RUP => val.ceil(),
+        RM_RMM => val.round(),
+        _ => val.round_ties_even(),
+    }
+}
+
+// IEEE 754-2008 minNum as required by FMIN: a single NaN operand is
+// ignored, two NaN operands give the canonical NaN, and a signaling NaN
+// operand raises the invalid operation flag. -0.0 is less than +0.0.
+pub fn fmin_rv(a: f64, b: f64, fflags: &mut u8) -> f64 {
+    min_max(a, b, fflags, |a, b| a < b || (a == b && a.is_sign_negative()))
+}
+
+// IEEE 754-2008 maxNum as required by FMAX. +0.0 is greater than -0.0.
+pub fn fmax_rv(a: f64, b: f64, fflags: &mut u8) -> f64 {
+    min_max(a, b, fflags, |a, b| a > b || (a == b && b.is_sign_negative()))
+}
+
+// FMIN and FMAX on single precision values. Widening may quiet a
+// signaling NaN, so NV is decided on the f32 operands.
+pub fn fmin_rv_f32(a: f32, b: f32, fflags: &mut u8) -> f32 {
+    if is_signaling_nan_f32(a) || is_signaling_nan_f32(b) {
+        *fflags |= FFLAGS_NV;
+    }
+    fmin_rv(a as f64, b as f64, fflags) as f32
+}
+
+pub fn fmax_rv_f32(a: f32, b: f32, fflags: &mut u8) -> f32 {
+    if is_signaling_nan_f32(a) || is_signaling_nan_f32(b) {
+        *fflags |= FFLAGS_NV;
+    }
+    fmax_rv(a as f64, b as f64, fflags) as f32
+}
+
+fn min_max(a: f64, b: f64, fflags: &mut u8, pick_a: fn(f64, f64) -> bool) -> f64 {
+    if is_signaling_nan(a) || is_signaling_nan(b) {
+        *fflags |= FFLAGS_NV;
+    }
+
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => canonical_nan_f64(),
+        (true, false) => b,
+        (false, true) => a,
+        _ => if pick_a(a, b) { a } else { b }
+    }
+}
+
+// The flags `a + b` raises: NV for a signaling NaN operand or
+// infinities of opposite sign, and OF, UF and NX for a sum that had to be
+// rounded. Subtraction is the sum with `b` negated.
+pub fn add_flags(a: f64, b: f64) -> u8 {
+    if let Some(flags) = add_special_flags(is_signaling_nan(a) || is_signaling_nan(b), a, b) {
+        return flags;
+    }
+
+    let sum = a + b;
+    rounding_flags(sum, sum.is_infinite(), sum_err(a, b, sum) == 0.0, f64::MIN_POSITIVE)
+}
+
+// The flags `a + b` raises in single precision, where `rounded` is the
+// sum narrowed by the instruction's rounding mode.
+pub fn add_flags_f32(a: f32, b: f32, rounded: f32) -> u8 {
+    let signaling = is_signaling_nan_f32(a) || is_signaling_nan_f32(b);
+    let (a, b) = (a as f64, b as f64);
+    if let Some(flags) = add_special_flags(signaling, a, b) {
+        return flags;
+    }
+
+    let sum = a + b;
+    narrowed_flags(rounded, sum_err(a, b, sum) == 0.0 && rounded as f64 == sum, sum)
+}
+
+// The flags `a * b` raises: NV for 0 * inf or a signaling NaN operand,
+// and OF, UF and NX for a product that had to be rounded.
+pub fn mul_flags(a: f64, b: f64) -> u8 {
+    if let Some(flags) = mul_special_flags(is_signaling_nan(a) || is_signaling_nan(b), a, b) {
+        return flags;
+    }
+
+    let product = a * b;
+    rounding_flags(product, product.is_infinite(), mul_exact(a, b, product), f64::MIN_POSITIVE)
+}
+
+// The product of two f32 values is exact in an f64, so only narrowing it
+// rounds.
+pub fn mul_flags_f32(a: f32, b: f32, rounded: f32) -> u8 {
+    let signaling = is_signaling_nan_f32(a) || is_signaling_nan_f32(b);
+    let (a, b) = (a as f64, b as f64);
+    if let Some(flags) = mul_special_flags(signaling, a, b) {
+        return flags;
+    }
+
+    let product = a * b;
+    narrowed_flags(rounded, rounded as f64 == product, product)
+}
+
+// The flags `a * b + c` raises: NV as the product and the sum would, and
+// OF, UF and NX for a result that had to be rounded. The fused negated
+// forms pass their operands negated.
+pub fn fma_flags(a: f64, b: f64, c: f64) -> u8 {
+    let signaling = is_signaling_nan(a) || is_signaling_nan(b) || is_signaling_nan(c);
+    if let Some(flags) = fma_special_flags(signaling, a, b, c) {
+        return flags;
+    }
+
+    let result = a.mul_add(b, c);
+    rounding_flags(result, result.is_infinite(), fma_exact(a, b, c, result), f64::MIN_POSITIVE)
+}
+
+pub fn fma_flags_f32(a: f32, b: f32, c: f32, rounded: f32) -> u8 {
+    let signaling = is_signaling_nan_f32(a) || is_signaling_nan_f32(b) || is_signaling_nan_f32(c);
+    let (a, b, c) = (a as f64, b as f64, c as f64);
+    if let Some(flags) = fma_special_flags(signaling, a, b, c) {
+        return flags;
+    }
+
+    let result = a.mul_add(b, c);
+    narrowed_flags(rounded, fma_exact(a, b, c, result) && rounded as f64 == result, result)
+}
+
+// The flags `a / b` raises: NV for 0/0, inf/inf or a signaling NaN
+// operand, DZ for a finite non-zero `a` over zero, and OF, UF and NX for
+// a quotient that had to be rounded.
+pub fn div_flags(a: f64, b: f64) -> u8 {
+    if let Some(flags) = div_special_flags(is_signaling_nan(a) || is_signaling_nan(b), a, b) {
+        return flags;
+    }
+
+    let quotient = a / b;
+    // The quotient is exact when the fused remainder is zero.
+    rounding_flags(quotient, quotient.is_infinite(), (-quotient).mul_add(b, a) == 0.0, f64::MIN_POSITIVE)
+}
+
+// The flags `a / b` raises in single precision, where `rounded` is the
+// quotient narrowed by the instruction's rounding mode, so OF and UF
+// follow the f32 range.
+pub fn div_flags_f32(a: f32, b: f32, rounded: f32) -> u8 {
+    let signaling = is_signaling_nan_f32(a) || is_signaling_nan_f32(b);
+    let (a, b) = (a as f64, b as f64);
+    if let Some(flags) = div_special_flags(signaling, a, b) {
+        return flags;
+    }
+
+    // The product of two f32 values is exact in an f64.
+    narrowed_flags(rounded, rounded as f64 * b == a, a / b)
+}
+
+// The flags of a sum whose operands alone decide them, or None if the
+// sum is finite and may need rounding.
+fn add_special_flags(signaling: bool, a: f64, b: f64) -> Option<u8> {
+    if signaling || (a.is_infinite() && b.is_infinite() && a.is_sign_negative() != b.is_sign_negative()) {
+        return Some(FFLAGS_NV);
+    }
+    if a.is_nan() || b.is_nan() || a.is_infinite() || b.is_infinite() {
+        return Some(0);
+    }
+    None
+}
+
+fn mul_special_flags(signaling: bool, a: f64, b: f64) -> Option<u8> {
+    if signaling || (a == 0.0 && b.is_infinite()) || (a.is_infinite() && b == 0.0) {
+        return Some(FFLAGS_NV);
+    }
+    if a.is_nan() || b.is_nan() || a.is_infinite() || b.is_infinite() {
+        return Some(0);
+    }
+    None
+}
+
+// An infinite product raises NV only against an infinite addend of the
+// other sign, as a sum would.
+fn fma_special_flags(signaling: bool, a: f64, b: f64, c: f64) -> Option<u8> {
+    match mul_special_flags(signaling, a, b) {
+        Some(FFLAGS_NV) => Some(FFLAGS_NV),
+        _ if c.is_nan() => Some(0),
+        Some(_) if a.is_nan() || b.is_nan() => Some(0),
+        Some(_) => add_special_flags(false, a * b, c),
+        None => c.is_infinite().then_some(0),
+    }
+}
+
+// The flags of a division whose operands alone decide them, or None if
+// the quotient is finite and may need rounding.
+fn div_special_flags(signaling: bool, a: f64, b: f64) -> Option<u8> {
+    if signaling || (a == 0.0 && b == 0.0) || (a.is_infinite() && b.is_infinite()) {
+        return Some(FFLAGS_NV);
+    }
+    if b == 0.0 && a.is_finite() {
+        return Some(FFLAGS_DZ);
+    }
+    if a.is_nan() || b.is_nan() || a.is_infinite() || b.is_infinite() {
+        return Some(0);
+    }
+    None
+}
+
+// The flags of a result from finite operands that rounded to `rounded`:
+// OF and NX when it overflowed, UF and NX when it is inexact and below
+// `min_normal`, and NX when it is otherwise inexact.
+fn rounding_flags(rounded: f64, overflow: bool, exact: bool, min_normal: f64) -> u8 {
+    if overflow {
+        return FFLAGS_OF | FFLAGS_NX;
+    }
+    if exact {
+        return 0;
+    }
+    if rounded.abs() < min_normal {
+        return FFLAGS_UF | FFLAGS_NX;
+    }
+    FFLAGS_NX
+}
+
+// `rounding_flags` for a single precision result narrowed from `wide`.
+// A mode that rounds toward zero stops at `f32::MAX`, so the result
+// overflowed whenever `wide` reached the next power of two.
+fn narrowed_flags(rounded: f32, exact: bool, wide: f64) -> u8 {
+    let overflow = rounded.is_infinite() || wide.abs() >= 2f64.powi(128);
+    rounding_flags(rounded as f64, overflow, exact, f32::MIN_POSITIVE as f64)
+}
+
+// The rounding error of `sum`, the f64 sum of `a` and `b`, which is
+// itself an f64 (Knuth's TwoSum).
+fn sum_err(a: f64, b: f64, sum: f64) -> f64 {
+    let b_part = sum - a;
+    (a - (sum - b_part)) + (b - b_part)
+}
+
+// Whether `product`, the f64 product of `a` and `b`, is exact. The fused
+// residual of a subnormal product may underflow as well, so this compares
+// the operands' mantissas instead.
+fn mul_exact(a: f64, b: f64, product: f64) -> bool {
+    if product == 0.0 {
+        return a == 0.0 || b == 0.0;
+    }
+
+    let ((a_mant, a_exp), (b_mant, b_exp)) = (decompose(a), decompose(b));
+    let (mant, exp) = decompose(product);
+    // A product of odd mantissas is odd.
+    mant as u128 == a_mant as u128 * b_mant as u128 && exp == a_exp + b_exp
+}
+
+// A finite, non-zero `val` as `mant * 2^exp` with `mant` odd, ignoring its
+// sign.
+fn decompose(val: f64) -> (u64, i32) {
+    let bits = val.to_bits();
+    let biased = (bits >> 52 & 0x7ff) as i32;
+    let frac = bits & ((1 << 52) - 1);
+    let (mant, exp) = if biased == 0 { (frac, -1074) } else { (frac | 1 << 52, biased - 1075) };
+    (mant >> mant.trailing_zeros(), exp + mant.trailing_zeros() as i32)
+}
+
+// Whether `result`, the f64 fma of `a`, `b` and `c`, is exact. It is when
+// a * b equals result - c, which `sum_err` splits into two f64 values.
+fn fma_exact(a: f64, b: f64, c: f64, result: f64) -> bool {
+    let diff = result - c;
+    a.mul_add(b, -diff) == sum_err(result, -c, diff)
+}
+
+// The flags the square root of `a` raises: NV below -0.0 or for a
+// signaling NaN, and NX when the root had to be rounded.
+pub fn sqrt_flags(a: f64) -> u8 {
+    if is_signaling_nan(a) || a < 0.0 {
+        return FFLAGS_NV;
+    }
+    let root = a.sqrt();
+    if root.is_finite() && root.mul_add(root, -a) != 0.0 {
+        return FFLAGS_NX;
+    }
+    0
+}
+
+// The square of an f32 root is exact in an f64.
+pub fn sqrt_flags_f32(a: f32, rounded: f32) -> u8 {
+    if is_signaling_nan_f32(a) || a < 0.0 {
+        return FFLAGS_NV;
+    }
+    if rounded.is_finite() && rounded as f64 * rounded as f64 != a as f64 {
+        return FFLAGS_NX;
+    }
+    0
+}
+
+// The flags converting `val` to an integer in `min..max` raises, where
+// `rounded` is `val` rounded to an integer: NV for NaN or out of range
+// values, which saturate, and NX when rounding changed the value.
+pub fn int_cvt_flags(val: f64, rounded: f64, min: f64, max: f64) -> u8 {
+    if val.is_nan() || rounded < min || rounded >= max {
+        return FFLAGS_NV;
+    }
+    if rounded != val {
+        return FFLAGS_NX;
+    }
+    0
+}
@@ -0,0 +1,268 @@
+use crate::breakpoint::BreakpointId;
+use crate::exceptions::Exception;
+use crate::memory::Dram;
+use crate::register::Register;
+use crate::soft::{SoftThread, StepOutcome};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+// The registers `g` and `G` transfer, in the order GDB numbers them for
+// RISC-V: x0-x31, then the pc.
+pub const GDB_REGISTERS: usize = 33;
+
+// The most bytes a single `m` packet may ask for.
+pub const GDB_MAX_READ: u64 = 0x1000;
+
+// Signal numbers in stop replies.
+pub const SIGILL: u8 = 4;
+pub const SIGTRAP: u8 = 5;
+pub const SIGSEGV: u8 = 11;
+
+/// A GDB Remote Serial Protocol server for a single hart, speaking over
+/// `stream`. It supports enough of the protocol for GDB to read and write
+/// registers and memory, step, continue and set software breakpoints.
+/// Memory is accessed as DRAM, without translation.
+pub struct GdbStub<S: Read + Write> {
+    stream: S,
+    breakpoints: HashMap<u64, BreakpointId>,
+}
+
+impl GdbStub<TcpStream> {
+    /// Wait for GDB to connect to `addr`, as `target remote` does.
+    pub fn listen(addr: impl ToSocketAddrs) -> io::Result<GdbStub<TcpStream>> {
+        let (stream, _) = TcpListener::bind(addr)?.accept()?;
+        Ok(GdbStub::new(stream))
+    }
+}
+
+impl<S: Read + Write> GdbStub<S> {
+    pub fn new(stream: S) -> GdbStub<S> {
+        GdbStub { stream, breakpoints: HashMap::new() }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /// Serve packets for `soft` until GDB detaches or hangs up. The
+    /// breakpoints GDB set are removed again on the way out.
+    pub fn attach(&mut self, soft: &mut SoftThread<u64, f64, Dram>) -> io::Result<()> {
+        let result = self.serve(soft);
+        for (_, id) in self.breakpoints.drain() {
+            soft.clear_breakpoint(id);
+        }
+        result
+    }
+
+    fn serve(&mut self, soft: &mut SoftThread<u64, f64, Dram>) -> io::Result<()> {
+        while let Some(packet) = self.read_packet()? {
+            let reply = match packet.split_first() {
+                Some((b'?', _)) => stop_reply(SIGTRAP),
+                Some((b'g', _)) => read_registers(soft),
+                Some((b'G', args)) => write_registers(soft, args),
+                Some((b'm', args)) => read_memory(soft, args),
+                Some((b'M', args)) => write_memory(soft, args),
+                Some((b'c', _)) => resume(soft, true),
+                Some((b's', _)) => resume(soft, false),
+                Some((b'Z', args)) => self.insert_breakpoint(soft, args),
+                Some((b'z', args)) => self.remove_breakpoint(soft, args),
+                Some((b'D', _)) => {
+                    self.write_packet("OK")?;
+                    return Ok(());
+                },
+                _ => String::new(),
+            };
+            self.write_packet(&reply)?;
+        }
+        Ok(())
+    }
+
+    // `Z0,addr,kind`. Only software breakpoints are supported.
+    fn insert_breakpoint(&mut self, soft: &mut SoftThread<u64, f64, Dram>, args: &[u8]) -> String {
+        match breakpoint_addr(args) {
+            Some(addr) => {
+                self.breakpoints.insert(addr, soft.set_breakpoint(addr));
+                "OK".into()
+            },
+            None => String::new(),
+        }
+    }
+
+    fn remove_breakpoint(&mut self, soft: &mut SoftThread<u64, f64, Dram>, args: &[u8]) -> String {
+        match breakpoint_addr(args) {
+            Some(addr) => {
+                if let Some(id) = self.breakpoints.remove(&addr) {
+                    soft.clear_breakpoint(id);
+                }
+                "OK".into()
+            },
+            None => String::new(),
+        }
+    }
+
+    // The body of the next `$body#checksum` packet, acknowledging it. Acks
+    // and interrupts between packets are skipped, and packets with a bad
+    // checksum are asked for again. None once the stream ends.
+    fn read_packet(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            loop {
+                match self.read_byte()? {
+                    Some(b'$') => break,
+                    Some(_) => {},
+                    None => return Ok(None),
+                }
+            }
+
+            let mut body = vec![];
+            loop {
+                match self.read_byte()? {
+                    Some(b'#') => break,
+                    Some(byte) => body.push(byte),
+                    None => return Ok(None),
+                }
+            }
+
+            let (Some(hi), Some(lo)) = (self.read_byte()?, self.read_byte()?) else {
+                return Ok(None);
+            };
+            let sent = std::str::from_utf8(&[hi, lo]).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if sent == Some(checksum(&body)) {
+                self.stream.write_all(b"+")?;
+                return Ok(Some(body));
+            }
+            self.stream.write_all(b"-")?;
+        }
+    }
+
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let mut byte = [0];
+        match self.stream.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    fn write_packet(&mut self, body: &str) -> io::Result<()> {
+        write!(self.stream, "${}#{:02x}", body, checksum(body.as_bytes()))?;
+        self.stream.flush()
+    }
+}
+
+fn checksum(body: &[u8]) -> u8 {
+    body.iter().fold(0, |sum, byte| sum.wrapping_add(*byte))
+}
+
+fn stop_reply(signal: u8) -> String {
+    format!("S{:02x}", signal)
+}
+
+// The signal GDB is told stopped the hart on `exception`.
+fn signal(exception: &Exception) -> u8 {
+    match exception {
+        Exception::Invalid(_) => SIGILL,
+        Exception::AccessFault | Exception::LoadAccessFault | Exception::StoreAMOAccessFault |
+        Exception::InstructionPageFault(_) | Exception::LoadPageFault(_) | Exception::StoreAMOPageFault(_) => SIGSEGV,
+        _ => SIGTRAP,
+    }
+}
+
+// Run until a breakpoint, an exception or the hart halting, or for a
+// single instruction unless `until_stop`. Environment calls are trapped
+// as in `run_until_halt`. A halted hart is reported as exited with the
+// code in `a0`.
+fn resume(soft: &mut SoftThread<u64, f64, Dram>, until_stop: bool) -> String {
+    loop {
+        if soft.is_halted() {
+            return format!("W{:02x}", soft.registers[Register::X10 as usize] as u8);
+        }
+        match soft.step() {
+            Ok(StepOutcome::Breakpoint) => return stop_reply(SIGTRAP),
+            Ok(_) => {},
+            Err(e @ Exception::EnvironmentCallFromUMode) |
+            Err(e @ Exception::EnvironmentCallFromSMode) |
+            Err(e @ Exception::EnvironmentCallFromMMode) => soft.take_trap(e),
+            Err(e) => return stop_reply(signal(&e)),
+        }
+        if !until_stop {
+            return stop_reply(SIGTRAP);
+        }
+    }
+}
+
+// Each register as 16 hex digits of its little endian bytes.
+pub(crate) fn read_registers(soft: &SoftThread<u64, f64, Dram>) -> String {
+    soft.registers[..GDB_REGISTERS - 1].iter().chain([soft.pc].iter())
+        .map(|val| encode_hex(&val.to_le_bytes()))
+        .collect()
+}
+
+pub(crate) fn write_registers(soft: &mut SoftThread<u64, f64, Dram>, args: &[u8]) -> String {
+    let Some(bytes) = decode_hex(args).filter(|bytes| bytes.len() == GDB_REGISTERS * 8) else {
+        return "E01".into();
+    };
+
+    let vals: Vec<u64> = bytes.chunks_exact(8).map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap())).collect();
+    soft.registers[1..GDB_REGISTERS - 1].copy_from_slice(&vals[1..GDB_REGISTERS - 1]);
+    soft.set_pc(vals[GDB_REGISTERS - 1]);
+    "OK".into()
+}
+
+// `m addr,length`
+fn read_memory(soft: &SoftThread<u64, f64, Dram>, args: &[u8]) -> String {
+    let Some((addr, len)) = parse_range(args).filter(|(_, len)| *len <= GDB_MAX_READ) else {
+        return "E01".into();
+    };
+
+    let mut buf = vec![0; len as usize];
+    match soft.store_raw(addr, &mut buf) {
+        Ok(()) => encode_hex(&buf),
+        Err(_) => "E14".into(),
+    }
+}
+
+// `M addr,length:XX...`
+fn write_memory(soft: &mut SoftThread<u64, f64, Dram>, args: &[u8]) -> String {
+    let Some(colon) = args.iter().position(|byte| *byte == b':') else {
+        return "E01".into();
+    };
+    let (Some((addr, len)), Some(data)) = (parse_range(&args[..colon]), decode_hex(&args[colon + 1..])) else {
+        return "E01".into();
+    };
+    if data.len() as u64 != len {
+        return "E01".into();
+    }
+
+    match soft.load_raw(addr, &data) {
+        Ok(()) => "OK".into(),
+        Err(_) => "E14".into(),
+    }
+}
+
+// `0,addr,kind`, the type and fields of a `Z` or `z` packet.
+fn breakpoint_addr(args: &[u8]) -> Option<u64> {
+    let args = std::str::from_utf8(args).ok()?;
+    let mut fields = args.split(',');
+    if fields.next()? != "0" {
+        return None;
+    }
+    u64::from_str_radix(fields.next()?, 16).ok()
+}
+
+fn parse_range(args: &[u8]) -> Option<(u64, u64)> {
+    let (addr, len) = std::str::from_utf8(args).ok()?.split_once(',')?;
+    Some((u64::from_str_radix(addr, 16).ok()?, u64::from_str_radix(len, 16).ok()?))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decode_hex(hex: &[u8]) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    hex.chunks_exact(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
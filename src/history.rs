@@ -0,0 +1,162 @@
+use crate::disasm::ABI_NAMES;
+use crate::memory::Memory;
+use crate::soft::SoftThread;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+// Default for `StepHistory::new`.
+pub const HISTORY_DEPTH: usize = 1000;
+
+/// What one instruction changed, as the values it overwrote: the pc
+/// before it and the registers and CSRs whose value it changed. Float
+/// registers are stored as bits.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SoftThreadSnapshot {
+    pub pc: u64,
+    pub registers: Vec<(usize, u64)>,
+    pub f_registers: Vec<(usize, u64)>,
+    pub csrs: Vec<(usize, u64)>,
+}
+
+impl SoftThreadSnapshot {
+    /// Put back the values the instruction overwrote.
+    pub fn restore<M: Memory<RegValue = u64, Bytes = Vec<u8>>>(&self, soft: &mut SoftThread<u64, f64, M>) {
+        soft.set_pc(self.pc);
+        for (idx, val) in self.registers.iter() {
+            soft.registers[*idx] = *val;
+        }
+        for (idx, bits) in self.f_registers.iter() {
+            soft.f_registers[*idx] = f64::from_bits(*bits);
+        }
+        for (idx, val) in self.csrs.iter() {
+            soft.write_csr_raw(*idx as u16, *val);
+        }
+    }
+}
+
+// The state of a hart before the instruction it is about to execute.
+#[derive(Clone, Debug)]
+struct State {
+    pc: u64,
+    registers: [u64; 33],
+    f_registers: [u64; 33],
+    csr: Box<[u64; 4096]>,
+}
+
+impl State {
+    fn of<M>(soft: &SoftThread<u64, f64, M>) -> State {
+        State {
+            pc: soft.pc,
+            registers: soft.registers,
+            f_registers: soft.f_registers.map(f64::to_bits),
+            csr: Box::new(soft.csr),
+        }
+    }
+
+    // The values in `self` that differ in `soft`.
+    fn diff<M>(&self, soft: &SoftThread<u64, f64, M>) -> SoftThreadSnapshot {
+        let changed = |old: &[u64], new: &[u64]| -> Vec<(usize, u64)> {
+            old.iter().zip(new.iter()).enumerate().filter(|(_, (old, new))| old != new).map(|(idx, (old, _))| (idx, *old)).collect()
+        };
+
+        SoftThreadSnapshot {
+            pc: self.pc,
+            registers: changed(&self.registers, &soft.registers),
+            f_registers: changed(&self.f_registers, &soft.f_registers.map(f64::to_bits)),
+            csrs: changed(&self.csr[..], &soft.csr),
+        }
+    }
+}
+
+/// The last `depth` instructions a hart executed, each as the snapshot
+/// that undoes it. Memory is not recorded, so stepping back over a store
+/// leaves the stored value in place.
+#[derive(Clone, Debug)]
+pub struct StepHistory {
+    pub depth: usize,
+    snapshots: VecDeque<SoftThreadSnapshot>,
+    // The state `save` last saw, until the instruction that follows it
+    // has been turned into a snapshot.
+    pending: Option<State>,
+}
+
+impl StepHistory {
+    pub fn new(depth: usize) -> StepHistory {
+        StepHistory { depth, snapshots: VecDeque::new(), pending: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len() + self.pending.is_some() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Record the state of `soft` before it executes an instruction. The
+    /// instruction executed since the last call becomes a snapshot.
+    pub fn save<M>(&mut self, soft: &SoftThread<u64, f64, M>) {
+        self.flush(soft);
+        self.pending = Some(State::of(soft));
+    }
+
+    /// Take the snapshot that undoes the last instruction `soft` executed.
+    pub fn pop<M>(&mut self, soft: &SoftThread<u64, f64, M>) -> Option<SoftThreadSnapshot> {
+        self.flush(soft);
+        self.snapshots.pop_back()
+    }
+
+    fn flush<M>(&mut self, soft: &SoftThread<u64, f64, M>) {
+        let Some(state) = self.pending.take() else {
+            return;
+        };
+        if self.depth == 0 {
+            return;
+        }
+
+        if self.snapshots.len() == self.depth {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(state.diff(soft));
+    }
+}
+
+impl Default for StepHistory {
+    fn default() -> StepHistory {
+        StepHistory::new(HISTORY_DEPTH)
+    }
+}
+
+/// The pc and the integer and float registers of a hart, without its CSRs
+/// or memory.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RegisterSnapshot {
+    pub xregs: [u64; 33],
+    pub fregs: [f64; 33],
+    pub pc: u64,
+}
+
+impl RegisterSnapshot {
+    /// The integer registers that differ in `other`, as `(index, value
+    /// here, value in other)`.
+    pub fn diff(&self, other: &RegisterSnapshot) -> Vec<(usize, u64, u64)> {
+        self.xregs.iter().zip(other.xregs.iter()).enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(idx, (old, new))| (idx, *old, *new))
+            .collect()
+    }
+}
+
+impl Display for RegisterSnapshot {
+    // One register per line, integer registers by ABI name, then float
+    // registers as bits, then the pc.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for (name, val) in ABI_NAMES.iter().zip(self.xregs.iter()) {
+            writeln!(f, "{:<5} 0x{:016x}", name, val)?;
+        }
+        for (idx, val) in self.fregs.iter().take(32).enumerate() {
+            writeln!(f, "{:<5} 0x{:016x}", format!("f{}", idx), val.to_bits())?;
+        }
+        writeln!(f, "{:<5} 0x{:016x}", "pc", self.pc)
+    }
+}
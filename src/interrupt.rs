@@ -0,0 +1,54 @@
+use std::fmt::{Debug, Formatter};
+
+// Set in `mcause` when the trap is an interrupt rather than an exception.
+pub const MCAUSE_INTERRUPT: u64 = 1 << 63;
+
+// The `mip` bits that wake a hart from `wfi` when enabled in `mie`: the
+// machine software, timer and external interrupts.
+pub const WFI_WAKE_MASK: u64 = 0x888;
+
+pub type WfiCallback = Box<dyn FnMut()>;
+
+/// The callback `SoftThread::set_wfi_hook` runs each time `wfi` finds no
+/// interrupt to wake up for, e.g. to advance `mtime` in a simulation of
+/// several harts.
+pub struct WfiHook(pub WfiCallback);
+
+impl Debug for WfiHook {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("WfiHook").finish_non_exhaustive()
+    }
+}
+
+/// The interrupts a hart can have pending in `mip`. The discriminant is
+/// the exception code written to `mcause` and the bit index in `mip` and
+/// `mie`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InterruptCause {
+    SupervisorSoftware = 1,
+    MachineSoftware = 3,
+    SupervisorTimer = 5,
+    MachineTimer = 7,
+    SupervisorExternal = 9,
+    MachineExternal = 11,
+}
+
+impl InterruptCause {
+    /// Every cause, from the highest priority to the lowest.
+    pub const PRIORITY: [InterruptCause; 6] = [
+        InterruptCause::MachineExternal,
+        InterruptCause::MachineSoftware,
+        InterruptCause::MachineTimer,
+        InterruptCause::SupervisorExternal,
+        InterruptCause::SupervisorSoftware,
+        InterruptCause::SupervisorTimer,
+    ];
+
+    pub fn code(&self) -> u64 {
+        *self as u64
+    }
+
+    pub fn mip_bit(&self) -> u64 {
+        1 << self.code()
+    }
+}
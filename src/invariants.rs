@@ -0,0 +1,23 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// An architectural invariant that did not hold after execution.
+/// `addr_or_reg` is the register index, CSR address or pc the violation
+/// was found at and `value` is the offending value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvariantViolation {
+    pub description: String,
+    pub addr_or_reg: u64,
+    pub value: u64,
+}
+
+impl InvariantViolation {
+    pub fn new(description: &str, addr_or_reg: u64, value: u64) -> InvariantViolation {
+        InvariantViolation { description: description.to_string(), addr_or_reg, value }
+    }
+}
+
+impl Display for InvariantViolation {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{} ({:#x} = {:#x})", self.description, self.addr_or_reg, self.value)
+    }
+}
@@ -0,0 +1,199 @@
+use crate::instructions::Instruction;
+use crate::register::Register;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+#[cfg(target_arch = "x86_64")]
+use dynasmrt::{dynasm, DynasmApi};
+
+// Blocks are compiled once they have been reached this many times.
+pub const HOT_THRESHOLD: u32 = 16;
+
+pub type FenceICallback = Box<dyn Fn(u64)>;
+
+/// The callback `SoftThread::set_fence_i_hook` runs with the pc of each
+/// `fence.i`, for backends that cache code outside the emulator.
+pub struct FenceIHook(pub FenceICallback);
+
+impl Debug for FenceIHook {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("FenceIHook").finish_non_exhaustive()
+    }
+}
+
+/// A basic block translated to native code. Calling it applies the effect
+/// of its `len` instructions to the integer register file; the caller
+/// advances the pc.
+pub struct NativeBlock {
+    #[cfg(target_arch = "x86_64")]
+    buf: dynasmrt::ExecutableBuffer,
+    #[cfg(target_arch = "x86_64")]
+    entry: dynasmrt::AssemblyOffset,
+    pub len: u64,
+}
+
+impl NativeBlock {
+    /// # Safety
+    /// `registers` must point to the 33 entry register file of a hart.
+    #[cfg(target_arch = "x86_64")]
+    pub unsafe fn call(&self, registers: *mut u64) {
+        let func: extern "sysv64" fn(*mut u64) = std::mem::transmute(self.buf.ptr(self.entry));
+        func(registers)
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    pub unsafe fn call(&self, _registers: *mut u64) {}
+}
+
+impl Debug for NativeBlock {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("NativeBlock").field("len", &self.len).finish()
+    }
+}
+
+/// Native translations of hot basic blocks, keyed by the pc they start at.
+/// Only register to register integer instructions are translated. A block
+/// ends before the first instruction that is not, which includes every
+/// branch, jump, memory and system instruction.
+#[derive(Debug, Default)]
+pub struct JitCache {
+    blocks: HashMap<u64, NativeBlock>,
+    counts: HashMap<u64, u32>,
+    pub hot_threshold: u32,
+}
+
+impl JitCache {
+    pub fn new() -> JitCache {
+        JitCache { hot_threshold: HOT_THRESHOLD, ..JitCache::default() }
+    }
+
+    pub fn get(&self, pc: u64) -> Option<&NativeBlock> {
+        self.blocks.get(&pc)
+    }
+
+    pub fn insert(&mut self, pc: u64, block: NativeBlock) {
+        self.blocks.insert(pc, block);
+    }
+
+    /// Count a visit to the block at `pc`, returning true on the visit that
+    /// makes it hot so that each block is only compiled once.
+    pub fn visit(&mut self, pc: u64) -> bool {
+        let count = self.counts.entry(pc).or_insert(0);
+        *count = count.saturating_add(1);
+        *count == self.hot_threshold.saturating_add(1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Drop every translation, e.g. after the code has been replaced.
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+        self.counts.clear();
+    }
+
+    pub fn is_supported(instruction: &Instruction) -> bool {
+        cfg!(target_arch = "x86_64") && matches!(instruction,
+            Instruction::Lui { .. } | Instruction::Addi { .. } | Instruction::Xori { .. } |
+            Instruction::Ori { .. } | Instruction::Andi { .. } | Instruction::Slli { .. } |
+            Instruction::Srli { .. } | Instruction::Srai { .. } | Instruction::Add { .. } |
+            Instruction::Sub { .. } | Instruction::Sll { .. } | Instruction::Xor { .. } |
+            Instruction::Srl { .. } | Instruction::Sra { .. } | Instruction::Or { .. } |
+            Instruction::And { .. } | Instruction::Addiw { .. } | Instruction::Addw { .. } |
+            Instruction::Subw { .. }
+        )
+    }
+
+    /// Translate `instrs`, all of which must be supported, into a block
+    /// that behaves exactly like the interpreter arms for them.
+    #[cfg(target_arch = "x86_64")]
+    pub fn compile_block(instrs: &[Instruction], pc: u64) -> NativeBlock {
+        let mut ops = dynasmrt::x64::Assembler::new().expect("unable to allocate JIT memory");
+        let entry = ops.offset();
+        let off = |reg: &Register| (*reg as i32) * 8;
+
+        for instruction in instrs {
+            match instruction {
+                Instruction::Lui { rd, imm } => {
+                    dynasm!(ops ; .arch x64 ; mov QWORD [rdi + off(rd)], *imm);
+                },
+                Instruction::Addi { rd, rs1, imm, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; add rax, *imm ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Xori { rd, rs1, imm, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; xor rax, *imm ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Ori { rd, rs1, imm, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; or rax, *imm ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Andi { rd, rs1, imm, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; and rax, *imm ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Slli { rd, rs1, shamt, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; shl rax, (*shamt & 0x3f) as i8 ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Srli { rd, rs1, shamt, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; shr rax, (*shamt & 0x3f) as i8 ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Srai { rd, rs1, shamt, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; sar rax, (*shamt & 0x3f) as i8 ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Add { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; add rax, QWORD [rdi + off(rs2)] ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Sub { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; sub rax, QWORD [rdi + off(rs2)] ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Xor { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; xor rax, QWORD [rdi + off(rs2)] ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Or { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; or rax, QWORD [rdi + off(rs2)] ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::And { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; and rax, QWORD [rdi + off(rs2)] ; mov QWORD [rdi + off(rd)], rax);
+                },
+                // x86 masks the shift count in cl to 6 bits, as RV64 does.
+                Instruction::Sll { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rcx, QWORD [rdi + off(rs2)] ; mov rax, QWORD [rdi + off(rs1)] ; shl rax, cl ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Srl { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rcx, QWORD [rdi + off(rs2)] ; mov rax, QWORD [rdi + off(rs1)] ; shr rax, cl ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Sra { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rcx, QWORD [rdi + off(rs2)] ; mov rax, QWORD [rdi + off(rs1)] ; sar rax, cl ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Addiw { rd, rs1, imm, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; add rax, *imm ; movsxd rax, eax ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Addw { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; add rax, QWORD [rdi + off(rs2)] ; movsxd rax, eax ; mov QWORD [rdi + off(rd)], rax);
+                },
+                Instruction::Subw { rd, rs1, rs2, .. } => {
+                    dynasm!(ops ; .arch x64 ; mov rax, QWORD [rdi + off(rs1)] ; sub rax, QWORD [rdi + off(rs2)] ; movsxd rax, eax ; mov QWORD [rdi + off(rd)], rax);
+                },
+                _ => panic!("{:?} at {:#x} cannot be compiled", instruction, pc),
+            }
+            // As in the interpreter, a write to x0 is discarded.
+            dynasm!(ops ; .arch x64 ; mov QWORD [rdi], 0);
+        }
+
+        dynasm!(ops ; .arch x64 ; ret);
+
+        NativeBlock {
+            buf: ops.finalize().expect("unable to finalize JIT block"),
+            entry,
+            len: instrs.len() as u64,
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    pub fn compile_block(_instrs: &[Instruction], _pc: u64) -> NativeBlock {
+        panic!("the JIT is only available on x86_64");
+    }
+}
@@ -1,9443 +1,13142 @@
-pub mod encoding;
-pub mod encoding_types;
-pub mod extensions;
-pub mod instructions;
-pub mod register;
-pub mod soft;
-pub mod vm;
-pub mod memory;
-pub mod exceptions;
-pub mod machine;
-pub mod consts;
-pub mod state;
-
-#[cfg(test)]
-mod tests {
-    #![allow(unused)]
-    use super::*;
-    use crate::memory::Memory;
-    use crate::encoding::{InstructionDecoder, OpCodeType, Unpacked, EncodingTable};
-    use crate::extensions::{Extension, Base};
-    use crate::encoding_types::*;
-    use crate::instructions::Instruction;
-    use crate::register::{HardWiredZero, Register, RegisterAbi, RegisterValue};
-    use crate::soft::SoftThread;
-
-    #[test]
-    fn test_match_register() {
-        let reg = Register::X0;
-
-        match reg {
-            Register::X0 => {
-                assert!(true);
-            }
-            _ => {
-                assert!(false);
-            }
-        }
-    }
-
-    #[test]
-    fn test_convert_valid_register_to_abi() {
-        let reg = Register::X0;
-        let abi: RegisterAbi = reg.into();
-        assert_eq!(abi, RegisterAbi::Zero(HardWiredZero))
-    }
-
-    #[test]
-    fn test_convert_valid_valid_opcode_to_opcode_type() {
-        let opcode = 0b0010111 as u8;
-        let opcode_type: OpCodeType = opcode.into();
-        assert_eq!(opcode_type, OpCodeType::U);
-    }
-
-    #[test]
-    fn test_convert_valid_invalid_opcode_to_opcode_type() {
-        let opcode = 0b0010110 as u8;
-        let opcode_type: OpCodeType = opcode.into();
-        assert_eq!(opcode_type, OpCodeType::Invalid);
-    }
-
-    #[test]
-    fn test_unpack_random_r_type_bits() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1011_0011 as u32;
-        let unpacked: Unpacked = bits.into();
-        assert!(unpacked.rd.is_some());
-        assert!(unpacked.rs1.is_some());
-        assert!(unpacked.rs2.is_some());
-        assert!(unpacked.func3.is_some());
-        assert!(unpacked.func7.is_some());
-        assert!(unpacked.fm.is_some());
-        assert!(unpacked.pred.is_some());
-        assert!(unpacked.succ.is_some());
-        assert_eq!(unpacked.opcode, 51);
-    }
-    #[test]
-    fn test_unpack_random_i_type_bits() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1000_0011 as u32;
-        let unpacked: Unpacked = bits.into();
-        assert!(unpacked.rd.is_some());
-        assert!(unpacked.rs1.is_some());
-        assert!(unpacked.func3.is_some());
-        assert!(unpacked.imm.is_some());
-        assert_eq!(unpacked.opcode, 3);
-    }
-
-    #[test]
-    fn test_unpack_random_s_type_bits() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1010_0011 as u32;
-        let unpacked: Unpacked = bits.into();
-        assert!(unpacked.imm.is_some());
-        assert!(unpacked.rs1.is_some());
-        assert!(unpacked.rs2.is_some());
-        assert_eq!(unpacked.opcode, 35);
-    }
-
-    #[test]
-    fn test_unpack_random_u_type_bits() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1011_0111 as u32;
-        let unpacked: Unpacked = bits.into();
-        assert!(unpacked.imm.is_some());
-        assert!(unpacked.rd.is_some());
-        assert_eq!(unpacked.opcode, 55);
-    }
-    #[test]
-    fn test_unpack_random_b_type_bits() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1011_0111 as u32;
-        let unpacked: Unpacked = bits.into();
-        assert!(unpacked.imm.is_some());
-        assert!(unpacked.rd.is_some());
-        assert_eq!(unpacked.opcode, 55);
-    }
-    #[test]
-    fn test_unpack_random_j_type_bits() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1110_1111 as u32;
-        let unpacked: Unpacked = bits.into();
-        assert!(unpacked.imm.is_some());
-        assert!(unpacked.rd.is_some());
-        assert_eq!(unpacked.opcode, 111);
-    }
-
-    #[test]
-    fn test_convert_valid_lui_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1011_0111 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Lui {
-                rd: Register::X25,
-                imm: -858996736
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_auipc_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1001_0111 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Auipc {
-                rd: Register::X25,
-                imm: -858996736
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_jal_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1110_1111 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Jal {
-                rd: Register::X25,
-                imm: -212584
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_jalr_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1110_0111 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Jalr {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_beq_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1110_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Beq {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -822,
-                func3: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_bne_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1001_0101_1110_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Bne {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -822,
-                func3: 1
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_blt_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1100_0101_1110_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Blt {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -822,
-                func3: 4
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_bge_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1101_0101_1110_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Bge {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -822,
-                func3: 5
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_bltu_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1110_0101_1110_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Bltu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -822,
-                func3: 6
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_bgeu_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1111_0101_1110_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Bgeu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -822,
-                func3: 7
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_lb_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1000_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Lb {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_lh_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1001_0101_1000_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Lh {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 1
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_lw_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1010_0101_1000_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Lw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 2
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_lbu_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1100_0101_1000_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Lbu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 4
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_lhu_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1101_0101_1000_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Lhu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 5
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_sb_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1010_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sb {
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: 111,
-                func3: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sh_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1001_0101_1010_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sh {
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: 111,
-                func3: 1
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sw_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1010_0101_1010_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sw {
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: 111,
-                func3: 2
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_addi_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Addi {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_slti_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1010_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Slti {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 2
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sltiu_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1011_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sltiu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 3
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_xori_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1100_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Xori {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 4
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_ori_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1110_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Ori {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 6
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_andi_bits_into_instruction() {
-        let bits: Inst = 0b1100_1100_1100_1010_1111_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Andi {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 7
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_slli_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1001_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Slli {
-                rd: Register::X11,
-                rs1: Register::X21,
-                shamt: 12,
-                func3: 1,
-                func7: 0
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_srli_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1101_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Srli {
-                rd: Register::X11,
-                rs1: Register::X21,
-                shamt: 12,
-                func3: 5,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_srai_bits_into_instruction() {
-        let bits: Inst = 0b0100_0000_1100_1010_1101_0101_1001_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Srai {
-                rd: Register::X11,
-                rs1: Register::X21,
-                shamt: 12,
-                func3: 5,
-                func7: 32
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_add_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1000_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Add {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sub_bits_into_instruction() {
-        let bits: Inst = 0b0100_0000_1100_1010_1000_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sub {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 32
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sll_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1001_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sll {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 1,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_slt_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1010_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Slt {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 2,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sltu_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1011_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sltu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 3,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_xor_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1100_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Xor {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 4,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_srl_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1101_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Srl {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sra_bits_into_instruction() {
-        let bits: Inst = 0b0100_0000_1100_1010_1101_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sra {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 32
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_or_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1110_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Or {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 6,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_and_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1111_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::And {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 7,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_fence_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1000_0101_1000_1111 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Fence {
-                rd: Register::X11,
-                rs1: Register::X21,
-                fm: 0,
-                pred: 0,
-                succ: 0,
-                func3: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_ecall_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0000_0000_0000_0000_0111_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(instruction, Instruction::ECall);
-    }
-
-    #[test]
-    fn test_convert_valid_ebreak_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0001_0000_0000_0000_0111_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(instruction, Instruction::EBreak);
-    }
-
-    #[test]
-    fn test_convert_valid_lwu_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0000_1000_0110_0100_0000_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Lwu {
-                rd: Register::X8,
-                rs1: Register::X16,
-                imm: 0,
-                func3: 6
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_ld_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0000_1000_0011_0100_0000_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Ld {
-                rd: Register::X8,
-                rs1: Register::X16,
-                imm: 0,
-                func3: 3
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sd_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0011_1000_0011_0100_0010_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sd {
-                rs1: Register::X16,
-                rs2: Register::X3,
-                imm: 8,
-                func3: 3
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_addiw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0011_1000_0000_0100_0001_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Addiw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                imm: 3,
-                func3: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_slliw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0011_1000_0001_0100_0001_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Slliw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                shamt: 3,
-                func3: 1,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_srliw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0011_1000_0101_0100_0001_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Srliw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                shamt: 3,
-                func3: 5,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sraiw_bits_into_instruction() {
-        let bits: Inst = 0b0100_0000_0011_1000_0101_0100_0001_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sraiw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                shamt: 3,
-                func3: 5,
-                func7: 32
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_addw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0011_1000_0000_0100_0011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Addw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                rs2: Register::X3,
-                func3: 0,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_subw_bits_into_instruction() {
-        let bits: Inst = 0b0100_0000_0011_1000_0000_0100_0011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Subw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                rs2: Register::X3,
-                func3: 0,
-                func7: 32
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sllw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0011_1000_0001_0100_0011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sllw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                rs2: Register::X3,
-                func3: 1,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_srlw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0011_1000_0101_0100_0011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Srlw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                rs2: Register::X3,
-                func3: 5,
-                func7: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_sraw_bits_into_instruction() {
-        let bits: Inst = 0b0100_0000_0011_1000_0101_0100_0011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Sraw {
-                rd: Register::X8,
-                rs1: Register::X16,
-                rs2: Register::X3,
-                func3: 5,
-                func7: 32
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_fence_i_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_1100_1010_1001_0101_1000_1111 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FenceI {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 12,
-                func3: 1
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_csrrw_bits_into_instruction() {
-        let bits: Inst = 0b1010_1010_1100_1110_1001_0110_1111_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Csrrw {
-                rd: Register::X13,
-                rs1: Register::X29,
-                csr: 2732,
-                func3: 1
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_csrrs_bits_into_instruction() {
-        let bits: Inst = 0b1010_1010_1100_1010_1010_0101_1111_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Csrrs {
-                rd: Register::X11,
-                rs1: Register::X21,
-                csr: 2732,
-                func3: 2
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_csrrc_bits_into_instruction() {
-        let bits: Inst = 0b1010_1010_1100_1010_1011_0101_1111_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Csrrc {
-                rd: Register::X11,
-                rs1: Register::X21,
-                csr: 2732,
-                func3: 3
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_csrrwi_bits_into_instruction() {
-        let bits: Inst = 0b1010_1010_1100_1010_1101_0101_1111_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Csrrwi {
-                rd: Register::X11,
-                uimm: 21,
-                csr: 2732,
-                func3: 5
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_csrrsi_bits_into_instruction() {
-        let bits: Inst = 0b1010_1010_1100_1010_1110_0101_1111_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Csrrsi {
-                rd: Register::X11,
-                uimm: 21,
-                csr: 2732,
-                func3: 6
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_csrrci_bits_into_instruction() {
-        let bits: Inst = 0b1010_1010_1100_1010_1111_0101_1111_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Csrrci {
-                rd: Register::X11,
-                uimm: 21,
-                csr: 2732,
-                func3: 7
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_mul_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1000_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Mul {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_mulh_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1001_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Mulh {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 1,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_mulhsu_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1010_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Mulhsu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 2,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_mulhu_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1011_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Mulhu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 3,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_div_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1100_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Div {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 4,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_divu_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1101_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Divu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_rem_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1110_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Rem {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 6,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_remu_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1111_0101_1011_0011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Remu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 7,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_mulw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1000_0101_1011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Mulw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_divw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1100_0101_1011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Divw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 4,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_divuw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1101_0101_1011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Divuw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_remw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1110_0101_1011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Remw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 6,
-                func7: 1
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_remuw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_1100_1010_1111_0101_1011_1011 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::RemuW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 7,
-                func7: 1
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_lrw_bits_into_instruction() {
-        let bits: Inst = 0b0001_0100_0000_0101_0010_0110_0010_1111 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::LrW {
-                rd: Register::X12,
-                rs1: Register::X10,
-                aq: 1,
-                rl: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_convert_valid_scw_bits_into_instruction() {
-        let bits: Inst = 0b0001_1010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::ScW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoswapw_bits_into_instruction() {
-        let bits: Inst = 0b0000_1010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoswapW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoaddw_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoaddW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoxorw_bits_into_instruction() {
-        let bits: Inst = 0b0010_0010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoxorW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoandw_bits_into_instruction() {
-        let bits: Inst = 0b0110_0010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoandW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoorw_bits_into_instruction() {
-        let bits: Inst = 0b0100_0010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoorW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amominw_bits_into_instruction() {
-        let bits: Inst = 0b1000_0010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmominW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amomaxw_bits_into_instruction() {
-        let bits: Inst = 0b1010_0010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmomaxW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amominuw_bits_into_instruction() {
-        let bits: Inst = 0b1100_0010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmominuW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amomaxuw_bits_into_instruction() {
-        let bits: Inst = 0b1110_0010_0000_0101_0010_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmomaxuW {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_lrd_bits_into_instruction() {
-        let bits: Inst = 0b0001_0100_0000_0101_0011_0110_0010_1111 as u32;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::LrD {
-                rd: Register::X12,
-                rs1: Register::X10,
-                aq: 1,
-                rl: 0
-            }
-        );
-    }
-    #[test]
-    fn test_convert_valid_scd_bits_into_instruction() {
-        let bits: Inst = 0b0001_1010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::ScD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoswapd_bits_into_instruction() {
-        let bits: Inst = 0b0000_1010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoswapD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoaddd_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoaddD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoxord_bits_into_instruction() {
-        let bits: Inst = 0b0010_0010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoxorD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amoandd_bits_into_instruction() {
-        let bits: Inst = 0b0110_0010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoandD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-    #[test]
-    fn test_convert_valid_amoord_bits_into_instruction() {
-        let bits: Inst = 0b0100_0010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoorD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-    #[test]
-    fn test_convert_valid_amomind_bits_into_instruction() {
-        let bits: Inst = 0b1000_0010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmominD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amomaxd_bits_into_instruction() {
-        let bits: Inst = 0b1010_0010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmomaxD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-    
-    #[test]
-    fn test_convert_valid_amominud_bits_into_instruction() {
-        let bits: Inst = 0b1100_0010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmominuD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_amomaxud_bits_into_instruction() {
-        let bits: Inst = 0b1110_0010_0000_0101_0011_0011_0010_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::AmomaxuD {
-                rd: Register::X6,
-                rs1: Register::X10,
-                rs2: Register::X0,
-                aq: 0,
-                rl: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_flw_bits_into_instruction() {
-        let bits: Inst = 0b0101_1011_1000_0101_0010_0011_0000_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Flw {
-                rd: Register::X6,
-                rs1: Register::X10,
-                imm: 1464
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsw_bits_into_instruction() {
-        let bits: Inst = 0b0101_1011_1000_0101_0010_0011_0010_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Fsw {
-                rs1: Register::X10,
-                rs2: Register::X24,
-                imm: 47
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmadds_bits_into_instruction() {
-        let bits: Inst = 0b0101_0000_0110_1100_1010_1110_1100_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaddS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmsubs_bits_into_instruction() {
-        let bits: Inst = 0b0101_0000_0110_1100_1010_1110_1100_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmsubS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fnmsubs_bits_into_instruction() {
-        let bits: Inst = 0b0101_0000_0110_1100_1010_1110_1100_1011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmsubS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fnmadds_bits_into_instruction() {
-        let bits: Inst = 0b0101_0000_0110_1100_1010_1110_1100_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmaddS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fadds_bits_into_instruction() {
-        let bits: Inst = 0b0000_0000_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FaddS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsubs_bits_into_instruction() {
-        let bits: Inst = 0b0000_1000_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsubS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmuls_bits_into_instruction() {
-        let bits: Inst = 0b0001_0000_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmulS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fdivs_bits_into_instruction() {
-        let bits: Inst = 0b0001_1000_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FdivS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsqrts_bits_into_instruction() {
-        let bits: Inst = 0b0101_1000_0000_0101_1010_1000_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsqrtS {
-                rd: Register::X16,
-                rs1: Register::X11,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjs_bits_into_instruction() {
-        let bits: Inst = 0b0010_0000_0110_0101_1000_1000_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjS {
-                rd: Register::X16,
-                rs1: Register::X11,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjns_bits_into_instruction() {
-        let bits: Inst = 0b0010_0000_0110_0101_1001_1000_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjnS {
-                rd: Register::X16,
-                rs1: Register::X11,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjxs_bits_into_instruction() {
-        let bits: Inst = 0b0010_0000_0110_0101_1010_1000_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjxS {
-                rd: Register::X16,
-                rs1: Register::X11,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmins_bits_into_instruction() {
-        let bits: Inst = 0b0010_1000_0110_0101_1000_1000_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FminS {
-                rd: Register::X16,
-                rs1: Register::X11,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmaxs_bits_into_instruction() {
-        let bits: Inst = 0b0010_1000_0110_0101_1001_1000_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaxS {
-                rd: Register::X16,
-                rs1: Register::X11,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtws_bits_into_instruction() {
-        let bits: Inst = 0b1100_0000_0000_0101_1011_0011_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWS {
-                rd: Register::X6,
-                rs1: Register::X11,
-                rm: 3
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtwus_bits_into_instruction() {
-        let bits: Inst = 0b1100_0000_0001_0101_1011_0011_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWUS {
-                rd: Register::X6,
-                rs1: Register::X11,
-                rm: 3
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmvxw_bits_into_instruction() {
-        let bits: Inst = 0b1110_0000_0000_0101_1000_0011_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvXW {
-                rd: Register::X6,
-                rs1: Register::X11,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_feqs_bits_into_instruction() {
-        let bits: Inst = 0b1110_0000_0000_0101_1000_0011_0101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvXW {
-                rd: Register::X6,
-                rs1: Register::X11,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_flts_bits_into_instruction() {
-        let bits: Inst = 0b1010_0000_0111_0110_1010_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FeqS {
-                rd: Register::X3,
-                rs1: Register::X13,
-                rs2: Register::X7,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fclasss_bits_into_instruction() {
-        let bits: Inst = 0b1110_0000_0000_0110_1001_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FclassS {
-                rd: Register::X3,
-                rs1: Register::X13,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtsw_bits_into_instruction() {
-        let bits: Inst = 0b1101_0000_0000_0110_1001_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSW {
-                rd: Register::X3,
-                rs1: Register::X13,
-                rm: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtswu_bits_into_instruction() {
-        let bits: Inst = 0b1101_0000_0001_0110_1001_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSWU {
-                rd: Register::X3,
-                rs1: Register::X13,
-                rm: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmvwx_bits_into_instruction() {
-        let bits: Inst = 0b1111_0000_0000_0110_1000_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvWX {
-                rd: Register::X3,
-                rs1: Register::X13,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtls_bits_into_instruction() {
-        let bits: Inst = 0b1100_0000_0010_0110_1001_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLS {
-                rd: Register::X3,
-                rs1: Register::X13,
-                rm: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtlus_bits_into_instruction() {
-        let bits: Inst = 0b1100_0000_0011_0110_1001_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLUS {
-                rd: Register::X3,
-                rs1: Register::X13,
-                rm: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtsl_bits_into_instruction() {
-        let bits: Inst = 0b1101_0000_0010_0110_1001_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSL {
-                rd: Register::X3,
-                rs1: Register::X13,
-                rm: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtslu_bits_into_instruction() {
-        let bits: Inst = 0b1101_0000_0011_0110_1001_0001_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSLU {
-                rd: Register::X3,
-                rs1: Register::X13,
-                rm: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fld_bits_into_instruction() {
-        let bits: Inst = 0b0101_1011_1000_0101_0011_0011_0000_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Fld {
-                rd: Register::X6,
-                rs1: Register::X10,
-                imm: 1464
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsd_bits_into_instruction() {
-        let bits: Inst = 0b0101_1011_1000_0101_0011_0011_0010_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Fsd {
-                rs1: Register::X10,
-                rs2: Register::X24,
-                imm: 47
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmaddd_bits_into_instruction() {
-        let bits: Inst = 0b0101_0010_0110_1100_1010_1110_1100_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaddD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmsubd_bits_into_instruction() {
-        let bits: Inst = 0b0101_0010_0110_1100_1010_1110_1100_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmsubD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fnmsubd_bits_into_instruction() {
-        let bits: Inst = 0b0101_0010_0110_1100_1010_1110_1100_1011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmsubD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmnaddd_bits_into_instruction() {
-        let bits: Inst = 0b0101_0010_0110_1100_1010_1110_1100_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmaddD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_faddd_bits_into_instruction() {
-        let bits: Inst = 0b0000_0010_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FaddD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsubd_bits_into_instruction() {
-        let bits: Inst = 0b0000_1010_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsubD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmuld_bits_into_instruction() {
-        let bits: Inst = 0b0001_0010_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmulD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fdivd_bits_into_instruction() {
-        let bits: Inst = 0b0001_1010_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FdivD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_sqrtd_bits_into_instruction() {
-        let bits: Inst = 0b0101_1010_0000_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsqrtD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjd_bits_into_instruction() {
-        let bits: Inst = 0b0010_0010_0110_1100_1000_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjnd_bits_into_instruction() {
-        let bits: Inst = 0b0010_0010_0110_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjnD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjxd_bits_into_instruction() {
-        let bits: Inst = 0b0010_0010_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjxD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmind_bits_into_instruction() {
-        let bits: Inst = 0b0010_1010_0110_1100_1000_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FminD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmaxd_bits_into_instruction() {
-        let bits: Inst = 0b0010_1010_0110_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaxD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtsd_bits_into_instruction() {
-        let bits: Inst = 0b0100_0000_0001_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtds_bits_into_instruction() {
-        let bits: Inst = 0b0100_0010_0000_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_feqd_bits_into_instruction() {
-        let bits: Inst = 0b1010_0010_0011_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FeqD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X3,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fltd_bits_into_instruction() {
-        let bits: Inst = 0b1010_0010_0011_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FltD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X3,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fled_bits_into_instruction() {
-        let bits: Inst = 0b1010_0010_0011_1100_1000_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FleD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X3,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fclassd_bits_into_instruction() {
-        let bits: Inst = 0b1110_0010_0000_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FclassD {
-                rd: Register::X29,
-                rs1: Register::X25,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtwd_bits_into_instruction() {
-        let bits: Inst = 0b1100_0010_0000_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtwud_bits_into_instruction() {
-        let bits: Inst = 0b1100_0010_0001_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWUD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtdw_bits_into_instruction() {
-        let bits: Inst = 0b1101_0010_0000_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDW {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtdwu_bits_into_instruction() {
-        let bits: Inst = 0b1101_0010_0001_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDWU {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtld_bits_into_instruction() {
-        let bits: Inst = 0b1100_0010_0010_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtlud_bits_into_instruction() {
-        let bits: Inst = 0b1100_0010_0011_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLUD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmvxd_bits_into_instruction() {
-        let bits: Inst = 0b1110_0010_0000_1100_1000_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvXD {
-                rd: Register::X29,
-                rs1: Register::X25,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtdl_bits_into_instruction() {
-        let bits: Inst = 0b1101_0010_0010_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDL {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtdlu_bits_into_instruction() {
-        let bits: Inst = 0b1101_0010_0011_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDLU {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmvdx_bits_into_instruction() {
-        let bits: Inst = 0b1111_0010_0000_1100_1000_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvDX {
-                rd: Register::X29,
-                rs1: Register::X25,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_flq_bits_into_instruction() {
-        let bits: Inst = 0b0101_1011_1000_0101_0100_0011_0000_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Flq {
-                rd: Register::X6,
-                rs1: Register::X10,
-                imm: 1464
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsq_bits_into_instruction() {
-        let bits: Inst = 0b0101_1011_1000_0101_0100_0011_0010_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::Fsq {
-                rs1: Register::X10,
-                rs2: Register::X24,
-                imm: 47
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmaddq_bits_into_instruction() {
-        let bits: Inst = 0b0101_0110_0110_1100_1100_1110_1100_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaddQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 4
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmsubq_bits_into_instruction() {
-        let bits: Inst = 0b0101_0110_0110_1100_1100_1110_1100_0111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmsubQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 4
-            }
-        )
-    }
-    #[test]
-    fn test_convert_valid_fnmsubq_bits_into_instruction() {
-        let bits: Inst = 0b0101_0110_0110_1100_1100_1110_1100_1011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmsubQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 4
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fnmaddq_bits_into_instruction() {
-        let bits: Inst = 0b0101_0110_0110_1100_1100_1110_1100_1111;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmaddQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rs3: Register::X10,
-                rm: 4
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_faddq_bits_into_instruction() {
-        let bits: Inst = 0b0000_0110_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FaddQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsubq_bits_into_instruction() {
-        let bits: Inst = 0b0000_1110_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsubQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmulq_bits_into_instruction() {
-        let bits: Inst = 0b0001_0110_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmulQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fdivq_bits_into_instruction() {
-        let bits: Inst = 0b0001_1110_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FdivQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsqrtq_bits_into_instruction() {
-        let bits: Inst = 0b0101_1110_0000_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsqrtQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjq_bits_into_instruction() {
-        let bits: Inst = 0b0010_0110_0110_1100_1000_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjnq_bits_into_instruction() {
-        let bits: Inst = 0b0010_0110_0110_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjnQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fsgnjxq_bits_into_instruction() {
-        let bits: Inst = 0b0010_0110_0110_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjxQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fminq_bits_into_instruction() {
-        let bits: Inst = 0b0010_1110_0110_1100_1000_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FminQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fmaxq_bits_into_instruction() {
-        let bits: Inst = 0b0010_1110_0110_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaxQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X6,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtsq_bits_into_instruction() {
-        let bits: Inst = 0b0100_0000_0011_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtqs_bits_into_instruction() {
-        let bits: Inst = 0b0100_0110_0000_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQS {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtdq_bits_into_instruction() {
-        let bits: Inst = 0b0100_0010_0011_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtqd_bits_into_instruction() {
-        let bits: Inst = 0b0100_0110_0001_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQD {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_feqq_bits_into_instruction() {
-        let bits: Inst = 0b1010_0110_0011_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FeqQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X3,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fltq_bits_into_instruction() {
-        let bits: Inst = 0b1010_0110_0011_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FltQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X3,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fleq_bits_into_instruction() {
-        let bits: Inst = 0b1010_0110_0011_1100_1000_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FleQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rs2: Register::X3,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fclassq_bits_into_instruction() {
-        let bits: Inst = 0b1110_0110_0000_1100_1001_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FclassQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtwq_bits_into_instruction() {
-        let bits: Inst = 0b1100_0110_0000_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtwuq_bits_into_instruction() {
-        let bits: Inst = 0b1100_0110_0001_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWUQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtqw_bits_into_instruction() {
-        let bits: Inst = 0b1101_0110_0000_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQW {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtqwu_bits_into_instruction() {
-        let bits: Inst = 0b1101_0110_0001_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQWU {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtlq_bits_into_instruction() {
-        let bits: Inst = 0b1100_0110_0010_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtluq_bits_into_instruction() {
-        let bits: Inst = 0b1100_0110_0011_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLUQ {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtql_bits_into_instruction() {
-        let bits: Inst = 0b1101_0110_0010_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQL {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_convert_valid_fcvtqlu_bits_into_instruction() {
-        let bits: Inst = 0b1101_0110_0011_1100_1010_1110_1101_0011;
-        let instruction: Instruction = bits.into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQLU {
-                rd: Register::X29,
-                rs1: Register::X25,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_valid_bits_for_invalid_base() {
-        let bits: Inst = 0b0000_0000_0000_1000_0110_0100_0000_0011 as u32;
-        let enc_table = EncodingTable::new(Extension::I, Base::I32);
-        let instruction: Instruction = Instruction::decode(bits, &enc_table);
-        assert_eq!(
-            instruction,
-            Instruction::Undefined
-        )
-
-    }
-
-    #[test]
-    fn test_valid_bits_for_invalid_ext() {
-        let bits: Inst = 0b0001_0100_0000_0101_0010_0110_0010_1111 as u32;
-        let enc_table = EncodingTable::new(Extension::M, Base::I32);
-        let instruction: Instruction = Instruction::decode(bits, &enc_table);
-        assert_eq!(
-            instruction,
-            Instruction::Undefined
-        )
-    }
-
-    #[test]
-    fn test_invalid_bits_for_valid_base_and_ext() {
-        let bits: Inst = 0b0001_0100_0001_0101_0010_0110_0010_1111 as u32;
-        let enc_table = EncodingTable::new(Extension::A, Base::I32);
-        let instruction: Instruction = Instruction::decode(bits, &enc_table);
-        assert_eq!(
-            instruction,
-            Instruction::Undefined
-        )
-    }
-
-    #[test]
-    fn test_fetch_and_decode_add_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Add {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_add_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_0011 as u8];
-
-        // Preload some values int the relevant registers
-        soft.registers[Register::X12 as usize] = 24u64;
-        soft.registers[Register::X21 as usize] = 32u64;
-
-        // Load the program code
-        soft.load_program(program);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            56u64
-        )
-    }
-
-    #[test]
-    fn test_fetch_and_decode_addi_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Addi {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_addi_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_0011 as u8];
-
-        // Preload value into rs1
-        soft.registers[Register::X21 as usize] = 1000u64;
-
-        // Load the program code
-        soft.load_program(program);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            4276u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lui_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Lui {
-                rd: Register::X10,
-                imm: -859144192,
-            }
-        );
-    }
-
-    #[test]
-    fn test_lui_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            (-859144192 as i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_auipc_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0001_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        println!("{:?}", instruction);
-        assert_eq!(
-            instruction,
-            Instruction::Auipc {
-                rd: Register::X10,
-                imm: -859144192,
-            }
-        );
-    }
-
-    #[test]
-    fn test_auipc_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0001_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        soft.execute();
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            (-859144192 as i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_jal_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Jal {
-                rd: Register::X10,
-                imm: -360112,
-            }
-        );
-    }
-    
-    #[test]
-    fn test_jal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        soft.execute();
-        println!("{}", (-360112i64) as u64);
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            4
-        );
-
-        assert_eq!(
-            soft.pc,
-            18446744073709191504
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_jalr_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Jalr {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-            }
-        );    
-    }
-
-    #[test]
-    fn test_jalr_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        soft.pc = 0;
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            4
-        );
-
-        assert_eq!(
-            soft.pc,
-            ((1000 + 3276) & !1) 
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_beq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Beq {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -2870,
-                func3: 0
-            }
-        );
-    }
-    
-    #[test]
-    fn test_beq_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 100;
-        soft.execute();
-        
-        assert_eq!(
-            soft.pc,
-            (-2870 as i64) as u64
-        )
-        
-    }
-
-    #[test]
-    fn test_beq_not_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 1000;
-        soft.execute();
-        
-        assert_eq!(
-            soft.pc,
-            4
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_bne_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Bne {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -2870,
-                func3: 1
-            }
-        );
-    }
-
-    #[test]
-    fn test_bne_not_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            (-2870 as i64) as u64
-        )
-    }
-
-    #[test]
-    fn test_bne_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 100;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            4
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_blt_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Blt {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -2870,
-                func3: 4
-            }
-        );
-    }
-
-    #[test]
-    fn test_blt_less_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            (-2870 as i64) as u64
-        )
-    }
-
-    #[test]
-    fn test_blt_greater_or_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.registers[Register::X12 as usize] = 100;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            4
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_bge_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Bge {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -2870,
-                func3: 5
-            }
-        );
-    }
-
-    #[test]
-    fn test_bge_greater_or_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.registers[Register::X20 as usize] = 100;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            (-2870 as i64) as u64
-        )
-    }
-
-    #[test]
-    fn test_bge_less_than_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 10;
-        soft.registers[Register::X12 as usize] = 100;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            4
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_bltu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Bltu {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -2870,
-                func3: 6
-            }
-        );
-    }
-
-    #[test]
-    fn test_bltu_less_than_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 10;
-        soft.registers[Register::X12 as usize] = 100;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            (-2870 as i64) as u64
-        )
-    }
-
-    #[test]
-    fn test_bltu_greater_than_or_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 10;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            4
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_bgeu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Bgeu {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: -2870,
-                func3: 7
-            }
-        );
-    }
-
-    #[test]
-    fn test_bgeu_greater_than_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 10;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            (-2870 as i64) as u64
-        )
-    }
-
-    #[test]
-    fn test_bgeu_less_than_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0110_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 10;
-        soft.registers[Register::X12 as usize] = 100;
-        soft.execute();
-
-        assert_eq!(
-            soft.pc,
-            4
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lb_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Lb {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 0,
-            }
-        );
-    }
-    
-    #[test]
-    fn test_lb_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.bus.write(3376, 235, 8);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            235
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lh_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Lh {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 1,
-            }
-        );
-    }
-    
-    #[test]
-    fn test_lh_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.bus.write(3376, 3000, 16);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            3000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Lw {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 2,
-            }
-        );
-    }
-    
-    #[test]
-    fn test_lw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.bus.write(3376, 100000, 32);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            100000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lbu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Lbu {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 4,
-            }
-        );
-    }
-    
-    #[test]
-    fn test_lbu_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.bus.write(3376, 235, 8);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            235
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lhu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Lhu {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 5,
-            }
-        );
-    }
-
-    #[test]
-    fn test_lhu_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.bus.write(3376, 3000, 16);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            3000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sb_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0010_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Sb {
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: 110,
-                func3: 0,
-            }
-        );
-    }
-    
-    #[test]
-    fn test_sb_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0010_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 235;
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&((210 as i64) as u64), 8).unwrap(),
-            235
-        )
-        
-    }
-
-    #[test]
-    fn fetch_and_decode_sh_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0010_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Sh {
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: 110,
-                func3: 1,
-            }
-        );
-    }    
-
-    #[test]
-    fn test_sh_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0010_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 3000;
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&210, 16).unwrap(),
-            3000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0010_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Sw {
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: 110,
-                func3: 2,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0010_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 100000;
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&210, 32).unwrap(),
-            100000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_slti_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Slti {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 2,
-            }
-        );
-    }
-    
-    #[test]
-    fn test_slti_less_than_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 2;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn test_slti_greater_or_equal_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            0
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_xori_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Xori {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 4,
-            }
-        );
-    }    
-
-    #[test]
-    fn test_xori_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 0b1100_1100_1100;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            0b0000_0000_0000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_ori_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Ori {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 6,
-            }
-        );
-    }
-
-    #[test]
-    fn test_ori_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 0b0011_0011_0011;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            0b1111_1111_1111
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_andi_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Andi {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 7,
-            }
-        );
-    }    
-
-    #[test]
-    fn test_andi_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 0b0011_0011_0011;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            0b0000_0000_0000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_slli_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Slli {
-                rd: Register::X10,
-                rs1: Register::X21,
-                shamt: 12,
-                func3: 1,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_slli_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            1000u64.wrapping_shl(12)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_srli_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Srli {
-                rd: Register::X10,
-                rs1: Register::X21,
-                shamt: 12,
-                func3: 5,
-                func7: 0,
-            }
-        );
-    }
-    
-    #[test]
-    fn test_srli_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            1000u64.wrapping_shr(12)
-        );
-    }
-
-    #[test]
-    fn fetch_and_decode_srai_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Srai {
-                rd: Register::X10,
-                rs1: Register::X21,
-                shamt: 12,
-                func3: 5,
-                func7: 32,
-            }
-        );
-    }
-
-    #[test]
-    fn test_srai_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            1000i64.wrapping_shr(12) as u64
-        );
-    }
-
-    #[test]
-    fn fetch_and_decode_sub_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Sub {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 32,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sub_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.registers[Register::X12 as usize] = 300;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            700
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sll_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Sll {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 1,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sll_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 12;
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            1000 << (((0b1100 & 0x3f) as u64) as u32)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_slt_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Slt {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 2,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_slt_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 12;
-        soft.registers[Register::X21 as usize] = 10;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sltu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Sltu {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 3,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sltu_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 12;
-        soft.registers[Register::X21 as usize] = 10;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            1            
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_xor_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Xor {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 4,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_xor_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 0b1100_1100_1100;
-        soft.registers[Register::X12 as usize] = 0b0011_0011_0011;
-        soft.execute();
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            0b1111_1111_1111
-        )
-        
-    }
-
-    #[test]
-    fn fetch_and_decode_srl_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Srl {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_srl_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 12;
-        soft.registers[Register::X21 as usize] = 10;
-        soft.execute();
-        let shamt = ((12 & 0x3f) as u64) as u32;
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            10u64.wrapping_shr(shamt)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sra_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Sra {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 32,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sra_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 12;
-        soft.registers[Register::X21 as usize] = 10;
-        soft.execute();
-        let shamt = ((12 & 0x3f) as u64) as u32;
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            (10i64.wrapping_shr(shamt) as u64)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_or_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Or {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 6,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_or_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 12;
-        soft.registers[Register::X21 as usize] = 10;
-        soft.execute();
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            (0b0000_1010 | 0b0000_1100)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_and_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::And {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 7,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_and_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0011_0011 as u8];
-        soft.load_program(program);
-        
-        soft.registers[Register::X12 as usize] = 12;
-        soft.registers[Register::X21 as usize] = 10;
-        soft.execute();
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            (0b0000_1010 & 0b0000_1100)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lwu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Lwu {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 6,
-            }
-        );
-    }
-
-    #[test]
-    fn test_lwu_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.bus.write(3376, 100000, 32);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            100000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_ld_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Ld {
-                rd: Register::X10,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 3,
-            }
-        );
-    }
-
-    #[test]
-    fn test_ld_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0000_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.bus.write(3376, 100000, 64);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            100000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0010_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Sd {
-                rs1: Register::X21,
-                rs2: Register::X12,
-                imm: 110,
-                func3: 3,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sd_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0010_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 100;
-        soft.registers[Register::X12 as usize] = 100000;
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&210, 64).unwrap(),
-            100000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_addiw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Addiw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3276,
-                func3: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_addiw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            4276
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_slliw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0001_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Slliw {
-                rd: Register::X10,
-                rs1: Register::X21,
-                shamt: 12,
-                func3: 1,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_slliw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0001_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.execute();
-        let shamt: u32 = ((12i32) as u32);
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            (((1000u64.wrapping_shl(shamt.try_into().unwrap()) as i32) as i64) as u64)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sraiw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Sraiw {
-                rd: Register::X10,
-                rs1: Register::X21,
-                shamt: 12,
-                func3: 5,
-                func7: 32,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sraiw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        let shamt = 12;
-        soft.execute();
-        
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            (((1000u64.wrapping_shr(shamt) as i32) as i64) as u64)
-        );
-    }
-
-    #[test]
-    fn fetch_and_decode_addw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Addw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_addw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 500;
-        soft.registers[Register::X12 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1500
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_subw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-
-        assert_eq!(
-            instruction,
-            Instruction::Subw {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 32,
-            }
-        );
-    }
-
-    #[test]
-    fn test_subw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.registers[Register::X12 as usize] = 300;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            700
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sllw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Sllw {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 1,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sllw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0011_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.registers[Register::X12 as usize] = 8;
-        soft.execute();
-        let shamt = ((0b1000 & 0x3f) as u64) as u32;
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            ((1000u32.wrapping_shl(shamt) as i32) as i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_srlw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Srlw {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 0,
-            }
-        );
-    }
-
-    #[test]
-    fn test_srlw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.registers[Register::X12 as usize] = 8;
-        soft.execute();
-        let shamt = ((0b1000 & 0x3f) as u64) as u32;
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            ((1000u32.wrapping_shr(shamt) as i32) as i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_sraw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Sraw {
-                rd: Register::X10,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 32,
-            }
-        );
-    }
-
-    #[test]
-    fn test_sraw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 1000;
-        soft.registers[Register::X12 as usize] = 8;
-        soft.execute();
-        let shamt = ((0b1000 & 0x3f) as u64) as u32;
-
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            (((1000u32 >> shamt) as i32) as i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_csrrw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Csrrw {
-                rd: Register::X10,
-                rs1: Register::X21,
-                csr: 1036,
-                func3: 1
-            }
-        );
-    }
-
-    #[test]
-    fn test_csrrw_execution_dest_non_zero() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        soft.csr[1036usize] = 1000;
-        soft.registers[Register::X21 as usize] = 500;
-        soft.execute();
-        let csr_val = 0b0011_1110_1000;
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            csr_val.zero_extend(&12)
-        );
-
-        assert_eq!(
-            soft.csr[1036usize],
-            soft.registers[Register::X21 as usize]
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_csrrs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Csrrs {
-                rd: Register::X10,
-                rs1: Register::X21,
-                csr: 1036,
-                func3: 2
-            }
-        );
-    }
-
-    #[test]
-    fn test_csrrs_execution_dest_non_zero() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        soft.csr[1036usize] = 1000;
-        soft.registers[Register::X21 as usize] = 500;
-        soft.execute();
-        let csr_val = 0b0011_1110_1000;
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            csr_val.zero_extend(&12)
-        );
-
-        assert_eq!(
-            soft.csr[1036usize],
-            (csr_val | soft.registers[Register::X21 as usize])
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_csrrc_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Csrrc {
-                rd: Register::X10,
-                rs1: Register::X21,
-                csr: 1036,
-                func3: 3
-            }
-        );
-    }
-
-    #[test]
-    fn test_csrrc_execution_dest_non_zero() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        soft.csr[1036usize] = 1000;
-        soft.registers[Register::X21 as usize] = 500;
-        soft.execute();
-        let csr_val = 0b0011_1110_1000;
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            csr_val.zero_extend(&12)
-        );
-
-        assert_eq!(
-            soft.csr[1036usize],
-            (csr_val & soft.registers[Register::X21 as usize])
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_csrrwi_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Csrrwi {
-                rd: Register::X10,
-                uimm: 21,
-                csr: 1036,
-                func3: 5
-            }
-        );
-    }
-
-    #[test]
-    fn test_csrrwi_execution_dest_non_zero() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        soft.csr[1036usize] = 1000;
-        soft.registers[Register::X21 as usize] = 500;
-        soft.execute();
-        let csr_val = 0b0011_1110_1000;
-        let imm = 21u64.zero_extend(&7);
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            csr_val
-        );
-
-        assert_eq!(
-            soft.csr[1036usize],
-            imm
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_csrrsi_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Csrrsi {
-                rd: Register::X10,
-                uimm: 21,
-                csr: 1036,
-                func3: 6
-            }
-        );
-    }
-
-    #[test]
-    fn test_csrrsi_execution_dest_non_zero() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        soft.csr[1036usize] = 1000;
-        soft.registers[Register::X21 as usize] = 500;
-        soft.execute();
-        let csr_val = 0b0011_1110_1000;
-        let imm = 21u64.zero_extend(&7);
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            csr_val
-        );
-
-        assert_eq!(
-            soft.csr[1036usize],
-            imm | csr_val
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_csrrci_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        
-        assert_eq!(
-            instruction,
-            Instruction::Csrrci {
-                rd: Register::X10,
-                uimm: 21,
-                csr: 1036,
-                func3: 7
-            }
-        );
-    }
-
-    #[test]
-    fn test_csrrci_execution_dest_non_zero() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0111_0011 as u8];
-        soft.load_program(program);
-        soft.csr[1036usize] = 1000;
-        soft.registers[Register::X21 as usize] = 500;
-        soft.execute();
-        let csr_val = 0b0011_1110_1000;
-        let imm = 21u64.zero_extend(&7);
-        
-        assert_eq!(
-            soft.registers[Register::X10 as usize],
-            csr_val
-        );
-
-        assert_eq!(
-            soft.csr[1036usize],
-            imm & csr_val
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_mul_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Mul {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_mul_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 10u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            200u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_mulh_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Mulh {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 1,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_mulh_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 25u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            500u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_mulhsu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Mulhsu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 2,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_mulhsu_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 25u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        let expected = 20i128.overflowing_mul(25i128).0;
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            expected as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_mulhu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Mulhu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 3,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_mulhu_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 25u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        let expected = 20u128.overflowing_mul(25u128).0;
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            expected as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_div_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Div {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 4,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_div_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 5u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            (4i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_divu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Divu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_divu_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 5u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            4u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_rem_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Rem {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 6,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_rem_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 17u64;
-        soft.registers[Register::X21 as usize] = 25u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            (8i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_remu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Remu {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 7,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_remu_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 17u64;
-        soft.registers[Register::X21 as usize] = 25u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            8u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_mulw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Mulw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 0,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_mulw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 25u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            500u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_divw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Divw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 4,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_divw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 5u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            (4i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_divuw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Divuw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 5,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_divuw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b1011_0011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 5u64;
-        soft.registers[Register::X21 as usize] = 20u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            4u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_remw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Remw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 6,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_remw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 17u64;
-        soft.registers[Register::X21 as usize] = 25u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            (8i64) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_remuw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::RemuW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X12,
-                func3: 7,
-                func7: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_remuw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b1011_1011 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X12 as usize] = 17u64;
-        soft.registers[Register::X21 as usize] = 25u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            8u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lrw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0010 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::LrW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_lrw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0010 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.bus.write(200, 1000, 32);
-        soft.execute();
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1000
-        );
-
-        assert!(
-            soft.res.contains(&200)
-        );
-    }
-
-    #[test]
-    fn fetch_and_decode_scw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::ScW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_scw_execution_addr_reserved() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.res.push(200);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            0
-        );
-
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            soft.registers[Register::X27 as usize]
-        );
-
-        assert!(
-            !soft.res.contains(&200)
-        );
-    }
-
-    #[test]
-    fn test_scw_execution_addr_not_reserved() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        );
-
-        assert_ne!(
-            soft.bus.read(&200, 32).unwrap(),
-            soft.registers[Register::X27 as usize]
-        );
-
-        assert!(
-            !soft.res.contains(&200)
-        );
-    }
-
-    #[test]
-    fn fetch_and_decode_amoswapw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoswapW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoswapw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            soft.registers[Register::X27 as usize]
-        );
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-    }
-
-    #[test]
-    fn fetch_and_decode_amoaddw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoaddW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoaddw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            6000
-        )
-
-    }
-
-    #[test]
-    fn fetch_and_decode_amoxorw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoxorW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoxorw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            0b1000001100000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amoandw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoandW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoandw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            0b1110001000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amoorw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoorW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoorw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            0b1001111101000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amominw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmominW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amominw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            1000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amomaxw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmomaxW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amomaxw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            5000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amominuw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmominuW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amominuw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            1000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amomaxuw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmomaxuW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-        
-    }
-
-    #[test]
-    fn test_amomaxuw_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 32);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 32).unwrap(),
-            5000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_lrd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0010 as u8, 0b0000_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::LrD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_lrd_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0010 as u8, 0b0000_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.bus.write(200, 1000, 64);
-        soft.execute();
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1000
-        );
-
-        assert!(
-            soft.res.contains(&200)
-        );
-    }
-
-    #[test]
-    fn fetch_and_decode_scd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::ScD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_scd_execution_address_reserved() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.res.push(200);
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            0
-        );
-
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            soft.registers[Register::X27 as usize]
-        );
-
-        assert!(
-            !soft.res.contains(&200)
-        );
-        
-    }
-
-    #[test]
-    fn test_scd_execution_address_not_reserved() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        );
-
-        assert_ne!(
-            soft.bus.read(&200, 64).unwrap(),
-            soft.registers[Register::X27 as usize]
-        );
-
-        assert!(
-            !soft.res.contains(&200)
-        );
-        
-    }
-
-    #[test]
-    fn fetch_and_decode_amoswapd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoswapD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoswapd_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 64);
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            soft.registers[Register::X27 as usize]
-        );
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-    }
-
-    #[test]
-    fn fetch_and_decode_amoxord_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoaddD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoxord_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 64);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            0b1000001100000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amoandd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoandD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoandd_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 64);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            0b1110001000
-        )
-        
-    }
-
-    #[test]
-    fn fetch_and_decode_amoord_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmoorD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amoord_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 64);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            0b1001111101000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amomind_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1000_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmominD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amomind_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1000_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 64);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            1000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amomaxd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmomaxD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amomaxd_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 64);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            5000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amominud_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmominuD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amominud_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 64);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            1000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_amomaxud_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::AmomaxuD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                aq: 0,
-                rl: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_amomaxud_execution() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 200;
-        soft.registers[Register::X27 as usize] = 1000;
-        soft.bus.write(200, 5000, 64);
-        soft.execute();
-        
-        // assert the value in rd == value in address at rs1
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            5000
-        );
-
-        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
-        assert_eq!(
-            soft.bus.read(&200, 64).unwrap(),
-            5000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_flw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1000_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Flw {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3643,
-            }
-        )        
-    }
-
-    #[test]
-    fn test_flw_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1000_0111 as u8];
-        soft.load_program(program);
-        soft.bus.write(3643, 5000, 32);
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            (f32::from_bits(5000 as u32)) as f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Fsw {
-                rs1: Register::X21,
-                rs2: Register::X27,
-                imm: 123,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsw_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_0111 as u8];
-        soft.load_program(program);
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(5000u32) as f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&123, 32).unwrap(),
-            5000
-        )
-    }
-
-    
-    #[test]
-    fn fetch_and_decode_fmadds_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaddS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmadds_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
-        
-        soft.load_program(program);
-        
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        soft.f_registers[Register::X28 as usize] = f32::from_bits(2u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let rs3_val = f32::from_bits(2u32) as f64;
-        let res = rs1_val.mul_add(rs2_val, rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmsubs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmsubS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmsubs_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        soft.f_registers[Register::X28 as usize] = f32::from_bits(2u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let rs3_val = f32::from_bits(2u32) as f64;
-        let res = rs1_val.mul_add(rs2_val, -rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fnmsubs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmsubS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fnmsubs_execute() {  
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        soft.f_registers[Register::X28 as usize] = f32::from_bits(2u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  -f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let rs3_val = -f32::from_bits(2u32) as f64;
-        let res = rs1_val.mul_add(rs2_val, rs3_val);
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-    
-    #[test]
-    fn fetch_and_decode_fnmadds_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmaddS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fnmadds_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
-        soft.load_program(program);
-    
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        soft.f_registers[Register::X28 as usize] = f32::from_bits(2u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  -f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let rs3_val = f32::from_bits(2u32) as f64;
-        let res = rs1_val.mul_add(rs2_val, rs3_val);
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fadds_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FaddS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fadds_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let res = rs1_val + rs2_val;
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsubs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsubS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsubs_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let res = rs1_val - rs2_val;
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmuls_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmulS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmuls_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let res = rs1_val * rs2_val;
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fdivs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FdivS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-
-    }
-
-    #[test]
-    fn test_fdivs_execute() {
-
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let res = rs1_val / rs2_val;
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsqrts_instruction() {
-
-        let mut soft = SoftThread::default();
-        let program = vec![0b0101_1000 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsqrtS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsqrts_execute() {
-
-        let mut soft = SoftThread::default();
-        let program = vec![0b0101_1000 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let res = rs1_val.sqrt();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsgnjs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjs_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-    
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let res = rs1_val.copysign(rs2_val);
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsgnjns_instruction() {
-
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjnS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjns_execute() {
-
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = -f32::from_bits(100u32) as f64;
-        let res = rs1_val.copysign(rs2_val);
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-    
-    #[test]
-    fn fetch_and_decode_fsgnjxs_instruction() {
-
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjxS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjxs_execute() {
-
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let sign_1 = (f32::from_bits(200u32)).to_bits() & 0x8000_0000;
-        let sign_2 = (f32::from_bits(100u32)).to_bits() & 0x8000_0000;
-        let other = (f32::from_bits(200u32)).to_bits() & 0x7fff_ffff;
-        let res = (f32::from_bits((sign_1 ^ sign_2) | other)) as f64;
-
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-
-    }
-
-    #[test]
-    fn fetch_and_decode_fmins_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_1001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FminS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmins_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_1001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-    
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let res = rs1_val.min(rs2_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-
-    }
-
-    #[test]
-    fn fetch_and_decode_fmaxs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_1001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaxS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmaxs_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_1001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-        
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let rs2_val = f32::from_bits(100u32) as f64;
-        let res = rs1_val.max(rs2_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtws_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtws_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-    
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-       
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let res = (rs1_val.round() as u32) as u64;
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtwus_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWUS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtwus_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-       
-        soft.execute();
-        
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let res = ((rs1_val.round() as u32) as i32) as u64;
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmvxw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0000 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvXW {
-                rd: Register::X11,
-                rs1: Register::X21,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmvxw_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0000 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-       
-        soft.execute();
-         
-        let rs1_val =  f32::from_bits(200u32) as f64;
-        let res = (((rs1_val.to_bits() & 0xffffffff) as i32) as i64) as u64;
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_feqs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FeqS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_feqs_eq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(200u32) as f64;
-
-        soft.execute();
-         
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn test_feqs_ne_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
-        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
-
-        soft.execute();
-         
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            0
-        )
-    }
-
-
-    #[test]
-    fn fetch_and_decode_flts_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FltS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_flts_lt_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.f_registers[Register::X27 as usize] = 200f64;
-
-        soft.execute();
-         
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn test_flts_eq_execute() { 
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        
-        soft.f_registers[Register::X21 as usize] = 200.0f64;
-        soft.f_registers[Register::X27 as usize] = 200.0f64;
-
-        soft.execute();
-         
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            0
-        )
-
-    }
-
-
-    #[test]
-    fn test_flts_gt_execute() { 
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        
-        soft.f_registers[Register::X21 as usize] = 300.0 as f64;
-        soft.f_registers[Register::X27 as usize] = 200.0 as f64;
-         
-        soft.execute();
-         
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            0
-        )
-
-    }
-
-    
-    #[test]
-    fn fetch_and_decode_fles_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FleS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fles_lt_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100.0f64;
-        soft.f_registers[Register::X27 as usize] = 200.0f64;
-        soft.execute();
-         
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-
-
-    #[test]
-    fn test_fles_eq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 200.0f64;
-        soft.f_registers[Register::X27 as usize] = 200.0f64;
-
-        soft.execute();
-         
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-    
-
-    #[test]
-    fn test_fles_gt_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 300.0f64;
-        soft.f_registers[Register::X27 as usize] = 200.0f64;
-
-        soft.execute();
-         
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            0
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fclasss_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FclassS {
-                rd: Register::X11,
-                rs1: Register::X21,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fclasss_execute() {
-        //FclassS doesn't currently
-        //do anything, need to implement
-        //class enum and logic for it
-    }
-
-
-    #[test]
-    fn fetch_and_decode_fcvtsw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtsw_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 300u64;
-        soft.execute();
-         
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            (((300u64) as i32) as f32) as f64 
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtswu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSWU {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtswu_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 300u64;
-        soft.execute();
-         
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            (((300u64) as u32) as f32) as f64 
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmvwx_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1111_0000 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvWX {
-                rd: Register::X11,
-                rs1: Register::X21,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmvwx_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1111_0000 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 300u64;
-        soft.execute(); 
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            f64::from_bits((300u64) & 0xffff_ffff) 
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtls_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0000 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtls_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0000 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 300.0f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            ((300.0f64) as f32).round() as u64 
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtlus_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0000 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLUS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtlus_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0000 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 300.0f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            ((300.0f64) as f32).round() as u64 
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtsl_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0000 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSL {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtsl_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0000 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 300u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            ((300u64) as f32) as f64 
-        )
-        
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtslu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0000 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSLU {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtslu_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0000 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 300u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            ((300u64) as f32) as f64 
-        )
-    }
-
-
-    #[test]
-    fn fetch_and_decode_fld_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1000_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Fld {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3643,
-            }
-        )        
-    }
-
-    #[test]
-    fn test_fld_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1000_0111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 3643;
-        let val = (5000.0f64).to_bits();
-        soft.bus.write(3643, val, 64);
-        soft.execute();
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            f64::from_bits(val)
-        )
-    }
-    
-   
-    #[test]
-    fn fetch_and_decode_fsd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Fsd {
-                rs1: Register::X21,
-                rs2: Register::X27,
-                imm: 123,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_0111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 123;
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(5000u64);
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&123, 64).unwrap(),
-            5000
-        )
-    }
-    
-    #[test]
-    fn fetch_and_decode_fmaddd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaddD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmaddd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
-        
-        soft.execute();
-        
-        let rs1_val =  f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let rs3_val = f64::from_bits(2u64);
-        let res = rs1_val.mul_add(rs2_val, rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-
-    #[test]
-    fn fetch_and_decode_fmsubd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmsubD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmsubd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
-        
-        soft.execute();
-        
-        let rs1_val =  f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let rs3_val = f64::from_bits(2u64);
-        let res = rs1_val.mul_add(rs2_val, -rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fnmsubd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmsubD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fnmsubd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
-        
-        soft.execute();
-        
-        let rs1_val =  -f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let rs3_val = f64::from_bits(2u64);
-        let res = rs1_val.mul_add(rs2_val, -rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fnmaddd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmaddD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fnmaddd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
-        
-        soft.execute();
-        
-        let rs1_val = -f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let rs3_val = f64::from_bits(2u64);
-        let res = rs1_val.mul_add(rs2_val, rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_faddd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FaddD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_faddd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64); 
-        let res = rs1_val + rs2_val;
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsubd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsubD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsubd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64); 
-        let res = rs1_val - rs2_val;
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-    
-    #[test]
-    fn fetch_and_decode_fmuld_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmulD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmuld_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64); 
-        let res = rs1_val * rs2_val;
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }    
-
-    #[test]
-    fn fetch_and_decode_fdivd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FdivD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fdivd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64); 
-        let res = rs1_val / rs2_val;
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }    
-
-    #[test]
-    fn fetch_and_decode_fsqrtd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0101_1010 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsqrtD {
-                rd: Register::X11,
-                rs1: Register::X21, 
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsqrtd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0101_1010 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-    
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let res = rs1_val.sqrt();
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-
-    }
-
-    #[test]
-    fn fetch_and_decode_fsgnjd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27, 
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let res = rs1_val.copysign(rs2_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-    
-    #[test]
-    fn fetch_and_decode_fsgnjnd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjnD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27, 
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjnd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let res = rs1_val.copysign(-rs2_val);
- 
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsgnjxd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjxD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27, 
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjxd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
-        soft.execute();
-        
-        let sign_1 = f64::from_bits(200u64).to_bits() & 0x8000_0000_0000_0000;
-        let sign_2 = f64::from_bits(100u64).to_bits() & 0x8000_0000_0000_0000;
-        let other =  f64::from_bits(200u64).to_bits() & 0x7fff_ffff_ffff_ffff;
-
-        let res = f64::from_bits((sign_1 ^ sign_2) | other);
- 
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-
-    }
-
-    #[test]
-    fn fetch_and_decode_fmind_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b0010_1011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FminD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmind_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b0010_1011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.f_registers[Register::X27 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            100f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmaxd_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b0010_1011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaxD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmaxd_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b0010_1011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.f_registers[Register::X27 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            200f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtsd_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b0100_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSD {
-                rd: Register::X11,
-                rs1: Register::X21, 
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtsd_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b0100_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            100f64
-        )
-
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtds_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b0100_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDS {
-                rd: Register::X11,
-                rs1: Register::X21, 
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtds_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b0100_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            (100f32) as f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_feqd_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FeqD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,  
-            }
-        )
-    }
-
-    #[test]
-    fn test_feqd_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fltd_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FltD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,  
-            }
-        )
-
-    }
-
-    #[test]
-    fn test_fltd_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = 50f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-        soft.execute();
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fled_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FleD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,  
-            }
-        )
-    }
-
-    #[test]
-    fn test_fled_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = 50f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-        soft.execute();
-        
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }    
-
-    #[test]
-    fn fetch_and_decode_fclassd_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1110_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FclassD {
-                rd: Register::X11,
-                rs1: Register::X21,
-            }
-        )
-    }
-
-    
-    #[test]
-    fn fetch_and_decode_fcvtwd_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1100_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtwd_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1100_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.execute();
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            ((100f64).round() as i32) as u64
-        )
-    } 
-
-
-    #[test]
-    fn fetch_and_decode_fcvtwud_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1100_0010 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWUD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtwud_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1100_0010 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.execute();
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            (((100f64).round() as u32) as i32) as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtdw_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1101_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtdw_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1101_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 100u64;
-        soft.execute();
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            ((100u64) as i32) as f64
-        )
-    } 
-
-    #[test]
-    fn fetch_and_decode_fcvtdwu_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1101_0010 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDWU {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtdwu_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1101_0010 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 100u64;
-        soft.execute();
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            ((100u64) as u32) as f64
-        )
-    } 
-
-    #[test]
-    fn fetch_and_decode_fcvtld_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1100_0010 as u8, 0b0010_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtld_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1100_0010 as u8, 0b0010_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.execute();
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            100f64.round() as u64
-        )
-    } 
-
-    #[test]
-    fn fetch_and_decode_fcvtlud_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1100_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLUD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtlud_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1100_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.execute();
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            100f64.round() as u64
-        )
-    }
-    
-    #[test]
-    fn fetch_and_decode_fmvxd_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1110_0010 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvXD {
-                rd: Register::X11,
-                rs1: Register::X21,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmvxd_execute() { 
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1110_0010 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.execute();
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            100f64.to_bits()
-        )
-    }    
-
-    #[test]
-    fn fetch_and_decode_fcvtdl_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1101_0010 as u8, 0b0010_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDL {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtdl_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1101_0010 as u8, 0b0010_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-    
-        soft.registers[Register::X21 as usize] = 100u64;
-        soft.execute();
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            100u64 as f64
-        )
-    }    
-
-    #[test]
-    fn fetch_and_decode_fcvtdlu_instruction() { 
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1101_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDLU {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-    
-    #[test]
-    fn test_fcvtdlu_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1101_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 100u64;
-        soft.execute();
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            100u64 as f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmvdx_instruction() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1111_0010 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmvDX {
-                rd: Register::X11,
-                rs1: Register::X21, 
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmvdx_execute() {
-        let mut soft = SoftThread::default(); 
-        let program = vec![0b1111_0010 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.execute();
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            100f64.to_bits()
-        )
-    }    
-
-    #[test]
-    fn fetch_and_decode_flq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1100_0101 as u8, 0b1000_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Flq {
-                rd: Register::X11,
-                rs1: Register::X21,
-                imm: 3643,
-            }
-        )        
-    }
-
-    #[test]
-    fn test_flq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1100_0101 as u8, 0b1000_0111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 3643;
-        let val = (5000.0f64).to_bits();
-        soft.bus.write(3643, val, 64);
-        soft.execute();
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            f64::from_bits(val)
-        )
-    } 
-    
-    #[test]
-    fn fetch_and_decode_fsq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1100_0101 as u8, 0b1010_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::Fsq {
-                rs1: Register::X21,
-                rs2: Register::X27,
-                imm: 123,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1100_0101 as u8, 0b1010_0111 as u8];
-        soft.load_program(program);
-        soft.registers[Register::X21 as usize] = 123;
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(5000u64);
-        soft.execute();
-
-        assert_eq!(
-            soft.bus.read(&123, 64).unwrap(),
-            5000
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmaddq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaddQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmaddq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
-        
-        soft.execute();
-        
-        let rs1_val =  f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let rs3_val = f64::from_bits(2u64);
-        let res = rs1_val.mul_add(rs2_val, rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmsubq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmsubQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmsubq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
-        
-        soft.execute();
-        
-        let rs1_val =  f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let rs3_val = f64::from_bits(2u64);
-        let res = rs1_val.mul_add(rs2_val, -rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fnmsubq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmsubQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fnmsubq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
-        
-        soft.execute();
-        
-        let rs1_val =  -f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let rs3_val = f64::from_bits(2u64);
-        let res = rs1_val.mul_add(rs2_val, -rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fnmaddq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FnmaddQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rs3: Register::X28,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fnmaddq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
-        
-        soft.execute();
-        
-        let rs1_val =  -f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let rs3_val = f64::from_bits(2u64);
-        let res = rs1_val.mul_add(rs2_val, rs3_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_faddq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FaddQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_faddq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let res = rs1_val + rs2_val;
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }    
-
-    #[test]
-    fn fetch_and_decode_fsubq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsubQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsubq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0000_1111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let res = rs1_val - rs2_val;
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmulq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmulQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmulq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let res = rs1_val * rs2_val;
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fdivq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FdivQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fdivq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0001_1111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let rs2_val = f64::from_bits(100u64);
-        let res = rs1_val / rs2_val;
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsqrtq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0101_1110 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsqrtQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 2
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsqrtq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0101_1110 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
-        
-        soft.execute();
-        
-        let rs1_val = f64::from_bits(200u64);
-        let res = rs1_val.sqrt();
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fsgnjq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-        soft.execute();
-
-        let rs1_val = 200f64;
-        let rs2_val = 100f64;
-        let res = rs1_val.copysign(rs2_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-
-    }
-
-    #[test]
-    fn fetch_and_decode_fsgnjnq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjnQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjnq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-        soft.execute();
-
-        let rs1_val = 200f64;
-        let rs2_val = -100f64;
-        let res = rs1_val.copysign(rs2_val);
-        
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-
-    }
-
-    #[test]
-    fn fetch_and_decode_fsgnjxq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FsgnjxQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fsgnjxq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
-        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
-        soft.execute();
-
-        let sign_1 = f64::from_bits(200u64).to_bits() & 0x8000_0000_0000_0000;
-        let sign_2 = f64::from_bits(100u64).to_bits() & 0x8000_0000_0000_0000;
-        let other =  f64::from_bits(200u64).to_bits() & 0x7fff_ffff_ffff_ffff;
-
-        let res = f64::from_bits((sign_1 ^ sign_2) | other);
- 
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            res
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fminq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_1111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FminQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fminq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_1111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            100f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fmaxq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_1111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FmaxQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27
-            }
-        )
-    }
-
-    #[test]
-    fn test_fmaxq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0010_1111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-    
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            200f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtsq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtSQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtsq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0000 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            200f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtqs_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0110 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQS {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtqs_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0110 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            200f64
-        )
-    }
-    
-    #[test]
-    fn fetch_and_decode_fcvtdq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtDQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtdq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            (200 as f32) as f64 
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtqd_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0110 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQD {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 1
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtqd_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b0100_0110 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            (200 as f32) as f64 
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_feqq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FeqQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-            }
-        )
-    }
-
-    #[test]
-    fn test_feqq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 100f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fltq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FltQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fltq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        
-        soft.f_registers[Register::X21 as usize] = 50f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fleq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FleQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rs2: Register::X27,
-            }
-        )
-    }
-
-    #[test]
-    fn test_fleq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 50f64;
-        soft.f_registers[Register::X27 as usize] = 100f64;
-
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            1
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fclassq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1110_0110 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FclassQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-            }
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtwq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0110 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-    
-
-    #[test]
-    fn test_fcvtwq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0110 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        
-        soft.load_program(program);
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            ((200f64.round() as i32) as u64)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtwuq_instruction() { 
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0110 as u8, 0b0001_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtWUQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-    
-    #[test]
-    fn test_fcvtwuq_execute() { 
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0110 as u8, 0b0001_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            (((200f64.round() as u32) as i32) as u64)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtqw_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0110 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQW {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-    
-    #[test]
-    fn test_fcvtqw_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0110 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 200u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            ((200f64.round() as i32) as f64)
-        )
-    }
-    
-    #[test]
-    fn fetch_and_decode_fcvtqwu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0110 as u8, 0b0001_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQWU {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtqwu_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0110 as u8, 0b0001_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 200u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            ((200f64.round() as u32) as f64)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtlq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0110 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtlq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0110 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            ((200f64.round() as u32) as u64)
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtluq_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0110 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtLUQ {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtluq_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1100_0110 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.f_registers[Register::X21 as usize] = 200f64;
-        soft.execute();
-
-        assert_eq!(
-            soft.registers[Register::X11 as usize],
-            200f64.round() as u64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtql_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0110 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQL {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtql_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0110 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-
-        soft.registers[Register::X21 as usize] = 200u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            200u64 as f64
-        )
-    }
-
-    #[test]
-    fn fetch_and_decode_fcvtqlu_instruction() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0110 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-        let instruction: Instruction = soft.fetch().into();
-        assert_eq!(
-            instruction,
-            Instruction::FcvtQLU {
-                rd: Register::X11,
-                rs1: Register::X21,
-                rm: 0
-            }
-        )
-    }
-
-    #[test]
-    fn test_fcvtqlu_execute() {
-        let mut soft = SoftThread::default();
-        let program = vec![0b1101_0110 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
-        soft.load_program(program);
-    
-        soft.registers[Register::X21 as usize] = 200u64;
-        soft.execute();
-
-        assert_eq!(
-            soft.f_registers[Register::X11 as usize],
-            200u64 as f64
-        )
-    }
-}
+pub mod encoding;
+pub mod encoding_types;
+pub mod extensions;
+pub mod instructions;
+pub mod register;
+pub mod soft;
+pub mod vm;
+pub mod memory;
+pub mod exceptions;
+pub mod machine;
+pub mod consts;
+pub mod state;
+pub mod csr;
+pub mod privilege;
+pub mod float;
+pub mod speed;
+pub mod elf;
+pub mod linux;
+pub mod mmu;
+pub mod region;
+pub mod trace;
+pub mod dtb;
+pub mod jit;
+pub mod invariants;
+pub mod peripheral;
+pub mod disasm;
+pub mod memory_model;
+pub mod sanitizer;
+pub mod validate;
+pub mod branch;
+pub mod interrupt;
+pub mod timing;
+pub mod watch;
+pub mod strace;
+pub mod breakpoint;
+pub mod asm;
+pub mod page_fault;
+pub mod coverage;
+pub mod history;
+pub mod ecall;
+pub mod bus;
+pub mod pmp;
+pub mod gdb;
+pub mod compressed;
+pub mod bitmanip;
+#[cfg(feature = "serde")]
+pub mod checkpoint;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused)]
+    use super::*;
+    use crate::memory::{self, Dram, Memory, UnalignedMode};
+    use crate::encoding::{InstructionDecoder, OpCodeType, Unpacked, EncodingTable};
+    use crate::extensions::{Extension, Base};
+    use crate::encoding_types::*;
+    use crate::instructions::Instruction;
+    use crate::register::{HardWiredZero, Register, RegisterAbi, RegisterValue};
+    use crate::soft::{SoftThread, StepOutcome, NOP};
+    use crate::asm::ParseError;
+    use crate::page_fault::PageFaultAction;
+    use crate::history;
+    use crate::ecall::{EcallResult, Errno, SbiResult};
+    use crate::bus::{Bus, Device};
+    use crate::bitmanip;
+    use crate::pmp::*;
+    use crate::gdb::{self, GdbStub};
+    use crate::vm::Cpu;
+    use crate::privilege::PrivilegeLevel;
+    use crate::exceptions::{Exception, StepError};
+    use crate::csr::*;
+    use crate::float::*;
+    use crate::elf::{self, Elf, ElfError};
+    use crate::mmu::*;
+    use crate::region::{AccessFlags, MemoryRegion, MprotectError};
+    use crate::trace::{self, BinaryTraceLogger, BinaryTraceReader, DynRingBuffer, ExecutionEvent, RingBuffer, TraceRecord};
+    use crate::dtb::DtbError;
+    use crate::jit::JitCache;
+    use crate::invariants::InvariantViolation;
+    use crate::peripheral::*;
+    use crate::disasm;
+    use crate::testing::AssertionError;
+    use crate::memory_model::*;
+    use crate::sanitizer::*;
+    use crate::validate::*;
+    use crate::branch::*;
+    use crate::interrupt::*;
+    use crate::timing::*;
+
+    #[test]
+    fn test_match_register() {
+        let reg = Register::X0;
+
+        match reg {
+            Register::X0 => {
+                assert!(true);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convert_valid_register_to_abi() {
+        let reg = Register::X0;
+        let abi: RegisterAbi = reg.into();
+        assert_eq!(abi, RegisterAbi::Zero(HardWiredZero))
+    }
+
+    #[test]
+    fn test_convert_valid_valid_opcode_to_opcode_type() {
+        let opcode = 0b0010111 as u8;
+        let opcode_type: OpCodeType = opcode.into();
+        assert_eq!(opcode_type, OpCodeType::U);
+    }
+
+    #[test]
+    fn test_convert_valid_invalid_opcode_to_opcode_type() {
+        let opcode = 0b0010110 as u8;
+        let opcode_type: OpCodeType = opcode.into();
+        assert_eq!(opcode_type, OpCodeType::Invalid);
+    }
+
+    #[test]
+    fn test_unpack_random_r_type_bits() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1011_0011 as u32;
+        let unpacked: Unpacked = bits.into();
+        assert!(unpacked.rd.is_some());
+        assert!(unpacked.rs1.is_some());
+        assert!(unpacked.rs2.is_some());
+        assert!(unpacked.func3.is_some());
+        assert!(unpacked.func7.is_some());
+        assert!(unpacked.fm.is_some());
+        assert!(unpacked.pred.is_some());
+        assert!(unpacked.succ.is_some());
+        assert_eq!(unpacked.opcode, 51);
+    }
+    #[test]
+    fn test_unpack_random_i_type_bits() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1000_0011 as u32;
+        let unpacked: Unpacked = bits.into();
+        assert!(unpacked.rd.is_some());
+        assert!(unpacked.rs1.is_some());
+        assert!(unpacked.func3.is_some());
+        assert!(unpacked.imm.is_some());
+        assert_eq!(unpacked.opcode, 3);
+    }
+
+    #[test]
+    fn test_unpack_random_s_type_bits() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1010_0011 as u32;
+        let unpacked: Unpacked = bits.into();
+        assert!(unpacked.imm.is_some());
+        assert!(unpacked.rs1.is_some());
+        assert!(unpacked.rs2.is_some());
+        assert_eq!(unpacked.opcode, 35);
+    }
+
+    #[test]
+    fn test_unpack_random_u_type_bits() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1011_0111 as u32;
+        let unpacked: Unpacked = bits.into();
+        assert!(unpacked.imm.is_some());
+        assert!(unpacked.rd.is_some());
+        assert_eq!(unpacked.opcode, 55);
+    }
+    #[test]
+    fn test_unpack_random_b_type_bits() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1011_0111 as u32;
+        let unpacked: Unpacked = bits.into();
+        assert!(unpacked.imm.is_some());
+        assert!(unpacked.rd.is_some());
+        assert_eq!(unpacked.opcode, 55);
+    }
+    #[test]
+    fn test_unpack_random_j_type_bits() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1110_1111 as u32;
+        let unpacked: Unpacked = bits.into();
+        assert!(unpacked.imm.is_some());
+        assert!(unpacked.rd.is_some());
+        assert_eq!(unpacked.opcode, 111);
+    }
+
+    #[test]
+    fn test_convert_valid_lui_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1011_0111 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Lui {
+                rd: Register::X25,
+                imm: -858996736
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_auipc_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1001_0111 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Auipc {
+                rd: Register::X25,
+                imm: -858996736
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_jal_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1100_1100_1100_1110_1111 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Jal {
+                rd: Register::X25,
+                imm: -212584
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_jalr_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1110_0111 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Jalr {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_beq_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1110_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Beq {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -822,
+                func3: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_bne_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1001_0101_1110_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Bne {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -822,
+                func3: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_blt_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1100_0101_1110_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Blt {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -822,
+                func3: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_bge_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1101_0101_1110_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Bge {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -822,
+                func3: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_bltu_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1110_0101_1110_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Bltu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -822,
+                func3: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_bgeu_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1111_0101_1110_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Bgeu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -822,
+                func3: 7
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_lb_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1000_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Lb {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_lh_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1001_0101_1000_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Lh {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_lw_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1010_0101_1000_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Lw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_lbu_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1100_0101_1000_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Lbu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_lhu_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1101_0101_1000_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Lhu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 5
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_sb_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1010_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sb {
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: 111,
+                func3: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sh_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1001_0101_1010_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sh {
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: 111,
+                func3: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sw_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1010_0101_1010_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sw {
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: 111,
+                func3: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_addi_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1000_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Addi {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: -820,
+                func3: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_slti_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1010_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Slti {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sltiu_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1011_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sltiu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_xori_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1100_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Xori {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_ori_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1110_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Ori {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_andi_bits_into_instruction() {
+        let bits: Inst = 0b1100_1100_1100_1010_1111_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Andi {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 7
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_slli_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1001_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Slli {
+                rd: Register::X11,
+                rs1: Register::X21,
+                shamt: 12,
+                func3: 1,
+                func7: 0
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_srli_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1101_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Srli {
+                rd: Register::X11,
+                rs1: Register::X21,
+                shamt: 12,
+                func3: 5,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_srai_bits_into_instruction() {
+        let bits: Inst = 0b0100_0000_1100_1010_1101_0101_1001_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Srai {
+                rd: Register::X11,
+                rs1: Register::X21,
+                shamt: 12,
+                func3: 5,
+                func7: 32
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_add_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1000_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Add {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sub_bits_into_instruction() {
+        let bits: Inst = 0b0100_0000_1100_1010_1000_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sub {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 32
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sll_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1001_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sll {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 1,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_slt_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1010_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Slt {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 2,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sltu_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1011_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sltu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 3,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_xor_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1100_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Xor {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 4,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_srl_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1101_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Srl {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sra_bits_into_instruction() {
+        let bits: Inst = 0b0100_0000_1100_1010_1101_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sra {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 32
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_or_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1110_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Or {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 6,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_and_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1111_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::And {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 7,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_fence_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1000_0101_1000_1111 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Fence {
+                rd: Register::X11,
+                rs1: Register::X21,
+                fm: 0,
+                pred: 0,
+                succ: 12,
+                func3: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_ecall_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0000_0000_0000_0000_0111_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(instruction, Instruction::ECall);
+    }
+
+    #[test]
+    fn test_convert_valid_ebreak_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0001_0000_0000_0000_0111_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(instruction, Instruction::EBreak);
+    }
+
+    #[test]
+    fn test_convert_valid_lwu_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0000_1000_0110_0100_0000_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Lwu {
+                rd: Register::X8,
+                rs1: Register::X16,
+                imm: 0,
+                func3: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_ld_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0000_1000_0011_0100_0000_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Ld {
+                rd: Register::X8,
+                rs1: Register::X16,
+                imm: 0,
+                func3: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sd_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0011_1000_0011_0100_0010_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sd {
+                rs1: Register::X16,
+                rs2: Register::X3,
+                imm: 8,
+                func3: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_addiw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0011_1000_0000_0100_0001_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Addiw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                imm: 3,
+                func3: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_slliw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0011_1000_0001_0100_0001_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Slliw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                shamt: 3,
+                func3: 1,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_srliw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0011_1000_0101_0100_0001_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Srliw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                shamt: 3,
+                func3: 5,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sraiw_bits_into_instruction() {
+        let bits: Inst = 0b0100_0000_0011_1000_0101_0100_0001_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sraiw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                shamt: 3,
+                func3: 5,
+                func7: 32
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_addw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0011_1000_0000_0100_0011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Addw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                rs2: Register::X3,
+                func3: 0,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_subw_bits_into_instruction() {
+        let bits: Inst = 0b0100_0000_0011_1000_0000_0100_0011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Subw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                rs2: Register::X3,
+                func3: 0,
+                func7: 32
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sllw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0011_1000_0001_0100_0011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sllw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                rs2: Register::X3,
+                func3: 1,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_srlw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0011_1000_0101_0100_0011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Srlw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                rs2: Register::X3,
+                func3: 5,
+                func7: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_sraw_bits_into_instruction() {
+        let bits: Inst = 0b0100_0000_0011_1000_0101_0100_0011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Sraw {
+                rd: Register::X8,
+                rs1: Register::X16,
+                rs2: Register::X3,
+                func3: 5,
+                func7: 32
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_fence_i_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_1100_1010_1001_0101_1000_1111 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FenceI {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 12,
+                func3: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_csrrw_bits_into_instruction() {
+        let bits: Inst = 0b1010_1010_1100_1110_1001_0110_1111_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Csrrw {
+                rd: Register::X13,
+                rs1: Register::X29,
+                csr: 2732,
+                func3: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_csrrs_bits_into_instruction() {
+        let bits: Inst = 0b1010_1010_1100_1010_1010_0101_1111_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Csrrs {
+                rd: Register::X11,
+                rs1: Register::X21,
+                csr: 2732,
+                func3: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_csrrc_bits_into_instruction() {
+        let bits: Inst = 0b1010_1010_1100_1010_1011_0101_1111_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Csrrc {
+                rd: Register::X11,
+                rs1: Register::X21,
+                csr: 2732,
+                func3: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_csrrwi_bits_into_instruction() {
+        let bits: Inst = 0b1010_1010_1100_1010_1101_0101_1111_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Csrrwi {
+                rd: Register::X11,
+                uimm: 21,
+                csr: 2732,
+                func3: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_csrrsi_bits_into_instruction() {
+        let bits: Inst = 0b1010_1010_1100_1010_1110_0101_1111_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Csrrsi {
+                rd: Register::X11,
+                uimm: 21,
+                csr: 2732,
+                func3: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_csrrci_bits_into_instruction() {
+        let bits: Inst = 0b1010_1010_1100_1010_1111_0101_1111_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Csrrci {
+                rd: Register::X11,
+                uimm: 21,
+                csr: 2732,
+                func3: 7
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_mul_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1000_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Mul {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_mulh_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1001_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Mulh {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 1,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_mulhsu_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1010_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Mulhsu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 2,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_mulhu_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1011_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Mulhu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 3,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_div_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1100_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Div {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 4,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_divu_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1101_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Divu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_rem_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1110_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Rem {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 6,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_remu_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1111_0101_1011_0011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Remu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 7,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_mulw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1000_0101_1011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Mulw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_divw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1100_0101_1011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Divw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 4,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_divuw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1101_0101_1011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Divuw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_remw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1110_0101_1011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Remw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 6,
+                func7: 1
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_remuw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_1100_1010_1111_0101_1011_1011 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::RemuW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 7,
+                func7: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_lrw_bits_into_instruction() {
+        let bits: Inst = 0b0001_0100_0000_0101_0010_0110_0010_1111 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::LrW {
+                rd: Register::X12,
+                rs1: Register::X10,
+                aq: 1,
+                rl: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_valid_scw_bits_into_instruction() {
+        let bits: Inst = 0b0001_1010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::ScW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoswapw_bits_into_instruction() {
+        let bits: Inst = 0b0000_1010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoswapW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoaddw_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoaddW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoxorw_bits_into_instruction() {
+        let bits: Inst = 0b0010_0010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoxorW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoandw_bits_into_instruction() {
+        let bits: Inst = 0b0110_0010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoandW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoorw_bits_into_instruction() {
+        let bits: Inst = 0b0100_0010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoorW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amominw_bits_into_instruction() {
+        let bits: Inst = 0b1000_0010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmominW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amomaxw_bits_into_instruction() {
+        let bits: Inst = 0b1010_0010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmomaxW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amominuw_bits_into_instruction() {
+        let bits: Inst = 0b1100_0010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmominuW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amomaxuw_bits_into_instruction() {
+        let bits: Inst = 0b1110_0010_0000_0101_0010_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmomaxuW {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_lrd_bits_into_instruction() {
+        let bits: Inst = 0b0001_0100_0000_0101_0011_0110_0010_1111 as u32;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::LrD {
+                rd: Register::X12,
+                rs1: Register::X10,
+                aq: 1,
+                rl: 0
+            }
+        );
+    }
+    #[test]
+    fn test_convert_valid_scd_bits_into_instruction() {
+        let bits: Inst = 0b0001_1010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::ScD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoswapd_bits_into_instruction() {
+        let bits: Inst = 0b0000_1010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoswapD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoaddd_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoaddD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoxord_bits_into_instruction() {
+        let bits: Inst = 0b0010_0010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoxorD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amoandd_bits_into_instruction() {
+        let bits: Inst = 0b0110_0010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoandD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+    #[test]
+    fn test_convert_valid_amoord_bits_into_instruction() {
+        let bits: Inst = 0b0100_0010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoorD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+    #[test]
+    fn test_convert_valid_amomind_bits_into_instruction() {
+        let bits: Inst = 0b1000_0010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmominD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amomaxd_bits_into_instruction() {
+        let bits: Inst = 0b1010_0010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmomaxD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+    
+    #[test]
+    fn test_convert_valid_amominud_bits_into_instruction() {
+        let bits: Inst = 0b1100_0010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmominuD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_amomaxud_bits_into_instruction() {
+        let bits: Inst = 0b1110_0010_0000_0101_0011_0011_0010_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::AmomaxuD {
+                rd: Register::X6,
+                rs1: Register::X10,
+                rs2: Register::X0,
+                aq: 0,
+                rl: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_flw_bits_into_instruction() {
+        let bits: Inst = 0b0101_1011_1000_0101_0010_0011_0000_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Flw {
+                rd: Register::X6,
+                rs1: Register::X10,
+                imm: 1464
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsw_bits_into_instruction() {
+        let bits: Inst = 0b0101_1011_1000_0101_0010_0011_0010_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Fsw {
+                rs1: Register::X10,
+                rs2: Register::X24,
+                imm: 47
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmadds_bits_into_instruction() {
+        let bits: Inst = 0b0101_0000_0110_1100_1010_1110_1100_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaddS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmsubs_bits_into_instruction() {
+        let bits: Inst = 0b0101_0000_0110_1100_1010_1110_1100_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmsubS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fnmsubs_bits_into_instruction() {
+        let bits: Inst = 0b0101_0000_0110_1100_1010_1110_1100_1011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmsubS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fnmadds_bits_into_instruction() {
+        let bits: Inst = 0b0101_0000_0110_1100_1010_1110_1100_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmaddS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fadds_bits_into_instruction() {
+        let bits: Inst = 0b0000_0000_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FaddS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsubs_bits_into_instruction() {
+        let bits: Inst = 0b0000_1000_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsubS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmuls_bits_into_instruction() {
+        let bits: Inst = 0b0001_0000_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmulS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fdivs_bits_into_instruction() {
+        let bits: Inst = 0b0001_1000_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FdivS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsqrts_bits_into_instruction() {
+        let bits: Inst = 0b0101_1000_0000_0101_1010_1000_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsqrtS {
+                rd: Register::X16,
+                rs1: Register::X11,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjs_bits_into_instruction() {
+        let bits: Inst = 0b0010_0000_0110_0101_1000_1000_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjS {
+                rd: Register::X16,
+                rs1: Register::X11,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjns_bits_into_instruction() {
+        let bits: Inst = 0b0010_0000_0110_0101_1001_1000_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjnS {
+                rd: Register::X16,
+                rs1: Register::X11,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjxs_bits_into_instruction() {
+        let bits: Inst = 0b0010_0000_0110_0101_1010_1000_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjxS {
+                rd: Register::X16,
+                rs1: Register::X11,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmins_bits_into_instruction() {
+        let bits: Inst = 0b0010_1000_0110_0101_1000_1000_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FminS {
+                rd: Register::X16,
+                rs1: Register::X11,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmaxs_bits_into_instruction() {
+        let bits: Inst = 0b0010_1000_0110_0101_1001_1000_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaxS {
+                rd: Register::X16,
+                rs1: Register::X11,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtws_bits_into_instruction() {
+        let bits: Inst = 0b1100_0000_0000_0101_1011_0011_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWS {
+                rd: Register::X6,
+                rs1: Register::X11,
+                rm: 3
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtwus_bits_into_instruction() {
+        let bits: Inst = 0b1100_0000_0001_0101_1011_0011_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWUS {
+                rd: Register::X6,
+                rs1: Register::X11,
+                rm: 3
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmvxw_bits_into_instruction() {
+        let bits: Inst = 0b1110_0000_0000_0101_1000_0011_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvXW {
+                rd: Register::X6,
+                rs1: Register::X11,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_feqs_bits_into_instruction() {
+        let bits: Inst = 0b1110_0000_0000_0101_1000_0011_0101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvXW {
+                rd: Register::X6,
+                rs1: Register::X11,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_flts_bits_into_instruction() {
+        let bits: Inst = 0b1010_0000_0111_0110_1010_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FeqS {
+                rd: Register::X3,
+                rs1: Register::X13,
+                rs2: Register::X7,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fclasss_bits_into_instruction() {
+        let bits: Inst = 0b1110_0000_0000_0110_1001_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FclassS {
+                rd: Register::X3,
+                rs1: Register::X13,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtsw_bits_into_instruction() {
+        let bits: Inst = 0b1101_0000_0000_0110_1001_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSW {
+                rd: Register::X3,
+                rs1: Register::X13,
+                rm: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtswu_bits_into_instruction() {
+        let bits: Inst = 0b1101_0000_0001_0110_1001_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSWU {
+                rd: Register::X3,
+                rs1: Register::X13,
+                rm: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmvwx_bits_into_instruction() {
+        let bits: Inst = 0b1111_0000_0000_0110_1000_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvWX {
+                rd: Register::X3,
+                rs1: Register::X13,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtls_bits_into_instruction() {
+        let bits: Inst = 0b1100_0000_0010_0110_1001_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLS {
+                rd: Register::X3,
+                rs1: Register::X13,
+                rm: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtlus_bits_into_instruction() {
+        let bits: Inst = 0b1100_0000_0011_0110_1001_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLUS {
+                rd: Register::X3,
+                rs1: Register::X13,
+                rm: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtsl_bits_into_instruction() {
+        let bits: Inst = 0b1101_0000_0010_0110_1001_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSL {
+                rd: Register::X3,
+                rs1: Register::X13,
+                rm: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtslu_bits_into_instruction() {
+        let bits: Inst = 0b1101_0000_0011_0110_1001_0001_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSLU {
+                rd: Register::X3,
+                rs1: Register::X13,
+                rm: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fld_bits_into_instruction() {
+        let bits: Inst = 0b0101_1011_1000_0101_0011_0011_0000_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Fld {
+                rd: Register::X6,
+                rs1: Register::X10,
+                imm: 1464
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsd_bits_into_instruction() {
+        let bits: Inst = 0b0101_1011_1000_0101_0011_0011_0010_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Fsd {
+                rs1: Register::X10,
+                rs2: Register::X24,
+                imm: 47
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmaddd_bits_into_instruction() {
+        let bits: Inst = 0b0101_0010_0110_1100_1010_1110_1100_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaddD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmsubd_bits_into_instruction() {
+        let bits: Inst = 0b0101_0010_0110_1100_1010_1110_1100_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmsubD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fnmsubd_bits_into_instruction() {
+        let bits: Inst = 0b0101_0010_0110_1100_1010_1110_1100_1011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmsubD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmnaddd_bits_into_instruction() {
+        let bits: Inst = 0b0101_0010_0110_1100_1010_1110_1100_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmaddD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_faddd_bits_into_instruction() {
+        let bits: Inst = 0b0000_0010_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FaddD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsubd_bits_into_instruction() {
+        let bits: Inst = 0b0000_1010_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsubD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmuld_bits_into_instruction() {
+        let bits: Inst = 0b0001_0010_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmulD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fdivd_bits_into_instruction() {
+        let bits: Inst = 0b0001_1010_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FdivD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_sqrtd_bits_into_instruction() {
+        let bits: Inst = 0b0101_1010_0000_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsqrtD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjd_bits_into_instruction() {
+        let bits: Inst = 0b0010_0010_0110_1100_1000_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjnd_bits_into_instruction() {
+        let bits: Inst = 0b0010_0010_0110_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjnD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjxd_bits_into_instruction() {
+        let bits: Inst = 0b0010_0010_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjxD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmind_bits_into_instruction() {
+        let bits: Inst = 0b0010_1010_0110_1100_1000_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FminD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmaxd_bits_into_instruction() {
+        let bits: Inst = 0b0010_1010_0110_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaxD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtsd_bits_into_instruction() {
+        let bits: Inst = 0b0100_0000_0001_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtds_bits_into_instruction() {
+        let bits: Inst = 0b0100_0010_0000_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_feqd_bits_into_instruction() {
+        let bits: Inst = 0b1010_0010_0011_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FeqD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X3,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fltd_bits_into_instruction() {
+        let bits: Inst = 0b1010_0010_0011_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FltD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X3,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fled_bits_into_instruction() {
+        let bits: Inst = 0b1010_0010_0011_1100_1000_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FleD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X3,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fclassd_bits_into_instruction() {
+        let bits: Inst = 0b1110_0010_0000_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FclassD {
+                rd: Register::X29,
+                rs1: Register::X25,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtwd_bits_into_instruction() {
+        let bits: Inst = 0b1100_0010_0000_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtwud_bits_into_instruction() {
+        let bits: Inst = 0b1100_0010_0001_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWUD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtdw_bits_into_instruction() {
+        let bits: Inst = 0b1101_0010_0000_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDW {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtdwu_bits_into_instruction() {
+        let bits: Inst = 0b1101_0010_0001_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDWU {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtld_bits_into_instruction() {
+        let bits: Inst = 0b1100_0010_0010_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtlud_bits_into_instruction() {
+        let bits: Inst = 0b1100_0010_0011_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLUD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmvxd_bits_into_instruction() {
+        let bits: Inst = 0b1110_0010_0000_1100_1000_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvXD {
+                rd: Register::X29,
+                rs1: Register::X25,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtdl_bits_into_instruction() {
+        let bits: Inst = 0b1101_0010_0010_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDL {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtdlu_bits_into_instruction() {
+        let bits: Inst = 0b1101_0010_0011_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDLU {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmvdx_bits_into_instruction() {
+        let bits: Inst = 0b1111_0010_0000_1100_1000_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvDX {
+                rd: Register::X29,
+                rs1: Register::X25,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_flq_bits_into_instruction() {
+        let bits: Inst = 0b0101_1011_1000_0101_0100_0011_0000_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Flq {
+                rd: Register::X6,
+                rs1: Register::X10,
+                imm: 1464
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsq_bits_into_instruction() {
+        let bits: Inst = 0b0101_1011_1000_0101_0100_0011_0010_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::Fsq {
+                rs1: Register::X10,
+                rs2: Register::X24,
+                imm: 47
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmaddq_bits_into_instruction() {
+        let bits: Inst = 0b0101_0110_0110_1100_1100_1110_1100_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaddQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 4
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmsubq_bits_into_instruction() {
+        let bits: Inst = 0b0101_0110_0110_1100_1100_1110_1100_0111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmsubQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 4
+            }
+        )
+    }
+    #[test]
+    fn test_convert_valid_fnmsubq_bits_into_instruction() {
+        let bits: Inst = 0b0101_0110_0110_1100_1100_1110_1100_1011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmsubQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 4
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fnmaddq_bits_into_instruction() {
+        let bits: Inst = 0b0101_0110_0110_1100_1100_1110_1100_1111;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmaddQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rs3: Register::X10,
+                rm: 4
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_faddq_bits_into_instruction() {
+        let bits: Inst = 0b0000_0110_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FaddQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsubq_bits_into_instruction() {
+        let bits: Inst = 0b0000_1110_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsubQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmulq_bits_into_instruction() {
+        let bits: Inst = 0b0001_0110_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmulQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fdivq_bits_into_instruction() {
+        let bits: Inst = 0b0001_1110_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FdivQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsqrtq_bits_into_instruction() {
+        let bits: Inst = 0b0101_1110_0000_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsqrtQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjq_bits_into_instruction() {
+        let bits: Inst = 0b0010_0110_0110_1100_1000_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjnq_bits_into_instruction() {
+        let bits: Inst = 0b0010_0110_0110_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjnQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fsgnjxq_bits_into_instruction() {
+        let bits: Inst = 0b0010_0110_0110_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjxQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fminq_bits_into_instruction() {
+        let bits: Inst = 0b0010_1110_0110_1100_1000_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FminQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fmaxq_bits_into_instruction() {
+        let bits: Inst = 0b0010_1110_0110_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaxQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X6,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtsq_bits_into_instruction() {
+        let bits: Inst = 0b0100_0000_0011_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtqs_bits_into_instruction() {
+        let bits: Inst = 0b0100_0110_0000_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQS {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtdq_bits_into_instruction() {
+        let bits: Inst = 0b0100_0010_0011_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtqd_bits_into_instruction() {
+        let bits: Inst = 0b0100_0110_0001_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQD {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_feqq_bits_into_instruction() {
+        let bits: Inst = 0b1010_0110_0011_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FeqQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X3,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fltq_bits_into_instruction() {
+        let bits: Inst = 0b1010_0110_0011_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FltQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X3,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fleq_bits_into_instruction() {
+        let bits: Inst = 0b1010_0110_0011_1100_1000_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FleQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rs2: Register::X3,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fclassq_bits_into_instruction() {
+        let bits: Inst = 0b1110_0110_0000_1100_1001_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FclassQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtwq_bits_into_instruction() {
+        let bits: Inst = 0b1100_0110_0000_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtwuq_bits_into_instruction() {
+        let bits: Inst = 0b1100_0110_0001_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWUQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtqw_bits_into_instruction() {
+        let bits: Inst = 0b1101_0110_0000_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQW {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtqwu_bits_into_instruction() {
+        let bits: Inst = 0b1101_0110_0001_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQWU {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtlq_bits_into_instruction() {
+        let bits: Inst = 0b1100_0110_0010_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtluq_bits_into_instruction() {
+        let bits: Inst = 0b1100_0110_0011_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLUQ {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtql_bits_into_instruction() {
+        let bits: Inst = 0b1101_0110_0010_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQL {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_convert_valid_fcvtqlu_bits_into_instruction() {
+        let bits: Inst = 0b1101_0110_0011_1100_1010_1110_1101_0011;
+        let instruction: Instruction = bits.into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQLU {
+                rd: Register::X29,
+                rs1: Register::X25,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_valid_bits_for_invalid_base() {
+        let bits: Inst = 0b0000_0000_0000_1000_0110_0100_0000_0011 as u32;
+        let enc_table = EncodingTable::new(Extension::I, Base::I32);
+        let instruction: Instruction = Instruction::decode(bits, &enc_table);
+        assert_eq!(
+            instruction,
+            Instruction::Undefined
+        )
+
+    }
+
+    #[test]
+    fn test_valid_bits_for_invalid_ext() {
+        let bits: Inst = 0b0001_0100_0000_0101_0010_0110_0010_1111 as u32;
+        let enc_table = EncodingTable::new(Extension::M, Base::I32);
+        let instruction: Instruction = Instruction::decode(bits, &enc_table);
+        assert_eq!(
+            instruction,
+            Instruction::Undefined
+        )
+    }
+
+    #[test]
+    fn test_invalid_bits_for_valid_base_and_ext() {
+        let bits: Inst = 0b0001_0100_0001_0101_0010_0110_0010_1111 as u32;
+        let enc_table = EncodingTable::new(Extension::A, Base::I32);
+        let instruction: Instruction = Instruction::decode(bits, &enc_table);
+        assert_eq!(
+            instruction,
+            Instruction::Undefined
+        )
+    }
+
+    #[test]
+    fn test_fetch_and_decode_add_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Add {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_add_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_0011 as u8];
+
+        // Preload some values int the relevant registers
+        soft.registers[Register::X12 as usize] = 24u64;
+        soft.registers[Register::X21 as usize] = 32u64;
+
+        // Load the program code
+        soft.load_program(program);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            56u64
+        )
+    }
+
+    #[test]
+    fn test_fetch_and_decode_addi_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Addi {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: -820,
+                func3: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_addi_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_0011 as u8];
+
+        // Preload value into rs1
+        soft.registers[Register::X21 as usize] = 1000u64;
+
+        // Load the program code
+        soft.load_program(program);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            180u64
+        )
+    }
+
+    #[test]
+    fn test_addi_wraps_on_overflow() {
+        let mut soft = SoftThread::default();
+        // addi ra, zero, -1; addi ra, ra, 1
+        soft.load_program(vec![0xff, 0xf0, 0x00, 0x93, 0x00, 0x10, 0x80, 0x93]);
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X1 as usize], u64::MAX);
+
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X1 as usize], 0);
+    }
+
+    #[test]
+    fn test_auipc_wraps_below_pc() {
+        let mut soft = SoftThread::default();
+        // auipc a0, 0xfffff
+        soft.load_image(&[0x17, 0xf5, 0xff, 0xff], 0x2000).unwrap();
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 0x1000);
+    }
+
+    #[test]
+    fn test_auipc_wraps_to_top_of_address_space() {
+        let mut soft = SoftThread::default();
+        // auipc a0, 0xfffff; auipc a1, 0x80000
+        soft.load_image(&[0x17, 0xf5, 0xff, 0xff, 0x97, 0x05, 0x00, 0x80], 0).unwrap();
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 0xffff_ffff_ffff_f000);
+        assert_eq!(soft.registers[Register::X11 as usize], 0xffff_ffff_8000_0004);
+        assert_eq!(soft.pc, 8);
+    }
+
+    #[test]
+    fn fetch_and_decode_lui_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Lui {
+                rd: Register::X10,
+                imm: -859144192,
+            }
+        );
+    }
+
+    #[test]
+    fn test_lui_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            (-859144192 as i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_auipc_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0001_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        println!("{:?}", instruction);
+        assert_eq!(
+            instruction,
+            Instruction::Auipc {
+                rd: Register::X10,
+                imm: -859144192,
+            }
+        );
+    }
+
+    #[test]
+    fn test_auipc_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0001_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        soft.execute();
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            (-859144192 as i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_jal_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Jal {
+                rd: Register::X10,
+                imm: -360112,
+            }
+        );
+    }
+    
+    #[test]
+    fn test_jal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        soft.execute();
+        println!("{}", (-360112i64) as u64);
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            4
+        );
+
+        assert_eq!(
+            soft.pc,
+            18446744073709191504
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_jalr_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Jalr {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+            }
+        );    
+    }
+
+    #[test]
+    fn test_jalr_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        soft.pc = 0;
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            4
+        );
+
+        assert_eq!(
+            soft.pc,
+            ((1000 + 3276) & !1) 
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_beq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Beq {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -2870,
+                func3: 0
+            }
+        );
+    }
+    
+    #[test]
+    fn test_beq_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 100;
+        soft.execute();
+        
+        assert_eq!(
+            soft.pc,
+            (-2870 as i64) as u64
+        )
+        
+    }
+
+    #[test]
+    fn test_beq_not_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 1000;
+        soft.execute();
+        
+        assert_eq!(
+            soft.pc,
+            4
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_bne_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Bne {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -2870,
+                func3: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_bne_not_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            (-2870 as i64) as u64
+        )
+    }
+
+    #[test]
+    fn test_bne_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 100;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            4
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_blt_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Blt {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -2870,
+                func3: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_blt_less_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            (-2870 as i64) as u64
+        )
+    }
+
+    #[test]
+    fn test_blt_greater_or_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.registers[Register::X12 as usize] = 100;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            4
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_bge_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Bge {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -2870,
+                func3: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_bge_greater_or_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.registers[Register::X20 as usize] = 100;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            (-2870 as i64) as u64
+        )
+    }
+
+    #[test]
+    fn test_bge_less_than_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 10;
+        soft.registers[Register::X12 as usize] = 100;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            4
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_bltu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Bltu {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -2870,
+                func3: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_bltu_less_than_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 10;
+        soft.registers[Register::X12 as usize] = 100;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            (-2870 as i64) as u64
+        )
+    }
+
+    #[test]
+    fn test_bltu_greater_than_or_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 10;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            4
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_bgeu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Bgeu {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: -2870,
+                func3: 7
+            }
+        );
+    }
+
+    #[test]
+    fn test_bgeu_greater_than_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 10;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            (-2870 as i64) as u64
+        )
+    }
+
+    #[test]
+    fn test_bgeu_less_than_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0110_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 10;
+        soft.registers[Register::X12 as usize] = 100;
+        soft.execute();
+
+        assert_eq!(
+            soft.pc,
+            4
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_lb_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Lb {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 0,
+            }
+        );
+    }
+    
+    #[test]
+    fn test_lb_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.bus.write(3376, 235, 8);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            235u8 as i8 as i64 as u64
+        )
+    }
+
+    #[test]
+    fn test_sign_extend_ignores_high_bits() {
+        assert_eq!(memory::sign_extend(0xffff_ffff_0000_0080, 8), 0xffff_ffff_ffff_ff80);
+        assert_eq!(memory::zero_extend(0xffff_ffff_0000_0080, 8), 0x80);
+        assert_eq!(memory::sign_extend(0x1234_7fff, 16), 0x7fff);
+        assert_eq!(memory::sign_extend(0x8000_0000, 32), 0xffff_ffff_8000_0000);
+        assert_eq!(memory::sign_extend(0x8000_0000_0000_0000, 64), 0x8000_0000_0000_0000);
+
+        // lb a0, 0(a1); lbu a2, 0(a1); lh a3, 0(a1)
+        let mut soft = SoftThread::default();
+        let code = [0x0005_8503u32, 0x0005_c603, 0x0005_9683];
+        soft.load_image(&code.iter().flat_map(|inst| inst.to_le_bytes()).collect::<Vec<u8>>(), 0).unwrap();
+        soft.registers[Register::X11 as usize] = 0x100;
+        soft.bus.write(0x100, 0x8080, 16);
+        for _ in 0..3 {
+            soft.execute().unwrap();
+        }
+
+        assert_eq!(soft.registers[Register::X10 as usize], 0xffff_ffff_ffff_ff80);
+        assert_eq!(soft.registers[Register::X12 as usize], 0x80);
+        assert_eq!(soft.registers[Register::X13 as usize], 0xffff_ffff_ffff_8080);
+    }
+
+    #[test]
+    fn fetch_and_decode_lh_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Lh {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 1,
+            }
+        );
+    }
+    
+    #[test]
+    fn test_lh_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.bus.write(3376, 3000, 16);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            3000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_lw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Lw {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 2,
+            }
+        );
+    }
+    
+    #[test]
+    fn test_lw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.bus.write(3376, 100000, 32);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            100000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_lbu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Lbu {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 4,
+            }
+        );
+    }
+    
+    #[test]
+    fn test_lbu_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.bus.write(3376, 235, 8);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            235
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_lhu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Lhu {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_lhu_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.bus.write(3376, 3000, 16);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            3000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sb_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0010_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Sb {
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: 110,
+                func3: 0,
+            }
+        );
+    }
+    
+    #[test]
+    fn test_sb_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0010_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 235;
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&((210 as i64) as u64), 8).unwrap(),
+            235
+        )
+        
+    }
+
+    #[test]
+    fn fetch_and_decode_sh_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0010_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Sh {
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: 110,
+                func3: 1,
+            }
+        );
+    }    
+
+    #[test]
+    fn test_sh_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0010_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 3000;
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&210, 16).unwrap(),
+            3000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0010_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Sw {
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: 110,
+                func3: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0010_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 100000;
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&210, 32).unwrap(),
+            100000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_slti_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Slti {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 2,
+            }
+        );
+    }
+    
+    #[test]
+    fn test_slti_less_than_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 2;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn test_slti_greater_or_equal_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            0
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_xori_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Xori {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 4,
+            }
+        );
+    }    
+
+    #[test]
+    fn test_xori_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 0b1100_1100_1100;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            0b0000_0000_0000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_ori_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Ori {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ori_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 0b0011_0011_0011;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            0b1111_1111_1111
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_andi_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Andi {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 7,
+            }
+        );
+    }    
+
+    #[test]
+    fn test_andi_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 0b0011_0011_0011;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            0b0000_0000_0000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_slli_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Slli {
+                rd: Register::X10,
+                rs1: Register::X21,
+                shamt: 12,
+                func3: 1,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_slli_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            1000u64.wrapping_shl(12)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_srli_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Srli {
+                rd: Register::X10,
+                rs1: Register::X21,
+                shamt: 12,
+                func3: 5,
+                func7: 0,
+            }
+        );
+    }
+    
+    #[test]
+    fn test_srli_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            1000u64.wrapping_shr(12)
+        );
+    }
+
+    #[test]
+    fn fetch_and_decode_srai_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Srai {
+                rd: Register::X10,
+                rs1: Register::X21,
+                shamt: 12,
+                func3: 5,
+                func7: 32,
+            }
+        );
+    }
+
+    #[test]
+    fn test_srai_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            1000i64.wrapping_shr(12) as u64
+        );
+    }
+
+    #[test]
+    fn fetch_and_decode_sub_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Sub {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 32,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sub_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.registers[Register::X12 as usize] = 300;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            700
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sll_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Sll {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 1,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sll_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 12;
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            1000 << (((0b1100 & 0x3f) as u64) as u32)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_slt_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Slt {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 2,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_slt_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 12;
+        soft.registers[Register::X21 as usize] = 10;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sltu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Sltu {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 3,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sltu_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 12;
+        soft.registers[Register::X21 as usize] = 10;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            1            
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_xor_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Xor {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 4,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_xor_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 0b1100_1100_1100;
+        soft.registers[Register::X12 as usize] = 0b0011_0011_0011;
+        soft.execute();
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            0b1111_1111_1111
+        )
+        
+    }
+
+    #[test]
+    fn fetch_and_decode_srl_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Srl {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_srl_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 12;
+        soft.registers[Register::X21 as usize] = 10;
+        soft.execute();
+        let shamt = ((12 & 0x3f) as u64) as u32;
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            10u64.wrapping_shr(shamt)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sra_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Sra {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 32,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sra_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 12;
+        soft.registers[Register::X21 as usize] = 10;
+        soft.execute();
+        let shamt = ((12 & 0x3f) as u64) as u32;
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            (10i64.wrapping_shr(shamt) as u64)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_or_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Or {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 6,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_or_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 12;
+        soft.registers[Register::X21 as usize] = 10;
+        soft.execute();
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            (0b0000_1010 | 0b0000_1100)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_and_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::And {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 7,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_and_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0011_0011 as u8];
+        soft.load_program(program);
+        
+        soft.registers[Register::X12 as usize] = 12;
+        soft.registers[Register::X21 as usize] = 10;
+        soft.execute();
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            (0b0000_1010 & 0b0000_1100)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_lwu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Lwu {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn test_lwu_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.bus.write(3376, 100000, 32);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            100000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_ld_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Ld {
+                rd: Register::X10,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ld_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0000_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.bus.write(3376, 100000, 64);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            100000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0010_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Sd {
+                rs1: Register::X21,
+                rs2: Register::X12,
+                imm: 110,
+                func3: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sd_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0010_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 100;
+        soft.registers[Register::X12 as usize] = 100000;
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&210, 64).unwrap(),
+            100000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_addiw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Addiw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3276,
+                func3: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_addiw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            4276
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_slliw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0001_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Slliw {
+                rd: Register::X10,
+                rs1: Register::X21,
+                shamt: 12,
+                func3: 1,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_slliw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0001_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.execute();
+        let shamt: u32 = ((12i32) as u32);
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            (((1000u64.wrapping_shl(shamt.try_into().unwrap()) as i32) as i64) as u64)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sraiw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Sraiw {
+                rd: Register::X10,
+                rs1: Register::X21,
+                shamt: 12,
+                func3: 5,
+                func7: 32,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sraiw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0001_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        let shamt = 12;
+        soft.execute();
+        
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            (((1000u64.wrapping_shr(shamt) as i32) as i64) as u64)
+        );
+    }
+
+    #[test]
+    fn test_srliw_execution() {
+        let mut soft = SoftThread::default();
+        // srliw a0, a1, 4; srliw a0, a1, 0
+        let program = vec![0x00, 0x45, 0xd5, 0x1b, 0x00, 0x05, 0xd5, 0x1b];
+        soft.load_program(program);
+        soft.registers[Register::X11 as usize] = 0xffff_ffff_8000_0000;
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 0x0800_0000);
+
+        soft.registers[Register::X11 as usize] = 0x1234_5678_8000_0001;
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 0xffff_ffff_8000_0001);
+    }
+
+    #[test]
+    fn fetch_and_decode_addw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Addw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_addw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 500;
+        soft.registers[Register::X12 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1500
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_subw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+
+        assert_eq!(
+            instruction,
+            Instruction::Subw {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 32,
+            }
+        );
+    }
+
+    #[test]
+    fn test_subw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b0011_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.registers[Register::X12 as usize] = 300;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            700
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sllw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Sllw {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 1,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sllw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0011_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.registers[Register::X12 as usize] = 8;
+        soft.execute();
+        let shamt = ((0b1000 & 0x3f) as u64) as u32;
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            ((1000u32.wrapping_shl(shamt) as i32) as i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_srlw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Srlw {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_srlw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.registers[Register::X12 as usize] = 8;
+        soft.execute();
+        let shamt = ((0b1000 & 0x3f) as u64) as u32;
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            ((1000u32.wrapping_shr(shamt) as i32) as i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_sraw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Sraw {
+                rd: Register::X10,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 32,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sraw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0011_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 1000;
+        soft.registers[Register::X12 as usize] = 8;
+        soft.execute();
+        let shamt = ((0b1000 & 0x3f) as u64) as u32;
+
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            (((1000u32 >> shamt) as i32) as i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_csrrw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Csrrw {
+                rd: Register::X10,
+                rs1: Register::X21,
+                csr: 1036,
+                func3: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_csrrw_execution_dest_non_zero() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0011_0100 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.csr[CSR_MSCRATCH as usize] = 1000;
+        soft.registers[Register::X21 as usize] = 500;
+        soft.execute();
+        let csr_val = 0b0011_1110_1000;
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            csr_val.zero_extend(&12)
+        );
+
+        assert_eq!(
+            soft.csr[CSR_MSCRATCH as usize],
+            soft.registers[Register::X21 as usize]
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_csrrs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Csrrs {
+                rd: Register::X10,
+                rs1: Register::X21,
+                csr: 1036,
+                func3: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_csrrs_execution_dest_non_zero() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0011_0100 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.csr[CSR_MSCRATCH as usize] = 1000;
+        soft.registers[Register::X21 as usize] = 500;
+        soft.execute();
+        let csr_val = 0b0011_1110_1000;
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            csr_val.zero_extend(&12)
+        );
+
+        assert_eq!(
+            soft.csr[CSR_MSCRATCH as usize],
+            (csr_val | soft.registers[Register::X21 as usize])
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_csrrc_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Csrrc {
+                rd: Register::X10,
+                rs1: Register::X21,
+                csr: 1036,
+                func3: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_csrrc_execution_dest_non_zero() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0011_0100 as u8, 0b0000_1010 as u8, 0b1011_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.csr[CSR_MSCRATCH as usize] = 1000;
+        soft.registers[Register::X21 as usize] = 500;
+        soft.execute();
+        let csr_val = 0b0011_1110_1000;
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            csr_val.zero_extend(&12)
+        );
+
+        assert_eq!(
+            soft.csr[CSR_MSCRATCH as usize],
+            (csr_val & !soft.registers[Register::X21 as usize])
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_csrrwi_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Csrrwi {
+                rd: Register::X10,
+                uimm: 21,
+                csr: 1036,
+                func3: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_csrrwi_execution_dest_non_zero() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0011_0100 as u8, 0b0000_1010 as u8, 0b1101_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.csr[CSR_MSCRATCH as usize] = 1000;
+        soft.registers[Register::X21 as usize] = 500;
+        soft.execute();
+        let csr_val = 0b0011_1110_1000;
+        let imm = 21u64.zero_extend(&7);
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            csr_val
+        );
+
+        assert_eq!(
+            soft.csr[CSR_MSCRATCH as usize],
+            imm
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_csrrsi_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Csrrsi {
+                rd: Register::X10,
+                uimm: 21,
+                csr: 1036,
+                func3: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_csrrsi_execution_dest_non_zero() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0011_0100 as u8, 0b0000_1010 as u8, 0b1110_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.csr[CSR_MSCRATCH as usize] = 1000;
+        soft.registers[Register::X21 as usize] = 500;
+        soft.execute();
+        let csr_val = 0b0011_1110_1000;
+        let imm = 21u64.zero_extend(&7);
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            csr_val
+        );
+
+        assert_eq!(
+            soft.csr[CSR_MSCRATCH as usize],
+            imm | csr_val
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_csrrci_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        
+        assert_eq!(
+            instruction,
+            Instruction::Csrrci {
+                rd: Register::X10,
+                uimm: 21,
+                csr: 1036,
+                func3: 7
+            }
+        );
+    }
+
+    #[test]
+    fn test_csrrc_clears_mstatus_bits() {
+        let mut soft = SoftThread::default();
+        let code = Instruction::from_assembly("csrrc zero, mstatus, a0", 0).unwrap().encode().unwrap();
+        soft.load_image(&code.to_le_bytes(), 0).unwrap();
+        soft.csr[CSR_MSTATUS as usize] = u64::MAX;
+        soft.registers[Register::X10 as usize] = 0xff;
+        soft.execute().unwrap();
+
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] & 0xff, 0);
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] >> 8, u64::MAX >> 8);
+    }
+
+    #[test]
+    fn test_csrrci_execution_dest_non_zero() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0011_0100 as u8, 0b0000_1010 as u8, 0b1111_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.csr[CSR_MSCRATCH as usize] = 1000;
+        soft.registers[Register::X21 as usize] = 500;
+        soft.execute();
+        let csr_val = 0b0011_1110_1000;
+        let imm = 21u64.zero_extend(&7);
+        
+        assert_eq!(
+            soft.registers[Register::X10 as usize],
+            csr_val
+        );
+
+        assert_eq!(
+            soft.csr[CSR_MSCRATCH as usize],
+            csr_val & !imm
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_mul_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Mul {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_mul_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 10u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            200u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_mulh_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Mulh {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 1,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_mulh_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 25u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            500u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_mulhsu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Mulhsu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 2,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_mulhsu_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1010_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 25u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        let expected = 20i128.overflowing_mul(25i128).0;
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            expected as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_mulhu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Mulhu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 3,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_mulhu_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1011_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 25u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        let expected = 20u128.overflowing_mul(25u128).0;
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            expected as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_div_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Div {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 4,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_div_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 5u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            (4i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_divu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Divu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_divu_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 5u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            4u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_rem_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Rem {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 6,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_rem_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 17u64;
+        soft.registers[Register::X21 as usize] = 25u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            (8i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_remu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Remu {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 7,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_remu_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 17u64;
+        soft.registers[Register::X21 as usize] = 25u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            8u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_mulw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Mulw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 0,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_mulw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 25u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            500u64
+        )
+    }
+
+    #[test]
+    fn test_w_multiply_divide_truncate_operands() {
+        let run = |inst: [u8; 4], lhs: u64, rhs: u64| {
+            let mut soft = SoftThread::default();
+            soft.load_image(&inst, 0x1000).unwrap();
+            soft.registers[Register::X11 as usize] = lhs;
+            soft.registers[Register::X12 as usize] = rhs;
+            soft.execute().unwrap();
+            soft.registers[Register::X10 as usize]
+        };
+        // mulw, divw, divuw and remw a0, a1, a2
+        let (mulw, divw, divuw, remw) = ([0x3b, 0x85, 0xc5, 0x02], [0x3b, 0xc5, 0xc5, 0x02], [0x3b, 0xd5, 0xc5, 0x02], [0x3b, 0xe5, 0xc5, 0x02]);
+
+        assert_eq!(run(mulw, 0x1_0000_0003, 0x2_0000_0005), 15);
+        assert_eq!(run(mulw, 0x7fff_ffff, 2), 0xffff_ffff_ffff_fffe);
+        assert_eq!(run(divw, 0xffff_ffff_ffff_fff8, 0x1_0000_0002), (-4i64) as u64);
+        assert_eq!(run(divw, 0x8000_0000, (-1i64) as u64), 0xffff_ffff_8000_0000);
+        assert_eq!(run(divuw, 0x1_ffff_fff8, 2), 0x7fff_fffc);
+        assert_eq!(run(divuw, 0x1_0000_0000, 0), u64::MAX);
+        assert_eq!(run(remw, 0x1_ffff_fff9, 2), (-1i64) as u64);
+    }
+
+    #[test]
+    fn fetch_and_decode_divw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Divw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 4,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_divw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1100_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 5u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            (4i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_divuw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Divuw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 5,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_divuw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1101_0101 as u8, 0b1011_0011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 5u64;
+        soft.registers[Register::X21 as usize] = 20u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            4u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_remw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Remw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 6,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_remw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1110_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 17u64;
+        soft.registers[Register::X21 as usize] = 25u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            (8i64) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_remuw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::RemuW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X12,
+                func3: 7,
+                func7: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_remuw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0010 as u8, 0b1100_1010 as u8, 0b1111_0101 as u8, 0b1011_1011 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X12 as usize] = 17u64;
+        soft.registers[Register::X21 as usize] = 25u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            8u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_lrw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0010 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::LrW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_lrw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0010 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.bus.write(200, 1000, 32);
+        soft.execute();
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1000
+        );
+
+        assert!(
+            soft.res.contains(&200)
+        );
+    }
+
+    #[test]
+    fn fetch_and_decode_scw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::ScW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_scw_execution_addr_reserved() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.res.push(200);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            0
+        );
+
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            soft.registers[Register::X27 as usize]
+        );
+
+        assert!(
+            !soft.res.contains(&200)
+        );
+    }
+
+    #[test]
+    fn test_scw_execution_addr_not_reserved() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        );
+
+        assert_ne!(
+            soft.bus.read(&200, 32).unwrap(),
+            soft.registers[Register::X27 as usize]
+        );
+
+        assert!(
+            !soft.res.contains(&200)
+        );
+    }
+
+    #[test]
+    fn fetch_and_decode_amoswapw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoswapW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoswapw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            soft.registers[Register::X27 as usize]
+        );
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+    }
+
+    #[test]
+    fn fetch_and_decode_amoaddw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoaddW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoaddw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            6000
+        )
+
+    }
+
+    #[test]
+    fn fetch_and_decode_amoxorw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoxorW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoxorw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            0b1000001100000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amoandw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoandW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoandw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            0b1110001000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amoorw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoorW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoorw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            0b1001111101000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amominw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmominW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amominw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            1000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amomaxw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmomaxW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amomaxw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            5000
+        )
+    }
+
+    #[test]
+    fn test_amominw_amomaxw_compare_signed() {
+        let mut soft = SoftThread::default();
+        soft.load_program(vec![0b1000_0011, 0b1011_1010, 0b1010_0101, 0b1010_1111]);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = -1i64 as u64;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        assert_eq!(soft.registers[Register::X11 as usize], 5000);
+        assert_eq!(soft.bus.read(&200, 32).unwrap(), 0xffff_ffff);
+
+        let mut soft = SoftThread::default();
+        soft.load_program(vec![0b1010_0011, 0b1011_1010, 0b1010_0101, 0b1010_1111]);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 5000;
+        soft.bus.write(200, 0x8000_0000, 32);
+        soft.execute();
+        assert_eq!(soft.registers[Register::X11 as usize], 0xffff_ffff_8000_0000);
+        assert_eq!(soft.bus.read(&200, 32).unwrap(), 5000);
+    }
+
+    #[test]
+    fn test_amoadd_wraps_on_overflow() {
+        // amoadd.w a1, s11, (s5)
+        let mut soft = SoftThread::default();
+        soft.load_program(vec![0x01, 0xba, 0xa5, 0xaf]);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1;
+        soft.bus.write(200, i32::MAX as u64, 32);
+        soft.execute();
+        assert_eq!(soft.registers[Register::X11 as usize], i32::MAX as u64);
+        assert_eq!(soft.bus.read(&200, 32).unwrap(), 0x8000_0000);
+
+        soft.pc = 0;
+        soft.execute();
+        assert_eq!(soft.registers[Register::X11 as usize], i32::MIN as i64 as u64);
+
+        // amoadd.d a1, s11, (s5)
+        let mut soft = SoftThread::default();
+        soft.load_program(vec![0x01, 0xba, 0xb5, 0xaf]);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1;
+        soft.bus.write(200, u64::MAX, 64);
+        soft.execute();
+        assert_eq!(soft.registers[Register::X11 as usize], u64::MAX);
+        assert_eq!(soft.bus.read(&200, 64).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_amomind_amomaxd_compare_signed() {
+        let mut soft = SoftThread::default();
+        soft.load_program(vec![0b1000_0011, 0b1011_1010, 0b1011_0101, 0b1010_1111]);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = -7i64 as u64;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+        assert_eq!(soft.registers[Register::X11 as usize], 5000);
+        assert_eq!(soft.bus.read(&200, 64).unwrap(), -7i64 as u64);
+
+        let mut soft = SoftThread::default();
+        soft.load_program(vec![0b1010_0011, 0b1011_1010, 0b1011_0101, 0b1010_1111]);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 5000;
+        soft.bus.write(200, -7i64 as u64, 64);
+        soft.execute();
+        assert_eq!(soft.registers[Register::X11 as usize], -7i64 as u64);
+        assert_eq!(soft.bus.read(&200, 64).unwrap(), 5000);
+    }
+
+    #[test]
+    fn fetch_and_decode_amominuw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmominuW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amominuw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            1000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amomaxuw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmomaxuW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+        
+    }
+
+    #[test]
+    fn test_amomaxuw_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 32);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 32).unwrap(),
+            5000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_lrd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0010 as u8, 0b0000_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::LrD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_lrd_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0010 as u8, 0b0000_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.bus.write(200, 1000, 64);
+        soft.execute();
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1000
+        );
+
+        assert!(
+            soft.res.contains(&200)
+        );
+    }
+
+    #[test]
+    fn fetch_and_decode_scd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::ScD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_scd_execution_address_reserved() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.res.push(200);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            0
+        );
+
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            soft.registers[Register::X27 as usize]
+        );
+
+        assert!(
+            !soft.res.contains(&200)
+        );
+        
+    }
+
+    #[test]
+    fn test_scd_execution_address_not_reserved() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        );
+
+        assert_ne!(
+            soft.bus.read(&200, 64).unwrap(),
+            soft.registers[Register::X27 as usize]
+        );
+
+        assert!(
+            !soft.res.contains(&200)
+        );
+        
+    }
+
+    #[test]
+    fn fetch_and_decode_amoswapd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoswapD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoswapd_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            soft.registers[Register::X27 as usize]
+        );
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+    }
+
+    #[test]
+    fn fetch_and_decode_amoxord_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoaddD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoxord_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            0b1000001100000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amoandd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoandD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoandd_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            0b1110001000
+        )
+        
+    }
+
+    #[test]
+    fn fetch_and_decode_amoord_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmoorD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amoord_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            0b1001111101000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amomind_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1000_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmominD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amomind_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1000_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            1000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amomaxd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmomaxD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amomaxd_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            5000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amominud_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmominuD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amominud_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            1000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_amomaxud_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::AmomaxuD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                aq: 0,
+                rl: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_amomaxud_execution() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_1111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 200;
+        soft.registers[Register::X27 as usize] = 1000;
+        soft.bus.write(200, 5000, 64);
+        soft.execute();
+        
+        // assert the value in rd == value in address at rs1
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            5000
+        );
+
+        // assert the value in memory at address in rs1 is equal to prev value + val in rs2
+        assert_eq!(
+            soft.bus.read(&200, 64).unwrap(),
+            5000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_flw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1000_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Flw {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3643,
+            }
+        )        
+    }
+
+    #[test]
+    fn test_flw_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1000_0111 as u8];
+        soft.load_program(program);
+        soft.bus.write(3643, 5000, 32);
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize].to_bits(),
+            NAN_BOX | 5000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Fsw {
+                rs1: Register::X21,
+                rs2: Register::X27,
+                imm: 123,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsw_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1010_0111 as u8];
+        soft.load_program(program);
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(5000u32));
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&123, 32).unwrap(),
+            5000
+        )
+    }
+
+    
+    #[test]
+    fn fetch_and_decode_fmadds_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaddS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmadds_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
+        
+        soft.load_program(program);
+        
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        soft.f_registers[Register::X28 as usize] = box_f32(f32::from_bits(2u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let rs3_val = f32::from_bits(2u32) as f64;
+        let res = rs1_val.mul_add(rs2_val, rs3_val);
+        
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmsubs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmsubS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmsubs_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        soft.f_registers[Register::X28 as usize] = box_f32(f32::from_bits(2u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let rs3_val = f32::from_bits(2u32) as f64;
+        let res = rs1_val.mul_add(rs2_val, -rs3_val);
+        
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fnmsubs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmsubS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fnmsubs_execute() {  
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        soft.f_registers[Register::X28 as usize] = box_f32(f32::from_bits(2u32));
+        
+        soft.execute();
+        
+        let rs1_val =  -f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let rs3_val = -f32::from_bits(2u32) as f64;
+        let res = rs1_val.mul_add(rs2_val, rs3_val);
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+    
+    #[test]
+    fn fetch_and_decode_fnmadds_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmaddS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fnmadds_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
+        soft.load_program(program);
+    
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        soft.f_registers[Register::X28 as usize] = box_f32(f32::from_bits(2u32));
+        
+        soft.execute();
+        
+        let rs1_val =  -f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let rs3_val = f32::from_bits(2u32) as f64;
+        let res = rs1_val.mul_add(rs2_val, rs3_val);
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fadds_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FaddS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fadds_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let res = rs1_val + rs2_val;
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsubs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsubS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsubs_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let res = rs1_val - rs2_val;
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmuls_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmulS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmuls_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let res = rs1_val * rs2_val;
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fdivs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FdivS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+
+    }
+
+    #[test]
+    fn test_fdivs_execute() {
+
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let res = rs1_val / rs2_val;
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsqrts_instruction() {
+
+        let mut soft = SoftThread::default();
+        let program = vec![0b0101_1000 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsqrtS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsqrts_execute() {
+
+        let mut soft = SoftThread::default();
+        let program = vec![0b0101_1000 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let res = rs1_val.sqrt();
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsgnjs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjs_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+    
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let res = rs1_val.copysign(rs2_val);
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsgnjns_instruction() {
+
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjnS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjns_execute() {
+
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = -f32::from_bits(100u32) as f64;
+        let res = rs1_val.copysign(rs2_val);
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+    
+    #[test]
+    fn fetch_and_decode_fsgnjxs_instruction() {
+
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjxS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjxs_execute() {
+
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let sign_1 = (f32::from_bits(200u32)).to_bits() & 0x8000_0000;
+        let sign_2 = (f32::from_bits(100u32)).to_bits() & 0x8000_0000;
+        let other = (f32::from_bits(200u32)).to_bits() & 0x7fff_ffff;
+        let res = (f32::from_bits((sign_1 ^ sign_2) | other)) as f64;
+
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+
+    }
+
+    #[test]
+    fn fetch_and_decode_fmins_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_1001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FminS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmins_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_1001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+    
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let res = rs1_val.min(rs2_val);
+        
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+
+    }
+
+    #[test]
+    fn fetch_and_decode_fmaxs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_1001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaxS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmaxs_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_1001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(100u32));
+        
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let rs2_val = f32::from_bits(100u32) as f64;
+        let res = rs1_val.max(rs2_val);
+        
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            res as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtws_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtws_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+    
+        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
+       
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let res = (rs1_val.round() as u32) as u64;
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtwus_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWUS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtwus_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
+       
+        soft.execute();
+        
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let res = ((rs1_val.round() as u32) as i32) as u64;
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmvxw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0000 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvXW {
+                rd: Register::X11,
+                rs1: Register::X21,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmvxw_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0000 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
+       
+        soft.execute();
+         
+        let rs1_val =  f32::from_bits(200u32) as f64;
+        let res = (((rs1_val.to_bits() & 0xffffffff) as i32) as i64) as u64;
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_feqs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FeqS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_feqs_eq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(f32::from_bits(200u32));
+        soft.f_registers[Register::X27 as usize] = box_f32(f32::from_bits(200u32));
+
+        soft.execute();
+         
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn test_feqs_ne_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f32::from_bits(200u32) as f64;
+        soft.f_registers[Register::X27 as usize] = f32::from_bits(100u32) as f64;
+
+        soft.execute();
+         
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            0
+        )
+    }
+
+
+    #[test]
+    fn fetch_and_decode_flts_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FltS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_flts_lt_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        
+        soft.f_registers[Register::X21 as usize] = box_f32(100.0);
+        soft.f_registers[Register::X27 as usize] = box_f32(200.0);
+
+        soft.execute();
+         
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn test_flts_eq_execute() { 
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        
+        soft.f_registers[Register::X21 as usize] = 200.0f64;
+        soft.f_registers[Register::X27 as usize] = 200.0f64;
+
+        soft.execute();
+         
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            0
+        )
+
+    }
+
+
+    #[test]
+    fn test_flts_gt_execute() { 
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        
+        soft.f_registers[Register::X21 as usize] = 300.0 as f64;
+        soft.f_registers[Register::X27 as usize] = 200.0 as f64;
+         
+        soft.execute();
+         
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            0
+        )
+
+    }
+
+    
+    #[test]
+    fn fetch_and_decode_fles_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FleS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fles_lt_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(100.0);
+        soft.f_registers[Register::X27 as usize] = box_f32(200.0);
+        soft.execute();
+         
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+
+
+    #[test]
+    fn test_fles_eq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(200.0);
+        soft.f_registers[Register::X27 as usize] = box_f32(200.0);
+
+        soft.execute();
+         
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+    
+
+    #[test]
+    fn test_fles_gt_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0001 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 300.0f64;
+        soft.f_registers[Register::X27 as usize] = 200.0f64;
+
+        soft.execute();
+         
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            0
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fclasss_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FclassS {
+                rd: Register::X11,
+                rs1: Register::X21,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fclasss_execute() {
+        let classes = [
+            (f32::NEG_INFINITY, FCLASS_NEG_INF),
+            (-1.5, FCLASS_NEG_NORMAL),
+            (-f32::MIN_POSITIVE / 2.0, FCLASS_NEG_SUBNORMAL),
+            (-0.0, FCLASS_NEG_ZERO),
+            (0.0, FCLASS_POS_ZERO),
+            (f32::MIN_POSITIVE / 2.0, FCLASS_POS_SUBNORMAL),
+            (1.5, FCLASS_POS_NORMAL),
+            (f32::INFINITY, FCLASS_POS_INF),
+            (f32::from_bits(0x7f80_0001), FCLASS_SIGNALING_NAN),
+            (f32::NAN, FCLASS_QUIET_NAN),
+        ];
+
+        for (bit, (val, class)) in classes.into_iter().enumerate() {
+            let mut soft = SoftThread::default();
+            // fclass.s x1, f3
+            soft.load_image(&[0xd3, 0x90, 0x01, 0xe0], 0).unwrap();
+            soft.f_registers[3] = f64::from_bits(NAN_BOX | val.to_bits() as u64);
+            soft.execute().unwrap();
+            assert_eq!(class, 1 << bit);
+            assert_eq!(soft.registers[1], class, "{:?}", val);
+        }
+    }
+
+
+    #[test]
+    fn fetch_and_decode_fcvtsw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtsw_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 300u64;
+        soft.execute();
+         
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            ((300u64) as i32) as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtswu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSWU {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtswu_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 300u64;
+        soft.execute();
+         
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            ((300u64) as u32) as f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmvwx_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1111_0000 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvWX {
+                rd: Register::X11,
+                rs1: Register::X21,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmvwx_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1111_0000 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 0xdead_beef_0000_012c;
+        soft.execute(); 
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize].to_bits(),
+            NAN_BOX | 300u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtls_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0000 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtls_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0000 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(300.0);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            ((300.0f64) as f32).round() as u64 
+        )
+    }
+
+    #[test]
+    fn test_fcvtls_fcvtld_are_signed() {
+        let mut soft = SoftThread::default();
+        // fcvt.l.s a0, fa0; fcvt.l.s a0, fa0; fcvt.l.d a1, fa1
+        soft.load_image(&[0x53, 0x75, 0x25, 0xc0, 0x53, 0x75, 0x25, 0xc0, 0xd3, 0xf5, 0x25, 0xc2], 0).unwrap();
+
+        soft.f_registers[Register::X10 as usize] = box_f32(-1.5);
+        soft.f_registers[Register::X11 as usize] = -3.0e9f64;
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 0xffff_ffff_ffff_fffe);
+
+        soft.f_registers[Register::X10 as usize] = box_f32(i64::MAX as f32);
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], i64::MAX as u64);
+
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X11 as usize], -3_000_000_000i64 as u64);
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtlus_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0000 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLUS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtlus_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0000 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(300.0);
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            ((300.0f64) as f32).round() as u64 
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtsl_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0000 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSL {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtsl_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0000 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 300u64;
+        soft.execute();
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            (300u64) as f32
+        )
+        
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtslu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0000 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSLU {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtslu_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0000 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 300u64;
+        soft.execute();
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            (300u64) as f32
+        )
+    }
+
+
+    #[test]
+    fn fetch_and_decode_fld_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1000_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Fld {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3643,
+            }
+        )        
+    }
+
+    #[test]
+    fn test_fld_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1000_0111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 3643;
+        let val = (5000.0f64).to_bits();
+        soft.bus.write(3643, val, 64);
+        soft.execute();
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            f64::from_bits(val)
+        )
+    }
+    
+   
+    #[test]
+    fn fetch_and_decode_fsd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Fsd {
+                rs1: Register::X21,
+                rs2: Register::X27,
+                imm: 123,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1011_0101 as u8, 0b1010_0111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 123;
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(5000u64);
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&123, 64).unwrap(),
+            5000
+        )
+    }
+    
+    #[test]
+    fn fetch_and_decode_fmaddd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaddD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmaddd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
+        
+        soft.execute();
+        
+        let rs1_val =  f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let rs3_val = f64::from_bits(2u64);
+        let res = rs1_val.mul_add(rs2_val, rs3_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+
+    #[test]
+    fn fetch_and_decode_fmsubd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmsubD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmsubd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
+        
+        soft.execute();
+        
+        let rs1_val =  f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let rs3_val = f64::from_bits(2u64);
+        let res = rs1_val.mul_add(rs2_val, -rs3_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fnmsubd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmsubD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fnmsubd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
+        
+        soft.execute();
+        
+        let rs1_val =  -f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let rs3_val = f64::from_bits(2u64);
+        let res = rs1_val.mul_add(rs2_val, -rs3_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fnmaddd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmaddD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fnmaddd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
+        
+        soft.execute();
+        
+        let rs1_val = -f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let rs3_val = f64::from_bits(2u64);
+        let res = rs1_val.mul_add(rs2_val, rs3_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_faddd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FaddD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_faddd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64); 
+        let res = rs1_val + rs2_val;
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsubd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsubD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsubd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64); 
+        let res = rs1_val - rs2_val;
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+    
+    #[test]
+    fn fetch_and_decode_fmuld_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmulD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmuld_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64); 
+        let res = rs1_val * rs2_val;
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }    
+
+    #[test]
+    fn fetch_and_decode_fdivd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FdivD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fdivd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64); 
+        let res = rs1_val / rs2_val;
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }    
+
+    #[test]
+    fn fetch_and_decode_fsqrtd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0101_1010 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsqrtD {
+                rd: Register::X11,
+                rs1: Register::X21, 
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsqrtd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0101_1010 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+    
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let res = rs1_val.sqrt();
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+
+    }
+
+    #[test]
+    fn fetch_and_decode_fsgnjd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27, 
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let res = rs1_val.copysign(rs2_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+    
+    #[test]
+    fn fetch_and_decode_fsgnjnd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjnD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27, 
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjnd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let res = rs1_val.copysign(-rs2_val);
+ 
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsgnjxd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjxD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27, 
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjxd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
+        soft.execute();
+        
+        let sign_1 = f64::from_bits(200u64).to_bits() & 0x8000_0000_0000_0000;
+        let sign_2 = f64::from_bits(100u64).to_bits() & 0x8000_0000_0000_0000;
+        let other =  f64::from_bits(200u64).to_bits() & 0x7fff_ffff_ffff_ffff;
+
+        let res = f64::from_bits((sign_1 ^ sign_2) | other);
+ 
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+
+    }
+
+    #[test]
+    fn fetch_and_decode_fmind_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b0010_1011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FminD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmind_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b0010_1011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.f_registers[Register::X27 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            100f64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmaxd_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b0010_1011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaxD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmaxd_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b0010_1011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.f_registers[Register::X27 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            200f64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtsd_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b0100_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSD {
+                rd: Register::X11,
+                rs1: Register::X21, 
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtsd_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b0100_0000 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.execute();
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            100f32
+        )
+
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtds_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b0100_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDS {
+                rd: Register::X11,
+                rs1: Register::X21, 
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtds_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b0100_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(100.0);
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            (100f32) as f64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_feqd_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FeqD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,  
+            }
+        )
+    }
+
+    #[test]
+    fn test_feqd_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fltd_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FltD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,  
+            }
+        )
+
+    }
+
+    #[test]
+    fn test_fltd_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = 50f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+        soft.execute();
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fled_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FleD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,  
+            }
+        )
+    }
+
+    #[test]
+    fn test_fled_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1010_0011 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = 50f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+        soft.execute();
+        
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }    
+
+    #[test]
+    fn fetch_and_decode_fclassd_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1110_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FclassD {
+                rd: Register::X11,
+                rs1: Register::X21,
+            }
+        )
+    }
+
+
+    #[test]
+    fn test_fclassd_execute() {
+        let classes = [
+            (f64::NEG_INFINITY, FCLASS_NEG_INF),
+            (-1.5, FCLASS_NEG_NORMAL),
+            (-f64::MIN_POSITIVE / 2.0, FCLASS_NEG_SUBNORMAL),
+            (-0.0, FCLASS_NEG_ZERO),
+            (0.0, FCLASS_POS_ZERO),
+            (f64::MIN_POSITIVE / 2.0, FCLASS_POS_SUBNORMAL),
+            (1.5, FCLASS_POS_NORMAL),
+            (f64::INFINITY, FCLASS_POS_INF),
+            (f64::from_bits(0x7ff0_0000_0000_0001), FCLASS_SIGNALING_NAN),
+            (f64::NAN, FCLASS_QUIET_NAN),
+        ];
+
+        for (bit, (val, class)) in classes.into_iter().enumerate() {
+            let mut soft = SoftThread::default();
+            // fclass.d x1, f3
+            soft.load_image(&[0xd3, 0x90, 0x01, 0xe2], 0).unwrap();
+            soft.f_registers[3] = val;
+            soft.execute().unwrap();
+            assert_eq!(class, 1 << bit);
+            assert_eq!(soft.registers[1], class, "{:?}", val);
+        }
+    }
+    
+    #[test]
+    fn fetch_and_decode_fcvtwd_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1100_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtwd_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1100_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.execute();
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            ((100f64).round() as i32) as u64
+        )
+    } 
+
+
+    #[test]
+    fn fetch_and_decode_fcvtwud_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1100_0010 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWUD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtwud_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1100_0010 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.execute();
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            (((100f64).round() as u32) as i32) as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtdw_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1101_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtdw_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1101_0010 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 100u64;
+        soft.execute();
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            ((100u64) as i32) as f64
+        )
+    } 
+
+    #[test]
+    fn fetch_and_decode_fcvtdwu_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1101_0010 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDWU {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtdwu_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1101_0010 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 100u64;
+        soft.execute();
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            ((100u64) as u32) as f64
+        )
+    } 
+
+    #[test]
+    fn fetch_and_decode_fcvtld_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1100_0010 as u8, 0b0010_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtld_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1100_0010 as u8, 0b0010_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.execute();
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            100f64.round() as u64
+        )
+    } 
+
+    #[test]
+    fn fetch_and_decode_fcvtlud_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1100_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLUD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtlud_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1100_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.execute();
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            100f64.round() as u64
+        )
+    }
+    
+    #[test]
+    fn fetch_and_decode_fmvxd_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1110_0010 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvXD {
+                rd: Register::X11,
+                rs1: Register::X21,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmvxd_execute() { 
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1110_0010 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.execute();
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            100f64.to_bits()
+        )
+    }    
+
+    #[test]
+    fn fetch_and_decode_fcvtdl_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1101_0010 as u8, 0b0010_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDL {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtdl_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1101_0010 as u8, 0b0010_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+    
+        soft.registers[Register::X21 as usize] = 100u64;
+        soft.execute();
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            100u64 as f64
+        )
+    }    
+
+    #[test]
+    fn fetch_and_decode_fcvtdlu_instruction() { 
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1101_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDLU {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+    
+    #[test]
+    fn test_fcvtdlu_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1101_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 100u64;
+        soft.execute();
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            100u64 as f64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmvdx_instruction() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1111_0010 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmvDX {
+                rd: Register::X11,
+                rs1: Register::X21, 
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmvdx_execute() {
+        let mut soft = SoftThread::default(); 
+        let program = vec![0b1111_0010 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8]; 
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 100f64.to_bits();
+        soft.execute();
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            100f64
+        )
+    }    
+
+    #[test]
+    fn test_fmv_round_trips_bit_patterns() {
+        let mut soft = SoftThread::default();
+        // fmv.d.x f1, a0; fmv.x.d a1, f1; fmv.w.x f2, a0; fmv.x.w a2, f2
+        soft.load_image(&[0xd3, 0x00, 0x05, 0xf2, 0xd3, 0x85, 0x00, 0xe2, 0x53, 0x01, 0x05, 0xf0, 0x53, 0x06, 0x01, 0xe0], 0x1000).unwrap();
+        // A signalling NaN pattern, which a numeric conversion would quieten.
+        soft.registers[Register::X10 as usize] = 0x7ff0_0000_8000_0001;
+        soft.run_until_halt().unwrap();
+
+        assert_eq!(soft.f_registers[1].to_bits(), 0x7ff0_0000_8000_0001);
+        assert_eq!(soft.registers[Register::X11 as usize], 0x7ff0_0000_8000_0001);
+        assert_eq!(soft.f_registers[2].to_bits(), NAN_BOX | 0x8000_0001);
+        assert_eq!(soft.registers[Register::X12 as usize], 0xffff_ffff_8000_0001);
+    }
+
+    #[test]
+    fn fetch_and_decode_flq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1100_0101 as u8, 0b1000_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Flq {
+                rd: Register::X11,
+                rs1: Register::X21,
+                imm: 3643,
+            }
+        )        
+    }
+
+    #[test]
+    fn test_flq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1100_0101 as u8, 0b1000_0111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 3643;
+        let val = (5000.0f64).to_bits();
+        soft.bus.write(3643, val, 64);
+        soft.execute();
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            f64::from_bits(val)
+        )
+    } 
+    
+    #[test]
+    fn fetch_and_decode_fsq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1100_0101 as u8, 0b1010_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::Fsq {
+                rs1: Register::X21,
+                rs2: Register::X27,
+                imm: 123,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0011 as u8, 0b1011_1010 as u8, 0b1100_0101 as u8, 0b1010_0111 as u8];
+        soft.load_program(program);
+        soft.registers[Register::X21 as usize] = 123;
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(5000u64);
+        soft.execute();
+
+        assert_eq!(
+            soft.bus.read(&123, 64).unwrap(),
+            5000
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmaddq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaddQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmaddq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
+        
+        soft.execute();
+        
+        let rs1_val =  f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let rs3_val = f64::from_bits(2u64);
+        let res = rs1_val.mul_add(rs2_val, rs3_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmsubq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmsubQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmsubq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_0111 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
+        
+        soft.execute();
+        
+        let rs1_val =  f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let rs3_val = f64::from_bits(2u64);
+        let res = rs1_val.mul_add(rs2_val, -rs3_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fnmsubq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmsubQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fnmsubq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
+        
+        soft.execute();
+        
+        let rs1_val =  -f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let rs3_val = f64::from_bits(2u64);
+        let res = rs1_val.mul_add(rs2_val, -rs3_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fnmaddq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FnmaddQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rs3: Register::X28,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fnmaddq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1100_1111 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        soft.f_registers[Register::X28 as usize] = f64::from_bits(2u64);
+        
+        soft.execute();
+        
+        let rs1_val =  -f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let rs3_val = f64::from_bits(2u64);
+        let res = rs1_val.mul_add(rs2_val, rs3_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_faddq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FaddQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_faddq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let res = rs1_val + rs2_val;
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }    
+
+    #[test]
+    fn fetch_and_decode_fsubq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsubQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsubq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_1111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let res = rs1_val - rs2_val;
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmulq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmulQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmulq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let res = rs1_val * rs2_val;
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fdivq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FdivQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fdivq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0001_1111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64);
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let rs2_val = f64::from_bits(100u64);
+        let res = rs1_val / rs2_val;
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsqrtq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0101_1110 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsqrtQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsqrtq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0101_1110 as u8, 0b0000_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64);
+        
+        soft.execute();
+        
+        let rs1_val = f64::from_bits(200u64);
+        let res = rs1_val.sqrt();
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fsgnjq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+        soft.execute();
+
+        let rs1_val = 200f64;
+        let rs2_val = 100f64;
+        let res = rs1_val.copysign(rs2_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+
+    }
+
+    #[test]
+    fn fetch_and_decode_fsgnjnq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjnQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjnq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+        soft.execute();
+
+        let rs1_val = 200f64;
+        let rs2_val = -100f64;
+        let res = rs1_val.copysign(rs2_val);
+        
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+
+    }
+
+    #[test]
+    fn fetch_and_decode_fsgnjxq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FsgnjxQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fsgnjxq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = f64::from_bits(200u64); 
+        soft.f_registers[Register::X27 as usize] = f64::from_bits(100u64); 
+        soft.execute();
+
+        let sign_1 = f64::from_bits(200u64).to_bits() & 0x8000_0000_0000_0000;
+        let sign_2 = f64::from_bits(100u64).to_bits() & 0x8000_0000_0000_0000;
+        let other =  f64::from_bits(200u64).to_bits() & 0x7fff_ffff_ffff_ffff;
+
+        let res = f64::from_bits((sign_1 ^ sign_2) | other);
+ 
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            res
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fminq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_1111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FminQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fminq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_1111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            100f64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fmaxq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_1111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FmaxQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27
+            }
+        )
+    }
+
+    #[test]
+    fn test_fmaxq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0010_1111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+    
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            200f64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtsq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtSQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtsq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0000 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            unbox_f32(soft.f_registers[Register::X11 as usize]),
+            200f32
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtqs_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0110 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQS {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtqs_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0110 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = box_f32(200.0);
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            200f64
+        )
+    }
+    
+    #[test]
+    fn fetch_and_decode_fcvtdq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtDQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtdq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0010 as u8, 0b0011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            (200 as f32) as f64 
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtqd_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0110 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQD {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtqd_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0100_0110 as u8, 0b0001_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            (200 as f32) as f64 
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_feqq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FeqQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+            }
+        )
+    }
+
+    #[test]
+    fn test_feqq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1010_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 100f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fltq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FltQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fltq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        
+        soft.f_registers[Register::X21 as usize] = 50f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fleq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FleQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rs2: Register::X27,
+            }
+        )
+    }
+
+    #[test]
+    fn test_fleq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1010_0111 as u8, 0b1011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 50f64;
+        soft.f_registers[Register::X27 as usize] = 100f64;
+
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            1
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fclassq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1110_0110 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FclassQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+            }
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtwq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0110 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+    
+
+    #[test]
+    fn test_fcvtwq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0110 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        
+        soft.load_program(program);
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            ((200f64.round() as i32) as u64)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtwuq_instruction() { 
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0110 as u8, 0b0001_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtWUQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+    
+    #[test]
+    fn test_fcvtwuq_execute() { 
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0110 as u8, 0b0001_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            (((200f64.round() as u32) as i32) as u64)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtqw_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0110 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQW {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+    
+    #[test]
+    fn test_fcvtqw_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0110 as u8, 0b0000_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 200u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            ((200f64.round() as i32) as f64)
+        )
+    }
+    
+    #[test]
+    fn fetch_and_decode_fcvtqwu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0110 as u8, 0b0001_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQWU {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtqwu_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0110 as u8, 0b0001_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 200u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            ((200f64.round() as u32) as f64)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtlq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0110 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtlq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0110 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            ((200f64.round() as u32) as u64)
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtluq_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0110 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtLUQ {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtluq_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1100_0110 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.f_registers[Register::X21 as usize] = 200f64;
+        soft.execute();
+
+        assert_eq!(
+            soft.registers[Register::X11 as usize],
+            200f64.round() as u64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtql_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0110 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQL {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtql_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0110 as u8, 0b0010_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+
+        soft.registers[Register::X21 as usize] = 200u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            200u64 as f64
+        )
+    }
+
+    #[test]
+    fn fetch_and_decode_fcvtqlu_instruction() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0110 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+        let instruction: Instruction = soft.fetch().into();
+        assert_eq!(
+            instruction,
+            Instruction::FcvtQLU {
+                rd: Register::X11,
+                rs1: Register::X21,
+                rm: 0
+            }
+        )
+    }
+
+    #[test]
+    fn test_fcvtqlu_execute() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b1101_0110 as u8, 0b0011_1010 as u8, 0b1000_0101 as u8, 0b1101_0011 as u8];
+        soft.load_program(program);
+    
+        soft.registers[Register::X21 as usize] = 200u64;
+        soft.execute();
+
+        assert_eq!(
+            soft.f_registers[Register::X11 as usize],
+            200u64 as f64
+        )
+    }
+
+    #[test]
+    fn test_ecall_from_user_mode_traps_to_machine_mode() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b0000_0000 as u8, 0b0000_0000 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.priv_level = PrivilegeLevel::User;
+        soft.csr[CSR_MTVEC as usize] = 4;
+
+        assert_eq!(soft.execute(), Err(Exception::EnvironmentCallFromUMode));
+        soft.run_until_halt().unwrap();
+
+        assert_eq!(soft.csr[CSR_MCAUSE as usize], 8);
+        assert_eq!(soft.csr[CSR_MEPC as usize], 0);
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] & MSTATUS_MPP, 0);
+        assert_eq!(soft.priv_level, PrivilegeLevel::Machine);
+        assert_eq!(soft.pc, 4);
+    }
+
+    #[test]
+    fn test_ecall_from_supervisor_mode_traps_to_machine_mode() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b0000_0000 as u8, 0b0000_0000 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.priv_level = PrivilegeLevel::Supervisor;
+        soft.csr[CSR_MTVEC as usize] = 4;
+
+        assert_eq!(soft.execute(), Err(Exception::EnvironmentCallFromSMode));
+        soft.run_until_halt().unwrap();
+
+        assert_eq!(soft.csr[CSR_MCAUSE as usize], 9);
+        assert_eq!(soft.csr[CSR_MEPC as usize], 0);
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] & MSTATUS_MPP, 1 << MSTATUS_MPP_SHIFT);
+        assert_eq!(soft.priv_level, PrivilegeLevel::Machine);
+    }
+
+    #[test]
+    fn test_run_until_halt_services_sbi_calls() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b0000_0000 as u8, 0b0000_0000 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.priv_level = PrivilegeLevel::Supervisor;
+        soft.registers[Register::X17 as usize] = 0x10;
+        soft.registers[Register::X16 as usize] = 3;
+        soft.set_sbi_handler(Box::new(|_, eid, fid| SbiResult::ok((eid + fid) as i64)));
+
+        soft.run_until_halt().unwrap();
+
+        assert_eq!(soft.registers[Register::X10 as usize], 0);
+        assert_eq!(soft.registers[Register::X11 as usize], 0x13);
+        assert_eq!(soft.csr[CSR_MCAUSE as usize], 0);
+        assert_eq!(soft.priv_level, PrivilegeLevel::Supervisor);
+        assert_eq!(soft.pc, 4);
+    }
+
+    #[test]
+    fn test_ecall_from_machine_mode_traps_to_machine_mode() {
+        let mut soft = SoftThread::default();
+        let program = vec![0b0000_0000 as u8, 0b0000_0000 as u8, 0b0000_0000 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.csr[CSR_MTVEC as usize] = 4;
+        soft.csr[CSR_MSTATUS as usize] = MSTATUS_MIE;
+
+        assert_eq!(soft.execute(), Err(Exception::EnvironmentCallFromMMode));
+        soft.run_until_halt().unwrap();
+
+        assert_eq!(soft.csr[CSR_MCAUSE as usize], 11);
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] & MSTATUS_MPP, 3 << MSTATUS_MPP_SHIFT);
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] & MSTATUS_MPIE, MSTATUS_MPIE);
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] & MSTATUS_MIE, 0);
+    }
+
+    #[test]
+    fn test_mmap_dram_large_allocation() {
+        let start = std::time::Instant::now();
+        let mut dram = Dram::mmap(1 << 30).unwrap();
+        let elapsed = start.elapsed();
+
+        let addr = 0x2f3a_51c8u64;
+        dram.write(addr, 0xdead_beef, 32).unwrap();
+
+        assert_eq!(dram.read(&addr, 32).unwrap(), 0xdead_beef);
+        assert_eq!(dram.read(&(addr + 4), 32).unwrap(), 0);
+        assert!(elapsed < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_csrrw_mstatus_from_user_mode_is_illegal() {
+        let mut soft = SoftThread::default();
+        // csrrw x10, mstatus, x21
+        let program = vec![0b0011_0000 as u8, 0b0000_1010 as u8, 0b1001_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+        soft.priv_level = PrivilegeLevel::User;
+        soft.registers[Register::X21 as usize] = 500;
+
+        assert_eq!(soft.execute(), Err(Exception::Invalid(0x300a_9573)));
+        assert_eq!(soft.csr[CSR_MSTATUS as usize], 0);
+        assert_eq!(soft.registers[Register::X10 as usize], 0);
+    }
+
+    #[test]
+    fn test_csrrw_reserved_csr_is_illegal() {
+        let mut soft = SoftThread::default();
+        // csrrw x10, 0x40c, x21
+        let program = vec![0b0100_0000 as u8, 0b1100_1010 as u8, 0b1001_0101 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+
+        assert_eq!(soft.execute(), Err(Exception::Invalid(0x40ca_9573)));
+    }
+
+    #[test]
+    fn test_validate_csr_access() {
+        let soft = SoftThread::default();
+
+        assert!(soft.validate_csr_access(CSR_MSCRATCH, true, PrivilegeLevel::Machine).is_ok());
+        assert!(soft.validate_csr_access(CSR_MSCRATCH, false, PrivilegeLevel::Supervisor).is_err());
+        assert!(soft.validate_csr_access(0x140, true, PrivilegeLevel::Supervisor).is_ok());
+        assert!(soft.validate_csr_access(0xc00, false, PrivilegeLevel::User).is_ok());
+        assert!(soft.validate_csr_access(0xc00, true, PrivilegeLevel::Machine).is_err());
+        assert!(soft.validate_csr_access(0xf14, false, PrivilegeLevel::Machine).is_ok());
+        assert!(soft.validate_csr_access(0x7ff, false, PrivilegeLevel::Machine).is_err());
+    }
+
+    #[test]
+    fn test_get_set_csr() {
+        let mut soft = SoftThread::default();
+        soft.set_csr(CSR_MEPC, 0x8000_0000).unwrap();
+        assert_eq!(soft.get_csr(CSR_MEPC), Ok(0x8000_0000));
+
+        assert_eq!(soft.set_csr(CSR_MHARTID, 1), Err(Exception::Invalid(0)));
+        soft.priv_level = PrivilegeLevel::User;
+        assert_eq!(soft.get_csr(CSR_MEPC), Err(Exception::Invalid(0)));
+    }
+
+    #[test]
+    fn test_validate_program() {
+        let mut soft = SoftThread::default();
+        let program = [
+            0x63, 0x00, 0x00, 0x10, // beq zero, zero, 0x100
+            0x67, 0x80, 0x00, 0x00, // ret
+            0x13, 0x05, 0x15, 0x00, // addi a0, a0, 1
+            0x73, 0x25, 0xf0, 0x7f, // csrr a0, 0x7ff
+        ];
+        soft.load_image(&program, 0x1000).unwrap();
+
+        let warnings: Vec<(u64, ValidationKind)> = soft.validate_program().into_iter()
+            .map(|(pc, warning)| (pc, warning.kind))
+            .collect();
+        assert_eq!(warnings, vec![
+            (0x1000, ValidationKind::JumpOutOfRange),
+            (0x1008, ValidationKind::Unreachable),
+            (0x100c, ValidationKind::ReservedCsr),
+        ]);
+        assert_eq!(soft.validate_program()[0].1.to_string(), "0x1000: jump to 0x1100 outside of the code");
+    }
+
+    #[test]
+    fn test_branch_profiling_alternating() {
+        let mut soft = SoftThread::default();
+        let block = [
+            0x13, 0xf3, 0x12, 0x00, // andi t1, t0, 1
+            0x63, 0x04, 0x03, 0x00, // beqz t1, 8
+            0x13, 0x00, 0x00, 0x00, // nop
+            0x93, 0x82, 0x12, 0x00, // addi t0, t0, 1
+        ];
+        soft.load_image(&block.repeat(4), 0x1000).unwrap();
+        soft.enable_branch_profiling();
+        while soft.in_program() {
+            soft.execute().unwrap();
+        }
+
+        let stats = soft.measure_branch_accuracy();
+        assert_eq!((stats.total_branches, stats.taken, stats.not_taken), (4, 2, 2));
+        assert_eq!(stats.taken_rate(), 0.5);
+        assert_eq!(stats.by_type().collect::<Vec<_>>(), vec![(&BranchType::Beq, 0.5)]);
+    }
+
+    #[test]
+    fn test_exception_display() {
+        let cases = [
+            (Exception::AddressMisaligned, "instruction address misaligned (cause=0, tval=0x0)"),
+            (Exception::AccessFault, "instruction access fault (cause=1"),
+            (Exception::Invalid(0x300a_9573), "illegal instruction (cause=2, tval=0x300a9573)"),
+            (Exception::Breakpoint, "breakpoint (cause=3"),
+            (Exception::LoadAddressMisaligned, "load address misaligned (cause=4"),
+            (Exception::LoadAccessFault, "load access fault (cause=5"),
+            (Exception::StoreAMOAddressMisaligned, "store/AMO address misaligned (cause=6"),
+            (Exception::StoreAMOAccessFault, "store/AMO access fault (cause=7"),
+            (Exception::EnvironmentCallFromUMode, "environment call from U-mode (cause=8"),
+            (Exception::EnvironmentCallFromMMode, "environment call from M-mode (cause=11"),
+            (Exception::LoadPageFault(0x1234), "load page fault (cause=13, tval=0x1234)"),
+            (Exception::StackSizeExceeded, "program too large"),
+            (Exception::SanitizerError(SanitizerKind::WriteToUnallocated), "sanitizer error: WriteToUnallocated"),
+        ];
+        for (exception, prefix) in cases {
+            assert!(exception.to_string().starts_with(prefix), "{} does not start with {}", exception, prefix);
+        }
+
+        let boxed: Box<dyn std::error::Error> = Box::new(Exception::Breakpoint);
+        assert_eq!(boxed.to_string(), "breakpoint (cause=3, tval=0x0)");
+    }
+
+    #[test]
+    fn test_dump_csr() {
+        let mut soft = SoftThread::default();
+        soft.csr[CSR_MSTATUS as usize] = 0x1888;
+        soft.csr[CSR_MISA as usize] = (2 << 62) | (1 << 0) | (1 << 8) | (1 << 12);
+        soft.csr[0x7c0] = 0xabc;
+
+        let mstatus = soft.dump_csr(CSR_MSTATUS);
+        assert!(mstatus.starts_with("mstatus (0x300) = 0x0000000000001888 [MPP=3 (M), MPIE=1, MIE=1, SPP=0 (U),"), "{}", mstatus);
+        assert!(mstatus.ends_with("SD=0]"));
+        assert_eq!(soft.dump_csr(CSR_MISA), "misa (0x301) = 0x8000000000001101 [MXL=2, EXT=AIM]");
+        assert_eq!(soft.dump_csr(CSR_MEPC), "mepc (0x341) = 0x0000000000000000");
+        assert_eq!(soft.dump_csr(0x7c0), "csr[0x7c0] = 0x0000000000000abc");
+
+        let all = soft.dump_all_csrs();
+        assert_eq!(all.lines().count(), 3);
+        assert_eq!(soft.dump_csrs(true).lines().count(), 4096);
+    }
+
+    #[test]
+    fn test_set_privilege_mode() {
+        let mut soft = SoftThread::default();
+        // csrr a0, mstatus
+        soft.load_image(&[0x73, 0x25, 0x00, 0x30], 0x1000).unwrap();
+        soft.set_privilege_mode(PrivilegeLevel::User);
+
+        assert_eq!(soft.execute(), Err(Exception::Invalid(0x3000_2573)));
+        assert_eq!(soft.pc, 0x1000);
+    }
+
+    #[test]
+    fn test_cycle_model_predictable_loop() {
+        let mut soft = SoftThread::default();
+        let program = [
+            0x93, 0x02, 0x40, 0x06, // li t0, 100
+            0x93, 0x82, 0xf2, 0xff, // addi t0, t0, -1
+            0xe3, 0x9e, 0x02, 0xfe, // bnez t0, -4
+        ];
+        soft.load_image(&program, 0x1000).unwrap();
+        soft.enable_cycle_model(CycleAccurateModel::new());
+        while soft.in_program() {
+            soft.execute().unwrap();
+        }
+
+        // Only the first and the last branch are mispredicted.
+        let model = soft.timing.as_ref().unwrap();
+        assert_eq!(model.instructions, 201);
+        assert_eq!(model.cycle_count, 201 + 2 * BRANCH_PENALTY);
+        assert!(model.ipc() > 0.95 && model.ipc() < 1.0);
+    }
+
+    #[test]
+    fn test_cycle_model_load_latency() {
+        let mut soft = SoftThread::default();
+        // lw a0, 0(sp) twice
+        soft.load_image(&[0x03, 0x25, 0x01, 0x00].repeat(2), 0x1000).unwrap();
+        soft.registers[Register::X2 as usize] = 0x2000;
+        soft.enable_cycle_model(CycleAccurateModel::new());
+        soft.step_n(2).unwrap();
+
+        let model = soft.timing.as_ref().unwrap();
+        assert_eq!(model.cycle_count, 2 + DRAM_LATENCY + L1_LATENCY);
+    }
+
+    #[test]
+    fn test_watch_register() {
+        let mut soft = SoftThread::default();
+        let program = [
+            0x13, 0x05, 0x10, 0x00, // li a0, 1
+            0x93, 0x05, 0x70, 0x00, // li a1, 7
+            0x13, 0x05, 0x15, 0x00, // addi a0, a0, 1
+            0x13, 0x00, 0x00, 0x00, // nop
+            0x13, 0x15, 0x25, 0x00, // slli a0, a0, 2
+            0x13, 0x06, 0x30, 0x00, // li a2, 3
+            0x33, 0x05, 0xc5, 0x00, // add a0, a0, a2
+            0x13, 0x00, 0x00, 0x00, // nop
+            0x13, 0x05, 0x00, 0x00, // li a0, 0
+            0x13, 0x00, 0x00, 0x00, // nop
+        ];
+        soft.load_image(&program, 0x1000).unwrap();
+
+        let changes = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let log = changes.clone();
+        soft.watch_register(Register::X10, Box::new(move |old, new| log.borrow_mut().push((old, new))));
+        soft.step_n(10).unwrap();
+        assert_eq!(*changes.borrow(), vec![(0, 1), (1, 2), (2, 8), (8, 11), (11, 0)]);
+
+        soft.unwatch_register(Register::X10);
+        soft.load_image(&program, 0x1000).unwrap();
+        soft.step_n(10).unwrap();
+        assert_eq!(changes.borrow().len(), 5);
+    }
+
+    #[test]
+    fn test_run_until_halt_stops_at_self_jump() {
+        let mut soft = SoftThread::default();
+        // li a0, 5; j 0; nop
+        soft.load_image(&[0x13, 0x05, 0x50, 0x00, 0x6f, 0x00, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00], 0x1000).unwrap();
+        assert!(!soft.is_halted());
+
+        soft.run_until_halt().unwrap();
+        assert!(soft.is_halted());
+        assert_eq!(soft.pc, 0x1004);
+        assert_eq!(soft.registers[Register::X10 as usize], 5);
+    }
+
+    #[test]
+    fn test_halt_conditions() {
+        let mut soft = SoftThread::default();
+        let program = [
+            0xb7, 0x22, 0x00, 0x00, // lui t0, 2
+            0x13, 0x03, 0x10, 0x00, // li t1, 1
+            0x23, 0xb0, 0x62, 0x00, // sd t1, 0(t0)
+            0x73, 0x00, 0x10, 0x00, // ebreak
+            0x13, 0x00, 0x00, 0x00, // nop
+        ];
+        soft.load_image(&program, 0x1000).unwrap();
+        soft.tohost = Some(0x2000);
+        soft.run_until_halt().unwrap();
+        assert_eq!(soft.pc, 0x100c);
+
+        soft.tohost = None;
+        assert!(!soft.is_halted());
+        assert_eq!(soft.run_until_halt(), Ok(()));
+        assert_eq!(soft.pc, 0x100c);
+        assert!(soft.is_halted());
+
+        soft.load_image(&program, 0x1000).unwrap();
+        assert!(!soft.is_halted());
+        soft.halt();
+        assert!(soft.is_halted());
+    }
+
+    #[test]
+    fn test_fence_fields_are_decoded_and_traced() {
+        // fence rw,rw; fence.tso; fence i,o
+        let mut soft = SoftThread::default();
+        soft.load_image(&[0x0f, 0x00, 0x30, 0x03, 0x0f, 0x00, 0x30, 0x83, 0x0f, 0x00, 0x40, 0x08], 0).unwrap();
+        soft.enable_ring_trace::<4>();
+        for _ in 0..3 {
+            soft.execute().unwrap();
+        }
+
+        assert_eq!(soft.pc, 12);
+        let fields: Vec<(u32, u32, u32)> = soft.ring_trace().map(|entry| match entry.decoded {
+            Instruction::Fence { fm, pred, succ, .. } => (fm, pred, succ),
+            other => panic!("expected a fence, got {:?}", other),
+        }).collect();
+        assert_eq!(fields, vec![(0, 3, 3), (8, 3, 3), (0, 8, 4)]);
+    }
+
+    #[test]
+    fn test_fence_ordering() {
+        use std::sync::atomic::Ordering;
+
+        assert_eq!(fence_ordering(FENCE_FM_TSO, 3, 3), Some(Ordering::SeqCst));
+        assert_eq!(fence_ordering(0, 3, 3), Some(Ordering::SeqCst));
+        assert_eq!(fence_ordering(0, FENCE_R, 3), Some(Ordering::Acquire));
+        assert_eq!(fence_ordering(0, FENCE_W, FENCE_W), Some(Ordering::Release));
+        assert_eq!(fence_ordering(0, 3, FENCE_W), Some(Ordering::AcqRel));
+        assert_eq!(fence_ordering(0, 8, 4), None);
+    }
+
+    // Count a0 down from 10 to 0, one call per step, with the return address
+    // saved in a 16 byte frame.
+    // main: addi a0, x0, 10; addi t0, x0, 16; jalr ra, 0(t0); ebreak
+    // f: addi sp, sp, -16; sd ra, 0(sp); addi a0, a0, -1; beq a0, x0, 8
+    //    jalr ra, 0(t0); ld ra, 0(sp); addi sp, sp, 16; jalr x0, 0(ra)
+    const RECURSE: [u8; 48] = [
+        0x13, 0x05, 0xa0, 0x00, 0x93, 0x02, 0x00, 0x01, 0xe7, 0x80, 0x02, 0x00, 0x73, 0x00, 0x10, 0x00,
+        0x13, 0x01, 0x01, 0xff, 0x23, 0x30, 0x11, 0x00, 0x13, 0x05, 0xf5, 0xff, 0x63, 0x04, 0x05, 0x00,
+        0xe7, 0x80, 0x02, 0x00, 0x83, 0x30, 0x01, 0x00, 0x13, 0x01, 0x01, 0x01, 0x67, 0x80, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_call_stack_depth() {
+        let mut soft = SoftThread::default();
+        soft.load_image(&RECURSE, 0).unwrap();
+        soft.registers[Register::X2 as usize] = 0x10000;
+        soft.average_frame_size = 16;
+
+        let mut deepest = 0;
+        while soft.execute().is_ok() {
+            deepest = deepest.max(soft.call_stack_depth());
+        }
+        assert!(soft.is_halted());
+        assert_eq!(deepest, 10);
+        assert_eq!(soft.call_stack_depth(), 0);
+    }
+
+    #[test]
+    fn test_max_stack_depth_overflow() {
+        let mut soft = SoftThread::default();
+        soft.load_image(&RECURSE, 0).unwrap();
+        soft.registers[Register::X2 as usize] = 0x10000;
+        soft.average_frame_size = 16;
+        soft.set_max_stack_depth(4);
+
+        let result = loop {
+            if let Err(err) = soft.execute() {
+                break err;
+            }
+        };
+        assert_eq!(result, Exception::StackOverflow);
+        assert_eq!(soft.call_stack_depth(), 4);
+        assert_eq!(soft.registers[Register::X10 as usize], 6);
+
+        // The frame was not allocated, so retrying overflows again.
+        let pc = soft.pc;
+        assert_eq!(soft.execute(), Err(Exception::StackOverflow));
+        assert_eq!(soft.pc, pc);
+    }
+
+    #[test]
+    fn test_conditional_breakpoint() {
+        let mut soft = SoftThread::default();
+        // addi a0, a0, 1; blt a0, t1, -4
+        let code = [0x13, 0x05, 0x15, 0x00, 0xe3, 0x4e, 0x65, 0xfe];
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.registers[Register::X6 as usize] = 100;
+        soft.set_conditional_breakpoint(0x1004, Box::new(|soft| soft.registers[Register::X10 as usize] == 42));
+
+        let mut hits = vec![];
+        while soft.in_program() {
+            match soft.execute() {
+                Err(Exception::Breakpoint) => hits.push((soft.pc, soft.registers[Register::X10 as usize])),
+                result => result.unwrap(),
+            }
+        }
+        assert_eq!(hits, vec![(0x1004, 42)]);
+        assert_eq!(soft.registers[Register::X10 as usize], 100);
+        assert!(!soft.is_halted());
+
+        assert!(soft.remove_conditional_breakpoint(0x1004));
+        assert!(!soft.remove_conditional_breakpoint(0x1004));
+        assert_eq!(soft.fetch_at(0x1004), 0xfe65_4ee3);
+    }
+
+    #[test]
+    fn test_unaligned_access_modes() {
+        // lw a0, 3(x0); sw a1, 5(x0)
+        let code = [0x03, 0x25, 0x30, 0x00, 0xa3, 0x22, 0xb0, 0x00];
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.load_raw(0, &[0x00, 0x00, 0x00, 0x11, 0x22, 0x33, 0x84, 0x00]).unwrap();
+        soft.registers[Register::X11 as usize] = 0xaabb_ccdd;
+
+        soft.step_n(2).unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 0xffff_ffff_8433_2211);
+        let mut bytes = [0u8; 4];
+        soft.store_raw(5, &mut bytes).unwrap();
+        assert_eq!(bytes, [0xdd, 0xcc, 0xbb, 0xaa]);
+
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.emulate_unaligned_access(UnalignedMode::Trap);
+        assert_eq!(soft.execute(), Err(Exception::LoadAddressMisaligned));
+        assert_eq!(soft.pc, 0x1000);
+        soft.pc = 0x1004;
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOAddressMisaligned));
+    }
+
+    // A riscv-tests style check of test 2 that stores 1 to tohost at 0x2000
+    // on success, or (2 << 1) | 1 on failure, then spins.
+    fn tohost_program(b: u8) -> Vec<u8> {
+        vec![
+            0x93, 0x01, 0x20, 0x00, // addi gp, x0, 2
+            0x13, 0x05, 0x50, 0x00, // addi a0, x0, 5
+            0x93, 0x05, b << 4, 0x00, // addi a1, x0, b
+            0x63, 0x1a, 0xb5, 0x00, // bne a0, a1, fail
+            0x93, 0x02, 0x10, 0x00, // addi t0, x0, 1
+            0x37, 0x23, 0x00, 0x00, // lui t1, 2
+            0x23, 0x30, 0x53, 0x00, // sd t0, 0(t1)
+            0x6f, 0x00, 0x00, 0x00, // j 0
+            0x93, 0x92, 0x11, 0x00, // fail: slli t0, gp, 1
+            0x93, 0xe2, 0x12, 0x00, // ori t0, t0, 1
+            0x37, 0x23, 0x00, 0x00, // lui t1, 2
+            0x23, 0x30, 0x53, 0x00, // sd t0, 0(t1)
+            0x6f, 0x00, 0x00, 0x00, // j 0
+        ]
+    }
+
+    #[test]
+    fn test_run_until_tohost() {
+        let mut soft = SoftThread::default();
+        soft.load_image(&tohost_program(5), 0x1000).unwrap();
+        assert_eq!(soft.run_until_tohost(0x2000), Ok(1));
+        assert!(soft.is_halted());
+
+        let mut soft = SoftThread::default();
+        soft.load_image(&tohost_program(6), 0x1000).unwrap();
+        assert_eq!(soft.run_until_tohost(0x2000), Err(Exception::TestFailed(2)));
+    }
+
+    #[test]
+    fn test_print_trace_on_panic_reports_the_last_instructions() {
+        let mut soft = SoftThread::default();
+        // li a0, 1; addi a1, a0, 2; csrrw ra, cycle, sp
+        let code = [0x13, 0x05, 0x10, 0x00, 0x93, 0x05, 0x25, 0x00, 0xf3, 0x10, 0x01, 0xc0];
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.print_trace_on_panic(true);
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+        let exception = soft.execute().unwrap_err();
+        assert!(matches!(exception, Exception::Invalid(_)));
+
+        let report = soft.crash_report(&exception);
+        assert!(report.starts_with("trap at 0x1008: illegal instruction"));
+        assert!(report.contains("last 3 instructions:"));
+        assert!(report.contains("0x00001000: addi a0, zero, 1"));
+        assert!(report.contains("0x00001004: addi a1, a0, 2"));
+        assert!(report.contains("0x00001008: csrrw"));
+
+        soft.print_trace_on_panic(false);
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.execute().unwrap();
+        assert_eq!(trace::panic_trace(), "");
+    }
+
+    #[test]
+    fn test_from_assembly_matches_llvm_mc() {
+        // Encodings from `llvm-mc -triple=riscv64 -mattr=+m -show-encoding`.
+        let cases = [
+            ("lui a0, 0x12345", 0x1234_5537),
+            ("auipc t1, 0xfffff", 0xffff_f317),
+            ("jal ra, 2048", 0x0010_00ef),
+            ("jalr zero, 0(ra)", 0x0000_8067),
+            ("beq a0, a1, 16", 0x00b5_0863),
+            ("bne t0, zero, -8", 0xfe02_9ce3),
+            ("bltu s1, s2, 4094", 0x7f24_efe3),
+            ("bgeu a5, a4, -4096", 0x80e7_f063),
+            ("lb a0, -1(sp)", 0xfff1_0503),
+            ("lhu t2, 2047(gp)", 0x7ff1_d383),
+            ("lwu a3, 8(a2)", 0x0086_6683),
+            ("ld s0, -2048(s1)", 0x8004_b403),
+            ("sb a1, 3(a0)", 0x00b5_01a3),
+            ("sw a0, -8(sp)", 0xfea1_2c23),
+            ("sd ra, 24(sp)", 0x0011_3c23),
+            ("addi a0, a0, 1", 0x0015_0513),
+            ("sltiu t0, t1, -5", 0xffb3_3293),
+            ("xori a2, a2, 0x7ff", 0x7ff6_4613),
+            ("andi s3, s4, -1", 0xfffa_7993),
+            ("slli a0, a1, 63", 0x03f5_9513),
+            ("srai t0, t0, 33", 0x4212_d293),
+            ("sraiw a0, a0, 31", 0x41f5_551b),
+            ("sub a0, a1, a2", 0x40c5_8533),
+            ("sra t3, t4, t5", 0x41ee_de33),
+            ("sltu x5, x6, x7", 0x0073_32b3),
+            ("subw s5, s6, s7", 0x417b_0abb),
+            ("mulhsu a0, a1, a2", 0x02c5_a533),
+            ("remuw t6, t5, t4", 0x03df_7fbb),
+            ("fence rw, w", 0x0310_000f),
+            ("csrrs a0, mstatus, zero", 0x3000_2573),
+            ("csrrwi zero, 0x340, 31", 0x340f_d073),
+            ("ecall", 0x0000_0073),
+            ("ebreak", 0x0010_0073),
+            ("fence.tso", 0x8330_000f),
+        ];
+
+        for (asm, inst) in cases {
+            let instruction = Instruction::from_assembly(asm, 0).unwrap();
+            assert_eq!(instruction.encode(), Some(inst), "{}", asm);
+        }
+    }
+
+    #[test]
+    fn test_from_assembly_round_trips_disassembly() {
+        let instruction = Instruction::from_assembly("beq a0, a1, 0x1010", 0x1000).unwrap();
+        assert_eq!(instruction.encode(), Some(0x00b5_0863));
+        assert_eq!(disasm::disassemble(&instruction, 0x1000), "beq a0, a1, 0x1010");
+
+        let addi = Instruction::from_assembly("addi a0, a0, -3", 0).unwrap();
+        assert_eq!(Instruction::decode(addi.encode().unwrap(), &EncodingTable::default()), addi);
+    }
+
+    #[test]
+    fn test_from_assembly_errors() {
+        assert_eq!(Instruction::from_assembly("frob a0, a1", 0), Err(ParseError::UnknownMnemonic("frob".to_string())));
+        assert_eq!(Instruction::from_assembly("add a0, a1, q7", 0), Err(ParseError::InvalidRegister("q7".to_string())));
+        assert_eq!(Instruction::from_assembly("addi a0, a0, 2048", 0), Err(ParseError::ImmediateOutOfRange(2048)));
+        assert_eq!(Instruction::from_assembly("beq a0, a1, 3", 0), Err(ParseError::ImmediateOutOfRange(3)));
+        assert_eq!(Instruction::from_assembly("slliw a0, a0, 32", 0), Err(ParseError::ImmediateOutOfRange(32)));
+        assert_eq!(Instruction::from_assembly("sw a0, 8", 0), Err(ParseError::InvalidRegister("8".to_string())));
+        assert_eq!(Instruction::from_assembly("addi a0, a0, 1x", 0), Err(ParseError::InvalidImmediate("1x".to_string())));
+        assert_eq!(Instruction::from_assembly("sub a0, a1", 0), Err(ParseError::OperandCount(3, 2)));
+    }
+
+    #[test]
+    fn test_rdinstret_and_rdcycle_count_retired_instructions() {
+        let asm = ["csrrs a0, instret, zero", "addi t0, t0, 1", "csrrs a1, instret, zero", "csrrs a2, instret, zero",
+            "csrrs a3, 0xb00, zero"];
+        let code: Vec<u8> = asm.iter()
+            .flat_map(|line| Instruction::from_assembly(line, 0).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0x1000).unwrap();
+        (0..asm.len()).for_each(|_| soft.execute().unwrap());
+
+        let (a0, a1, a2) = (soft.registers[Register::X10 as usize], soft.registers[Register::X11 as usize],
+            soft.registers[Register::X12 as usize]);
+        assert!(a0 < a1 && a1 < a2);
+        assert_eq!((a0, a1, a2), (0, 2, 3));
+        assert_eq!(soft.registers[Register::X13 as usize], 4);
+        assert_eq!(soft.get_csr(0xb02).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_execute_until_pc() {
+        let addi = Instruction::from_assembly("addi a0, a0, 1", 0).unwrap().encode().unwrap();
+        let code: Vec<u8> = (0..32).flat_map(|_| addi.to_le_bytes()).collect();
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0x1000).unwrap();
+
+        assert_eq!(soft.execute_until_pc(0x1000 + 20 * 4, 100), Ok(20));
+        assert_eq!(soft.pc, 0x1050);
+        assert_eq!(soft.registers[Register::X10 as usize], 20);
+
+        assert_eq!(soft.execute_until_pc(0x1053, 100), Err(StepError::MisalignedTarget));
+        assert_eq!(soft.execute_until_pc(0x1070, 4), Err(StepError::StepLimitReached));
+        assert_eq!(soft.pc, 0x1060);
+    }
+
+    #[test]
+    fn test_quad_conversions() {
+        // fcvt.{s.q,q.s,d.q,q.d} fa0, fa1 with the rounding mode in bits 14:12
+        let fcvt = |func7: u32, rs2: u32, rm: u32| func7 << 25 | rs2 << 20 | 11 << 15 | rm << 12 | 10 << 7 | 0b1010011;
+        let run = |inst: u32, val: f64| {
+            let mut soft = SoftThread::default();
+            soft.f_registers[Register::X11 as usize] = val;
+            soft.execute_block(&[inst]).unwrap();
+            soft.f_registers[Register::X10 as usize]
+        };
+        let fine = 1.0 + 2f64.powi(-30);
+        let single = |inst: u32, val: f64| unbox_f32(run(inst, val));
+
+        assert_eq!(single(fcvt(0b0100000, 0b00011, RM_RNE), fine), 1.0);
+        assert_eq!(single(fcvt(0b0100000, 0b00011, RM_RUP), fine), 1.0 + 2f32.powi(-23));
+        assert_eq!(single(fcvt(0b0100000, 0b00011, RM_RTZ), -fine), -1.0);
+        assert_eq!(single(fcvt(0b0100000, 0b00011, RM_RDN), -fine), -1.0 - 2f32.powi(-23));
+        assert_eq!(single(fcvt(0b0100000, 0b00011, RM_RTZ), 1e300), f32::MAX);
+
+        // A single that isn't NaN-boxed reads as the canonical NaN.
+        assert_eq!(run(fcvt(0b0100011, 0b00000, RM_RNE), box_f32(1.5)), 1.5);
+        assert_eq!(run(fcvt(0b0100011, 0b00000, RM_RNE), 0.25).to_bits(), CANONICAL_NAN);
+
+        assert_eq!(run(fcvt(0b0100001, 0b00011, RM_RNE), fine), fine);
+        assert_eq!(run(fcvt(0b0100011, 0b00001, RM_RNE), fine), fine);
+    }
+
+    #[test]
+    fn test_memory_map_lists_regions_and_gaps() {
+        let mut soft = SoftThread::default();
+        soft.add_region(MemoryRegion::new(0x8000_0000, 0x800_0000, AccessFlags::ALL).with_name("DRAM"));
+        soft.add_region(MemoryRegion::new(0x1000_0000, 0x100, AccessFlags::READ | AccessFlags::WRITE).with_name("UART"));
+        soft.add_region(MemoryRegion::new(0x0200_0000, 0x1_0000, AccessFlags::READ | AccessFlags::WRITE).with_name("CLINT"));
+
+        let map = soft.memory_map();
+        let lines: Vec<String> = map.iter().map(|entry| entry.to_string()).collect();
+        assert_eq!(lines, [
+            "0x02000000 - 0x02010000 CLINT          [RW-]",
+            "0x02010000 - 0x10000000 <unmapped>     [---]",
+            "0x10000000 - 0x10000100 UART           [RW-]",
+            "0x10000100 - 0x80000000 <unmapped>     [---]",
+            "0x80000000 - 0x88000000 DRAM           [RWX]",
+        ]);
+        assert_eq!(map[1].flags, AccessFlags::NONE);
+
+        soft.mprotect(0x8000_0000, 0x1000, AccessFlags::READ).unwrap();
+        assert_eq!(soft.memory_map()[4].name, "DRAM");
+        assert_eq!(soft.memory_map()[4].end, 0x8000_1000);
+    }
+
+    #[test]
+    fn test_csrw_writes_without_reading() {
+        let asm = ["csrrw zero, mstatus, a0", "csrrs a1, mstatus, zero", "csrrwi zero, mscratch, 5", "csrrs a2, mscratch, zero"];
+        let code: Vec<u8> = asm.iter()
+            .flat_map(|line| Instruction::from_assembly(line, 0).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.registers[Register::X10 as usize] = 0x1888;
+        soft.registers[Register::X11 as usize] = 7;
+        (0..asm.len()).for_each(|_| soft.execute().unwrap());
+
+        assert_eq!(soft.get_csr(CSR_MSTATUS).unwrap(), 0x1888);
+        assert_eq!(soft.registers[Register::X11 as usize], 0x1888);
+        assert_eq!(soft.registers[Register::X12 as usize], 5);
+        assert_eq!(soft.registers[Register::X0 as usize], 0);
+    }
+
+    // A thread with `program` assembled and loaded at 0, where pc starts.
+    fn soft_with_asm(program: &[&str]) -> SoftThread<u64, f64, Dram> {
+        let code: Vec<u8> = program.iter().enumerate()
+            .flat_map(|(idx, asm)| Instruction::from_assembly(asm, 4 * idx as u64).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0).unwrap();
+        soft
+    }
+
+    // A log for a hook to push to, and the handle the hook moves.
+    fn recorder<T>() -> (std::rc::Rc<std::cell::RefCell<Vec<T>>>, std::rc::Rc<std::cell::RefCell<Vec<T>>>) {
+        let log = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        (log.clone(), log)
+    }
+
+    #[test]
+    fn test_page_fault_handler_fills_demand_paged_region() {
+        let program = ["lui a1, 0x8", "ld a0, 8(a1)", "addi a0, a0, 1", "sd a0, 16(a1)"];
+        let mut soft = soft_with_asm(&program);
+        soft.unmap_page(0x8000);
+
+        let (faults, seen) = recorder();
+        soft.page_fault_handler(Box::new(move |addr, access, _| {
+            seen.borrow_mut().push((addr, access));
+            let mut page = vec![0u8; 16];
+            page[8..].copy_from_slice(&41u64.to_le_bytes());
+            PageFaultAction::Fill(page)
+        }));
+
+        for _ in 0..program.len() {
+            soft.execute().unwrap();
+        }
+
+        assert_eq!(*faults.borrow(), vec![(0x8008, AccessType::Load)]);
+        assert_eq!(soft.registers[Register::X10 as usize], 42);
+        assert_eq!(soft.bus.read(&0x8010, 64).unwrap(), 42);
+        assert_eq!(soft.pc, 16);
+    }
+
+    #[test]
+    fn test_page_fault_handler_retry_and_trap() {
+        let mut soft = soft_with_asm(&["sd a0, 0(a1)"]);
+        soft.registers[Register::X11 as usize] = 0x1000;
+        soft.unmap_page(0x1000);
+        soft.page_fault_handler(Box::new(|_, _, _| PageFaultAction::Trap));
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOPageFault(0x1000)));
+        assert_eq!(soft.pc, 0);
+
+        soft.page_fault_handler(Box::new(|addr, _, soft| {
+            soft.map_page(addr);
+            PageFaultAction::Retry
+        }));
+        soft.registers[Register::X10 as usize] = 7;
+        soft.execute().unwrap();
+        assert_eq!(soft.bus.read(&0x1000, 64).unwrap(), 7);
+        assert_eq!(soft.pc, 4);
+
+        // A retry without mapping the page returns the second fault.
+        soft.pc = 0;
+        soft.unmap_page(0x1000);
+        let (faults, seen) = recorder();
+        soft.page_fault_handler(Box::new(move |addr, _, _| {
+            seen.borrow_mut().push(addr);
+            PageFaultAction::Retry
+        }));
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOPageFault(0x1000)));
+        assert_eq!(*faults.borrow(), vec![0x1000]);
+        assert_eq!(soft.pc, 0);
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        let mut soft = SoftThread::default();
+        let mut out = vec![];
+        assert!(soft.coverage_report(&mut out).is_err());
+
+        let program = ["addi a0, a0, 1", "addi a0, a0, 1", "add a1, a0, a0", "addi a0, a0, 1"];
+        let code: Vec<u8> = program.iter()
+            .flat_map(|asm| Instruction::from_assembly(asm, 0).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+        soft.load_image(&code, 0).unwrap();
+        soft.enable_instruction_histogram();
+        for _ in 0..program.len() {
+            soft.execute().unwrap();
+        }
+
+        soft.coverage_report(&mut out).unwrap();
+        let report = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(&lines[..4], ["| Instruction | Count | % |", "|---|---:|---:|", "| addi | 3 | 75.0% |", "| add | 1 | 25.0% |"]);
+        assert!(lines.contains(&"| fclass.s | 0 | 0.0% |"));
+        assert_eq!(lines.len(), 2 + 272 + 2);
+        assert_eq!(lines.last(), Some(&"Total: 2 / 272 instruction types executed (0.7%)"));
+    }
+
+    #[test]
+    fn test_nop_sled_detection() {
+        let addi = Instruction::from_assembly("addi a0, a0, 1", 0).unwrap().encode().unwrap();
+        let mut code = vec![addi, NOP, NOP, addi];
+        code.extend([NOP; 100]);
+        code.push(addi);
+        let bytes: Vec<u8> = code.iter().flat_map(|inst| inst.to_le_bytes()).collect();
+
+        let mut soft = SoftThread::default();
+        soft.load_image(&bytes, 0x1000).unwrap();
+        soft.emulate_nop_sled_detection(50);
+        for _ in 0..54 {
+            soft.execute().unwrap();
+        }
+
+        assert_eq!(soft.execute(), Err(Exception::NopSledDetected { start_pc: 0x1010 }));
+        assert_eq!(soft.pc, 0x1010 + 50 * 4);
+        assert_eq!(soft.registers[Register::X10 as usize], 2);
+    }
+
+    #[test]
+    fn test_step_back_restores_initial_state() {
+        let program = [
+            "addi a0, zero, 5", "addi a1, zero, -3", "mul a2, a0, a1", "slli a3, a2, 4", "sub a4, a3, a0",
+            "csrrw a5, mscratch, a4", "sltu t0, a1, a0", "jal ra, 0x1020", "xori t1, t0, 7", "divu t2, a1, a0",
+        ];
+        let code: Vec<u8> = program.iter().enumerate()
+            .flat_map(|(idx, asm)| Instruction::from_assembly(asm, 0x1000 + 4 * idx as u64).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.registers[Register::X15 as usize] = 0xdead;
+        soft.enable_step_history(history::HISTORY_DEPTH);
+        let (registers, csr, pc) = (soft.registers, soft.csr, soft.pc);
+
+        for _ in 0..10 {
+            soft.execute().unwrap();
+        }
+        assert_ne!(soft.registers, registers);
+
+        for _ in 0..10 {
+            assert!(soft.step_back());
+        }
+        assert!(!soft.step_back());
+        assert_eq!(soft.registers, registers);
+        assert_eq!(soft.csr, csr);
+        assert_eq!(soft.pc, pc);
+    }
+
+    #[test]
+    fn test_step_history_depth() {
+        let mut soft = SoftThread::default();
+        let code = Instruction::from_assembly("addi a0, a0, 1", 0).unwrap().encode().unwrap();
+        soft.load_image(&code.to_le_bytes().repeat(8), 0).unwrap();
+        assert!(!soft.step_back());
+
+        soft.enable_step_history(3);
+        for _ in 0..8 {
+            soft.execute().unwrap();
+        }
+        for _ in 0..3 {
+            assert!(soft.step_back());
+        }
+        assert!(!soft.step_back());
+        assert_eq!(soft.registers[Register::X10 as usize], 5);
+        assert_eq!(soft.pc, 20);
+    }
+
+    #[test]
+    fn test_register_ecall_handler() {
+        let mut soft = soft_with_asm(&["addi a7, zero, 999", "addi a0, zero, 40", "ecall", "addi a0, a0, 1", "addi a7, zero, 998", "ecall"]);
+        soft.register_ecall_handler(999, Box::new(|soft| EcallResult::ok(soft.registers[Register::X10 as usize] as i64 + 2)));
+        for _ in 0..5 {
+            soft.execute().unwrap();
+        }
+        assert_eq!(soft.registers[Register::X10 as usize], 43);
+
+        // Syscalls without a handler are still left to the host.
+        assert_eq!(soft.execute(), Err(Exception::EnvironmentCallFromMMode));
+        assert_eq!(soft.ecall(), EcallResult::err(Errno(linux::ENOSYS)));
+        assert_eq!(soft.registers[Register::X10 as usize] as i64, -linux::ENOSYS);
+
+        soft.register_ecall_handler(998, Box::new(|_| EcallResult::err(Errno(linux::EBADF))));
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize] as i64, -linux::EBADF);
+        assert_eq!(soft.pc, 24);
+    }
+
+    #[test]
+    fn test_set_syscall_handler() {
+        let mut soft = soft_with_asm(&[
+            "addi a7, zero, 64", "addi a0, zero, 1", "addi a2, zero, 5", "ecall", "addi a7, zero, 93", "ecall",
+        ]);
+        let (calls, seen) = recorder();
+        soft.set_syscall_handler(Box::new(move |soft, number| {
+            let args: Vec<u64> = (10..16).map(|reg| soft.registers[reg]).collect();
+            seen.borrow_mut().push((number, args[0], args[2]));
+            match number {
+                93 => Err(Exception::General),
+                _ => {
+                    soft.registers[Register::X10 as usize] = args[2];
+                    Ok(())
+                },
+            }
+        }));
+
+        for _ in 0..5 {
+            soft.execute().unwrap();
+        }
+        assert_eq!(soft.registers[Register::X10 as usize], 5);
+        assert_eq!(soft.execute(), Err(Exception::General));
+        assert_eq!(soft.pc, 20);
+        assert_eq!(*calls.borrow(), vec![(64, 1, 5), (93, 5, 5)]);
+    }
+
+    #[test]
+    fn test_attach_debugger_intercepts_ebreak() {
+        let mut soft = soft_with_asm(&["addi a0, zero, 42", "ebreak", "addi a1, zero, 1", "ebreak"]);
+        soft.execute().unwrap();
+        assert_eq!(soft.execute(), Err(Exception::Breakpoint));
+        assert_eq!(soft.pc, 4);
+
+        let (exit_codes, seen) = recorder();
+        soft.attach_debugger(Box::new(move |soft| {
+            seen.borrow_mut().push(soft.registers[Register::X10 as usize]);
+            soft.registers[Register::X10 as usize] = 0;
+            if soft.pc == 12 {
+                soft.pc = 0x100;
+            }
+        }));
+        for _ in 0..3 {
+            soft.execute().unwrap();
+        }
+
+        assert_eq!(*exit_codes.borrow(), vec![42, 0]);
+        assert_eq!(soft.registers[Register::X11 as usize], 1);
+        assert_eq!(soft.pc, 0x100);
+    }
+
+    #[test]
+    fn test_memory_barrier_hook() {
+        let mut soft = soft_with_asm(&["fence rw, w", "fence.tso", "fence"]);
+        assert_eq!(soft.bus.read(&0, 32).unwrap(), 0x0310_000f);
+        let (fences, seen) = recorder();
+        soft.set_memory_barrier_hook(Box::new(move |fm, pred, succ| seen.borrow_mut().push((fm, pred, succ))));
+        for _ in 0..3 {
+            soft.execute().unwrap();
+        }
+
+        assert_eq!(*fences.borrow(), vec![(0, 0b0011, 0b0001), (0b1000, 0b0011, 0b0011), (0, 0b1111, 0b1111)]);
+        assert_eq!(soft.pc, 12);
+    }
+
+    #[test]
+    fn test_fence_i_hook() {
+        let mut soft = soft_with_asm(&["fence.i", "fence.i"]);
+        soft.execute().unwrap();
+        assert_eq!(soft.pc, 4);
+
+        let (flushes, seen) = recorder();
+        soft.set_fence_i_hook(Box::new(move |pc| seen.borrow_mut().push(pc)));
+        soft.execute().unwrap();
+        assert_eq!(*flushes.borrow(), vec![4]);
+        assert_eq!(soft.pc, 8);
+    }
+
+    #[test]
+    fn test_step_outcomes() {
+        let program = ["addi a0, zero, 1", "bne a0, a0, 0x100", "beq a0, a0, 0x10", "addi a0, zero, 2", "ebreak", "jal zero, 0x14"];
+        let code: Vec<u8> = program.iter().enumerate()
+            .flat_map(|(idx, asm)| Instruction::from_assembly(asm, 4 * idx as u64).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0).unwrap();
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.step(), Ok(StepOutcome::BranchTaken(0x10)));
+        assert_eq!(soft.step(), Ok(StepOutcome::Breakpoint));
+        assert_eq!(soft.execute(), Err(Exception::Breakpoint));
+        assert_eq!(soft.registers[Register::X10 as usize], 1);
+
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0).unwrap();
+        soft.pc = 0x14;
+        assert_eq!(soft.step(), Ok(StepOutcome::Halted));
+        assert_eq!(soft.pc, 0x14);
+    }
+
+    #[test]
+    fn test_step_checks_tohost_after_stores() {
+        let mut soft = soft_with_asm(&["sd zero, 0(a1)", "sw a0, 4(a1)", "sd a0, 8(a1)", "sd a0, 0(a1)"]);
+        soft.tohost = Some(0x100);
+        soft.registers[Register::X10 as usize] = 1;
+        soft.registers[Register::X11 as usize] = 0x100;
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.step(), Ok(StepOutcome::Halted));
+
+        soft.bus.write(0x100, 0, 64).unwrap();
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.step(), Ok(StepOutcome::Halted));
+    }
+
+    #[test]
+    fn test_execution_trace() {
+        let addi = Instruction::from_assembly("addi a0, a0, 1", 0).unwrap().encode().unwrap();
+        let mut soft = SoftThread::default();
+        soft.load_image(&addi.to_le_bytes().repeat(6), 0).unwrap();
+        assert!(soft.take_trace().is_empty());
+
+        soft.enable_trace(4);
+        for _ in 0..6 {
+            soft.execute().unwrap();
+        }
+
+        let trace = soft.take_trace();
+        let pcs: Vec<u64> = trace.iter().map(|event| event.pc).collect();
+        assert_eq!(pcs, vec![8, 12, 16, 20]);
+        let events: Vec<ExecutionEvent> = trace.into_iter().collect();
+        assert_eq!(events[0].raw_inst, addi);
+        assert_eq!(events[0].registers_before[Register::X10 as usize], 2);
+        assert_eq!(events[3].registers_before[Register::X10 as usize], 5);
+        assert!(soft.take_trace().is_empty());
+
+        // A breakpoint that stops the hart before the instruction records
+        // nothing, and the instruction is recorded once it resumes.
+        soft.pc = 0;
+        soft.set_conditional_breakpoint(0, Box::new(|_| true));
+        assert_eq!(soft.execute(), Err(Exception::Breakpoint));
+        assert!(soft.take_trace().is_empty());
+        soft.execute().unwrap();
+        let events: Vec<ExecutionEvent> = soft.take_trace().into_iter().collect();
+        assert_eq!(events.iter().map(|event| (event.pc, event.raw_inst)).collect::<Vec<_>>(), [(0, addi)]);
+        soft.remove_conditional_breakpoint(0);
+
+        soft.disable_trace();
+        soft.pc = 0;
+        soft.execute().unwrap();
+        assert!(soft.take_trace().is_empty());
+    }
+
+    #[test]
+    fn test_pc_breakpoints() {
+        let addi = Instruction::from_assembly("addi a0, a0, 1", 0).unwrap().encode().unwrap();
+        let code = addi.to_le_bytes().repeat(4);
+        let mut soft = SoftThread::default();
+        soft.load_image(&code, 0).unwrap();
+        let id = soft.set_breakpoint(0x4);
+        assert_eq!(soft.set_breakpoint(0x4), id);
+        let other = soft.set_breakpoint(0x8);
+        assert_ne!(other, id);
+
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.step(), Ok(StepOutcome::Breakpoint));
+        assert_eq!(soft.pc, 0x4);
+        assert_eq!(soft.registers[Register::X10 as usize], 1);
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.registers[Register::X10 as usize], 2);
+
+        assert!(soft.clear_breakpoint(other));
+        assert!(!soft.clear_breakpoint(other));
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.pc, 0xc);
+
+        soft.clear_all_breakpoints();
+        soft.pc = 0;
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+    }
+
+    #[test]
+    fn test_pc_breakpoints_survive_load_program() {
+        let addi = Instruction::from_assembly("addi a0, a0, 1", 0).unwrap().encode().unwrap();
+        let mut soft = SoftThread::default();
+        soft.set_breakpoint(0);
+        soft.load_program(addi.to_be_bytes().repeat(2).to_vec()).unwrap();
+        assert_eq!(soft.step(), Ok(StepOutcome::Breakpoint));
+        assert_eq!(soft.registers[Register::X10 as usize], 0);
+    }
+
+    #[test]
+    fn test_register_snapshot() {
+        let addi = Instruction::from_assembly("addi a0, a0, 5", 0).unwrap().encode().unwrap();
+        let mut soft = SoftThread::default();
+        soft.load_image(&addi.to_le_bytes(), 0).unwrap();
+        let before = soft.snapshot();
+        soft.execute().unwrap();
+        let after = soft.snapshot();
+
+        assert_eq!(before.diff(&after), vec![(Register::X10 as usize, 0, 5)]);
+        assert_eq!(after.pc, 4);
+        let table = after.to_string();
+        assert!(table.contains("a0    0x0000000000000005\n"));
+        assert!(table.ends_with("pc    0x0000000000000004\n"));
+
+        soft.restore_snapshot(&before);
+        assert_eq!(soft.snapshot(), before);
+    }
+
+    #[test]
+    fn test_dram_snapshot_restore_region() {
+        let mut dram = Dram::new();
+        dram.mem[0x100..0x104].copy_from_slice(&[1, 2, 3, 4]);
+        let saved = dram.snapshot_region(0x100, 4).unwrap();
+        assert_eq!(saved, vec![1, 2, 3, 4]);
+
+        dram.mem[0x100..0x108].fill(0xff);
+        dram.restore_region(0x100, &saved).unwrap();
+        assert_eq!(&dram.mem[0x100..0x108], &[1, 2, 3, 4, 0xff, 0xff, 0xff, 0xff]);
+
+        assert_eq!(dram.snapshot_region(memory::MEM_SIZE - 2, 4), Err(Exception::StoreAMOAccessFault));
+        assert_eq!(dram.snapshot_region(u64::MAX, 2), Err(Exception::StoreAMOAccessFault));
+        assert_eq!(dram.restore_region(memory::MEM_SIZE, &[0]), Err(Exception::StoreAMOAccessFault));
+    }
+
+    #[test]
+    fn test_clint_sets_mtip() {
+        let asm = ["lui t0, 0x2004", "addi t1, zero, 6", "sd t1, 0(t0)", "lui t2, 0x200c", "addi t2, t2, -8", "ld a0, 0(t2)"];
+        let code: Vec<u8> = asm.iter().enumerate()
+            .flat_map(|(idx, asm)| Instruction::from_assembly(asm, idx as u64 * 4).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+        let mut soft = SoftThread::default();
+        let idx = soft.attach_clint(2);
+        soft.load_image(&code, 0).unwrap();
+        let mtip = InterruptCause::MachineTimer.mip_bit();
+
+        for _ in 0..4 {
+            soft.execute().unwrap();
+        }
+        let clint = soft.mmio.get_mut::<Clint>(idx).unwrap().clone();
+        assert_eq!(clint.mtimecmp[0], 6);
+        assert_eq!(clint.mtime, 8);
+        assert_eq!(soft.csr[CSR_MIP as usize] & mtip, mtip);
+
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 12);
+    }
+
+    // Map 0x4000_3000 to 0x20000 and the 2 MB megapage at 0x4020_0000 to
+    // 0x200000 through a table rooted at 0x10000, returning satp.
+    fn sv39_tables(dram: &mut Dram) -> u64 {
+        let leaf = PTE_V | PTE_R | PTE_W | PTE_A | PTE_D;
+        dram.write(0x10000 + 8, (0x11 << PTE_PPN_SHIFT) | PTE_V, 64).unwrap();
+        dram.write(0x11000, (0x12 << PTE_PPN_SHIFT) | PTE_V, 64).unwrap();
+        dram.write(0x11000 + 8, (0x200 << PTE_PPN_SHIFT) | leaf, 64).unwrap();
+        dram.write(0x12000 + 3 * 8, (0x20 << PTE_PPN_SHIFT) | leaf, 64).unwrap();
+        SATP_MODE_SV39 << 60 | 0x10
+    }
+
+    #[test]
+    fn test_sv39_translate() {
+        let mut dram = Dram::new();
+        let mut mmu = Sv39Mmu::new(sv39_tables(&mut dram));
+        assert!(mmu.enabled());
+        assert_eq!(mmu.translate(0x4000_3abc, AccessType::Store, &dram), Ok(0x20abc));
+        assert_eq!(mmu.translate(0x4021_2345, AccessType::Load, &dram), Ok(0x21_2345));
+        assert_eq!(mmu.translate(0x4000_3000, AccessType::Instruction, &dram), Err(Exception::InstructionPageFault(0x4000_3000)));
+        assert_eq!(mmu.translate(0x4000_4000, AccessType::Load, &dram), Err(Exception::LoadPageFault(0x4000_4000)));
+        assert_eq!(mmu.translate(1 << 40, AccessType::Load, &dram), Err(Exception::LoadPageFault(1 << 40)));
+
+        mmu.user = true;
+        assert_eq!(mmu.translate(0x4000_3000, AccessType::Load, &dram), Err(Exception::LoadPageFault(0x4000_3000)));
+    }
+
+    #[test]
+    fn test_sv39_loads_and_sfence_vma() {
+        let ld = Instruction::from_assembly("ld a0, 0(t0)", 0).unwrap().encode().unwrap();
+        let sfence = Instruction::from_assembly("sfence.vma", 4).unwrap().encode().unwrap();
+        assert_eq!(sfence, 0x1200_0073);
+        assert_eq!(Instruction::from_assembly("sfence.vma a0, a1", 0).unwrap().encode().unwrap(), 0x12b5_0073);
+        let code: Vec<u8> = [ld, sfence, ld].iter().flat_map(|inst| inst.to_be_bytes()).collect();
+
+        let mut soft = SoftThread::default();
+        soft.load_program(code).unwrap();
+        soft.csr[CSR_SATP as usize] = sv39_tables(&mut soft.bus);
+        soft.priv_level = PrivilegeLevel::Supervisor;
+        soft.bus.write(0x20008, 0x1111, 64).unwrap();
+        soft.bus.write(0x21008, 0x2222, 64).unwrap();
+        soft.registers[Register::X5 as usize] = 0x4000_3008;
+
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 0x1111);
+        // The TLB keeps the old translation until sfence.vma.
+        soft.bus.write(0x12000 + 3 * 8, (0x21 << PTE_PPN_SHIFT) | PTE_V | PTE_R | PTE_A, 64).unwrap();
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 0x2222);
+    }
+
+    #[test]
+    fn test_sv39_pages_can_map_devices() {
+        let code: Vec<u8> = ["lbu a1, 5(t0)", "lbu a2, 0(t1)"].iter().enumerate()
+            .flat_map(|(idx, asm)| Instruction::from_assembly(asm, 4 * idx as u64).unwrap().encode().unwrap().to_be_bytes())
+            .collect();
+        let mut soft = SoftThread::default();
+        soft.load_program(code).unwrap();
+        soft.mmio.attach(Uart16550::new());
+        soft.csr[CSR_SATP as usize] = sv39_tables(&mut soft.bus);
+        soft.priv_level = PrivilegeLevel::Supervisor;
+        // 0x4000_5000 maps the UART and 0x4000_6000 a page nothing backs.
+        let leaf = PTE_V | PTE_R | PTE_A;
+        soft.bus.write(0x12000 + 5 * 8, (UART_BASE >> PAGE_SHIFT) << PTE_PPN_SHIFT | leaf, 64).unwrap();
+        soft.bus.write(0x12000 + 6 * 8, (1 << 30) << PTE_PPN_SHIFT | leaf, 64).unwrap();
+        soft.registers[Register::X5 as usize] = 0x4000_5000;
+        soft.registers[Register::X6 as usize] = 0x4000_6000;
+
+        let mut mmu = Sv39Mmu::new(soft.csr[CSR_SATP as usize]);
+        assert_eq!(mmu.translate(0x4000_5005, AccessType::Load, &soft.bus), Ok(UART_BASE + 5));
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X11 as usize], UART_LSR_THRE);
+        assert_eq!(soft.execute(), Err(Exception::LoadAccessFault));
+
+        // A page table outside DRAM is still an access fault.
+        mmu = Sv39Mmu::new(SATP_MODE_SV39 << 60 | 1 << 40);
+        assert_eq!(mmu.translate(0x4000_5000, AccessType::Load, &soft.bus), Err(Exception::LoadAccessFault));
+    }
+
+    #[test]
+    fn test_mret_and_sret_restore_privilege() {
+        let mret = Instruction::from_assembly("mret", 0).unwrap().encode().unwrap();
+        let sret = Instruction::from_assembly("sret", 0).unwrap().encode().unwrap();
+        assert_eq!((mret, sret), (0x3020_0073, 0x1020_0073));
+        let mut soft = SoftThread::default();
+        soft.load_image(&mret.to_le_bytes(), 0x100).unwrap();
+        soft.load_image(&sret.to_le_bytes(), 0x200).unwrap();
+        soft.pc = 0x100;
+        soft.csr[CSR_MEPC as usize] = 0x200;
+        soft.csr[CSR_SEPC as usize] = 0x300;
+        soft.csr[CSR_MSTATUS as usize] = 1 << MSTATUS_MPP_SHIFT | MSTATUS_MPIE | MSTATUS_SPIE;
+
+        soft.execute().unwrap();
+        assert_eq!(soft.priv_level, PrivilegeLevel::Supervisor);
+        assert_eq!(soft.pc, 0x200);
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] & (MSTATUS_MPP | MSTATUS_MIE | MSTATUS_MPIE), MSTATUS_MIE | MSTATUS_MPIE);
+
+        soft.execute().unwrap();
+        assert_eq!(soft.priv_level, PrivilegeLevel::User);
+        assert_eq!(soft.pc, 0x300);
+        assert_eq!(soft.csr[CSR_MSTATUS as usize] & (MSTATUS_SPP | MSTATUS_SIE | MSTATUS_SPIE), MSTATUS_SIE | MSTATUS_SPIE);
+
+        soft.pc = 0x200;
+        assert_eq!(soft.execute(), Err(Exception::Invalid(sret as u64)));
+    }
+
+    #[test]
+    fn test_mret_outside_machine_mode() {
+        let mret = Instruction::from_assembly("mret", 0).unwrap().encode().unwrap();
+        let mut soft = SoftThread::default();
+        soft.load_image(&mret.to_le_bytes(), 0x100).unwrap();
+        soft.csr[CSR_MEPC as usize] = 0x200;
+        soft.csr[CSR_MSTATUS as usize] = 1 << MSTATUS_MPP_SHIFT | MSTATUS_MPRV;
+        soft.priv_level = PrivilegeLevel::Supervisor;
+        assert_eq!(soft.execute(), Err(Exception::Invalid(mret as u64)));
+        assert_eq!(soft.pc, 0x100);
+
+        // Returning to S-mode clears MPRV.
+        soft.priv_level = PrivilegeLevel::Machine;
+        soft.execute().unwrap();
+        assert_eq!((soft.pc, soft.priv_level), (0x200, PrivilegeLevel::Supervisor));
+        assert!(!soft.mstatus().mprv());
+    }
+
+    #[test]
+    fn test_sret_traps_when_tsr_is_set() {
+        let sret = Instruction::from_assembly("sret", 0).unwrap().encode().unwrap();
+        let mut soft = SoftThread::default();
+        soft.load_image(&sret.to_le_bytes(), 0x100).unwrap();
+        soft.csr[CSR_SEPC as usize] = 0x200;
+        soft.csr[CSR_MSTATUS as usize] = MSTATUS_TSR;
+        soft.priv_level = PrivilegeLevel::Supervisor;
+        assert_eq!(soft.execute(), Err(Exception::Invalid(sret as u64)));
+        assert_eq!(soft.pc, 0x100);
+
+        // M-mode may still sret with TSR set.
+        soft.priv_level = PrivilegeLevel::Machine;
+        soft.execute().unwrap();
+        assert_eq!(soft.pc, 0x200);
+        assert_eq!(soft.priv_level, PrivilegeLevel::User);
+    }
+
+    #[test]
+    fn test_wfi_stalls_until_an_interrupt() {
+        let wfi = Instruction::from_assembly("wfi", 0).unwrap().encode().unwrap();
+        assert_eq!(wfi, 0x1050_0073);
+        let mut soft = SoftThread::default();
+        soft.load_image(&wfi.to_le_bytes(), 0x100).unwrap();
+        let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        soft.set_wfi_hook(Box::new(move || counter.set(counter.get() + 1)));
+
+        assert_eq!(soft.step(), Ok(StepOutcome::WaitForInterrupt));
+        assert_eq!(soft.step(), Ok(StepOutcome::WaitForInterrupt));
+        assert_eq!((soft.pc, calls.get()), (0x100, 2));
+
+        // A pending, enabled timer interrupt wakes the hart even with
+        // mstatus.MIE clear, and wfi then acts as a nop.
+        soft.csr[CSR_MIE as usize] = InterruptCause::MachineTimer.mip_bit();
+        soft.csr[CSR_MIP as usize] = InterruptCause::MachineTimer.mip_bit();
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!((soft.pc, calls.get()), (0x104, 2));
+    }
+
+    #[test]
+    fn test_interrupt_during_wfi_returns_past_it() {
+        let wfi = Instruction::from_assembly("wfi", 0).unwrap().encode().unwrap();
+        let mut soft = SoftThread::default();
+        soft.load_image(&wfi.to_le_bytes(), 0x100).unwrap();
+        soft.csr[CSR_MTVEC as usize] = 0x400;
+        soft.csr[CSR_MSTATUS as usize] = MSTATUS_MIE;
+        assert_eq!(soft.step(), Ok(StepOutcome::WaitForInterrupt));
+
+        soft.csr[CSR_MIE as usize] = InterruptCause::MachineSoftware.mip_bit();
+        soft.csr[CSR_MIP as usize] = InterruptCause::MachineSoftware.mip_bit();
+        soft.step().unwrap();
+        assert_eq!(soft.pc, 0x400);
+        assert_eq!(soft.csr[CSR_MEPC as usize], 0x104);
+    }
+
+    #[test]
+    fn test_wfi_stall_counts_cycles_but_not_instructions() {
+        let mut soft = soft_with_asm(&["wfi", "addi a0, a0, 1", "addi a0, a0, 1"]);
+        for _ in 0..3 {
+            assert_eq!(soft.step(), Ok(StepOutcome::WaitForInterrupt));
+        }
+        assert_eq!((soft.get_csr(CSR_MINSTRET), soft.get_csr(CSR_MCYCLE)), (Ok(0), Ok(3)));
+
+        // Moving the pc, as GDB's `G` does, ends the stall, so a later
+        // interrupt returns to the new pc rather than 4 past it.
+        let regs = gdb::read_registers(&soft);
+        let regs = format!("{}0400000000000000", &regs[..regs.len() - 16]);
+        assert_eq!(gdb::write_registers(&mut soft, regs.as_bytes()), "OK");
+        soft.csr[CSR_MTVEC as usize] = 0x400;
+        soft.csr[CSR_MSTATUS as usize] = MSTATUS_MIE;
+        soft.csr[CSR_MIE as usize] = InterruptCause::MachineSoftware.mip_bit();
+        soft.csr[CSR_MIP as usize] = InterruptCause::MachineSoftware.mip_bit();
+        soft.step().unwrap();
+        assert_eq!((soft.pc, soft.csr[CSR_MEPC as usize]), (0x400, 4));
+    }
+
+    #[test]
+    fn test_mstatus_fields() {
+        let mut mstatus = Mstatus(MSTATUS_MIE | 1 << MSTATUS_MPP_SHIFT);
+        assert!(mstatus.mie() && !mstatus.mpie());
+        assert_eq!(mstatus.mpp(), PrivilegeLevel::Supervisor);
+        assert_eq!(mstatus.spp(), PrivilegeLevel::User);
+        mstatus.set_spp(PrivilegeLevel::Supervisor);
+        mstatus.set_mprv(true);
+        mstatus.set_mpp(PrivilegeLevel::Machine);
+        assert_eq!(mstatus.0, MSTATUS_MIE | MSTATUS_MPP | MSTATUS_SPP | MSTATUS_MPRV);
+
+        mstatus.set_fs(FS_DIRTY);
+        assert!(mstatus.sd());
+        assert_eq!(mstatus.fs(), FS_DIRTY);
+        mstatus.set_fs(1);
+        assert!(!mstatus.sd());
+    }
+
+    #[test]
+    fn test_float_instructions_dirty_fs() {
+        // fsd f1, 0(a0); fadd.d f1, f2, f3
+        let (fsd, fadd): (u32, u32) = (0x0015_3027, 0x0231_00d3);
+        let mut soft = SoftThread::default();
+        soft.load_image(&[fsd.to_le_bytes(), fadd.to_le_bytes()].concat(), 0).unwrap();
+        soft.registers[Register::X10 as usize] = 0x100;
+
+        soft.execute().unwrap();
+        assert_eq!(soft.mstatus().fs(), 0);
+        soft.execute().unwrap();
+        assert_eq!(soft.mstatus().fs(), FS_DIRTY);
+        assert!(soft.mstatus().sd());
+    }
+
+    #[test]
+    fn test_misa_reflects_extensions() {
+        assert_eq!(csr::misa(Base::I32, Extension::M), 1 << 30 | 1 << 8 | 1 << 12 | 1 << 18 | 1 << 20);
+        assert_eq!(csr::misa(Base::I64, Extension::C), 2 << 62 | 1 << 2 | 1 << 8 | 1 << 18 | 1 << 20);
+        assert_eq!(csr::misa(Base::I64, Extension::Zbb), 2 << 62 | 1 << 8 | 1 << 18 | 1 << 20);
+        let csrr = Instruction::from_assembly("csrrs a0, 0x301, zero", 0).unwrap().encode().unwrap();
+        let csrw = Instruction::from_assembly("csrrw zero, 0x301, a1", 4).unwrap().encode().unwrap();
+        let mut soft = SoftThread::default();
+        soft.load_image(&[csrr.to_le_bytes(), csrw.to_le_bytes()].concat(), 0).unwrap();
+        soft.registers[Register::X11 as usize] = 0;
+
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+        let misa = 2 << 62 | 1 << 0 | 1 << 1 | 1 << 2 | 1 << 3 | 1 << 5 | 1 << 8 | 1 << 12 | 1 << 18 | 1 << 20;
+        assert_eq!(soft.registers[Register::X10 as usize], misa);
+        assert_eq!(soft.get_csr(CSR_MISA), Ok(misa));
+        assert_eq!(soft.dump_csr(CSR_MISA), "misa (0x301) = 0x800000000014112f [MXL=2, EXT=ABCDFIMSU]");
+    }
+
+    #[test]
+    fn test_float_exceptions_accrue_in_fflags() {
+        // fdiv.d f1, f2, f3; fsqrt.d f1, f2
+        let (fdiv, fsqrt): (u32, u32) = (0x1a31_70d3, 0x5a01_70d3);
+        let mut soft = SoftThread::default();
+        soft.load_image(&[fdiv.to_le_bytes(), fsqrt.to_le_bytes()].concat(), 0).unwrap();
+        soft.f_registers[2] = -1.0;
+        soft.f_registers[3] = 0.0;
+
+        soft.execute().unwrap();
+        assert_eq!(soft.f_registers[1], f64::NEG_INFINITY);
+        assert_eq!(soft.read_csr_raw(CSR_FFLAGS), FFLAGS_DZ as u64);
+        soft.execute().unwrap();
+        assert!(soft.f_registers[1].is_nan());
+        assert_eq!(soft.read_csr_raw(CSR_FFLAGS), (FFLAGS_DZ | FFLAGS_NV) as u64);
+
+        // fdiv.s f1, f2, f3 rounds to single precision, so overflows where
+        // fdiv.d would not.
+        let fdiv_s: u32 = 0x1831_70d3;
+        soft.load_image(&[fdiv_s.to_le_bytes(), fdiv_s.to_le_bytes()].concat(), 0).unwrap();
+        soft.write_csr_raw(CSR_FFLAGS, 0);
+        soft.f_registers[2] = box_f32(1e38);
+        soft.f_registers[3] = box_f32(1e-38);
+        soft.execute().unwrap();
+        assert_eq!(unbox_f32(soft.f_registers[1]), f32::INFINITY);
+        assert_eq!(soft.read_csr_raw(CSR_FFLAGS), (FFLAGS_OF | FFLAGS_NX) as u64);
+
+        soft.write_csr_raw(CSR_FFLAGS, 0);
+        soft.f_registers[2] = box_f32(6.0);
+        soft.f_registers[3] = box_f32(3.0);
+        soft.execute().unwrap();
+        assert_eq!(unbox_f32(soft.f_registers[1]), 2.0);
+        assert_eq!(soft.read_csr_raw(CSR_FFLAGS), 0);
+    }
+
+    #[test]
+    fn test_fcsr_aliases_frm_and_fflags() {
+        let mut soft = soft_with_asm(&["csrrw zero, 0x3, a0", "csrrw zero, 0x1, zero", "csrrs a1, 0x3, zero"]);
+        soft.registers[Register::X10 as usize] = 0b011_10001 | 0x100;
+
+        soft.execute().unwrap();
+        assert_eq!(soft.read_csr_raw(CSR_FRM), 0b011);
+        assert_eq!(soft.read_csr_raw(CSR_FFLAGS), 0b10001);
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X11 as usize], 0b011_00000);
+    }
+
+    #[test]
+    fn test_fcvt_rounds_by_rm() {
+        // fcvt.w.d a0, f1 with rm = rtz, dyn and the reserved 5
+        let (rtz, dyn_rm, reserved): (u32, u32, u32) = (0xc200_9553, 0xc200_f553, 0xc200_d553);
+        let mut soft = SoftThread::default();
+        soft.load_image(&[rtz.to_le_bytes(), dyn_rm.to_le_bytes(), reserved.to_le_bytes()].concat(), 0).unwrap();
+        soft.f_registers[1] = -2.5;
+        soft.write_csr_raw(CSR_FRM, RM_RDN as u64);
+
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize] as i64, -2);
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize] as i64, -3);
+        assert_eq!(soft.execute(), Err(Exception::Invalid(reserved as u64)));
+    }
+
+    #[test]
+    fn test_arithmetic_rounds_by_rm() {
+        // op f1, f2, f3 with the rounding mode in bits 14:12
+        let op = |func7: u32, rs2: u32, rm: u32| func7 << 25 | rs2 << 20 | 2 << 15 | rm << 12 | 1 << 7 | 0b1010011;
+        // fmadd.s f1, f2, f3, f4
+        let fmadd_s = |rm: u32| 4 << 27 | 3 << 20 | 2 << 15 | rm << 12 | 1 << 7 | 0b1000011;
+        let run = |inst: u32| {
+            let mut soft = SoftThread::default();
+            soft.f_registers[2] = box_f32(1.0);
+            soft.f_registers[3] = box_f32(2f32.powi(-30));
+            soft.f_registers[4] = box_f32(-1.0);
+            soft.write_csr_raw(CSR_FRM, RM_RUP as u64);
+            soft.execute_block(&[inst]).map(|_| unbox_f32(soft.f_registers[1]))
+        };
+
+        assert_eq!(run(op(0b0000000, 3, RM_RNE)), Ok(1.0));
+        assert_eq!(run(op(0b0000000, 3, RM_RUP)), Ok(1.0 + 2f32.powi(-23)));
+        assert_eq!(run(op(0b0000000, 3, RM_DYN)), Ok(1.0 + 2f32.powi(-23)));
+        assert_eq!(run(op(0b0000100, 3, RM_RDN)), Ok(1.0 - 2f32.powi(-24)));
+        assert_eq!(run(op(0b0000100, 3, RM_RTZ)), Ok(1.0 - 2f32.powi(-24)));
+        assert_eq!(run(op(0b0000100, 3, RM_RNE)), Ok(1.0));
+        assert_eq!(run(fmadd_s(RM_RNE)), Ok(-1.0));
+        assert_eq!(run(fmadd_s(RM_RUP)), Ok(-1.0 + 2f32.powi(-24)));
+
+        // fadd, fmul and fsqrt in each precision, fcvt.s.w, fcvt.d.s and fmadd.s
+        let insts = |rm: u32| [
+            op(0b0000000, 3, rm), op(0b0000001, 3, rm), op(0b0000011, 3, rm),
+            op(0b0001000, 3, rm), op(0b0001001, 3, rm), op(0b0001011, 3, rm),
+            op(0b0101100, 0, rm), op(0b0101101, 0, rm), op(0b0101111, 0, rm),
+            op(0b1101000, 0, rm), op(0b0100001, 0, rm), fmadd_s(rm),
+        ];
+        for rm in [5, 6] {
+            for inst in insts(rm) {
+                assert_eq!(run(inst), Err(Exception::Invalid(inst as u64)), "{inst:#010x}");
+            }
+        }
+        let mut soft = SoftThread::default();
+        soft.write_csr_raw(CSR_FRM, 5);
+        for inst in insts(RM_DYN) {
+            assert_eq!(soft.execute_block(&[inst]), Err(Exception::Invalid(inst as u64)), "{inst:#010x}");
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_sets_fflags() {
+        // op f1, f2, f3 (x1 for the comparisons), fsqrt f1, f2 and
+        // fmadd f1, f2, f3, f4, in single (fmt 0) or double (fmt 1)
+        let op = |func7: u32, rm: u32| func7 << 25 | 3 << 20 | 2 << 15 | rm << 12 | 1 << 7 | 0b1010011;
+        let fsqrt = |fmt: u32| (0b0101100 | fmt) << 25 | 2 << 15 | 1 << 7 | 0b1010011;
+        let fmadd = |fmt: u32| 4 << 27 | fmt << 25 | 3 << 20 | 2 << 15 | 1 << 7 | 0b1000011;
+        let flags = |inst: u32, operands: [f64; 3]| {
+            let mut soft = SoftThread::default();
+            soft.f_registers[2..5].copy_from_slice(&operands);
+            soft.execute_block(&[inst]).unwrap();
+            soft.read_csr_raw(CSR_FFLAGS) as u8
+        };
+        let (inf, snan, qnan) = (f64::INFINITY, f64::from_bits(0x7ff4_0000_0000_0000), f64::NAN);
+        let (fadd_d, fsub_d, fmul_d) = (op(0b0000001, RM_RNE), op(0b0000101, RM_RNE), op(0b0001001, RM_RNE));
+
+        assert_eq!(flags(fadd_d, [inf, -inf, 0.0]), FFLAGS_NV);
+        assert_eq!(flags(fsub_d, [inf, inf, 0.0]), FFLAGS_NV);
+        assert_eq!(flags(fadd_d, [inf, inf, 0.0]), 0);
+        assert_eq!(flags(fadd_d, [snan, 1.0, 0.0]), FFLAGS_NV);
+        assert_eq!(flags(fadd_d, [qnan, 1.0, 0.0]), 0);
+        assert_eq!(flags(fadd_d, [1.0, 1.0, 0.0]), 0);
+        assert_eq!(flags(fadd_d, [1.0, 2f64.powi(-60), 0.0]), FFLAGS_NX);
+        assert_eq!(flags(fadd_d, [f64::MAX, f64::MAX, 0.0]), FFLAGS_OF | FFLAGS_NX);
+        assert_eq!(flags(fmul_d, [0.0, inf, 0.0]), FFLAGS_NV);
+        assert_eq!(flags(fmul_d, [3.0, 1.0 / 3.0, 0.0]), FFLAGS_NX);
+        assert_eq!(flags(fmul_d, [f64::MAX, 2.0, 0.0]), FFLAGS_OF | FFLAGS_NX);
+        assert_eq!(flags(fmul_d, [f64::from_bits(1), 0.5, 0.0]), FFLAGS_UF | FFLAGS_NX);
+        assert_eq!(flags(fmul_d, [f64::MIN_POSITIVE, 0.5, 0.0]), 0);
+        assert_eq!(flags(fmadd(1), [0.0, inf, 1.0]), FFLAGS_NV);
+        assert_eq!(flags(fmadd(1), [0.0, inf, qnan]), FFLAGS_NV);
+        assert_eq!(flags(fmadd(1), [inf, 1.0, -inf]), FFLAGS_NV);
+        assert_eq!(flags(fmadd(1), [1.0, 1.0, snan]), FFLAGS_NV);
+        assert_eq!(flags(fmadd(1), [2.0, 3.0, 1.0]), 0);
+        assert_eq!(flags(fmadd(1), [1.0 + 2f64.powi(-30), 1.0 + 2f64.powi(-30), 0.0]), FFLAGS_NX);
+        assert_eq!(flags(fmadd(1), [1.0 + 2f64.powi(-30), 1.0 - 2f64.powi(-30), 2f64.powi(-60)]), 0);
+
+        let s = box_f32;
+        let (fadd_s, fmul_s) = (op(0b0000000, RM_RNE), op(0b0001000, RM_RNE));
+        assert_eq!(flags(fadd_s, [s(1.0), s(2f32.powi(-30)), 0.0]), FFLAGS_NX);
+        assert_eq!(flags(fadd_s, [s(1.0), s(2f32.powi(-23)), 0.0]), 0);
+        assert_eq!(flags(fadd_s, [s(f32::INFINITY), s(f32::NEG_INFINITY), 0.0]), FFLAGS_NV);
+        assert_eq!(flags(fadd_s, [s(f32::from_bits(0x7fa0_0000)), s(1.0), 0.0]), FFLAGS_NV);
+        assert_eq!(flags(fmul_s, [s(f32::MAX), s(2.0), 0.0]), FFLAGS_OF | FFLAGS_NX);
+        assert_eq!(flags(op(0b0001000, RM_RTZ), [s(f32::MAX), s(2.0), 0.0]), FFLAGS_OF | FFLAGS_NX);
+        assert_eq!(flags(fmul_s, [s(f32::from_bits(1)), s(0.5), 0.0]), FFLAGS_UF | FFLAGS_NX);
+        assert_eq!(flags(fmul_s, [s(0.0), s(f32::INFINITY), 0.0]), FFLAGS_NV);
+        assert_eq!(flags(fmadd(0), [s(0.0), s(f32::INFINITY), s(1.0)]), FFLAGS_NV);
+        assert_eq!(flags(fmadd(0), [s(3.0), s(1.0 / 3.0), s(-1.0)]), 0);
+        assert_eq!(flags(fmadd(0), [s(1.0 + 2f32.powi(-20)), s(1.0 + 2f32.powi(-20)), s(0.0)]), FFLAGS_NX);
+        assert_eq!(flags(fsqrt(0), [s(2.0), 0.0, 0.0]), FFLAGS_NX);
+        assert_eq!(flags(fsqrt(0), [s(4.0), 0.0, 0.0]), 0);
+        assert_eq!(flags(fsqrt(0), [s(f32::from_bits(0x7fa0_0000)), 0.0, 0.0]), FFLAGS_NV);
+
+        // feq only signals on a signaling NaN, flt and fle on any NaN
+        for fmt in [0, 1] {
+            let (one, nan, signaling) = if fmt == 0 {
+                (s(1.0), s(f32::NAN), s(f32::from_bits(0x7fa0_0000)))
+            } else {
+                (1.0, qnan, snan)
+            };
+            let (feq, flt, fle) = (op(0b1010000 | fmt, 0b010), op(0b1010000 | fmt, 0b001), op(0b1010000 | fmt, 0b000));
+            assert_eq!(flags(feq, [one, nan, 0.0]), 0);
+            assert_eq!(flags(feq, [signaling, one, 0.0]), FFLAGS_NV);
+            assert_eq!(flags(flt, [one, nan, 0.0]), FFLAGS_NV);
+            assert_eq!(flags(fle, [nan, one, 0.0]), FFLAGS_NV);
+            assert_eq!(flags(flt, [one, one, 0.0]), 0);
+            assert_eq!(flags(fle, [one, one, 0.0]), 0);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_nans_are_canonical() {
+        // fadd.d f1, f2, f3
+        let fadd: u32 = 0x0231_00d3;
+        for (a, b) in [(f64::from_bits(0x7ff8_dead_beef_0001), 1.0), (f64::INFINITY, f64::NEG_INFINITY)] {
+            let mut soft = SoftThread::default();
+            soft.load_image(&fadd.to_le_bytes(), 0).unwrap();
+            soft.f_registers[2] = a;
+            soft.f_registers[3] = b;
+            soft.execute().unwrap();
+            assert_eq!(soft.f_registers[1].to_bits(), CANONICAL_NAN);
+        }
+        assert_eq!((canonical_nan_f64() as f32).to_bits(), canonical_nan_f32().to_bits());
+    }
+
+    #[test]
+    fn test_cycle_counts_cpi_and_upper_halves() {
+        let program = ["csrrs a0, 0xc00, zero", "csrrs a1, 0xc02, zero", "csrrs a2, 0xc80, zero", "csrrs a3, 0xc82, zero"];
+        let mut soft = soft_with_asm(&program);
+        soft.cpi = 3;
+        soft.emulate_csr_counter_increment(1 << 32);
+
+        for _ in 0..program.len() {
+            soft.execute().unwrap();
+        }
+        assert_eq!(soft.registers[Register::X10 as usize], 3 << 32);
+        assert_eq!(soft.registers[Register::X11 as usize], (1 << 32) + 1);
+        assert_eq!(soft.registers[Register::X12 as usize], 3);
+        assert_eq!(soft.registers[Register::X13 as usize], 1);
+    }
+
+    #[test]
+    fn test_bus_dispatches_to_attached_devices() {
+        let mut bus = Bus::new();
+        let idx = bus.attach(Clint::new(1));
+        assert!(bus.contains(CLINT_BASE + CLINT_MTIME as u64) && !bus.contains(CLINT_BASE + CLINT_SIZE));
+        bus.write(CLINT_BASE + CLINT_MTIMECMP as u64, 0x1234, 64).unwrap();
+        assert_eq!(bus.get_mut::<Clint>(idx).unwrap().mtimecmp[0], 0x1234);
+        assert_eq!(bus.read(&0x8000_0000, 64), Err(Exception::LoadAccessFault));
+        assert_eq!(bus.write(0x8000_0000, 0, 64), Err(Exception::StoreAMOAccessFault));
+
+        let mut soft = soft_with_asm(&["sd a1, 8(a0)", "ld a2, 8(a0)", "sd zero, 0(a0)"]);
+        soft.mmio.attach(Clint::new(1));
+        soft.registers[Register::X10 as usize] = CLINT_BASE + CLINT_MTIMECMP as u64;
+        soft.registers[Register::X11 as usize] = 0xabcd;
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X12 as usize], 0xabcd);
+        assert_eq!(soft.mmio.get_mut::<Clint>(0).unwrap().mtimecmp[1], 0xabcd);
+
+        // A CLINT on the bus drives MTIP from the next instruction on.
+        let mtip = InterruptCause::MachineTimer.mip_bit();
+        assert_eq!(soft.csr[CSR_MIP as usize] & mtip, 0);
+        soft.execute().unwrap();
+        soft.pc = 0;
+        soft.execute().unwrap();
+        assert_eq!(soft.csr[CSR_MIP as usize] & mtip, mtip);
+    }
+
+    #[test]
+    fn test_soft_thread_runs_on_a_bus() {
+        let code: Vec<u8> = ["lbu a1, 5(a0)", "lbu a2, 0(a0)", "sb a2, 0(a0)", "ld a3, 0(a4)", "sd a3, 0(a4)"].iter().enumerate()
+            .flat_map(|(idx, asm)| Instruction::from_assembly(asm, 4 * idx as u64).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+        let mut bus = Bus::new();
+        bus.attach(Dram::new());
+        let mut soft = SoftThread::with_memory(bus, EncodingTable::default());
+        soft.load_image(&code, 0).unwrap();
+        let mut uart = Uart16550::new();
+        uart.push_rx(b'x');
+        let uart = soft.bus.attach(uart);
+        let clint = soft.bus.attach(Clint::new(1));
+        soft.registers[Register::X10 as usize] = UART_BASE;
+        soft.registers[Register::X14 as usize] = 0x8000_0000;
+
+        for _ in 0..3 {
+            soft.execute().unwrap();
+        }
+        assert_eq!(soft.registers[Register::X11 as usize], UART_LSR_THRE | UART_LSR_DR);
+        assert_eq!(soft.registers[Register::X12 as usize], b'x' as u64);
+        assert_eq!(soft.bus.get_mut::<Uart16550>(uart).unwrap().drain_tx(), b"x");
+        assert_eq!(soft.bus.get_mut::<Clint>(clint).unwrap().mtime, 3);
+
+        // Nothing is mapped at 0x8000_0000.
+        assert_eq!(soft.execute(), Err(Exception::LoadAccessFault));
+        soft.set_pc(16);
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOAccessFault));
+    }
+
+    #[test]
+    fn test_uart_on_the_bus() {
+        let mut soft = soft_with_asm(&["lbu a1, 5(a0)", "lbu a2, 0(a0)", "sb a2, 0(a0)", "sb a2, 0(a0)"]);
+        let mut uart = Uart16550::new();
+        uart.push_rx(b'x');
+        let idx = soft.mmio.attach(uart);
+        soft.registers[Register::X10 as usize] = UART_BASE;
+
+        for _ in 0..3 {
+            soft.execute().unwrap();
+        }
+        assert_eq!(soft.registers[Register::X11 as usize], UART_LSR_THRE | UART_LSR_DR);
+        assert_eq!(soft.registers[Register::X12 as usize], b'x' as u64);
+        let uart = soft.mmio.get_mut::<Uart16550>(idx).unwrap();
+        assert_eq!(uart.drain_tx(), b"x");
+        assert!(uart.drain_tx().is_empty());
+
+        let (sent, sink) = recorder();
+        uart.tx_callback = Some(Box::new(move |byte| sink.borrow_mut().push(byte)));
+        soft.execute().unwrap();
+        assert_eq!(*sent.borrow(), b"x");
+        assert!(soft.mmio.get_mut::<Uart16550>(idx).unwrap().drain_tx().is_empty());
+    }
+
+    #[test]
+    fn test_pmp_matching_modes() {
+        let mut pmp = Pmp::new();
+        let user = PrivilegeLevel::User;
+        assert_eq!(pmp.check(0x3000, 4, AccessType::Store, user), Ok(()));
+
+        // 0: NAPOT 0x0-0xfff RX, 1: NA4 0x2000 RW, 2: TOR 0x2004-0x2fff R
+        pmp.write_csr(CSR_PMPADDR0, 0x1ff);
+        pmp.write_csr(CSR_PMPADDR0 + 1, 0x2000 >> 2);
+        pmp.write_csr(CSR_PMPADDR0 + 2, 0x3000 >> 2);
+        let napot = PMP_A_NAPOT << PMPCFG_A_SHIFT | PMPCFG_R | PMPCFG_X;
+        let na4 = PMP_A_NA4 << PMPCFG_A_SHIFT | PMPCFG_R | PMPCFG_W;
+        let tor = PMP_A_TOR << PMPCFG_A_SHIFT | PMPCFG_R;
+        pmp.write_csr(CSR_PMPCFG0, napot | na4 << 8 | tor << 16);
+
+        assert_eq!(pmp.check(0xffc, 4, AccessType::Instruction, user), Ok(()));
+        assert_eq!(pmp.check(0x100, 4, AccessType::Store, user), Err(Exception::StoreAMOAccessFault));
+        assert_eq!(pmp.check(0x2000, 4, AccessType::Store, user), Ok(()));
+        assert_eq!(pmp.check(0x2000, 8, AccessType::Load, user), Err(Exception::LoadAccessFault));
+        assert_eq!(pmp.check(0x2ff8, 8, AccessType::Load, user), Ok(()));
+        assert_eq!(pmp.check(0x2ff8, 8, AccessType::Instruction, user), Err(Exception::AccessFault));
+        assert_eq!(pmp.check(0x3000, 4, AccessType::Load, PrivilegeLevel::Supervisor), Err(Exception::LoadAccessFault));
+        assert_eq!(pmp.check(0x3000, 4, AccessType::Store, PrivilegeLevel::Machine), Ok(()));
+    }
+
+    #[test]
+    fn test_pmp_csr_writes_guard_user_accesses() {
+        let mut soft = soft_with_asm(&["csrrw zero, 0x3b0, a0", "csrrw zero, 0x3a0, a1", "sw a0, 0(zero)"]);
+        soft.registers[Register::X10 as usize] = 0x1ff;
+        soft.registers[Register::X11 as usize] = PMP_A_NAPOT << PMPCFG_A_SHIFT | PMPCFG_R | PMPCFG_X;
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+        assert_eq!(soft.pmp.pmpaddr[0], 0x1ff);
+
+        soft.priv_level = PrivilegeLevel::User;
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOAccessFault));
+    }
+
+    #[test]
+    fn test_pmp_guards_user_atomics() {
+        // lw a0, 0(a1); amoadd.w a0, a2, (a1); lr.w a0, (a1); sc.w a3, a2, (a1); amoadd.w a0, a2, (a3)
+        let lw = Instruction::from_assembly("lw a0, 0(a1)", 0).unwrap().encode().unwrap();
+        let code = [lw, 0x00c5a52f, 0x1005a52f, 0x18c5a6af, 0x00c6a52f];
+        let mut soft = SoftThread::default();
+        soft.load_image(&code.iter().flat_map(|inst| inst.to_le_bytes()).collect::<Vec<u8>>(), 0).unwrap();
+        soft.bus.write(0x1000, 7, 32).unwrap();
+        soft.registers[Register::X11 as usize] = 0x1000;
+        soft.registers[Register::X12 as usize] = 1;
+        soft.registers[Register::X13 as usize] = 0x1002;
+
+        // 0: NAPOT 0x0-0xfff RX, 1: TOR 0x1000-0x1fff R
+        soft.pmp.write_csr(CSR_PMPADDR0, 0x1ff);
+        soft.pmp.write_csr(CSR_PMPADDR0 + 1, 0x2000 >> 2);
+        let napot = PMP_A_NAPOT << PMPCFG_A_SHIFT | PMPCFG_R | PMPCFG_X;
+        let tor = PMP_A_TOR << PMPCFG_A_SHIFT | PMPCFG_R;
+        soft.pmp.write_csr(CSR_PMPCFG0, napot | tor << 8);
+        soft.priv_level = PrivilegeLevel::User;
+
+        soft.execute().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 7);
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOAccessFault));
+        soft.pc = 8;
+        soft.execute().unwrap();
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOAccessFault));
+        soft.pc = 16;
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOAddressMisaligned));
+        assert_eq!(soft.bus.read(&0x1000, 32).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_gdb_stub_session() {
+        // GDB's side of the session, and a stream that replays it.
+        struct Session {
+            input: std::io::Cursor<Vec<u8>>,
+            output: Vec<u8>,
+        }
+        impl std::io::Read for Session {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.input.read(buf)
+            }
+        }
+        impl std::io::Write for Session {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.output.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let packet = |body: &str| format!("${}#{:02x}", body, body.bytes().fold(0u8, |sum, byte| sum.wrapping_add(byte)));
+
+        let mut soft = soft_with_asm(&["addi a0, a0, 1", "addi a0, a0, 2", "addi a0, a0, 3", "ebreak"]);
+        let first = (soft.bus.read(&0, 32).unwrap() as u32).to_le_bytes();
+
+        let requests = ["?", "s", "Z0,8,4", "c", "m0,4", "M100,2:beef", "m100,2", "z0,8,4", "vMustReplyEmpty", "D"];
+        let mut input: Vec<u8> = b"$?#00+".to_vec();
+        input.extend(requests.iter().flat_map(|body| packet(body).into_bytes()));
+        let mut stub = GdbStub::new(Session { input: std::io::Cursor::new(input), output: vec![] });
+        stub.attach(&mut soft).unwrap();
+
+        let replies = ["S05", "S05", "OK", "S05", &first.iter().map(|byte| format!("{:02x}", byte)).collect::<String>(),
+            "OK", "beef", "OK", "", "OK"];
+        let expected: String = std::iter::once("-".to_string())
+            .chain(replies.iter().map(|reply| format!("+{}", packet(reply))))
+            .collect();
+        assert_eq!(String::from_utf8(stub.into_inner().output).unwrap(), expected);
+        assert_eq!(soft.pc, 8);
+        assert_eq!(soft.registers[Register::X10 as usize], 3);
+    }
+
+    #[test]
+    fn test_gdb_register_packets() {
+        let mut soft = SoftThread::default();
+        soft.registers[Register::X1 as usize] = 0x1122_3344_5566_7788;
+        soft.pc = 0x8000_0000;
+        let regs = gdb::read_registers(&soft);
+        assert_eq!(regs.len(), 33 * 16);
+        assert_eq!(&regs[16..32], "8877665544332211");
+        assert_eq!(&regs[32 * 16..], "0000008000000000");
+
+        let mut other = SoftThread::default();
+        assert_eq!(gdb::write_registers(&mut other, regs.as_bytes()), "OK");
+        assert_eq!(other.registers[..32], soft.registers[..32]);
+        assert_eq!(other.pc, soft.pc);
+        assert_eq!(gdb::write_registers(&mut other, b"00"), "E01");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let mut soft = soft_with_asm(&["addi a0, a0, 7", "sd a0, 0(a1)", "addi a0, a0, 1"]);
+        soft.registers[Register::X11 as usize] = 0x1000;
+        soft.f_registers[3] = f64::from_bits(0x7ff8_0000_dead_beef);
+        soft.res.push(0x2000);
+        soft.write_csr_raw(CSR_PMPADDR0, 0x1ff);
+        soft.execute().unwrap();
+        soft.execute().unwrap();
+
+        let json = serde_json::to_string(&soft).unwrap();
+        let mut restored: SoftThread<u64, f64, Dram> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.registers, soft.registers);
+        assert_eq!(restored.f_registers[3].to_bits(), 0x7ff8_0000_dead_beef);
+        assert_eq!(restored.csr, soft.csr);
+        assert_eq!(restored.pmp.pmpaddr[0], 0x1ff);
+        assert_eq!((restored.pc, &restored.res), (8, &vec![0x2000]));
+        assert_eq!(restored.bus.mem.len(), soft.bus.mem.len());
+        assert_eq!(restored.bus.readdw(&0x1000), 7);
+
+        restored.execute().unwrap();
+        assert_eq!(restored.registers[Register::X10 as usize], 8);
+        assert!(serde_json::from_str::<Dram>(r#"{"len":0,"size":0,"mem":"AA==","flags":""}"#).is_err());
+        assert!(serde_json::from_str::<Dram>(r#"{"len":4096,"size":0,"mem":"","flags":""}"#).is_err());
+        assert!(serde_json::from_str::<Dram>(r#"{"len":0,"size":4,"mem":"","flags":""}"#).is_err());
+        assert!(serde_json::from_str::<Dram>(r#"{"len":1099511627776,"size":0,"mem":"","flags":""}"#).is_err());
+        let dram: Dram = serde_json::from_str(r#"{"len":4096,"size":4,"mem":"","flags":"AA=="}"#).unwrap();
+        assert_eq!((dram.mem.len(), dram.flags.len()), (4096, 1));
+    }
+
+    #[test]
+    fn test_accesses_past_dram_fault() {
+        let encode = |asm: &str| Instruction::from_assembly(asm, 0).unwrap().encode().unwrap();
+        let mut soft = SoftThread::default();
+        let len = soft.bus.mem.len() as u64;
+        soft.registers[Register::X10 as usize] = u64::MAX;
+        soft.registers[Register::X11 as usize] = 0x7fff_ffff_0000;
+        soft.registers[Register::X12 as usize] = len - 2;
+        soft.registers[Register::X13 as usize] = len - 8;
+
+        assert_eq!(soft.execute_block(&[encode("ld a0, 0(a1)")]), Err(Exception::LoadAccessFault));
+        assert_eq!(soft.execute_block(&[encode("sd a0, 0(a1)")]), Err(Exception::StoreAMOAccessFault));
+        // A misaligned access split into bytes faults before writing any.
+        assert_eq!(soft.execute_block(&[encode("lw a0, 0(a2)")]), Err(Exception::LoadAccessFault));
+        assert_eq!(soft.execute_block(&[encode("sw a0, 0(a2)")]), Err(Exception::StoreAMOAccessFault));
+        assert_eq!(soft.bus.mem[len as usize - 2..], [0, 0]);
+        assert_eq!(soft.execute_block(&[encode("sd a0, 0(a3)")]), Ok(1));
+        assert_eq!(soft.bus.read(&(len - 8), 64).unwrap(), u64::MAX);
+        assert!(soft.bus.read(&(len - 4), 64).is_err());
+        assert!(soft.bus.write(u64::MAX, 0, 16).is_err());
+    }
+
+    #[test]
+    fn test_decode_compressed() {
+        assert_eq!(Instruction::decode_compressed(0x8082), Instruction::CJr { rs1: Register::X1 });
+        assert_eq!(Instruction::decode_compressed(0x0000), Instruction::Undefined);
+        assert_eq!(Instruction::decode_compressed(0x0808), Instruction::CAddi4spn { rd: Register::X10, imm: 16 });
+        assert_eq!(Instruction::decode_compressed(0x0808).expand(),
+            Instruction::Addi { rd: Register::X10, rs1: Register::X2, imm: 16, func3: 0 });
+        assert_eq!(disasm::disassemble(&Instruction::decode_compressed(0xe406), 0), "c.sdsp ra, 8(sp)");
+        assert_eq!(disasm::disassemble(&Instruction::decode_compressed(0x60a2), 0), "c.ldsp ra, 8(sp)");
+        // c.beqz a0, -2
+        assert_eq!(disasm::disassemble(&Instruction::decode_compressed(0xdd7d), 0x10), "c.beqz a0, 0xe");
+    }
+
+    #[test]
+    fn test_compressed_instructions_advance_by_two() {
+        // c.li a0, 5; c.addi a0, 3; c.mv a1, a0; c.add a1, a0; c.slli a1, 2;
+        // addi a2, zero, 1; c.bnez a0, +4; c.li a3, 7; c.lui a4, 1; c.ebreak
+        let halves = [0x4515u16, 0x050d, 0x85aa, 0x95aa, 0x058a, 0x0613, 0x0010, 0xe111, 0x469d, 0x6705, 0x9002];
+        let mut soft = SoftThread::default();
+        soft.load_image(&halves.iter().flat_map(|half| half.to_le_bytes()).collect::<Vec<u8>>(), 0).unwrap();
+        let mut pcs = vec![];
+        while soft.step().unwrap() != StepOutcome::Breakpoint {
+            pcs.push(soft.pc);
+        }
+
+        assert_eq!(pcs, [2, 4, 6, 8, 10, 14, 18, 20]);
+        assert_eq!(soft.pc, 20);
+        assert_eq!(soft.registers[Register::X10 as usize], 8);
+        assert_eq!(soft.registers[Register::X11 as usize], 64);
+        assert_eq!(soft.registers[Register::X12 as usize], 1);
+        assert_eq!(soft.registers[Register::X13 as usize], 0);
+        assert_eq!(soft.registers[Register::X14 as usize], 0x1000);
+    }
+
+    #[test]
+    fn test_x0_stays_zero_after_compressed_jumps() {
+        // c.j +2; c.li a0, 5; c.ebreak
+        let halves = [0xa009u16, 0x4515, 0x9002];
+        assert_eq!(Instruction::decode_compressed(halves[0]), Instruction::CJ { imm: 2 });
+        let mut soft = SoftThread::default();
+        soft.load_image(&halves.iter().flat_map(|half| half.to_le_bytes()).collect::<Vec<u8>>(), 0).unwrap();
+
+        assert_eq!(soft.execute_until_pc(2, 10), Ok(1));
+        assert_eq!(soft.registers[Register::X0 as usize], 0);
+        assert_eq!(soft.step(), Ok(StepOutcome::Continue));
+        assert_eq!(soft.registers[Register::X10 as usize], 5);
+    }
+
+    #[test]
+    fn test_bitmanip_instructions() {
+        // sh2add a2, a0, a1; andn a3, a0, a1; clz a4, a0; cpopw a5, a1; sext.b a6, a1; rori a7, a0, 4;
+        // orc.b s2, a1; rev8 s3, a0; clmul s4, a0, a1; bseti s5, zero, 63; bexti s6, a0, 9; min s7, a0, a1;
+        // add.uw s8, a1, a0
+        let code = [
+            0x20b54633u32, 0x40b576b3, 0x60051713, 0x6025979b, 0x60459813, 0x60455893, 0x2875d913,
+            0x6b855993, 0x0ab51a33, 0x2bf01a93, 0x48955b13, 0x0ab54bb3, 0x08a58c3b,
+        ];
+        let mut soft = SoftThread::default();
+        soft.load_image(&code.iter().flat_map(|inst| inst.to_le_bytes()).collect::<Vec<u8>>(), 0).unwrap();
+        soft.registers[Register::X10 as usize] = 0x1234;
+        soft.registers[Register::X11 as usize] = -128i64 as u64;
+        for _ in 0..code.len() {
+            soft.execute().unwrap();
+        }
+
+        let reg = |idx: usize| soft.registers[idx];
+        assert_eq!(reg(12), 0x4850);
+        assert_eq!(reg(13), 0x34);
+        assert_eq!(reg(14), 51);
+        assert_eq!(reg(15), 25);
+        assert_eq!(reg(16), -128i64 as u64);
+        assert_eq!(reg(17), 0x4000_0000_0000_0123);
+        assert_eq!(reg(18), u64::MAX);
+        assert_eq!(reg(19), 0x3412_0000_0000_0000);
+        assert_eq!(reg(20), 0xffff_ffff_fff8_f600);
+        assert_eq!(reg(21), 1 << 63);
+        assert_eq!(reg(22), 1);
+        assert_eq!(reg(23), -128i64 as u64);
+        assert_eq!(reg(24), 0x1_0000_11b4);
+        assert_eq!(bitmanip::clmul(0x1234, -128i64 as u64) >> 64, 0xe13);
+    }
+
+    #[test]
+    fn test_decode_bitmanip() {
+        let enc_table = EncodingTable::new(Extension::G, Base::I64);
+        let text = |inst: u32| disasm::disassemble(&Instruction::decode(inst, &enc_table), 0);
+        assert_eq!(text(0x20b54633), "sh2add a2, a0, a1");
+        assert_eq!(text(0x2875d913), "orc.b s2, a1");
+        assert_eq!(text(0x2bf01a93), "bseti s5, zero, 63");
+        assert_eq!(text(0x08a58c3b), "add.uw s8, a1, a0");
+        // slli and srai are still decoded as before.
+        assert_eq!(text(0x00451513), "slli a0, a0, 4");
+        assert_eq!(text(0x40455513), "srai a0, a0, 4");
+
+        // Outside G, each of Zba, Zbb, Zbc and Zbs is enabled on its own.
+        let zba = EncodingTable::new(Extension::Zba, Base::I64);
+        assert_eq!(Instruction::decode(0x20b54633, &zba), Instruction::Sh2add { rd: Register::X12, rs1: Register::X10, rs2: Register::X11 });
+        assert_eq!(Instruction::decode(0x40b576b3, &zba), Instruction::Undefined);
+        assert_eq!(Instruction::decode(0x0ab51a33, &EncodingTable::new(Extension::Zbs, Base::I64)), Instruction::Undefined);
+        assert_ne!(Instruction::decode(0x0ab51a33, &EncodingTable::new(Extension::Zbc, Base::I64)), Instruction::Undefined);
+        assert_ne!(Instruction::decode(0x00451513, &zba), Instruction::Undefined);
+    }
+
+    #[test]
+    fn test_load_raw_store_raw_round_trip() {
+        let mut soft = SoftThread::default();
+        let data: Vec<u8> = (0..4096u32).map(|i| (i * 7 + 3) as u8).collect();
+        soft.load_raw(0x1000, &data).unwrap();
+
+        let mut buf = vec![0u8; 4096];
+        soft.store_raw(0x1000, &mut buf).unwrap();
+
+        assert_eq!(buf, data);
+        assert_eq!(soft.bus.read(&0x1000, 8).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_load_raw_out_of_bounds() {
+        let mut soft = SoftThread::default();
+        let len = soft.bus.mem.len() as u64;
+        let mut buf = [0u8; 8];
+
+        assert_eq!(soft.load_raw(len - 4, &[0u8; 8]), Err(Exception::InvalidAddr));
+        assert_eq!(soft.store_raw(len - 4, &mut buf), Err(Exception::InvalidAddr));
+        assert_eq!(soft.load_raw(u64::MAX, &[0u8; 1]), Err(Exception::InvalidAddr));
+        assert!(soft.load_raw(len - 8, &[0u8; 8]).is_ok());
+    }
+
+    #[test]
+    fn test_mhartid_is_unique_per_hart() {
+        let mut cpu = Cpu::with_harts(4);
+        // csrr a0, mhartid
+        let program = vec![0b1111_0001 as u8, 0b0100_0000 as u8, 0b0010_0101 as u8, 0b0111_0011 as u8];
+        for core in cpu.cores.iter_mut() {
+            core.load_program(program.clone()).unwrap();
+        }
+        cpu.run().unwrap();
+
+        for (id, core) in cpu.cores.iter().enumerate() {
+            assert_eq!(core.registers[Register::X10 as usize], id as u64);
+            assert_eq!(core.hart_id(), id as u64);
+        }
+    }
+
+    #[test]
+    fn test_mhartid_is_read_only() {
+        let mut soft = SoftThread::default();
+        // csrw mhartid, a0
+        let program = vec![0b1111_0001 as u8, 0b0100_0101 as u8, 0b0001_0000 as u8, 0b0111_0011 as u8];
+        soft.load_program(program);
+
+        assert_eq!(soft.execute(), Err(Exception::Invalid(0xf145_1073)));
+        assert_eq!(soft.hart_id(), 0);
+    }
+
+    #[test]
+    fn test_fmin_fmax_normal_operands() {
+        let mut fflags = 0u8;
+
+        assert_eq!(fmin_rv(1.5, -2.0, &mut fflags), -2.0);
+        assert_eq!(fmax_rv(1.5, -2.0, &mut fflags), 1.5);
+        assert!(fmin_rv(0.0, -0.0, &mut fflags).is_sign_negative());
+        assert!(fmax_rv(-0.0, 0.0, &mut fflags).is_sign_positive());
+        assert_eq!(fflags, 0);
+    }
+
+    #[test]
+    fn test_fmin_fmax_one_quiet_nan() {
+        let mut fflags = 0u8;
+
+        assert_eq!(fmin_rv(f64::NAN, 3.0, &mut fflags), 3.0);
+        assert_eq!(fmax_rv(3.0, f64::NAN, &mut fflags), 3.0);
+        assert_eq!(fflags, 0);
+    }
+
+    #[test]
+    fn test_fmin_fmax_signaling_nan_sets_invalid() {
+        let mut fflags = 0u8;
+        let snan = f64::from_bits(0x7ff4_0000_0000_0000);
+
+        assert_eq!(fmin_rv(snan, 3.0, &mut fflags), 3.0);
+        assert_eq!(fflags, FFLAGS_NV);
+
+        fflags = 0;
+        assert_eq!(fmax_rv(-1.0, snan, &mut fflags), -1.0);
+        assert_eq!(fflags, FFLAGS_NV);
+    }
+
+    #[test]
+    fn test_fmin_fmax_both_nan_gives_canonical_nan() {
+        let mut fflags = 0u8;
+        let nan = f64::from_bits(0xfff8_0000_dead_beef);
+
+        assert_eq!(fmin_rv(nan, f64::NAN, &mut fflags).to_bits(), CANONICAL_NAN);
+        assert_eq!(fmax_rv(f64::NAN, nan, &mut fflags).to_bits(), CANONICAL_NAN);
+        assert_eq!((fmax_rv(nan, nan, &mut fflags) as f32).to_bits(), 0x7fc0_0000);
+        assert_eq!(fflags, 0);
+    }
+
+    #[test]
+    fn test_fmin_s_execution_sets_fflags() {
+        let mut soft = SoftThread::default();
+        // fmin.s fa0, fa1, fa2
+        let program = vec![0b0010_1000 as u8, 0b1100_0101 as u8, 0b1000_0101 as u8, 0b0101_0011 as u8];
+        soft.load_program(program);
+        soft.f_registers[Register::X11 as usize] = box_f32(f32::from_bits(0x7fa0_0000));
+        soft.f_registers[Register::X12 as usize] = box_f32(2.5);
+        soft.execute().unwrap();
+
+        assert_eq!(unbox_f32(soft.f_registers[Register::X10 as usize]), 2.5);
+        assert_eq!(soft.csr[CSR_FFLAGS as usize] as u8, FFLAGS_NV);
+    }
+
+    #[test]
+    fn test_single_values_round_trip_nan_boxed() {
+        let mut soft = SoftThread::default();
+        // fmv.w.x f1, a0; fadd.s f2, f1, f1; fsw f1, 0(a2); fsw f2, 0(a3);
+        // flw f3, 0(a3); fmv.x.w a1, f3
+        let program: [u32; 6] = [0xf00500d3, 0x00108153, 0x00162027, 0x0026a027, 0x0006a187, 0xe00185d3];
+        soft.load_image(&program.iter().flat_map(|inst| inst.to_le_bytes()).collect::<Vec<u8>>(), 0).unwrap();
+        soft.registers[Register::X10 as usize] = 1.0f32.to_bits() as u64;
+        soft.registers[Register::X12 as usize] = 0x100;
+        soft.registers[Register::X13 as usize] = 0x104;
+        for _ in 0..program.len() {
+            soft.execute().unwrap();
+        }
+
+        assert_eq!(soft.f_registers[1].to_bits(), NAN_BOX | 0x3f80_0000);
+        assert_eq!(soft.f_registers[2].to_bits(), NAN_BOX | 0x4000_0000);
+        assert_eq!(soft.bus.read(&0x100, 32).unwrap(), 0x3f80_0000);
+        assert_eq!(soft.bus.read(&0x104, 32).unwrap(), 0x4000_0000);
+        assert_eq!(soft.f_registers[3].to_bits(), NAN_BOX | 0x4000_0000);
+        assert_eq!(soft.registers[Register::X11 as usize], 0x4000_0000);
+        assert_eq!(soft.read_csr_raw(CSR_FFLAGS), 0);
+
+        // fmv.x.w sign-extends bit 31, and an unboxed value stores as the canonical NaN.
+        soft.f_registers[3] = box_f32(-2.0);
+        soft.execute_block(&[0xe00185d3]).unwrap();
+        assert_eq!(soft.registers[Register::X11 as usize], 0xffff_ffff_c000_0000);
+        soft.f_registers[1] = 1.0;
+        soft.execute_block(&[0x00162027]).unwrap();
+        assert_eq!(soft.bus.read(&0x100, 32).unwrap(), 0x7fc0_0000);
+    }
+
+    #[test]
+    fn test_load_program_all_harts() {
+        let mut cpu = Cpu::with_harts(4);
+        let program = vec![
+            0x73, 0x25, 0x40, 0xf1, // csrr a0, mhartid
+            0x93, 0x12, 0x35, 0x00, // slli t0, a0, 3
+            0x37, 0x13, 0x00, 0x00, // lui t1, 1
+            0xb3, 0x82, 0x62, 0x00, // add t0, t0, t1
+            0x23, 0xb0, 0xa2, 0x00, // sd a0, 0(t0)
+        ];
+        cpu.load_program_all_harts(&program, 0x200).unwrap();
+
+        for core in cpu.cores.iter() {
+            assert_eq!(core.pc, 0x200);
+            assert_eq!(core.registers[Register::X2 as usize], core.bus.mem.len() as u64);
+        }
+
+        cpu.run().unwrap();
+
+        for (id, core) in cpu.cores.iter().enumerate() {
+            let addr = 0x1000 + id as u64 * 8;
+            assert_eq!(core.bus.read(&addr, 64).unwrap(), id as u64);
+            assert_eq!(core.pc, 0x200 + program.len() as u64);
+        }
+    }
+
+    #[test]
+    fn test_load_binary() {
+        let mut soft = SoftThread::default();
+        soft.stack_size = 0x100;
+        soft.load_raw(0x1010, &[0xff; 0x100]).unwrap();
+        let binary = [
+            0xef, 0xbe, 0xad, 0xde, // .word 0xdeadbeef
+            0x13, 0x05, 0x50, 0x00, // li a0, 5
+            0x13, 0x00, 0x00, 0x00, // nop
+        ];
+        soft.load_binary(&binary, 0x1000, 0x1004).unwrap();
+
+        assert_eq!(soft.pc, 0x1004);
+        assert_eq!(soft.registers[Register::X2 as usize], 0x1110);
+        assert_eq!(soft.bus.read(&0x1000, 32).unwrap(), 0xdeadbeef);
+        assert_eq!(soft.bus.read(&0x1108, 64).unwrap(), 0);
+
+        soft.execute().unwrap();
+        assert_eq!(soft.pc, 0x1008);
+        assert_eq!(soft.registers[Register::X10 as usize], 5);
+    }
+
+    #[test]
+    fn test_sanitizer_use_after_free() {
+        let mut soft = SoftThread::default();
+        let program = [
+            0xb7, 0x08, 0x01, 0x00, // lui a7, 0x10
+            0x13, 0x05, 0x00, 0x01, // li a0, 16
+            0x73, 0x00, 0x00, 0x00, // ecall (malloc)
+            0x13, 0x04, 0x05, 0x00, // mv s0, a0
+            0x23, 0x30, 0xb4, 0x00, // sd a1, 0(s0)
+            0x93, 0x88, 0x18, 0x00, // addi a7, a7, 1
+            0x73, 0x00, 0x00, 0x00, // ecall (free)
+            0x23, 0x30, 0xb4, 0x00, // sd a1, 0(s0)
+        ];
+        soft.load_image(&program, 0x1000).unwrap();
+        soft.registers[Register::X2 as usize] = 0x10_0000;
+        soft.enable_sanitizer();
+
+        soft.step_n(5).unwrap();
+        let ptr = soft.registers[Register::X8 as usize];
+        assert_eq!(ptr, SANITIZER_HEAP_BASE);
+        let sanitizer = soft.sanitizer.as_ref().unwrap();
+        assert_eq!(sanitizer.shadow_map[&ptr], MemoryState { allocated: true, initialized: true });
+        assert_eq!(sanitizer.shadow_map[&(ptr + 8)], MemoryState { allocated: true, initialized: false });
+
+        soft.step_n(2).unwrap();
+        assert_eq!(soft.execute(), Err(Exception::SanitizerError(SanitizerKind::WriteToUnallocated)));
+        assert_eq!(soft.pc, 0x101c);
+    }
+
+    #[test]
+    fn test_sanitizer_stack_overflow() {
+        let mut soft = SoftThread::default();
+        // addi sp, sp, -16 twice
+        soft.load_image(&[0x13, 0x01, 0x01, 0xff].repeat(2), 0x1000).unwrap();
+        soft.registers[Register::X2 as usize] = 0x10_0000;
+        soft.enable_sanitizer();
+        soft.sanitizer.as_mut().unwrap().stack_size = 16;
+
+        soft.execute().unwrap();
+        assert_eq!(soft.execute(), Err(Exception::StackOverflow));
+        assert_eq!(soft.registers[Register::X2 as usize], 0x10_0000 - 16);
+        assert_eq!(soft.pc, 0x1004);
+
+        // mv sp, zero
+        soft.load_image(&[0x13, 0x01, 0x00, 0x00], 0x1000).unwrap();
+        assert_eq!(soft.execute(), Err(Exception::StackOverflow));
+        assert_eq!(soft.registers[Register::X2 as usize], 0x10_0000 - 16);
+        assert_eq!(soft.pc, 0x1000);
+    }
+
+    // Run the store buffering litmus test, in which each hart stores to one
+    // location and then loads the other, returning what each hart loaded.
+    fn store_buffering(model: Option<MemoryModel>, fence: bool) -> (u64, u64) {
+        let mut cpu = Cpu::with_harts(2);
+        let barrier: &[u8] = if fence { &[0x0f, 0x00, 0x20, 0x01] } else { &[0x13, 0x00, 0x00, 0x00] };
+        let programs = [
+            [&[0x23, 0x30, 0xb6, 0x00][..], barrier, &[0x03, 0xb5, 0x06, 0x00], &[0x13, 0x00, 0x00, 0x00]].concat(), // sd a1, 0(a2); ld a0, 0(a3)
+            [&[0x23, 0xb0, 0xb6, 0x00][..], barrier, &[0x03, 0x35, 0x06, 0x00], &[0x13, 0x00, 0x00, 0x00]].concat(), // sd a1, 0(a3); ld a0, 0(a2)
+        ];
+        for (core, program) in cpu.cores.iter_mut().zip(programs.iter()) {
+            core.load_image(program, 0x200).unwrap();
+            core.registers[Register::X11 as usize] = 1;
+            core.registers[Register::X12 as usize] = 0x1000;
+            core.registers[Register::X13 as usize] = 0x1008;
+        }
+        if let Some(model) = model {
+            cpu.set_memory_model(model);
+        }
+
+        cpu.run().unwrap();
+
+        (cpu.cores[0].registers[Register::X10 as usize], cpu.cores[1].registers[Register::X10 as usize])
+    }
+
+    #[test]
+    fn test_memory_model_store_buffering() {
+        assert_eq!(store_buffering(Some(MemoryModel::new(MemoryOrdering::SequentiallyConsistent)), false), (1, 1));
+        assert_eq!(store_buffering(Some(MemoryModel::new(MemoryOrdering::Tso)), false), (0, 0));
+        assert_eq!(store_buffering(Some(MemoryModel::new(MemoryOrdering::Rvwmo)), false), (0, 0));
+        assert_eq!(store_buffering(Some(MemoryModel::new(MemoryOrdering::Rvwmo)), true), (1, 1));
+        assert_eq!(store_buffering(None, false), (0, 0));
+    }
+
+    #[test]
+    fn test_store_buffer_drains_for_spin_waits() {
+        for ordering in [MemoryOrdering::Tso, MemoryOrdering::Rvwmo] {
+            let mut cpu = Cpu::with_harts(2);
+            let programs: [&[u8]; 2] = [
+                // sd a1, 0(a2); 1: ld a4, 0(a3); beqz a4, 1b
+                &[0x23, 0x30, 0xb6, 0x00, 0x03, 0xb7, 0x06, 0x00, 0xe3, 0x0e, 0x07, 0xfe],
+                // 1: ld a0, 0(a2); beqz a0, 1b; sd a1, 0(a3)
+                &[0x03, 0x35, 0x06, 0x00, 0xe3, 0x0e, 0x05, 0xfe, 0x23, 0xb0, 0xb6, 0x00],
+            ];
+            for (core, program) in cpu.cores.iter_mut().zip(programs) {
+                core.load_image(program, 0x200).unwrap();
+                core.registers[Register::X11 as usize] = 1;
+                core.registers[Register::X12 as usize] = 0x1000;
+                core.registers[Register::X13 as usize] = 0x1008;
+            }
+            cpu.set_memory_model(MemoryModel::new(ordering));
+
+            cpu.run().unwrap();
+            assert_eq!(cpu.cores[1].registers[Register::X10 as usize], 1);
+            assert_eq!(cpu.cores[0].registers[Register::X14 as usize], 1);
+        }
+    }
+
+    #[test]
+    fn test_failed_store_conditional_is_not_buffered() {
+        let mut cpu = Cpu::with_harts(2);
+        // sc.w a0, a1, (a2) without a reservation
+        cpu.cores[0].load_image(&[0x2f, 0x25, 0xb6, 0x18], 0x200).unwrap();
+        cpu.cores[0].registers[Register::X11 as usize] = 5;
+        cpu.cores[0].registers[Register::X12 as usize] = 0x1000;
+        cpu.cores[1].load_image(&[0x13, 0x00, 0x00, 0x00], 0x200).unwrap();
+        cpu.cores[1].load_raw(0x1000, &7u32.to_le_bytes()).unwrap();
+        cpu.set_memory_model(MemoryModel::new(MemoryOrdering::Tso));
+
+        cpu.run().unwrap();
+        assert_eq!(cpu.cores[0].registers[Register::X10 as usize], 1);
+        assert_eq!(cpu.cores[1].bus.read(&0x1000, 32).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_store_buffer_drain_order() {
+        let mut buffer = StoreBuffer::new();
+        buffer.push(0x10, vec![1]);
+        buffer.push(0x20, vec![2]);
+        buffer.push(0x10, vec![3]);
+
+        let mut tso = buffer.clone();
+        assert_eq!(tso.pop(MemoryOrdering::Tso), Some((0x10, vec![1])));
+        assert_eq!(tso.len(), 2);
+
+        assert_eq!(buffer.pop(MemoryOrdering::Rvwmo), Some((0x10, vec![3])));
+        assert_eq!(buffer.pop(MemoryOrdering::Rvwmo), Some((0x20, vec![2])));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_barrier_sync_publishes_phase_one_stores() {
+        let mut cpu = Cpu::with_harts(4);
+        cpu.set_memory_model(MemoryModel::new(MemoryOrdering::Tso));
+        // Phase 1 stores mhartid + 1 to 0x2000 + 8 * mhartid, the barrier is
+        // the fence and phase 2 sums the four slots into a0.
+        let code = [
+            0xf3, 0x22, 0x40, 0xf1, 0x13, 0x93, 0x32, 0x00, 0xb7, 0x23, 0x00, 0x00, 0x33, 0x03, 0x73, 0x00,
+            0x13, 0x8e, 0x12, 0x00, 0x23, 0x30, 0xc3, 0x01, 0x0f, 0x00, 0x30, 0x03, 0x83, 0xbe, 0x03, 0x00,
+            0x33, 0x05, 0xd5, 0x01, 0x83, 0xbe, 0x83, 0x00, 0x33, 0x05, 0xd5, 0x01, 0x83, 0xbe, 0x03, 0x01,
+            0x33, 0x05, 0xd5, 0x01, 0x83, 0xbe, 0x83, 0x01, 0x33, 0x05, 0xd5, 0x01,
+        ];
+        cpu.load_program_all_harts(&code, 0x1000).unwrap();
+
+        cpu.barrier_sync(0x1018).unwrap();
+        for core in cpu.cores.iter() {
+            assert_eq!(core.pc, 0x1018);
+            for slot in 0..4u64 {
+                assert_eq!(core.bus.read(&(0x2000 + 8 * slot), 64).unwrap(), slot + 1);
+            }
+        }
+
+        cpu.run().unwrap();
+        assert!(cpu.cores.iter().all(|core| core.registers[Register::X10 as usize] == 10));
+        assert_eq!(cpu.barrier_sync(0x1018), Err(Exception::BarrierNotReached));
+    }
+
+    #[test]
+    fn test_clint_ipi_between_harts() {
+        let mut cpu = Cpu::with_harts(2);
+        let sender = [
+            0xb7, 0x02, 0x00, 0x02, // lui t0, 0x2000
+            0x93, 0x82, 0x42, 0x00, // addi t0, t0, 4
+            0x13, 0x03, 0x10, 0x00, // li t1, 1
+            0x23, 0xa0, 0x62, 0x00, // sw t1, 0(t0)
+        ];
+        let receiver = [
+            &[0x17, 0x03, 0x00, 0x00][..], // auipc t1, 0
+            &[0x13, 0x03, 0x03, 0x03],     // addi t1, t1, 48
+            &[0xf3, 0x13, 0x53, 0x30],     // csrrw t2, mtvec, t1
+            &[0x93, 0x02, 0x80, 0x00],     // li t0, 8
+            &[0x73, 0xa0, 0x42, 0x30],     // csrs mie, t0
+            &[0x73, 0xa0, 0x02, 0x30],     // csrs mstatus, t0
+            &[0x13, 0x00, 0x00, 0x00].repeat(8), // nops, the last two being the handler
+        ].concat();
+        cpu.cores[0].load_image(&sender, 0x1000).unwrap();
+        cpu.cores[1].load_image(&receiver, 0x1000).unwrap();
+
+        cpu.run().unwrap();
+
+        let hart = &cpu.cores[1];
+        assert_ne!(hart.csr[CSR_MIP as usize] & InterruptCause::MachineSoftware.mip_bit(), 0);
+        assert_eq!(hart.csr[CSR_MCAUSE as usize], MCAUSE_INTERRUPT | 3);
+        assert_eq!(hart.csr[CSR_MEPC as usize], 0x1018);
+        assert_eq!(hart.csr[CSR_MSTATUS as usize] & MSTATUS_MIE, 0);
+        assert_eq!(cpu.cores[0].csr[CSR_MIP as usize], 0);
+    }
+
+    #[test]
+    fn test_harts_share_one_clint() {
+        let mut cpu = Cpu::with_harts(2);
+        cpu.attach_clint(1);
+        // Hart 0 sets hart 1's msip and mtimecmp, then reads both back.
+        let sender = [
+            "lui t0, 0x2000", "addi t1, zero, 1", "sw t1, 4(t0)", "lw a0, 4(t0)",
+            "lui t2, 0x2004", "sd t1, 8(t2)", "ld a1, 8(t2)",
+        ];
+        let code: Vec<u8> = sender.iter().enumerate()
+            .flat_map(|(idx, asm)| Instruction::from_assembly(asm, idx as u64 * 4).unwrap().encode().unwrap().to_le_bytes())
+            .collect();
+        cpu.cores[0].load_image(&code, 0).unwrap();
+        for _ in 0..sender.len() {
+            cpu.cores[0].execute().unwrap();
+        }
+        assert_eq!(cpu.cores[0].registers[Register::X10 as usize], 1);
+        assert_eq!(cpu.cores[0].registers[Register::X11 as usize], 1);
+
+        let msip = InterruptCause::MachineSoftware.mip_bit();
+        let mtip = InterruptCause::MachineTimer.mip_bit();
+        cpu.cores[1].load_image(&NOP.to_le_bytes(), 0).unwrap();
+        cpu.cores[1].execute().unwrap();
+        assert_eq!(cpu.cores[1].csr[CSR_MIP as usize] & (msip | mtip), msip | mtip);
+        assert_eq!(cpu.cores[0].csr[CSR_MIP as usize] & (msip | mtip), 0);
+
+        // Clearing msip through the CLINT clears MSIP.
+        cpu.clint.as_ref().unwrap().borrow_mut().msip[1] = 0;
+        cpu.cores[1].pc = 0;
+        cpu.cores[1].execute().unwrap();
+        assert_eq!(cpu.cores[1].csr[CSR_MIP as usize] & msip, 0);
+        assert_eq!(cpu.clint.as_ref().unwrap().borrow().mtime, 9);
+
+        // A fork keeps the shared CLINT but not one of its own.
+        assert_eq!(cpu.cores[1].fork().mmio.len(), 1);
+        let mut soft = SoftThread::default();
+        soft.attach_clint(1);
+        assert!(soft.fork().mmio.is_empty());
+    }
+
+    #[test]
+    fn test_broadcast_interrupt_and_send_ipi() {
+        let mut cpu = Cpu::with_harts(3);
+        cpu.send_ipi(0, 2, InterruptCause::MachineSoftware);
+        let mip: Vec<u64> = cpu.cores.iter().map(|core| core.csr[CSR_MIP as usize]).collect();
+        assert_eq!(mip, vec![0, 0, 1 << 3]);
+
+        cpu.broadcast_interrupt(1, InterruptCause::SupervisorSoftware);
+        let mip: Vec<u64> = cpu.cores.iter().map(|core| core.csr[CSR_MIP as usize]).collect();
+        assert_eq!(mip, vec![1 << 1, 0, (1 << 3) | (1 << 1)]);
+    }
+
+    #[test]
+    fn test_speed_monitor_reports_mips() {
+        let mut soft = SoftThread::default();
+        // addi t0, t0, 1 repeated
+        let program = [0x93u8, 0x82, 0x12, 0x00].repeat(1024).iter().rev().copied().collect();
+        soft.load_program(program).unwrap();
+        soft.enable_speed_monitor(std::time::Duration::from_millis(10));
+
+        let reports = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = reports.clone();
+        soft.set_speed_callback(Box::new(move |_| counter.set(counter.get() + 1)));
+
+        let start = std::time::Instant::now();
+        while start.elapsed() < std::time::Duration::from_millis(100) {
+            soft.pc = 0;
+            soft.run_until_halt().unwrap();
+        }
+
+        let speed = soft.speed.as_ref().unwrap();
+        assert!(speed.current_mips() > 0.0);
+        assert!(speed.instructions() > 0);
+        assert!(reports.get() > 0);
+    }
+
+    // A minimal RV64 executable with a single RWX segment covering the
+    // whole file, loaded at `vaddr` with the code following the headers.
+    fn build_elf(vaddr: u64, code: &[u8]) -> Vec<u8> {
+        let mut elf = vec![0u8; 64 + 56];
+        elf[..4].copy_from_slice(&[0x7f, b'E', b'L', b'F']);
+        elf[4] = 2;
+        elf[5] = 1;
+        elf[6] = 1;
+        elf[16..18].copy_from_slice(&2u16.to_le_bytes());
+        elf[18..20].copy_from_slice(&243u16.to_le_bytes());
+        elf[24..32].copy_from_slice(&(vaddr + 120).to_le_bytes());
+        elf[32..40].copy_from_slice(&64u64.to_le_bytes());
+        elf[54..56].copy_from_slice(&56u16.to_le_bytes());
+        elf[56..58].copy_from_slice(&1u16.to_le_bytes());
+
+        let size = (120 + code.len()) as u64;
+        elf[64..68].copy_from_slice(&1u32.to_le_bytes());
+        elf[68..72].copy_from_slice(&7u32.to_le_bytes());
+        elf[80..88].copy_from_slice(&vaddr.to_le_bytes());
+        elf[88..96].copy_from_slice(&vaddr.to_le_bytes());
+        elf[96..104].copy_from_slice(&size.to_le_bytes());
+        elf[104..112].copy_from_slice(&size.to_le_bytes());
+
+        elf.extend_from_slice(code);
+        elf
+    }
+
+    #[test]
+    fn test_run_elf_hello_world() {
+        let mut code = vec![
+            0x13, 0x05, 0x10, 0x00, // addi a0, x0, 1
+            0xb7, 0x05, 0x01, 0x00, // lui a1, 0x10
+            0x93, 0x85, 0xc5, 0x09, // addi a1, a1, 0x9c
+            0x13, 0x06, 0xe0, 0x00, // addi a2, x0, 14
+            0x93, 0x08, 0x00, 0x04, // addi a7, x0, 64
+            0x73, 0x00, 0x00, 0x00, // ecall
+            0x03, 0x35, 0x01, 0x00, // ld a0, 0(sp)
+            0x93, 0x08, 0xd0, 0x05, // addi a7, x0, 93
+            0x73, 0x00, 0x00, 0x00, // ecall
+        ];
+        code.extend_from_slice(b"Hello, world!\n");
+
+        let path = std::env::temp_dir().join(format!("trecho_hello_{}.elf", std::process::id()));
+        std::fs::write(&path, build_elf(0x10000, &code)).unwrap();
+
+        let mut soft = SoftThread::default();
+        let mut stdout = vec![];
+        let code = soft.run_elf_with_io(&path, &["hello", "world"], &mut std::io::empty(), &mut stdout);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(code.unwrap(), 2);
+        assert_eq!(String::from_utf8(stdout).unwrap(), "Hello, world!\n");
+    }
+
+    #[test]
+    fn test_load_elf_and_run_exit_code() {
+        let mut code = vec![
+            0x13, 0x05, 0x20, 0x00, // addi a0, x0, 2
+            0x93, 0x05, 0x80, 0x09, // addi a1, x0, 152
+            0x13, 0x06, 0x30, 0x00, // addi a2, x0, 3
+            0x93, 0x08, 0x00, 0x04, // addi a7, x0, 64
+            0x73, 0x00, 0x00, 0x00, // ecall
+            0x13, 0x05, 0xa0, 0x02, // addi a0, x0, 42
+            0x93, 0x08, 0xe0, 0x05, // addi a7, x0, 94
+            0x73, 0x00, 0x00, 0x00, // ecall
+        ];
+        code.extend_from_slice(b"err");
+
+        let path = std::env::temp_dir().join(format!("trecho_exit42_{}.elf", std::process::id()));
+        std::fs::write(&path, build_elf(0, &code)).unwrap();
+
+        let mut soft = SoftThread::default();
+        // The counters wrap during the run.
+        soft.csr[CSR_MINSTRET as usize] = u64::MAX - 2;
+        soft.csr[CSR_MCYCLE as usize] = u64::MAX - 2;
+        let status = soft.load_elf_and_run(&path, &["exit42"], 1000);
+        std::fs::remove_file(&path).unwrap();
+
+        let status = status.unwrap();
+        assert_eq!(status.code, 42);
+        assert_eq!(status.exception, None);
+        assert_eq!(status.instructions, 6);
+        assert_eq!(status.cycles, 6);
+        assert_eq!(status.stderr, b"err");
+        assert!(status.stdout.is_empty());
+    }
+
+    #[test]
+    fn test_load_elf_and_run_times_out() {
+        let path = std::env::temp_dir().join(format!("trecho_spin_{}.elf", std::process::id()));
+        std::fs::write(&path, build_elf(0, &[0x6f, 0x00, 0x00, 0x00])).unwrap();
+
+        let mut soft = SoftThread::default();
+        let status = soft.load_elf_and_run(&path, &["spin"], 100);
+        std::fs::remove_file(&path).unwrap();
+
+        let status = status.unwrap();
+        assert_eq!(status.exception, Some(Exception::Timeout));
+        assert_eq!(status.instructions, 100);
+    }
+
+    // A `Write` whose bytes can still be read after it is boxed.
+    #[derive(Clone, Default)]
+    struct SharedBuf(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_strace_mode_logs_syscalls() {
+        let mut code = vec![
+            0x13, 0x05, 0x10, 0x00, // addi a0, x0, 1
+            0xb7, 0x05, 0x01, 0x00, // lui a1, 0x10
+            0x93, 0x85, 0xc5, 0x09, // addi a1, a1, 0x9c
+            0x13, 0x06, 0x30, 0x00, // addi a2, x0, 3
+            0x93, 0x08, 0x00, 0x04, // addi a7, x0, 64
+            0x73, 0x00, 0x00, 0x00, // ecall
+            0x13, 0x05, 0x00, 0x00, // addi a0, x0, 0
+            0x93, 0x08, 0xd0, 0x05, // addi a7, x0, 93
+            0x73, 0x00, 0x00, 0x00, // ecall
+        ];
+        code.extend_from_slice(b"hi\n");
+
+        let path = std::env::temp_dir().join(format!("trecho_strace_{}.elf", std::process::id()));
+        std::fs::write(&path, build_elf(0x10000, &code)).unwrap();
+
+        let log = SharedBuf::default();
+        let mut soft = SoftThread::default();
+        soft.strace_mode(Box::new(log.clone()));
+        let mut stdout = vec![];
+        let code = soft.run_elf_with_io(&path, &["hi"], &mut std::io::empty(), &mut stdout);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(code.unwrap(), 0);
+        let log = String::from_utf8(log.0.borrow().clone()).unwrap();
+        assert_eq!(log, "[0x1008c] write(fd=1, buf=0x1009c \"hi\\n\", count=3) = 3\n[0x10098] exit(status=0) = ?\n");
+    }
+
+    #[test]
+    fn test_setup_stack_layout() {
+        let mut soft = SoftThread::default();
+        let top = soft.bus.mem.len() as u64;
+        let sp = crate::linux::setup_stack(&mut soft, &["prog", "-v"], &["HOME=/"], 0x10078, top).unwrap();
+        let word = |addr: u64| soft.bus.read(&addr, 64).unwrap();
+        let string = |addr: u64, len: usize| {
+            let mut buf = vec![0u8; len];
+            soft.store_raw(addr, &mut buf).unwrap();
+            buf
+        };
+
+        assert_eq!(sp % 16, 0);
+        assert_eq!(word(sp), 2);
+        assert_eq!(string(word(sp + 8), 5), b"prog\0");
+        assert_eq!(string(word(sp + 16), 3), b"-v\0");
+        assert_eq!(word(sp + 24), 0);
+        assert_eq!(string(word(sp + 32), 7), b"HOME=/\0");
+        assert_eq!(word(sp + 40), 0);
+        assert_eq!(word(sp + 48), crate::linux::AT_PAGESZ);
+        assert_eq!(word(sp + 56), 4096);
+        assert_eq!(word(sp + 64), crate::linux::AT_ENTRY);
+        assert_eq!(word(sp + 72), 0x10078);
+    }
+
+    #[test]
+    fn test_linux_syscalls_reject_oversized_arguments() {
+        let mut soft = SoftThread::default();
+        let (mut stdin, mut stdout) = (std::io::empty(), vec![]);
+        let mut syscalls = crate::linux::LinuxSyscalls::new(&mut stdin, &mut stdout, 0x1000, 0x10000);
+        let mut call = |soft: &mut SoftThread<u64, f64, Dram>, number: u64, args: [u64; 4]| {
+            soft.registers[Register::X17 as usize] = number;
+            soft.registers[Register::X10 as usize..Register::X14 as usize].copy_from_slice(&args);
+            syscalls.handle(soft).unwrap();
+            soft.registers[Register::X10 as usize] as i64
+        };
+
+        let anonymous = crate::linux::MAP_ANONYMOUS;
+        assert_eq!(call(&mut soft, crate::linux::SYS_MMAP, [0, u64::MAX, 0, anonymous]), -crate::linux::EINVAL);
+        assert_eq!(call(&mut soft, crate::linux::SYS_MMAP, [0, u64::MAX - 0x2000, 0, anonymous]), -crate::linux::ENOMEM);
+        assert_eq!(call(&mut soft, crate::linux::SYS_WRITE, [1, 0, u64::MAX, 0]), -crate::linux::EFAULT);
+        assert_eq!(call(&mut soft, crate::linux::SYS_READ, [0, 0, u64::MAX, 0]), -crate::linux::EFAULT);
+        assert_eq!(call(&mut soft, crate::linux::SYS_MMAP, [0, 0x1000, 0, anonymous]), 0xf000);
+    }
+
+    #[test]
+    fn test_linux_syscalls_fault_on_bad_pointers() {
+        let mut soft = SoftThread::default();
+        let end = soft.bus.mem.len() as u64;
+        let (mut stdin, mut stdout) = (&b"input"[..], vec![]);
+        let mut syscalls = crate::linux::LinuxSyscalls::new(&mut stdin, &mut stdout, 0x1000, 0x10000);
+        let mut call = |soft: &mut SoftThread<u64, f64, Dram>, number: u64, args: [u64; 4]| {
+            soft.registers[Register::X17 as usize] = number;
+            soft.registers[Register::X10 as usize..Register::X14 as usize].copy_from_slice(&args);
+            assert_eq!(syscalls.handle(soft), Ok(crate::linux::SyscallResult::Continue));
+            soft.registers[Register::X10 as usize] as i64
+        };
+
+        let efault = -crate::linux::EFAULT;
+        assert_eq!(call(&mut soft, crate::linux::SYS_READ, [0, end - 2, 4, 0]), efault);
+        assert_eq!(call(&mut soft, crate::linux::SYS_WRITE, [1, end, 1, 0]), efault);
+        assert_eq!(call(&mut soft, crate::linux::SYS_WRITEV, [1, end - 8, 1, 0]), efault);
+        soft.load_raw(0x100, &end.to_le_bytes()).unwrap();
+        soft.load_raw(0x108, &1u64.to_le_bytes()).unwrap();
+        assert_eq!(call(&mut soft, crate::linux::SYS_WRITEV, [1, 0x100, 1, 0]), efault);
+        assert_eq!(call(&mut soft, crate::linux::SYS_READ, [0, end - 2, 2, 0]), 2);
+        drop(call);
+        assert!(stdout.is_empty());
+    }
+
+    #[test]
+    fn test_elf_parse_rejects_bad_input() {
+        let mut elf = build_elf(0x10000, &[0x73, 0x00, 0x00, 0x00]);
+        assert_eq!(Elf::parse(&elf[..32]), Err(ElfError::Truncated));
+        assert_eq!(Elf::parse(&elf).unwrap().entry, 0x10078);
+
+        let mut huge = elf.clone();
+        huge[96..104].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(Elf::parse(&huge), Err(ElfError::Truncated));
+        let mut huge = elf.clone();
+        huge[32..40].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(Elf::parse(&huge), Err(ElfError::Truncated));
+
+        let mut segment = Elf::parse(&elf).unwrap();
+        segment.segments[0].memsz = u64::MAX;
+        assert_eq!(SoftThread::default().load_elf(&segment), Err(Exception::InvalidAddr));
+
+        elf[18] = 62;
+        assert_eq!(Elf::parse(&elf), Err(ElfError::UnsupportedMachine(62)));
+
+        elf[0] = 0;
+        assert_eq!(Elf::parse(&elf), Err(ElfError::BadMagic));
+    }
+
+    #[test]
+    fn test_elf_parse_reads_symbol_table() {
+        let mut elf = build_elf(0x10000, &[0x73, 0x00, 0x00, 0x00]);
+        let strtab = elf.len();
+        elf.extend_from_slice(b"\0_start\0$x\0");
+
+        // A null symbol, `_start` as a function and a `$x` mapping symbol.
+        let symtab = elf.len();
+        elf.extend_from_slice(&[0u8; 24]);
+        for (name, info, value) in [(1u32, 0x12u8, 0x10078u64), (8, 0x00, 0x10078)] {
+            let mut sym = [0u8; 24];
+            sym[..4].copy_from_slice(&name.to_le_bytes());
+            sym[4] = info;
+            sym[6..8].copy_from_slice(&1u16.to_le_bytes());
+            sym[8..16].copy_from_slice(&value.to_le_bytes());
+            elf.extend_from_slice(&sym);
+        }
+
+        let shoff = elf.len();
+        elf.extend_from_slice(&[0u8; 64 * 3]);
+        let shdr = |idx: usize| shoff + idx * 64;
+        elf[shdr(1) + 4..shdr(1) + 8].copy_from_slice(&2u32.to_le_bytes());
+        elf[shdr(1) + 24..shdr(1) + 32].copy_from_slice(&(symtab as u64).to_le_bytes());
+        elf[shdr(1) + 32..shdr(1) + 40].copy_from_slice(&72u64.to_le_bytes());
+        elf[shdr(1) + 40..shdr(1) + 44].copy_from_slice(&2u32.to_le_bytes());
+        elf[shdr(2) + 24..shdr(2) + 32].copy_from_slice(&(strtab as u64).to_le_bytes());
+        elf[40..48].copy_from_slice(&(shoff as u64).to_le_bytes());
+        elf[58..60].copy_from_slice(&64u16.to_le_bytes());
+        elf[60..62].copy_from_slice(&3u16.to_le_bytes());
+
+        let parsed = Elf::parse(&elf).unwrap();
+        assert_eq!(parsed.symbols, std::collections::HashMap::from([(0x10078, "_start".to_string())]));
+
+        let mut soft = SoftThread::default();
+        soft.load_elf(&parsed).unwrap();
+        assert_eq!(soft.symbol_at(0x1007c), Some(("_start", 4)));
+
+        // A bad section header table loses the symbols but not the program.
+        for (at, bad) in [(40, u64::MAX), (shdr(1) + 32, u64::MAX), (shdr(1) + 40, 7)] {
+            let mut elf = elf.clone();
+            elf[at..at + 8].copy_from_slice(&bad.to_le_bytes());
+            let parsed = Elf::parse(&elf).unwrap();
+            assert!(parsed.symbols.is_empty());
+            assert_eq!(parsed.entry, 0x10078);
+        }
+    }
+
+    // Map the virtual page 0x0040_5000 to the physical page 0x0030_0000
+    // through a root table at 0x0010_0000 and a leaf table at 0x0010_1000.
+    fn sv32_table(flags: u64) -> (Dram, u32) {
+        let mut dram = Dram::new();
+        let root = 0x0010_0000u64;
+        let leaf = 0x0010_1000u64;
+        let vaddr = 0x0040_5000u64;
+
+        dram.write(root + (vaddr >> 22) * 4, ((leaf >> 12) << 10) | PTE_V, 32).unwrap();
+        dram.write(leaf + ((vaddr >> 12) & 0x3ff) * 4, ((0x0030_0000 >> 12) << 10) | flags, 32).unwrap();
+        dram.write(0x0030_0123, 0xab, 8).unwrap();
+
+        (dram, (1 << 31) | (root >> 12) as u32)
+    }
+
+    #[test]
+    fn test_walk_sv32_translates_mapped_page() {
+        let (dram, satp) = sv32_table(PTE_V | PTE_R | PTE_A);
+        let paddr = walk_sv32(satp, 0x0040_5123, &dram, AccessType::Load).unwrap();
+
+        assert_eq!(paddr, 0x0030_0123);
+        assert_eq!(dram.read(&(paddr as u64), 8).unwrap(), 0xab);
+    }
+
+    #[test]
+    fn test_walk_sv32_faults() {
+        let (dram, satp) = sv32_table(PTE_V | PTE_R | PTE_A);
+
+        assert_eq!(walk_sv32(satp, 0x0040_6000, &dram, AccessType::Load), Err(Exception::LoadPageFault(0x0040_6000)));
+        assert_eq!(walk_sv32(satp, 0x0040_5000, &dram, AccessType::Store), Err(Exception::StoreAMOPageFault(0x0040_5000)));
+        assert_eq!(walk_sv32(satp, 0x0040_5000, &dram, AccessType::Instruction), Err(Exception::InstructionPageFault(0x0040_5000)));
+
+        let (dram, satp) = sv32_table(PTE_V | PTE_R);
+        assert_eq!(walk_sv32(satp, 0x0040_5000, &dram, AccessType::Load), Err(Exception::LoadPageFault(0x0040_5000)));
+    }
+
+    #[test]
+    fn test_u32_register_value_arithmetic() {
+        assert_eq!(<u32 as RegisterValue>::XLEN, 32);
+        assert_eq!(<u64 as RegisterValue>::XLEN, 64);
+
+        assert_eq!(u32::MAX.oflow_add(&2), 1);
+        assert_eq!(0u32.oflow_sub(&1), u32::MAX);
+        assert_eq!((-7i32 as u32).less_than_signed(&1), 1);
+        assert_eq!((-7i32 as u32).less_than(&1), 0);
+
+        assert_eq!((-7i32 as u32).oflow_div_signed(&2), -3i32 as u32);
+        assert_eq!((i32::MIN as u32).oflow_div_signed(&(-1i32 as u32)), i32::MIN as u32);
+        assert_eq!(5u32.oflow_div_signed(&0), u32::MAX);
+        assert_eq!((-7i32 as u32).oflow_rem_signed(&2), -1i32 as u32);
+        assert_eq!((i32::MIN as u32).oflow_rem_signed(&(-1i32 as u32)), 0);
+
+        assert_eq!((-2i32 as u32).oflow_mul_high_signed(&3), u32::MAX);
+        assert_eq!(u32::MAX.oflow_mul_high_unsigned(&u32::MAX), 0xffff_fffe);
+        assert_eq!((-2i32 as u32).oflow_mul_high_signed_unsigned(&u32::MAX), -2i32 as u32);
+
+        assert_eq!(0x8000_0000u32.shr_signed(&4), 0xf800_0000);
+        assert_eq!(0xdead_beefu32.zero_extend(&32), 0xdead_beef);
+        assert_eq!(0xdead_beefu32.zero_extend(&16), 0xbeef);
+        assert_eq!(0x0000_8000u32.sign_extend(&16), 0xffff_8000);
+        assert_eq!(0x1234_5678u32.revb(), 0x7856_3412);
+    }
+
+    #[test]
+    fn test_branch_to_non_executable_region_faults() {
+        let mut soft = SoftThread::default();
+        // jalr x0, 0(a1)
+        soft.load_image(&[0x67, 0x80, 0x05, 0x00], 0x200).unwrap();
+        soft.registers[Register::X11 as usize] = 0x300;
+        soft.add_region(MemoryRegion::new(0x200, 0x100, AccessFlags::READ | AccessFlags::EXECUTE));
+        soft.add_region(MemoryRegion::new(0x300, 0x100, AccessFlags::READ | AccessFlags::WRITE));
+
+        soft.execute().unwrap();
+        assert_eq!(soft.pc, 0x300);
+        assert_eq!(soft.execute(), Err(Exception::AccessFault));
+    }
+
+    #[test]
+    fn test_store_to_read_only_region_faults() {
+        let mut soft = SoftThread::default();
+        // sw a0, 0(a1)
+        soft.load_image(&[0x23, 0xa0, 0xa5, 0x00], 0x200).unwrap();
+        soft.add_region(MemoryRegion::new(0x1000, 0x1000, AccessFlags::READ));
+        soft.registers[Register::X10 as usize] = 0xdead;
+        soft.registers[Register::X11 as usize] = 0x1800;
+
+        assert_eq!(soft.execute(), Err(Exception::StoreAMOAccessFault));
+        assert_eq!(soft.bus.read(&0x1800, 32).unwrap(), 0);
+
+        soft.mprotect(0x1800, 0x100, AccessFlags::READ | AccessFlags::WRITE).unwrap();
+        soft.execute().unwrap();
+        assert_eq!(soft.bus.read(&0x1800, 32).unwrap(), 0xdead);
+    }
+
+    #[test]
+    fn test_mprotect_splits_regions() {
+        let mut soft = SoftThread::default();
+        soft.add_region(MemoryRegion::new(0x1000, 0x3000, AccessFlags::ALL));
+        soft.mprotect(0x2000, 0x1000, AccessFlags::NONE).unwrap();
+
+        assert_eq!(soft.regions, vec![
+            MemoryRegion::new(0x1000, 0x1000, AccessFlags::ALL),
+            MemoryRegion::new(0x2000, 0x1000, AccessFlags::NONE),
+            MemoryRegion::new(0x3000, 0x1000, AccessFlags::ALL),
+        ]);
+        assert_eq!(soft.check_access(0x2800, AccessType::Load), Err(Exception::LoadAccessFault));
+        assert!(soft.check_access(0x3800, AccessType::Load).is_ok());
+        assert_eq!(soft.mprotect(0x8000, 0x10, AccessFlags::READ), Err(MprotectError::NotMapped));
+        assert_eq!(soft.mprotect(0x1000, 0, AccessFlags::READ), Err(MprotectError::InvalidRange));
+    }
+
+    #[test]
+    fn test_ring_buffer_overwrites_oldest() {
+        let mut ring = RingBuffer::<u32, 4>::new();
+        assert!(ring.is_empty());
+
+        (0..3).for_each(|i| ring.push(i));
+        assert_eq!(ring.iter().copied().collect::<Vec<u32>>(), vec![0, 1, 2]);
+
+        (3..10).for_each(|i| ring.push(i));
+        assert_eq!(ring.len(), 4);
+        assert_eq!(ring.iter().copied().collect::<Vec<u32>>(), vec![6, 7, 8, 9]);
+        assert_eq!(ring.last(), Some(&9));
+
+        let mut sized = DynRingBuffer::new(4);
+        (0..10).for_each(|i| sized.push(i));
+        assert_eq!(sized.capacity(), ring.capacity());
+        assert_eq!(sized.into_iter().collect::<Vec<u32>>(), ring.into_iter().collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_ring_trace_keeps_last_instructions() {
+        let mut soft = SoftThread::default();
+        // addi t0, t0, 1 repeated
+        let program = [0x93u8, 0x82, 0x12, 0x00].repeat(100).iter().rev().copied().collect();
+        soft.load_program(program).unwrap();
+        soft.enable_ring_trace::<16>();
+        soft.run_until_halt().unwrap();
+
+        let pcs: Vec<u64> = soft.ring_trace().map(|entry| entry.pc).collect();
+        assert_eq!(pcs, (84..100).map(|i| i * 4).collect::<Vec<u64>>());
+
+        let last = soft.ring_trace().last().unwrap();
+        assert_eq!(last.raw, 0x0012_8293);
+        assert!(matches!(last.decoded, Instruction::Addi { .. }));
+        assert_eq!(soft.registers[Register::X5 as usize], 100);
+    }
+
+    #[test]
+    fn test_execute_block() {
+        let mut soft = SoftThread::default();
+        soft.pc = 0x1000;
+        // li a0, 1; add a1, a0, a0; add a2, a1, a0; add a3, a2, a1; add a4, a3, a2
+        let block = [0x0010_0513, 0x00a5_05b3, 0x00a5_8633, 0x00b6_06b3, 0x00c6_8733];
+        assert_eq!(soft.execute_block(&block), Ok(5));
+
+        let regs = [Register::X10, Register::X11, Register::X12, Register::X13, Register::X14];
+        let values: Vec<u64> = regs.iter().map(|reg| soft.registers[*reg as usize]).collect();
+        assert_eq!(values, vec![1, 2, 3, 5, 8]);
+        assert_eq!(soft.pc, 0x1014);
+    }
+
+    #[test]
+    fn test_execute_block_stops_at_jump() {
+        let mut soft = SoftThread::default();
+        soft.registers[Register::X5 as usize] = 0x2000;
+        // li a0, 1; jr t0; add a1, a0, a0
+        assert_eq!(soft.execute_block(&[0x0010_0513, 0x0002_8067, 0x00a5_05b3]), Ok(2));
+        assert_eq!(soft.pc, 0x2000);
+        assert_eq!(soft.registers[Register::X11 as usize], 0);
+    }
+
+    #[test]
+    fn test_generate_core_dump() {
+        let path = std::env::temp_dir().join(format!("trecho_core_{}", std::process::id()));
+        let mut soft = SoftThread::default();
+        soft.pc = 0x1004;
+        (1..32).for_each(|idx| soft.registers[idx] = idx as u64 * 0x1111);
+        soft.f_registers[3] = 1.5;
+        soft.add_region(MemoryRegion::new(0x1000, 0x100, AccessFlags::READ | AccessFlags::EXECUTE));
+        soft.load_raw(0x1000, &[0xde, 0xad, 0xbe, 0xef]).unwrap();
+        soft.generate_core_dump(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(u16::from_le_bytes([bytes[16], bytes[17]]), elf::ET_CORE);
+        let core = Elf::parse(&bytes).unwrap();
+        assert_eq!(core.segments.len(), 1);
+        assert_eq!(core.segments[0].vaddr, 0x1000);
+        assert_eq!(core.segments[0].flags, elf::PF_R | elf::PF_X);
+        assert_eq!(core.segments[0].data.len(), 0x100);
+        assert_eq!(core.segments[0].data[..4], [0xde, 0xad, 0xbe, 0xef]);
+
+        let notes = elf::parse_notes(&bytes).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!((notes[0].name.as_str(), notes[0].kind), ("CORE", elf::NT_PRSTATUS));
+        let reg = |idx: usize| {
+            let at = elf::PRSTATUS_REG + idx * 8;
+            u64::from_le_bytes(notes[0].desc[at..at + 8].try_into().unwrap())
+        };
+        assert_eq!(reg(0), 0x1004);
+        assert_eq!(reg(2), 0x2222);
+        assert_eq!(reg(31), 31 * 0x1111);
+
+        assert_eq!(notes[1].kind, elf::NT_PRFPREG);
+        assert_eq!(notes[1].desc.len(), elf::PRFPREG_SIZE);
+        assert_eq!(u64::from_le_bytes(notes[1].desc[24..32].try_into().unwrap()), 1.5f64.to_bits());
+    }
+
+    #[test]
+    fn test_binary_trace_round_trip() {
+        let path = std::env::temp_dir().join(format!("trecho_trace_{}.bin", std::process::id()));
+        let records: Vec<TraceRecord> = (0..1000u64).map(|i| TraceRecord {
+            pc: 0x8000_0000 + i * 4,
+            raw_inst: 0x0012_8293 ^ i as u32,
+            rd_written: (i % 32) as u8,
+            rd_value: i.wrapping_mul(0x9e37_79b9_7f4a_7c15),
+        }).collect();
+
+        let mut logger = BinaryTraceLogger::open(&path).unwrap();
+        records.iter().for_each(|record| logger.write_record(record));
+        logger.flush_and_close().unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 1000 * 21);
+
+        let read: Vec<TraceRecord> = BinaryTraceReader::open(&path).unwrap().collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, records);
+    }
+
+    #[test]
+    fn test_binary_trace_records_written_register() {
+        let path = std::env::temp_dir().join(format!("trecho_exec_trace_{}.bin", std::process::id()));
+        let mut soft = SoftThread::default();
+        // li a0, 5; addi a1, a0, 1; nop
+        soft.load_image(&[0x13, 0x05, 0x50, 0x00, 0x93, 0x05, 0x15, 0x00, 0x13, 0x00, 0x00, 0x00], 0x1000).unwrap();
+        soft.enable_binary_trace(&path).unwrap();
+        soft.step_n(3).unwrap();
+        soft.trace = None;
+
+        let read: Vec<TraceRecord> = BinaryTraceReader::open(&path).unwrap().collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, vec![
+            TraceRecord { pc: 0x1000, raw_inst: 0x0050_0513, rd_written: 10, rd_value: 5 },
+            TraceRecord { pc: 0x1004, raw_inst: 0x0015_0593, rd_written: 11, rd_value: 6 },
+            TraceRecord { pc: 0x1008, raw_inst: 0x0000_0013, rd_written: 0, rd_value: 0 },
+        ]);
+    }
+
+    #[test]
+    fn test_set_memory_size_grow_and_shrink() {
+        let mut soft = SoftThread::default();
+        assert_eq!(soft.bus.mem.len(), 4 << 20);
+        soft.load_raw((4 << 20) - 8, &0xfeed_f00d_u64.to_le_bytes()).unwrap();
+
+        soft.set_memory_size(8 << 20, true).unwrap();
+        assert_eq!(soft.bus.mem.len(), 8 << 20);
+        assert_eq!(soft.bus.read(&((4 << 20) - 8), 64).unwrap(), 0xfeed_f00d);
+        assert_eq!(soft.bus.read(&((8 << 20) - 8), 64).unwrap(), 0);
+        assert_eq!(soft.registers[Register::X2 as usize], 8 << 20);
+        assert_eq!(soft.bus.flags.len(), (8 << 20) / crate::consts::INDEX_SIZE);
+        assert!(soft.bus.write_array(6 << 20, vec![1; 8]).is_ok());
+        assert!(soft.bus.set_flag(((8 << 20) - 1) >> crate::consts::INDEX_SHIFTS, crate::consts::DIRTY).is_ok());
+
+        soft.csr[CSR_MTVEC as usize] = 0x30_0000;
+        assert_eq!(soft.set_memory_size(2 << 20, false), Err(Exception::AddressInUse));
+        assert_eq!(soft.bus.mem.len(), 8 << 20);
+
+        soft.csr[CSR_MTVEC as usize] = 0x1000;
+        soft.set_memory_size(2 << 20, false).unwrap();
+        let mut buf = [0u8; 8];
+        assert_eq!(soft.store_raw((4 << 20) - 8, &mut buf), Err(Exception::InvalidAddr));
+        assert_eq!(soft.registers[Register::X2 as usize], 8 << 20);
+        assert_eq!(soft.bus.flags.len(), (2 << 20) / crate::consts::INDEX_SIZE);
+        assert!(soft.bus.write_array(3 << 20, vec![1; 8]).is_err());
+    }
+
+    // A 64 byte flattened device tree with an empty root node.
+    fn minimal_dtb() -> Vec<u8> {
+        let header: [u32; 10] = [0xd00d_feed, 64, 40, 60, 40, 17, 16, 0, 4, 20];
+        let mut dtb: Vec<u8> = header.iter().flat_map(|word| word.to_be_bytes()).collect();
+        // FDT_BEGIN_NODE "", FDT_END_NODE, FDT_END, then padding
+        for word in [1u32, 0, 2, 9, 0, 0] {
+            dtb.extend_from_slice(&word.to_be_bytes());
+        }
+        dtb
+    }
+
+    #[test]
+    fn test_load_dtb() {
+        let mut soft = SoftThread::default();
+        let dtb = minimal_dtb();
+        assert_eq!(dtb.len(), 64);
+        soft.load_dtb(&dtb, 0x20_0000).unwrap();
+
+        let mut buf = vec![0u8; 64];
+        soft.store_raw(0x20_0000, &mut buf).unwrap();
+        assert_eq!(buf, dtb);
+        assert_eq!(soft.registers[Register::X11 as usize], 0x20_0000);
+    }
+
+    #[test]
+    fn test_load_dtb_rejects_bad_magic_and_overlap() {
+        let mut soft = SoftThread::default();
+        let mut dtb = minimal_dtb();
+        soft.load_image(&[0x13, 0x00, 0x00, 0x00], 0x1000).unwrap();
+
+        assert!(matches!(soft.load_dtb(&dtb, 0xffc), Err(DtbError::OverlapsCode)));
+
+        dtb[0] = 0;
+        assert!(matches!(soft.load_dtb(&dtb, 0x20_0000), Err(DtbError::BadMagic)));
+        assert_eq!(soft.registers[Register::X11 as usize], 0);
+    }
+
+    // A random mix of the integer ALU instructions the JIT supports, with
+    // the odd slt thrown in to split the code into blocks.
+    fn random_alu_program(count: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) as u32
+        };
+
+        let mut code = vec![];
+        for _ in 0..count {
+            let (rd, rs1, rs2) = (next() & 0x1f, next() & 0x1f, next() & 0x1f);
+            let r_type = |funct7: u32, funct3: u32, opcode: u32| (funct7 << 25) | (rs2 << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | opcode;
+            let i_type = |imm: u32, funct3: u32, opcode: u32| ((imm & 0xfff) << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | opcode;
+            let imm = next();
+            // The decoder only accepts RV32 style five bit shift amounts.
+            let shamt = next() & 0x1f;
+            let inst = match next() % 20 {
+                0 => (imm & 0xffff_f000) | (rd << 7) | 0x37,
+                1 => i_type(imm, 0, 0x13),
+                2 => i_type(imm, 4, 0x13),
+                3 => i_type(imm, 6, 0x13),
+                4 => i_type(imm, 7, 0x13),
+                5 => i_type(shamt, 1, 0x13),
+                6 => i_type(shamt, 5, 0x13),
+                7 => i_type(0x400 | shamt, 5, 0x13),
+                8 => r_type(0, 0, 0x33),
+                9 => r_type(0x20, 0, 0x33),
+                10 => r_type(0, 1, 0x33),
+                11 => r_type(0, 4, 0x33),
+                12 => r_type(0, 5, 0x33),
+                13 => r_type(0x20, 5, 0x33),
+                14 => r_type(0, 6, 0x33),
+                15 => r_type(0, 7, 0x33),
+                16 => i_type(imm, 0, 0x1b),
+                17 => r_type(0, 0, 0x3b),
+                18 => r_type(0x20, 0, 0x3b),
+                _ => r_type(0, 2, 0x33),
+            };
+            code.extend(inst.to_le_bytes());
+        }
+        code
+    }
+
+    #[test]
+    fn test_jit_matches_interpreter() {
+        let code = random_alu_program(10_000, 0x7265_6368_6f);
+        let mut interp = SoftThread::default();
+        let mut jit = SoftThread::default();
+        interp.load_image(&code, 0x1000).unwrap();
+        jit.load_image(&code, 0x1000).unwrap();
+        jit.jit.hot_threshold = 0;
+
+        // The second pass starts from the first pass's registers and runs
+        // entirely out of the block cache.
+        for _ in 0..2 {
+            interp.pc = 0x1000;
+            jit.pc = 0x1000;
+            interp.run_until_halt().unwrap();
+            while jit.in_program() {
+                jit.execute_jit().unwrap();
+            }
+            assert_eq!(jit.registers, interp.registers);
+            assert_eq!(jit.pc, interp.pc);
+        }
+
+        if cfg!(target_arch = "x86_64") {
+            assert!(jit.jit.len() > 100);
+        }
+    }
+
+    #[test]
+    fn test_jit_compiles_hot_blocks_only() {
+        let mut soft = SoftThread::default();
+        // addi a0, a0, 1; addi a0, a0, 1; slt a1, a0, a0
+        soft.load_image(&[0x13, 0x05, 0x15, 0x00, 0x13, 0x05, 0x15, 0x00, 0xb3, 0x25, 0xa5, 0x00], 0x1000).unwrap();
+        soft.jit.hot_threshold = 1;
+
+        soft.execute_jit().unwrap();
+        assert!(soft.jit.is_empty());
+        assert_eq!(soft.pc, 0x1004);
+
+        soft.pc = 0x1000;
+        soft.execute_jit().unwrap();
+        assert_eq!(soft.registers[Register::X10 as usize], 3);
+        if cfg!(target_arch = "x86_64") {
+            assert_eq!(soft.pc, 0x1008);
+            assert_eq!(soft.jit.get(0x1000).map(|block| block.len), Some(2));
+        }
+
+        soft.load_image(&[0x13, 0x00, 0x00, 0x00], 0x1000).unwrap();
+        assert!(soft.jit.is_empty());
+    }
+
+    #[test]
+    fn test_jit_bypassed_for_watches_and_timing() {
+        let run = |soft: &mut SoftThread<u64, f64, Dram>| {
+            // addi a0, a0, 1; addi a0, a0, 1
+            soft.load_image(&[0x13, 0x05, 0x15, 0x00, 0x13, 0x05, 0x15, 0x00], 0x1000).unwrap();
+            soft.jit.hot_threshold = 0;
+            soft.execute_jit().unwrap();
+            assert!(soft.jit.is_empty());
+            assert_eq!(soft.pc, 0x1004);
+        };
+
+        let (seen, log) = recorder();
+        let mut soft = SoftThread::default();
+        soft.watch_register(Register::X10, Box::new(move |old, new| log.borrow_mut().push((old, new))));
+        run(&mut soft);
+        assert_eq!(*seen.borrow(), vec![(0, 1)]);
+
+        let mut soft = SoftThread::default();
+        soft.enable_cycle_model(CycleAccurateModel::new());
+        run(&mut soft);
+
+        let mut soft = SoftThread::default();
+        soft.priv_level = PrivilegeLevel::User;
+        run(&mut soft);
+
+        let mut soft = SoftThread::default();
+        soft.pmp.write_csr(CSR_PMPADDR0, u64::MAX);
+        soft.pmp.write_csr(CSR_PMPCFG0, PMP_A_NAPOT << PMPCFG_A_SHIFT | PMPCFG_R | PMPCFG_X);
+        assert!(soft.pmp.is_enabled());
+        run(&mut soft);
+    }
+
+    #[test]
+    fn test_fork_replays_from_snapshot() {
+        let mut soft = SoftThread::default();
+        // addi a0, a0, 1; add a1, a1, a0; sd a1, 0(a4); jr a3
+        soft.load_image(&[0x13, 0x05, 0x15, 0x00, 0xb3, 0x85, 0xa5, 0x00, 0x23, 0x30, 0xb7, 0x00, 0x67, 0x80, 0x06, 0x00], 0x1000).unwrap();
+        soft.pc = 0x1000;
+        soft.registers[Register::X13 as usize] = 0x1000;
+        soft.registers[Register::X14 as usize] = 0x2000;
+        soft.f_registers[1] = 1.5;
+
+        let mut fork = soft.fork();
+        let iteration = |soft: &mut SoftThread<u64, f64, Dram>| {
+            for _ in 0..4 {
+                soft.execute().unwrap();
+            }
+        };
+
+        let mut snapshot = None;
+        for idx in 1..=100 {
+            iteration(&mut soft);
+            if idx == 50 {
+                let mut mem = [0u8; 8];
+                soft.store_raw(0x2000, &mut mem).unwrap();
+                snapshot = Some((soft.registers, soft.pc, mem));
+            }
+        }
+
+        for _ in 0..50 {
+            iteration(&mut fork);
+        }
+
+        let (registers, pc, mem) = snapshot.unwrap();
+        let mut fork_mem = [0u8; 8];
+        fork.store_raw(0x2000, &mut fork_mem).unwrap();
+        assert_eq!(fork.registers, registers);
+        assert_eq!(fork.pc, pc);
+        assert_eq!(fork_mem, mem);
+        assert_eq!(fork.f_registers[1], 1.5);
+        assert_eq!(u64::from_le_bytes(mem), 50 * 51 / 2);
+        assert_eq!(soft.registers[Register::X11 as usize], 100 * 101 / 2);
+    }
+
+    #[test]
+    fn test_fork_gets_unique_hart_id() {
+        let soft = SoftThread::default();
+        let first = soft.fork();
+        let second = soft.fork();
+        assert_ne!(first.hart_id(), soft.hart_id());
+        assert_ne!(first.hart_id(), second.hart_id());
+    }
+
+    #[test]
+    fn test_invariants_hold_after_load() {
+        let mut soft = SoftThread::default();
+        // The reset sp is MEM_SIZE, which lies past the end of the default DRAM.
+        assert_eq!(soft.verify_risc_v_invariants().len(), 1);
+
+        soft.load_image(&[0x13, 0x05, 0x15, 0x00], 0x1000).unwrap();
+        soft.registers[Register::X2 as usize] = soft.bus.mem.len() as u64;
+        soft.execute().unwrap();
+        assert_eq!(soft.verify_risc_v_invariants(), vec![]);
+    }
+
+    #[test]
+    fn test_invariants_report_corruption() {
+        let mut soft = SoftThread::default();
+        soft.registers[0] = 0xdead;
+        soft.pc = 0x1001;
+        soft.csr[CSR_MSTATUS as usize] = 2 << MSTATUS_MPP_SHIFT;
+        soft.csr[0x3ff] = 1;
+        soft.registers[Register::X2 as usize] = u64::MAX;
+
+        let violations = soft.verify_risc_v_invariants();
+        assert_eq!(violations.len(), 5);
+        assert!(violations.contains(&InvariantViolation::new("x0 is not zero", 0, 0xdead)));
+        assert!(violations.iter().any(|v| v.addr_or_reg == 0x3ff && v.value == 1));
+        assert!(violations.iter().any(|v| v.addr_or_reg == CSR_MSTATUS as u64));
+    }
+
+    #[test]
+    fn test_clint_ticks_with_execution() {
+        let mut soft = SoftThread::default();
+        let clint = soft.mmio.attach(Clint::new(1));
+        // 1 000 x addi a0, a0, 1
+        let code: Vec<u8> = [0x13, 0x05, 0x15, 0x00].repeat(1000);
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.run_until_halt().unwrap();
+
+        let device = soft.mmio.get_mut::<Clint>(clint).unwrap();
+        assert_eq!(device.read(CLINT_MTIME, 8), 1000);
+        assert_eq!(device.read(CLINT_MTIME + 4, 4), 0);
+        assert_eq!(soft.registers[Register::X10 as usize], 1000);
+    }
+
+    #[test]
+    fn test_clint_and_uart_registers() {
+        let mut clint = Clint::new(1);
+        Peripheral::write(&mut clint, CLINT_MTIMECMP, 0x10, 4);
+        Peripheral::write(&mut clint, CLINT_MTIMECMP + 4, 0x1, 4);
+        assert_eq!(clint.mtimecmp[0], 0x1_0000_0010);
+        Peripheral::tick(&mut clint, 0x1_0000_0010);
+        assert!(clint.timer_pending(0) && !clint.timer_pending(1));
+        Peripheral::write(&mut clint, CLINT_MSIP + 4, 0xff, 4);
+        assert_eq!(Peripheral::read(&mut clint, CLINT_MSIP + 4, 4), 1);
+        assert!(clint.software_pending(1) && !clint.software_pending(0));
+
+        let mut uart = UartPeripheral::new();
+        assert_eq!(Peripheral::read(&mut uart, UART_LSR, 1), UART_LSR_THRE);
+        uart.push_input(b"hi");
+        assert_eq!(Peripheral::read(&mut uart, UART_LSR, 1), UART_LSR_THRE | UART_LSR_DR);
+        assert_eq!(Peripheral::read(&mut uart, UART_RBR_THR, 1), b'h' as u64);
+        Peripheral::write(&mut uart, UART_RBR_THR, b'!' as u64, 1);
+        let copy = uart.clone();
+        assert_eq!(copy, uart);
+        assert_eq!(uart.drain_tx(), b"!");
+        assert_ne!(copy, uart);
+        assert!(uart.contains(UART_BASE + UART_LSR as u64));
+        assert!(Uart16550::with_base(0x1000).contains(0x1005));
+    }
+
+    #[test]
+    fn test_disassemble_function_labels_loop() {
+        let mut soft = SoftThread::default();
+        // addi a0, a0, 1; add a2, a2, a0; xori a3, a3, 5; bne a0, a1, -12
+        // sub a4, a4, a0; c.nop; jalr a5; ld a0, 8(sp)
+        let code = [
+            0x13, 0x05, 0x15, 0x00, 0x33, 0x06, 0xa6, 0x00, 0x93, 0xc6, 0x56, 0x00, 0xe3, 0x1a, 0xb5, 0xfe,
+            0x33, 0x07, 0xa7, 0x40, 0x01, 0x00, 0xe7, 0x80, 0x07, 0x00, 0x03, 0x35, 0x81, 0x00,
+        ];
+        soft.load_image(&code, 0x1000).unwrap();
+
+        let lines = soft.disassemble_function(0x1000, 0x1000 + code.len() as u64);
+        let text: Vec<&str> = lines.iter().map(|(_, line)| line.as_str()).collect();
+        assert_eq!(text, vec![
+            "<loop_back:> addi a0, a0, 1",
+            "add a2, a2, a0",
+            "xori a3, a3, 5",
+            "bne a0, a1, 0x1000 ; <+0x0>",
+            "sub a4, a4, a0",
+            "c.nop",
+            "jalr ra, 0(a5) ; <+??>",
+            "ld a0, 8(sp)",
+        ]);
+        assert_eq!(lines[0].0, 0x1000);
+        assert_eq!(lines[6].0, 0x1016);
+    }
+
+    #[test]
+    fn test_disassemble_function_names_branch_targets() {
+        let mut soft = SoftThread::default();
+        // addi a0, x0, 1; beq a0, x0, 12; ebreak; nop; add a0, a0, a0; jalr x0, 0(ra)
+        let code = [
+            0x13, 0x05, 0x10, 0x00, 0x63, 0x06, 0x05, 0x00, 0x73, 0x00, 0x10, 0x00, 0x13, 0x00, 0x00, 0x00,
+            0x33, 0x05, 0xa5, 0x00, 0x67, 0x80, 0x00, 0x00,
+        ];
+        soft.load_image(&code, 0x1000).unwrap();
+        soft.load_symbol_table(std::collections::HashMap::from([(0x1000, "main".to_string()), (0x1010, "double".to_string())]));
+
+        let lines = soft.disassemble_function(0x1000, 0x1010);
+        assert_eq!(lines[1].1, "beq a0, zero, double ; <+0x10>");
+        assert_eq!(soft.symbol_at(0x1014), Some(("double", 4)));
+        assert_eq!(soft.symbol_at(0x100c), Some(("main", 0xc)));
+        assert_eq!(soft.symbol_at(0xfff), None);
+    }
+
+    #[test]
+    fn test_backtrace_follows_frame_pointers() {
+        let mut soft = SoftThread::default();
+        soft.load_symbol_table(std::collections::HashMap::from([(0x1000, "main".to_string()), (0x1100, "leaf".to_string())]));
+        // The frame of leaf at 0x7ff0 returns to main + 0x10 and the frame
+        // of main at 0x8000 returns to main + 0xc0 with a saved s0 of 0.
+        soft.load_raw(0x7fe0, &0x8000u64.to_le_bytes()).unwrap();
+        soft.load_raw(0x7fe8, &0x1010u64.to_le_bytes()).unwrap();
+        soft.load_raw(0x7ff0, &0u64.to_le_bytes()).unwrap();
+        soft.load_raw(0x7ff8, &0x10c0u64.to_le_bytes()).unwrap();
+        soft.pc = 0x1104;
+        soft.registers[Register::X8 as usize] = 0x7ff0;
+
+        assert_eq!(soft.backtrace(), vec![
+            (0x1104, "leaf + 0x4".to_string()),
+            (0x1010, "main + 0x10".to_string()),
+            (0x10c0, "main + 0xc0".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_mnemonics_follow_assembler_names() {
+        let mnemonic = |bits: u32| disasm::mnemonic(&Instruction::decode(bits, &EncodingTable::default()));
+        // fcvt.wu.s a0, f1; amoadd.w a0, a1, (a2); fence.i
+        assert_eq!(mnemonic(0xc010_f553), "fcvt.wu.s");
+        assert_eq!(mnemonic(0x00b6_252f), "amoadd.w");
+        assert_eq!(mnemonic(0x0000_100f), "fence.i");
+    }
+
+    #[test]
+    fn test_step_n_stops_after_n() {
+        let mut soft = SoftThread::default();
+        // 10 x addi a0, a0, 1
+        soft.load_image(&[0x13, 0x05, 0x15, 0x00].repeat(10), 0x1000).unwrap();
+        assert_eq!(soft.step_n(0), Ok(0));
+        assert_eq!(soft.pc, 0x1000);
+
+        assert_eq!(soft.step_n(5), Ok(5));
+        assert_eq!(soft.pc, 0x1000 + 20);
+        assert_eq!(soft.registers[Register::X10 as usize], 5);
+    }
+
+    #[test]
+    fn test_step_n_reports_executed_count_on_exception() {
+        let mut soft = SoftThread::default();
+        // addi a0, a0, 1; addi a0, a0, 1; ecall
+        soft.load_image(&[0x13, 0x05, 0x15, 0x00, 0x13, 0x05, 0x15, 0x00, 0x73, 0x00, 0x00, 0x00], 0x1000).unwrap();
+        assert_eq!(soft.step_n(5), Err((2, Exception::EnvironmentCallFromMMode)));
+        assert_eq!(soft.pc, 0x1008);
+    }
+
+    #[test]
+    fn test_assert_helpers() {
+        let mut soft = SoftThread::default();
+        // addi a0, a0, 1; sd a0, 0(a4)
+        soft.load_image(&[0x13, 0x05, 0x15, 0x00, 0x23, 0x30, 0xa7, 0x00], 0x1000).unwrap();
+        soft.registers[Register::X14 as usize] = 0x2000;
+        soft.f_registers[1] = 0.1 + 0.2;
+        soft.run_until_halt().unwrap();
+
+        assert_eq!(soft.assert_register(Register::X10, 1), Ok(()));
+        assert_eq!(soft.assert_float_register(Register::X1, 0.3, 1e-9), Ok(()));
+        assert_eq!(soft.assert_csr(CSR_MHARTID, 0), Ok(()));
+        assert_eq!(soft.assert_memory(0x2000, &[1, 0, 0, 0]), Ok(()));
+
+        let err = soft.assert_register(Register::X10, 42).unwrap_err();
+        assert_eq!(err, AssertionError::Register { register: Register::X10, expected: 42, actual: 1 });
+        assert_eq!(err.to_string(), "X10: expected 0x2a, found 0x1");
+        assert!(soft.assert_float_register(Register::X1, 0.3, 0.0).is_err());
+        assert!(matches!(soft.assert_memory(u64::MAX - 1, &[0, 0]), Err(AssertionError::Memory { ref actual, .. }) if actual.is_empty()));
+    }
+}
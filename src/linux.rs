@@ -0,0 +1,265 @@
+use crate::elf::ElfError;
+use crate::exceptions::Exception;
+use crate::memory::Memory;
+use crate::register::Register;
+use crate::soft::SoftThread;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read, Write};
+
+// Syscall numbers of the generic Linux ABI used by RISC-V.
+pub const SYS_IOCTL: u64 = 29;
+pub const SYS_READ: u64 = 63;
+pub const SYS_WRITE: u64 = 64;
+pub const SYS_WRITEV: u64 = 66;
+pub const SYS_EXIT: u64 = 93;
+pub const SYS_EXIT_GROUP: u64 = 94;
+pub const SYS_SET_TID_ADDRESS: u64 = 96;
+pub const SYS_BRK: u64 = 214;
+pub const SYS_MUNMAP: u64 = 215;
+pub const SYS_MMAP: u64 = 222;
+
+pub const EBADF: i64 = 9;
+pub const ENOMEM: i64 = 12;
+pub const EFAULT: i64 = 14;
+pub const EINVAL: i64 = 22;
+pub const ENOTTY: i64 = 25;
+pub const ENOSYS: i64 = 38;
+
+pub const MAP_ANONYMOUS: u64 = 0x20;
+pub const PAGE_SIZE: u64 = 4096;
+
+// Auxiliary vector entry types.
+pub const AT_NULL: u64 = 0;
+pub const AT_PAGESZ: u64 = 6;
+pub const AT_ENTRY: u64 = 9;
+pub const AT_RANDOM: u64 = 25;
+
+#[derive(Debug)]
+pub enum RunError {
+    Io(io::Error),
+    Elf(ElfError),
+    Exception(Exception),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            RunError::Io(e) => write!(f, "{}", e),
+            RunError::Elf(e) => write!(f, "{}", e),
+            RunError::Exception(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl From<io::Error> for RunError {
+    fn from(e: io::Error) -> RunError {
+        RunError::Io(e)
+    }
+}
+
+impl From<ElfError> for RunError {
+    fn from(e: ElfError) -> RunError {
+        RunError::Elf(e)
+    }
+}
+
+impl From<Exception> for RunError {
+    fn from(e: Exception) -> RunError {
+        RunError::Exception(e)
+    }
+}
+
+/// How a program run by `SoftThread::load_elf_and_run` ended. `code` is
+/// -1 when it stopped with `exception` rather than exiting.
+#[derive(Debug, PartialEq)]
+pub struct ExitStatus {
+    pub code: i32,
+    pub instructions: u64,
+    pub cycles: u64,
+    pub exception: Option<Exception>,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SyscallResult {
+    Continue,
+    Exit(i32),
+}
+
+/// Services the Linux syscalls a statically linked newlib or musl program
+/// needs to start, print and exit. The heap grows up from `brk` and
+/// anonymous mappings are carved downwards from `mmap_top`.
+pub struct LinuxSyscalls<'a> {
+    stdin: &'a mut dyn Read,
+    stdout: &'a mut dyn Write,
+    stderr: Option<&'a mut dyn Write>,
+    brk_start: u64,
+    brk: u64,
+    mmap_top: u64,
+}
+
+impl<'a> LinuxSyscalls<'a> {
+    pub fn new(stdin: &'a mut dyn Read, stdout: &'a mut dyn Write, brk: u64, mmap_top: u64) -> LinuxSyscalls<'a> {
+        LinuxSyscalls { stdin, stdout, stderr: None, brk_start: brk, brk, mmap_top }
+    }
+
+    /// Send what the program writes to fd 2 to `stderr` instead of the
+    /// emulator's own stderr.
+    pub fn with_stderr(mut self, stderr: &'a mut dyn Write) -> LinuxSyscalls<'a> {
+        self.stderr = Some(stderr);
+        self
+    }
+
+    /// Perform the syscall in `a7` with arguments in `a0`-`a5` and place
+    /// the result, or a negated errno, in `a0`. The pc is not advanced.
+    pub fn handle<M: Memory<RegValue = u64, Bytes = Vec<u8>>>(&mut self, soft: &mut SoftThread<u64, f64, M>) -> Result<SyscallResult, Exception> {
+        let arg = |reg: Register| soft.registers[reg as usize];
+        let (a0, a1, a2, a3) = (arg(Register::X10), arg(Register::X11), arg(Register::X12), arg(Register::X13));
+
+        let ret: i64 = match arg(Register::X17) {
+            SYS_EXIT | SYS_EXIT_GROUP => {
+                let _ = self.stdout.flush();
+                return Ok(SyscallResult::Exit(a0 as i32));
+            },
+            SYS_READ => {
+                if a0 != 0 {
+                    -EBADF
+                } else if let Some(mut buf) = guest_buf(soft, a1, a2) {
+                    let count = self.stdin.read(&mut buf).unwrap_or(0);
+                    soft.load_raw(a1, &buf[..count])?;
+                    count as i64
+                } else {
+                    -EFAULT
+                }
+            },
+            SYS_WRITE => match guest_buf(soft, a1, a2) {
+                Some(mut buf) => {
+                    soft.store_raw(a1, &mut buf)?;
+                    self.write_fd(a0, &buf)
+                },
+                None => -EFAULT,
+            },
+            SYS_WRITEV => {
+                let mut total = 0;
+                for idx in 0..a2 {
+                    let mut iov = [0u8; 16];
+                    let addr = idx.checked_mul(16).and_then(|offset| a1.checked_add(offset));
+                    if addr.is_none_or(|addr| soft.store_raw(addr, &mut iov).is_err()) {
+                        return Ok(self.ret(soft, -EFAULT));
+                    }
+                    let base = u64::from_le_bytes(iov[..8].try_into().unwrap());
+                    let len = u64::from_le_bytes(iov[8..].try_into().unwrap());
+
+                    let Some(mut buf) = guest_buf(soft, base, len) else {
+                        return Ok(self.ret(soft, -EFAULT));
+                    };
+                    soft.store_raw(base, &mut buf)?;
+                    let written = self.write_fd(a0, &buf);
+                    if written < 0 {
+                        return Ok(self.ret(soft, written));
+                    }
+                    total += written;
+                }
+                total
+            },
+            SYS_BRK => {
+                if a0 >= self.brk_start && a0 <= self.mmap_top {
+                    if a0 > self.brk {
+                        soft.load_raw(self.brk, &vec![0u8; (a0 - self.brk) as usize])?;
+                    }
+                    self.brk = a0;
+                }
+                self.brk as i64
+            },
+            SYS_MMAP => match a1.checked_add(PAGE_SIZE - 1).map(|len| len & !(PAGE_SIZE - 1)) {
+                _ if a3 & MAP_ANONYMOUS == 0 => -ENOSYS,
+                None | Some(0) => -EINVAL,
+                Some(len) if self.brk.checked_add(len).is_none_or(|end| self.mmap_top < end) => -ENOMEM,
+                Some(len) => {
+                    self.mmap_top -= len;
+                    soft.load_raw(self.mmap_top, &vec![0u8; len as usize])?;
+                    self.mmap_top as i64
+                },
+            },
+            SYS_MUNMAP => 0,
+            SYS_SET_TID_ADDRESS => 1,
+            SYS_IOCTL => -ENOTTY,
+            _ => -ENOSYS,
+        };
+
+        Ok(self.ret(soft, ret))
+    }
+
+    fn ret<M>(&self, soft: &mut SoftThread<u64, f64, M>, val: i64) -> SyscallResult {
+        soft.registers[Register::X10 as usize] = val as u64;
+        SyscallResult::Continue
+    }
+
+    fn write_fd(&mut self, fd: u64, buf: &[u8]) -> i64 {
+        let result = match fd {
+            1 => self.stdout.write_all(buf),
+            2 => match self.stderr.as_mut() {
+                Some(stderr) => stderr.write_all(buf),
+                None => io::stderr().write_all(buf),
+            },
+            _ => return -EBADF,
+        };
+
+        match result {
+            Ok(()) => buf.len() as i64,
+            Err(_) => -EBADF,
+        }
+    }
+}
+
+// A zeroed buffer for the `len` bytes of the program's memory at `addr`,
+// or None if they are not all in memory, which the syscalls report as
+// EFAULT rather than faulting the hart.
+fn guest_buf<M: Memory<RegValue = u64, Bytes = Vec<u8>>>(soft: &SoftThread<u64, f64, M>, addr: u64, len: u64) -> Option<Vec<u8>> {
+    let len = usize::try_from(len).ok()?;
+    soft.raw_range(addr, len).ok().map(|_| vec![0u8; len])
+}
+
+/// Lay out the initial process stack below `top` as the RISC-V Linux ABI
+/// expects it: argc at the returned stack pointer, followed by the argv
+/// pointers, a NULL, the envp pointers, a NULL and the auxiliary vector.
+/// The strings themselves are placed above the vectors.
+pub fn setup_stack<M: Memory<RegValue = u64, Bytes = Vec<u8>>>(soft: &mut SoftThread<u64, f64, M>, args: &[&str], env: &[&str], entry: u64, top: u64) -> Result<u64, Exception> {
+    let mut ptr = top;
+    let mut push_str = |soft: &mut SoftThread<u64, f64, M>, s: &str| -> Result<u64, Exception> {
+        ptr -= s.len() as u64 + 1;
+        soft.load_raw(ptr, s.as_bytes())?;
+        soft.load_raw(ptr + s.len() as u64, &[0])?;
+        Ok(ptr)
+    };
+
+    let argv = args.iter().map(|arg| push_str(soft, arg)).collect::<Result<Vec<u64>, Exception>>()?;
+    let envp = env.iter().map(|var| push_str(soft, var)).collect::<Result<Vec<u64>, Exception>>()?;
+
+    // 16 bytes for AT_RANDOM, which libc uses to seed the stack protector.
+    ptr = (ptr - 16) & !0xf;
+    let random = ptr;
+    soft.load_raw(random, &[0x5a; 16])?;
+
+    let auxv = [(AT_PAGESZ, PAGE_SIZE), (AT_ENTRY, entry), (AT_RANDOM, random), (AT_NULL, 0)];
+
+    let mut words = vec![args.len() as u64];
+    words.extend(&argv);
+    words.push(0);
+    words.extend(&envp);
+    words.push(0);
+    for (key, val) in auxv {
+        words.push(key);
+        words.push(val);
+    }
+
+    let sp = (ptr - words.len() as u64 * 8) & !0xf;
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    soft.load_raw(sp, &bytes)?;
+
+    Ok(sp)
+}
@@ -1,302 +1,569 @@
-#![allow(unused, unused_mut, dead_code)]
-use crate::exceptions::Exception;
-use crate::register::RegisterValue;
-use std::fmt::{Display, Formatter};
-use std::error::Error;
-use crate::consts::{MAX_MEM, INDICES, INDEX_SHIFTS, DIRTY};
-
-pub const BASE: u64 = 0x8000_0000;
-pub const BYTE: u8 = 8;
-pub const DOUBLEWORD: u8 = 64;
-pub const HALFWORD: u8 = 16;
-pub const WORD: u8 = 32;
-pub const MEM_SIZE: u64 = 1024 * 1024 * 128;
-
-// Trait to provide memory functionality and types. 
-// b == Byte
-// hw == Half Word
-// w == Word
-// dw == DoubleWord
-pub trait Memory: Default {
-    type RegValue: RegisterValue + From<u8> + From<u16> + From<u32> + From<u64>;
-    type Bytes;
-    type Error: Error;
-
-    fn init(&mut self, addr: u64, size: u64, flags: u8, source: Option<Self::Bytes>, offset: u64) -> Result<(), Self::Error>;
-    fn get_flag(&mut self, index: u64) -> Result<u8, Self::Error>;
-    fn set_flag(&mut self, index: u64, flag: u8) -> Result<(), Self::Error>;
-    fn clear_flag(&mut self, index: u64, flag: u8) -> Result<(), Self::Error>;
-    fn get_indices(addr: u64, size: u64) -> Result<(u64, u64), Self::Error>;
-
-    fn execute_readhw(&mut self, addr: u64) -> Self::RegValue;
-    fn execute_readw(&mut self, addr: u64) -> Self::RegValue;
-
-    fn read(&self, addr: &Self::RegValue, size: u8) -> Result<Self::RegValue, Self::Error>;
-    fn readb(&self, addr: &Self::RegValue) -> Self::RegValue;
-    fn readhw(&self, addr: &Self::RegValue) -> Self::RegValue;
-    fn readw(&self, addr: &Self::RegValue) -> Self::RegValue;
-    fn readdw(&self, addr: &Self::RegValue) -> Self::RegValue;
-
-    fn write_array(&mut self, addr: Self::RegValue, val: Self::Bytes) -> Result<(), Self::Error>;
-    fn write(&mut self, addr: u64, value: u64, size: u8) -> Result<(), Self::Error>;
-    fn writeb(&mut self, addr: Self::RegValue, val: Self::RegValue);
-    fn writehw(&mut self, addr: Self::RegValue, val: Self::RegValue);
-    fn writew(&mut self, addr: Self::RegValue, val: Self::RegValue);
-    fn writedw(&mut self, addr: u64, val: u64);
-    fn into_u64(&self, val: &Self::RegValue) -> u64;
-    fn into_i64(&self, val: &Self::RegValue) -> i64;
-    fn into_u32(&self, val: &Self::RegValue) -> u32;
-    fn into_i32(&self, val: &Self::RegValue) -> i32;
-
-
-}
-
-pub trait ReadOnlyMemory: Default {}
-
-#[derive(Debug, Clone)]
-pub struct Dram {
-    pub mem: Vec<u8>,
-    flags: Vec<u8>,
-    size: u64,
-}
-
-impl Dram {
-    pub fn new() -> Dram {
-        Dram {
-            mem: vec![0; MAX_MEM],
-            flags: vec![0; INDICES],
-            size: 0
-        }
-    }
-
-    pub fn init(&mut self, bin: Vec<u8>) {
-        self.size = bin.len() as u64;
-        self.mem.splice(..bin.len(), bin.iter().cloned());
-    }
-}
-
-#[derive(Debug, Clone)]
-pub enum MemError {
-    OutOfBounds,
-    LoadAccessFault,
-    StoreAMOAccessFault
-}
-
-impl Display for MemError {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-impl Error for MemError {}
-
-impl Memory for Dram {
-    type RegValue = u64;
-    type Bytes = Vec<u8>;
-    type Error = MemError;
-
-    fn init(&mut self, addr: u64, size: u64, flags: u8, source: Option<Self::Bytes>, offset: u64) -> Result<(), Self::Error> {
-        let mut written = 0;
-        if offset > 0 {
-            let actual = std::cmp::min(size, offset);
-            self.writeb(addr, actual);
-            written += actual;
-        }
-        if let Some(src) = source {
-            let actual = std::cmp::min(size - written, src.len() as u64);
-            if actual > 0 {
-                self.write_array((addr + written), src[0..actual as usize].to_vec());
-                written += actual;
-            }
-        }
-        if written < size {
-            self.writeb(addr + written, size - written);
-        }
-
-        Ok(())
-    }
-
-    fn get_flag(&mut self, idx: u64) -> Result<u8, Self::Error> {
-        if idx < INDICES as u64 {
-            Ok(self.flags[idx as usize])
-        } else {
-            Err(MemError::OutOfBounds)
-        }
-    }
-
-    fn set_flag(&mut self, idx: u64, flag: u8) -> Result<(), Self::Error> {
-        if idx < INDICES as u64 {
-            self.flags[idx as usize] |= flag;
-            Ok(())
-        } else {
-            Err(MemError::OutOfBounds)
-        }
-    }
-
-    fn clear_flag(&mut self, idx: u64, flag: u8) -> Result<(), Self::Error> {
-        if idx < INDICES as u64 {
-            self.flags[idx as usize] &= !flag;
-            Ok(())
-        } else {
-            Err(MemError::OutOfBounds)
-        }
-    }
-
-    fn get_indices(addr: u64, size: u64) -> Result<(u64, u64), Self::Error> {
-        let (end, overflow) = addr.overflowing_add(size);
-        if overflow {
-            return Err(MemError::OutOfBounds);
-        }
-
-        if end > MAX_MEM as u64 {
-            return Err(MemError::OutOfBounds);
-        }
-
-        let idx = addr >> INDEX_SHIFTS;
-        let idx_end = (end - 1) >> INDEX_SHIFTS;
-
-        Ok((idx, idx_end))
-    }
-
-    fn execute_readhw(&mut self, addr: u64) -> Self::RegValue { 
-        self.readhw(&addr)
-    }
-
-    fn execute_readw(&mut self, addr: u64) -> Self::RegValue {
-        self.readw(&addr)
-    }
-    
-    fn read(&self, addr: &Self::RegValue, size: u8) -> Result<Self::RegValue, Self::Error> {
-        match size {
-            BYTE => {
-                Ok(self.readb(addr))
-            },
-            HALFWORD => {
-                Ok(self.readhw(addr))
-            },
-            WORD => {
-                Ok(self.readw(addr))
-            },
-            DOUBLEWORD => {
-                Ok(self.readdw(addr))
-            },
-            _ => return Err(MemError::LoadAccessFault)
-        }
-    }
-
-    fn readb(&self, addr: &Self::RegValue) -> Self::RegValue {
-        let idx: usize = *addr as usize;
-        self.mem[idx] as u64
-    }
-    fn readhw(&self, addr: &Self::RegValue) -> Self::RegValue {
-        let idx: usize = *addr as usize;
-        return (self.mem[idx] as u64) | ((self.mem[idx + 1] as u64) << 8);
-    }
-    fn readw(&self, addr: &Self::RegValue) -> Self::RegValue {
-        let idx: usize = *addr as usize;
-        return (self.mem[idx] as u64) | 
-            ((self.mem[idx + 1] as u64) << 8) | 
-            ((self.mem[idx + 2] as u64) << 16) | 
-            ((self.mem[idx + 3] as u64) << 24);
-    }
-
-    fn readdw(&self, addr: &Self::RegValue) -> Self::RegValue {
-        let idx: usize = *addr as usize;
-        return (self.mem[idx] as u64) |
-            ((self.mem[idx + 1] as u64) << 8) |
-            ((self.mem[idx + 2] as u64) << 16) |
-            ((self.mem[idx + 3] as u64) << 24) |
-            ((self.mem[idx + 4] as u64) << 32) |
-            ((self.mem[idx + 5] as u64) << 40) |
-            ((self.mem[idx + 6] as u64) << 48) |
-            ((self.mem[idx + 7] as u64) << 56)        
-    }
-
-    fn write_array(&mut self, addr: Self::RegValue, value: Self::Bytes) -> Result<(), Self::Error> {
-        let size = value.len() as u64;
-        if size == 0 {
-            return Ok(());
-        }
-        let indices = Self::get_indices(addr, size)?;
-        self.set_flag(addr, DIRTY);
-        let arr = &mut self.mem[addr as usize..(addr + size) as usize];
-        arr.copy_from_slice(&value);
-        Ok(())
-    }
-
-    fn write(&mut self, addr: u64, value: u64, size: u8) -> Result<(), Self::Error> {
-        match size {
-            BYTE => { self.writeb(addr, value) },
-            HALFWORD => { self.writehw(addr, value) },
-            WORD => { self.writew(addr, value) },
-            DOUBLEWORD => { self.writedw(addr, value) },
-            _ => return Err(MemError::StoreAMOAccessFault),
-        }
-        Ok(())    
-    }
-    fn writeb(&mut self, addr: u64, val: u64) {
-        let idx: usize = addr as usize;
-        self.mem[idx] = val as u8;
-    }
-
-    fn writehw(&mut self, addr: u64, val: u64) {
-        let idx: usize = addr as usize;
-        self.mem[idx] = (val & 0xff) as u8;
-        self.mem[idx + 1] = ((val >> 8) & 0xff) as u8;
-    }
-    
-    fn writew(&mut self, addr: u64, val: u64) {
-        let idx: usize = addr as usize;
-        self.mem[idx] = (val & 0xff) as u8;
-        self.mem[idx + 1] = ((val >> 8) & 0xff) as u8;
-        self.mem[idx + 2] = ((val >> 16) & 0xff) as u8;
-        self.mem[idx + 3] = ((val >> 24) & 0xff) as u8;
-    }
-    
-    fn writedw(&mut self, addr: u64, val: u64) {
-        let idx: usize = addr as usize;
-        self.mem[idx] = (val & 0xff) as u8;
-        self.mem[idx + 1] = ((val >> 8) & 0xff) as u8;
-        self.mem[idx + 2] = ((val >> 16) & 0xff) as u8;
-        self.mem[idx + 3] = ((val >> 24) & 0xff) as u8;
-        self.mem[idx + 4] = ((val >> 32) & 0xff) as u8;
-        self.mem[idx + 5] = ((val >> 40) & 0xff) as u8;
-        self.mem[idx + 6] = ((val >> 48) & 0xff) as u8;
-        self.mem[idx + 7] = ((val >> 56) & 0xff) as u8;
-
-    }
-    
-    fn into_u64(&self, val: &Self::RegValue) -> u64 {
-        *val as u64
-    }
-
-    fn into_i64(&self, val: &Self::RegValue) -> i64 {
-        *val as i64
-    }
-
-    fn into_u32(&self, val: &Self::RegValue) -> u32 {
-        *val as u32
-    }
-
-    fn into_i32(&self, val: &Self::RegValue) -> i32 {
-        *val as i32
-    }
-}
-
-impl Default for Dram {
-    fn default() -> Dram {
-        Dram {
-            mem: vec![0; MAX_MEM],
-            flags: vec![0; INDICES],
-            size: 0
-        }
-    }
-}
-
-#[inline(always)]
-pub fn memset(arr: &mut [u8], val: u8) {
-    let p = arr.as_mut_ptr();
-    unsafe {
-        std::ptr::write_bytes(p, val, arr.len())
-    }
-}
+#![allow(unused, unused_mut, dead_code)]
+use crate::bus::Device;
+use crate::exceptions::Exception;
+use crate::register::RegisterValue;
+use std::fmt::{Display, Formatter};
+use std::error::Error;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use crate::consts::{MAX_MEM, INDICES, INDEX_SHIFTS, INDEX_SIZE, DIRTY};
+
+pub const BASE: u64 = 0x8000_0000;
+pub const BYTE: u8 = 8;
+pub const DOUBLEWORD: u8 = 64;
+pub const HALFWORD: u8 = 16;
+pub const WORD: u8 = 32;
+pub const MEM_SIZE: u64 = 1024 * 1024 * 128;
+
+/// What a hart does with a load or store whose address is not a multiple
+/// of its size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnalignedMode {
+    /// Raise a load or store/AMO address misaligned exception.
+    Trap,
+    /// Split the access into byte accesses, as hardware support would.
+    #[default]
+    Emulate,
+}
+
+// Trait to provide memory functionality and types. 
+// b == Byte
+// hw == Half Word
+// w == Word
+// dw == DoubleWord
+pub trait Memory {
+    type RegValue: RegisterValue + From<u8> + From<u16> + From<u32> + From<u64>;
+    type Bytes;
+    type Error: Error;
+
+    fn init(&mut self, addr: u64, size: u64, flags: u8, source: Option<Self::Bytes>, offset: u64) -> Result<(), Self::Error>;
+    fn get_flag(&mut self, index: u64) -> Result<u8, Self::Error>;
+    fn set_flag(&mut self, index: u64, flag: u8) -> Result<(), Self::Error>;
+    fn clear_flag(&mut self, index: u64, flag: u8) -> Result<(), Self::Error>;
+    fn get_indices(&self, addr: u64, size: u64) -> Result<(u64, u64), Self::Error>;
+
+    fn execute_readhw(&mut self, addr: u64) -> Self::RegValue;
+    fn execute_readw(&mut self, addr: u64) -> Self::RegValue;
+
+    fn read(&self, addr: &Self::RegValue, size: u8) -> Result<Self::RegValue, Self::Error>;
+
+    /// Read `size` bits at `addr` for a load, which unlike `read` may change
+    /// what is there, as reading a UART's receive buffer does.
+    fn load(&mut self, addr: &Self::RegValue, size: u8) -> Result<Self::RegValue, Self::Error> {
+        self.read(addr, size)
+    }
+
+    fn readb(&self, addr: &Self::RegValue) -> Self::RegValue;
+    fn readhw(&self, addr: &Self::RegValue) -> Self::RegValue;
+    fn readw(&self, addr: &Self::RegValue) -> Self::RegValue;
+    fn readdw(&self, addr: &Self::RegValue) -> Self::RegValue;
+
+    fn write_array(&mut self, addr: Self::RegValue, val: Self::Bytes) -> Result<(), Self::Error>;
+
+    /// Fill `buf` with the bytes from `addr` up, the reverse of
+    /// `write_array`.
+    fn read_array(&self, addr: u64, buf: &mut [u8]) -> Result<(), Self::Error> {
+        for (idx, byte) in buf.iter_mut().enumerate() {
+            let val = self.read(&Self::RegValue::from(addr.wrapping_add(idx as u64)), BYTE)?;
+            *byte = self.into_u64(&val) as u8;
+        }
+        Ok(())
+    }
+    fn write(&mut self, addr: u64, value: u64, size: u8) -> Result<(), Self::Error>;
+    fn writeb(&mut self, addr: Self::RegValue, val: Self::RegValue);
+    fn writehw(&mut self, addr: Self::RegValue, val: Self::RegValue);
+    fn writew(&mut self, addr: Self::RegValue, val: Self::RegValue);
+    fn writedw(&mut self, addr: u64, val: u64);
+    fn into_u64(&self, val: &Self::RegValue) -> u64;
+    fn into_i64(&self, val: &Self::RegValue) -> i64;
+    fn into_u32(&self, val: &Self::RegValue) -> u32;
+    fn into_i32(&self, val: &Self::RegValue) -> i32;
+
+    /// Advance by `cycles` instructions. Only devices have anything to do.
+    fn tick(&mut self, _cycles: u64) {}
+
+    /// The `mip` bits this memory drives for the hart `hart_id`, and which
+    /// of them are set. Plain memory drives none.
+    fn interrupts(&self, _hart_id: u64) -> (u64, u64) {
+        (0, 0)
+    }
+}
+
+pub trait ReadOnlyMemory: Default {}
+
+// The storage behind a Dram. Small memories live on the heap, large ones
+// are mapped straight from the OS so that pages are only committed (and
+// zeroed) the first time they are touched. Both deref to a byte slice so
+// the Memory impl does not need to know which one it is using.
+#[derive(Debug)]
+pub enum DramBacking {
+    Heap(Vec<u8>),
+    Mapped { ptr: *mut u8, len: usize },
+}
+
+// The mapping is owned exclusively by the backing and freed on drop, so it
+// can move between and be shared across threads like a Vec<u8> can.
+unsafe impl Send for DramBacking {}
+unsafe impl Sync for DramBacking {}
+
+impl DramBacking {
+    #[cfg(unix)]
+    pub fn map(len: usize) -> io::Result<DramBacking> {
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_ANONYMOUS | libc::MAP_PRIVATE,
+                -1,
+                0
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(DramBacking::Mapped { ptr: ptr as *mut u8, len })
+    }
+
+    #[cfg(windows)]
+    pub fn map(len: usize) -> io::Result<DramBacking> {
+        let ptr = unsafe {
+            windows::VirtualAlloc(
+                std::ptr::null_mut(),
+                len,
+                windows::MEM_RESERVE | windows::MEM_COMMIT,
+                windows::PAGE_READWRITE
+            )
+        };
+        if ptr.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(DramBacking::Mapped { ptr: ptr as *mut u8, len })
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    pub fn map(len: usize) -> io::Result<DramBacking> {
+        Ok(DramBacking::Heap(vec![0; len]))
+    }
+}
+
+impl Deref for DramBacking {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            DramBacking::Heap(mem) => mem,
+            DramBacking::Mapped { ptr, len } => unsafe { std::slice::from_raw_parts(*ptr, *len) },
+        }
+    }
+}
+
+impl DerefMut for DramBacking {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            DramBacking::Heap(mem) => mem,
+            DramBacking::Mapped { ptr, len } => unsafe { std::slice::from_raw_parts_mut(*ptr, *len) },
+        }
+    }
+}
+
+impl Clone for DramBacking {
+    fn clone(&self) -> DramBacking {
+        match self {
+            DramBacking::Heap(mem) => DramBacking::Heap(mem.clone()),
+            DramBacking::Mapped { len, .. } => {
+                match DramBacking::map(*len) {
+                    Ok(mut mapped) => {
+                        mapped.copy_from_slice(self);
+                        mapped
+                    },
+                    Err(_) => DramBacking::Heap(self.to_vec()),
+                }
+            }
+        }
+    }
+}
+
+impl Drop for DramBacking {
+    fn drop(&mut self) {
+        if let DramBacking::Mapped { ptr, len } = *self {
+            #[cfg(unix)]
+            unsafe {
+                libc::munmap(ptr as *mut libc::c_void, len);
+            }
+            #[cfg(windows)]
+            unsafe {
+                windows::VirtualFree(ptr as *mut std::ffi::c_void, 0, windows::MEM_RELEASE);
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::ffi::c_void;
+
+    pub const MEM_COMMIT: u32 = 0x1000;
+    pub const MEM_RESERVE: u32 = 0x2000;
+    pub const MEM_RELEASE: u32 = 0x8000;
+    pub const PAGE_READWRITE: u32 = 0x04;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn VirtualAlloc(addr: *mut c_void, size: usize, alloc_type: u32, protect: u32) -> *mut c_void;
+        pub fn VirtualFree(addr: *mut c_void, size: usize, free_type: u32) -> i32;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Dram {
+    pub mem: DramBacking,
+    pub(crate) flags: Vec<u8>,
+    pub(crate) size: u64,
+}
+
+impl Dram {
+    pub fn new() -> Dram {
+        Dram {
+            mem: DramBacking::Heap(vec![0; MAX_MEM]),
+            flags: vec![0; INDICES],
+            size: 0
+        }
+    }
+
+    // Create a Dram of `size` bytes backed by an anonymous OS mapping
+    // instead of a zeroed Vec, which keeps startup cheap for large sizes.
+    pub fn mmap(size: usize) -> io::Result<Dram> {
+        Ok(Dram {
+            mem: DramBacking::map(size)?,
+            flags: vec![0; size.div_ceil(INDEX_SIZE)],
+            size: 0
+        })
+    }
+
+    // Grow or shrink the memory to `size` bytes. Contents up to the smaller
+    // of the two sizes are kept and any new bytes are zero, as are the
+    // flags of any new pages.
+    pub fn resize(&mut self, size: usize) -> io::Result<()> {
+        match &mut self.mem {
+            DramBacking::Heap(mem) => mem.resize(size, 0),
+            DramBacking::Mapped { .. } => {
+                let mut mapped = DramBacking::map(size)?;
+                let keep = std::cmp::min(size, self.mem.len());
+                mapped[..keep].copy_from_slice(&self.mem[..keep]);
+                self.mem = mapped;
+            }
+        }
+        self.flags.resize(size.div_ceil(INDEX_SIZE), 0);
+
+        Ok(())
+    }
+
+    pub fn init(&mut self, bin: Vec<u8>) {
+        self.size = bin.len() as u64;
+        self.mem[..bin.len()].copy_from_slice(&bin);
+    }
+
+    /// Copy `len` bytes starting at `start`, for `restore_region` to put
+    /// back later without cloning the whole memory.
+    pub fn snapshot_region(&self, start: u64, len: u64) -> Result<Vec<u8>, Exception> {
+        let range = self.region(start, len)?;
+        Ok(self.mem[range].to_vec())
+    }
+
+    /// Write `data` back at `start`, where `snapshot_region` copied it from.
+    pub fn restore_region(&mut self, start: u64, data: &[u8]) -> Result<(), Exception> {
+        let range = self.region(start, data.len() as u64)?;
+        self.mem[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    // Whether the `size` bit access at `addr` is within the memory backing
+    // this Dram.
+    fn in_bounds(&self, addr: u64, size: u8) -> bool {
+        addr.checked_add(size as u64 / 8).is_some_and(|end| end <= self.mem.len() as u64)
+    }
+
+    // The bytes from `start` to `start + len`, if they are within the
+    // memory backing this Dram.
+    fn region(&self, start: u64, len: u64) -> Result<std::ops::Range<usize>, Exception> {
+        match start.checked_add(len) {
+            Some(end) if end <= self.mem.len() as u64 => Ok(start as usize..end as usize),
+            _ => Err(Exception::StoreAMOAccessFault),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum MemError {
+    OutOfBounds,
+    LoadAccessFault,
+    StoreAMOAccessFault
+}
+
+impl Display for MemError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for MemError {}
+
+impl Memory for Dram {
+    type RegValue = u64;
+    type Bytes = Vec<u8>;
+    type Error = MemError;
+
+    fn init(&mut self, addr: u64, size: u64, flags: u8, source: Option<Self::Bytes>, offset: u64) -> Result<(), Self::Error> {
+        let mut written = 0;
+        if offset > 0 {
+            let actual = std::cmp::min(size, offset);
+            self.writeb(addr, actual);
+            written += actual;
+        }
+        if let Some(src) = source {
+            let actual = std::cmp::min(size - written, src.len() as u64);
+            if actual > 0 {
+                self.write_array((addr + written), src[0..actual as usize].to_vec());
+                written += actual;
+            }
+        }
+        if written < size {
+            self.writeb(addr + written, size - written);
+        }
+
+        Ok(())
+    }
+
+    fn get_flag(&mut self, idx: u64) -> Result<u8, Self::Error> {
+        if idx < self.flags.len() as u64 {
+            Ok(self.flags[idx as usize])
+        } else {
+            Err(MemError::OutOfBounds)
+        }
+    }
+
+    fn set_flag(&mut self, idx: u64, flag: u8) -> Result<(), Self::Error> {
+        if idx < self.flags.len() as u64 {
+            self.flags[idx as usize] |= flag;
+            Ok(())
+        } else {
+            Err(MemError::OutOfBounds)
+        }
+    }
+
+    fn clear_flag(&mut self, idx: u64, flag: u8) -> Result<(), Self::Error> {
+        if idx < self.flags.len() as u64 {
+            self.flags[idx as usize] &= !flag;
+            Ok(())
+        } else {
+            Err(MemError::OutOfBounds)
+        }
+    }
+
+    fn get_indices(&self, addr: u64, size: u64) -> Result<(u64, u64), Self::Error> {
+        let (end, overflow) = addr.overflowing_add(size);
+        if overflow {
+            return Err(MemError::OutOfBounds);
+        }
+
+        if end > self.mem.len() as u64 {
+            return Err(MemError::OutOfBounds);
+        }
+
+        let idx = addr >> INDEX_SHIFTS;
+        let idx_end = (end - 1) >> INDEX_SHIFTS;
+
+        Ok((idx, idx_end))
+    }
+
+    fn execute_readhw(&mut self, addr: u64) -> Self::RegValue { 
+        self.readhw(&addr)
+    }
+
+    fn execute_readw(&mut self, addr: u64) -> Self::RegValue {
+        self.readw(&addr)
+    }
+    
+    fn read(&self, addr: &Self::RegValue, size: u8) -> Result<Self::RegValue, Self::Error> {
+        if !self.in_bounds(*addr, size) {
+            return Err(MemError::LoadAccessFault);
+        }
+        match size {
+            BYTE => {
+                Ok(self.readb(addr))
+            },
+            HALFWORD => {
+                Ok(self.readhw(addr))
+            },
+            WORD => {
+                Ok(self.readw(addr))
+            },
+            DOUBLEWORD => {
+                Ok(self.readdw(addr))
+            },
+            _ => return Err(MemError::LoadAccessFault)
+        }
+    }
+
+    fn readb(&self, addr: &Self::RegValue) -> Self::RegValue {
+        let idx: usize = *addr as usize;
+        self.mem[idx] as u64
+    }
+    fn readhw(&self, addr: &Self::RegValue) -> Self::RegValue {
+        let idx: usize = *addr as usize;
+        return (self.mem[idx] as u64) | ((self.mem[idx + 1] as u64) << 8);
+    }
+    fn readw(&self, addr: &Self::RegValue) -> Self::RegValue {
+        let idx: usize = *addr as usize;
+        return (self.mem[idx] as u64) | 
+            ((self.mem[idx + 1] as u64) << 8) | 
+            ((self.mem[idx + 2] as u64) << 16) | 
+            ((self.mem[idx + 3] as u64) << 24);
+    }
+
+    fn readdw(&self, addr: &Self::RegValue) -> Self::RegValue {
+        let idx: usize = *addr as usize;
+        return (self.mem[idx] as u64) |
+            ((self.mem[idx + 1] as u64) << 8) |
+            ((self.mem[idx + 2] as u64) << 16) |
+            ((self.mem[idx + 3] as u64) << 24) |
+            ((self.mem[idx + 4] as u64) << 32) |
+            ((self.mem[idx + 5] as u64) << 40) |
+            ((self.mem[idx + 6] as u64) << 48) |
+            ((self.mem[idx + 7] as u64) << 56)        
+    }
+
+    fn write_array(&mut self, addr: Self::RegValue, value: Self::Bytes) -> Result<(), Self::Error> {
+        let size = value.len() as u64;
+        if size == 0 {
+            return Ok(());
+        }
+        let indices = self.get_indices(addr, size)?;
+        self.set_flag(addr, DIRTY);
+        let arr = &mut self.mem[addr as usize..(addr + size) as usize];
+        arr.copy_from_slice(&value);
+        Ok(())
+    }
+
+    fn read_array(&self, addr: u64, buf: &mut [u8]) -> Result<(), Self::Error> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+        self.get_indices(addr, buf.len() as u64)?;
+        buf.copy_from_slice(&self.mem[addr as usize..addr as usize + buf.len()]);
+        Ok(())
+    }
+
+    fn write(&mut self, addr: u64, value: u64, size: u8) -> Result<(), Self::Error> {
+        if !self.in_bounds(addr, size) {
+            return Err(MemError::StoreAMOAccessFault);
+        }
+        match size {
+            BYTE => { self.writeb(addr, value) },
+            HALFWORD => { self.writehw(addr, value) },
+            WORD => { self.writew(addr, value) },
+            DOUBLEWORD => { self.writedw(addr, value) },
+            _ => return Err(MemError::StoreAMOAccessFault),
+        }
+        Ok(())    
+    }
+    fn writeb(&mut self, addr: u64, val: u64) {
+        let idx: usize = addr as usize;
+        self.mem[idx] = val as u8;
+    }
+
+    fn writehw(&mut self, addr: u64, val: u64) {
+        let idx: usize = addr as usize;
+        self.mem[idx] = (val & 0xff) as u8;
+        self.mem[idx + 1] = ((val >> 8) & 0xff) as u8;
+    }
+    
+    fn writew(&mut self, addr: u64, val: u64) {
+        let idx: usize = addr as usize;
+        self.mem[idx] = (val & 0xff) as u8;
+        self.mem[idx + 1] = ((val >> 8) & 0xff) as u8;
+        self.mem[idx + 2] = ((val >> 16) & 0xff) as u8;
+        self.mem[idx + 3] = ((val >> 24) & 0xff) as u8;
+    }
+    
+    fn writedw(&mut self, addr: u64, val: u64) {
+        let idx: usize = addr as usize;
+        self.mem[idx] = (val & 0xff) as u8;
+        self.mem[idx + 1] = ((val >> 8) & 0xff) as u8;
+        self.mem[idx + 2] = ((val >> 16) & 0xff) as u8;
+        self.mem[idx + 3] = ((val >> 24) & 0xff) as u8;
+        self.mem[idx + 4] = ((val >> 32) & 0xff) as u8;
+        self.mem[idx + 5] = ((val >> 40) & 0xff) as u8;
+        self.mem[idx + 6] = ((val >> 48) & 0xff) as u8;
+        self.mem[idx + 7] = ((val >> 56) & 0xff) as u8;
+
+    }
+    
+    fn into_u64(&self, val: &Self::RegValue) -> u64 {
+        *val as u64
+    }
+
+    fn into_i64(&self, val: &Self::RegValue) -> i64 {
+        *val as i64
+    }
+
+    fn into_u32(&self, val: &Self::RegValue) -> u32 {
+        *val as u32
+    }
+
+    fn into_i32(&self, val: &Self::RegValue) -> i32 {
+        *val as i32
+    }
+}
+
+/// DRAM mapped from physical address 0, for a `Bus` that holds it
+/// alongside devices.
+impl Device for Dram {
+    fn base_addr(&self) -> u64 {
+        0
+    }
+
+    fn size(&self) -> u64 {
+        self.mem.len() as u64
+    }
+}
+
+impl Default for Dram {
+    fn default() -> Dram {
+        Dram {
+            mem: DramBacking::Heap(vec![0; MAX_MEM]),
+            flags: vec![0; INDICES],
+            size: 0
+        }
+    }
+}
+
+#[inline(always)]
+pub fn memset(arr: &mut [u8], val: u8) {
+    let p = arr.as_mut_ptr();
+    unsafe {
+        std::ptr::write_bytes(p, val, arr.len())
+    }
+}
+
+/// The low `bits` bits of `val`, with anything above them cleared.
+pub fn zero_extend(val: u64, bits: u8) -> u64 {
+    match bits {
+        64 => val,
+        _ => val & ((1 << bits) - 1),
+    }
+}
+
+/// The low `bits` bits of `val`, with bit `bits - 1` copied into every bit
+/// above them whatever `val` held there.
+pub fn sign_extend(val: u64, bits: u8) -> u64 {
+    let val = zero_extend(val, bits);
+    match bits {
+        64 => val,
+        _ if val & (1 << (bits - 1)) != 0 => val | !((1 << bits) - 1),
+        _ => val,
+    }
+}
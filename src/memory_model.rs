@@ -0,0 +1,161 @@
+use crate::instructions::Instruction;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::Ordering;
+
+// The `fm` of `fence.tso`.
+pub const FENCE_FM_TSO: u32 = 0b1000;
+
+// The predecessor and successor set bits of a `fence` for memory reads
+// and writes. The I and O bits above them order device accesses.
+pub const FENCE_R: u32 = 1 << 1;
+pub const FENCE_W: u32 = 1 << 0;
+
+// How many stores a hart may have in flight before the oldest drains.
+pub const STORE_BUFFER_DEPTH: usize = 8;
+// How many steps of its hart a buffered store may wait before it drains.
+pub const STORE_BUFFER_LATENCY: u64 = 64;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MemoryOrdering {
+    /// Every store is visible to every hart as soon as it executes.
+    #[default]
+    SequentiallyConsistent,
+    /// Stores are buffered per hart and drain in program order, as on x86.
+    Tso,
+    /// Stores are buffered per hart and stores to different addresses may
+    /// drain in any order. This implementation drains the newest first.
+    Rvwmo,
+}
+
+pub type MemoryBarrierCallback = Box<dyn FnMut(u32, u32, u32)>;
+
+/// The callback `SoftThread::set_memory_barrier_hook` runs with the `fm`,
+/// `pred` and `succ` fields of each `fence` the hart executes, for models
+/// of several cores to order their own memory accesses by.
+pub struct MemoryBarrierHook(pub MemoryBarrierCallback);
+
+impl Debug for MemoryBarrierHook {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("MemoryBarrierHook").finish_non_exhaustive()
+    }
+}
+
+/// The host barrier that gives at least the ordering a `fence` asks for
+/// between memory accesses, or `None` if it orders no memory accesses.
+/// `fence.tso` and any fence that orders earlier writes before later reads
+/// need a full barrier.
+pub fn fence_ordering(fm: u32, pred: u32, succ: u32) -> Option<Ordering> {
+    if fm == FENCE_FM_TSO || (pred & FENCE_W != 0 && succ & FENCE_R != 0) {
+        return Some(Ordering::SeqCst);
+    }
+
+    if succ & (FENCE_R | FENCE_W) == 0 {
+        return None;
+    }
+
+    match (pred & FENCE_R != 0, pred & FENCE_W != 0) {
+        (true, true) => Some(Ordering::AcqRel),
+        (true, false) => Some(Ordering::Acquire),
+        (false, true) => Some(Ordering::Release),
+        (false, false) => None,
+    }
+}
+
+/// How stores made by one hart of a `Cpu` become visible to the others.
+/// A hart always sees its own stores immediately. Buffered stores drain
+/// when the buffer holds more than `store_buffer_depth` of them, once they
+/// have waited `store_buffer_latency` steps of their hart, when the hart
+/// executes a `fence` that orders earlier writes and when the hart runs
+/// off the end of its code. The latency bound keeps a hart spinning on a
+/// flag another hart has set from waiting forever.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryModel {
+    pub ordering: MemoryOrdering,
+    pub store_buffer_depth: usize,
+    pub store_buffer_latency: u64,
+}
+
+impl MemoryModel {
+    pub fn new(ordering: MemoryOrdering) -> MemoryModel {
+        MemoryModel { ordering, store_buffer_depth: STORE_BUFFER_DEPTH, store_buffer_latency: STORE_BUFFER_LATENCY }
+    }
+}
+
+impl Default for MemoryModel {
+    fn default() -> MemoryModel {
+        MemoryModel::new(MemoryOrdering::default())
+    }
+}
+
+/// Stores a hart has made that the other harts cannot see yet.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StoreBuffer {
+    // The address and bytes of each store, with the step it was made on.
+    entries: VecDeque<(u64, Vec<u8>, u64)>,
+    steps: u64,
+}
+
+impl StoreBuffer {
+    pub fn new() -> StoreBuffer {
+        StoreBuffer::default()
+    }
+
+    pub fn push(&mut self, addr: u64, bytes: Vec<u8>) {
+        self.entries.push_back((addr, bytes, self.steps));
+    }
+
+    /// Count a step of the buffer's hart.
+    pub fn tick(&mut self) {
+        self.steps += 1;
+    }
+
+    /// Whether the oldest store has waited at least `latency` steps.
+    pub fn overdue(&self, latency: u64) -> bool {
+        self.entries.front().is_some_and(|(_, _, step)| self.steps - step >= latency)
+    }
+
+    /// Take the next store that may become visible under `ordering`. Under
+    /// RVWMO the newest store is taken and older stores to the same address
+    /// are dropped, since they would otherwise overwrite it.
+    pub fn pop(&mut self, ordering: MemoryOrdering) -> Option<(u64, Vec<u8>)> {
+        match ordering {
+            MemoryOrdering::Rvwmo => {
+                let (addr, bytes, _) = self.entries.pop_back()?;
+                self.entries.retain(|(older, _, _)| *older != addr);
+                Some((addr, bytes))
+            },
+            _ => self.entries.pop_front().map(|(addr, bytes, _)| (addr, bytes)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// The address and width of the memory a store or AMO writes, given the
+/// register file before it executes.
+pub fn store_footprint(instruction: &Instruction, registers: &[u64]) -> Option<(u64, usize)> {
+    let offset = |rs1, imm: i32| registers[rs1 as usize].wrapping_add(imm as i64 as u64);
+    match *instruction {
+        Instruction::Sb { rs1, imm, .. } => Some((offset(rs1, imm), 1)),
+        Instruction::Sh { rs1, imm, .. } => Some((offset(rs1, imm), 2)),
+        Instruction::Sw { rs1, imm, .. } | Instruction::Fsw { rs1, imm, .. } => Some((offset(rs1, imm), 4)),
+        Instruction::Sd { rs1, imm, .. } | Instruction::Fsd { rs1, imm, .. } => Some((offset(rs1, imm), 8)),
+        Instruction::Fsq { rs1, imm, .. } => Some((offset(rs1, imm), 16)),
+        Instruction::ScW { rs1, .. } | Instruction::AmoswapW { rs1, .. } | Instruction::AmoaddW { rs1, .. } |
+        Instruction::AmoxorW { rs1, .. } | Instruction::AmoandW { rs1, .. } | Instruction::AmoorW { rs1, .. } |
+        Instruction::AmominW { rs1, .. } | Instruction::AmomaxW { rs1, .. } | Instruction::AmominuW { rs1, .. } |
+        Instruction::AmomaxuW { rs1, .. } => Some((registers[rs1 as usize], 4)),
+        Instruction::ScD { rs1, .. } | Instruction::AmoswapD { rs1, .. } | Instruction::AmoaddD { rs1, .. } |
+        Instruction::AmoxorD { rs1, .. } | Instruction::AmoandD { rs1, .. } | Instruction::AmoorD { rs1, .. } |
+        Instruction::AmominD { rs1, .. } | Instruction::AmomaxD { rs1, .. } | Instruction::AmominuD { rs1, .. } |
+        Instruction::AmomaxuD { rs1, .. } => Some((registers[rs1 as usize], 8)),
+        _ => None,
+    }
+}
@@ -0,0 +1,210 @@
+use crate::csr::{SATP_MODE_SHIFT, SATP_PPN};
+use crate::exceptions::Exception;
+use crate::memory::Memory;
+use std::collections::HashMap;
+
+pub const PAGE_SHIFT: u32 = 12;
+pub const PAGE_SIZE: u64 = 1 << PAGE_SHIFT;
+
+// Page table entry bits shared by all of the paged schemes.
+pub const PTE_V: u64 = 1 << 0;
+pub const PTE_R: u64 = 1 << 1;
+pub const PTE_W: u64 = 1 << 2;
+pub const PTE_X: u64 = 1 << 3;
+pub const PTE_U: u64 = 1 << 4;
+pub const PTE_G: u64 = 1 << 5;
+pub const PTE_A: u64 = 1 << 6;
+pub const PTE_D: u64 = 1 << 7;
+pub const PTE_PPN_SHIFT: u32 = 10;
+
+pub const SV32_LEVELS: usize = 2;
+pub const SV32_PTE_SIZE: u64 = 4;
+pub const SV32_SATP_PPN: u32 = (1 << 22) - 1;
+
+pub const SV39_LEVELS: usize = 3;
+pub const SV39_PTE_SIZE: u64 = 8;
+pub const SV39_PTE_PPN: u64 = (1 << 44) - 1;
+// The satp MODE that selects Sv39.
+pub const SATP_MODE_SV39: u64 = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessType {
+    Instruction,
+    Load,
+    Store,
+}
+
+impl AccessType {
+    pub fn page_fault(&self, vaddr: u64) -> Exception {
+        match self {
+            AccessType::Instruction => Exception::InstructionPageFault(vaddr),
+            AccessType::Load => Exception::LoadPageFault(vaddr),
+            AccessType::Store => Exception::StoreAMOPageFault(vaddr),
+        }
+    }
+
+    pub fn access_fault(&self) -> Exception {
+        match self {
+            AccessType::Instruction => Exception::AccessFault,
+            AccessType::Load => Exception::LoadAccessFault,
+            AccessType::Store => Exception::StoreAMOAccessFault,
+        }
+    }
+
+    // The leaf PTE permission bit this access needs.
+    fn permission(&self) -> u64 {
+        match self {
+            AccessType::Instruction => PTE_X,
+            AccessType::Load => PTE_R,
+            AccessType::Store => PTE_W,
+        }
+    }
+}
+
+/// Translate `vaddr` through the two level Sv32 page table rooted at the
+/// PPN in `satp`. Leaves must grant the permission `access` needs and
+/// have A (and D for stores) set, since the walker does not update them.
+/// The 34 bit physical address is left for the access to check, since it
+/// may be a device's. U-bit checks are left to the caller, which knows the
+/// privilege level.
+pub fn walk_sv32<M: Memory<RegValue = u64>>(satp: u32, vaddr: u32, bus: &M, access: AccessType) -> Result<u32, Exception> {
+    let fault = access.page_fault(vaddr as u64);
+    let vpn = [((vaddr >> 12) & 0x3ff) as u64, ((vaddr >> 22) & 0x3ff) as u64];
+    let mut table = ((satp & SV32_SATP_PPN) as u64) << PAGE_SHIFT;
+
+    for level in (0..SV32_LEVELS).rev() {
+        let pte_addr = table + vpn[level] * SV32_PTE_SIZE;
+        let pte = bus.read(&pte_addr, 32).map_err(|_| access.access_fault())?;
+        if pte & PTE_V == 0 || (pte & PTE_R == 0 && pte & PTE_W != 0) {
+            return Err(fault);
+        }
+
+        let ppn = pte >> PTE_PPN_SHIFT;
+        if pte & (PTE_R | PTE_X) == 0 {
+            table = ppn << PAGE_SHIFT;
+            continue;
+        }
+
+        if pte & access.permission() == 0 || pte & PTE_A == 0 {
+            return Err(fault);
+        }
+
+        if access == AccessType::Store && pte & PTE_D == 0 {
+            return Err(fault);
+        }
+
+        // A leaf in the root table is a 4 MB megapage, which must be aligned.
+        let paddr = if level == 1 {
+            if ppn & 0x3ff != 0 {
+                return Err(fault);
+            }
+            ((ppn >> 10) << 22) | (vaddr as u64 & 0x3f_ffff)
+        } else {
+            (ppn << PAGE_SHIFT) | (vaddr as u64 & (PAGE_SIZE - 1))
+        };
+
+        return Ok(paddr as u32);
+    }
+
+    Err(fault)
+}
+
+/// Sv39 translation for the address space `satp` selects, with a TLB of
+/// the 4 KB pages translated so far. The TLB is only flushed by `flush`,
+/// as for `sfence.vma`, so page table changes are not seen until then.
+/// Walks check A and D as `walk_sv32` does. `user` says whether accesses
+/// come from U-mode, which may only touch U pages; S-mode may not touch
+/// them at all.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sv39Mmu {
+    pub satp: u64,
+    pub user: bool,
+    // Virtual page number to the leaf PTE mapping it, rebased to the
+    // 4 KB page for superpages.
+    tlb: HashMap<u64, u64>,
+}
+
+impl Sv39Mmu {
+    pub fn new(satp: u64) -> Sv39Mmu {
+        Sv39Mmu { satp, ..Sv39Mmu::default() }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.satp >> SATP_MODE_SHIFT == SATP_MODE_SV39
+    }
+
+    /// Drop the cached translation of `vaddr`, or every translation.
+    pub fn flush(&mut self, vaddr: Option<u64>) {
+        match vaddr {
+            Some(vaddr) => {
+                self.tlb.remove(&(vaddr >> PAGE_SHIFT));
+            },
+            None => self.tlb.clear(),
+        }
+    }
+
+    /// The physical address of `va`, walking the page table in `bus` on a
+    /// TLB miss. Virtual addresses whose bits 63-39 do not all equal bit
+    /// 38 fault. A page table outside DRAM is an access fault, but the
+    /// physical address itself is left for the access to check, since it
+    /// may be a device's.
+    pub fn translate<M: Memory<RegValue = u64>>(&mut self, va: u64, access: AccessType, bus: &M) -> Result<u64, Exception> {
+        let fault = access.page_fault(va);
+        if ((va as i64) << 25 >> 25) as u64 != va {
+            return Err(fault);
+        }
+
+        let vpn = (va >> PAGE_SHIFT) & ((1 << 27) - 1);
+        let pte = match self.tlb.get(&vpn) {
+            Some(pte) => *pte,
+            None => {
+                let pte = self.walk(va, access, bus)?;
+                self.tlb.insert(vpn, pte);
+                pte
+            },
+        };
+
+        self.check(pte, access).ok_or(fault)?;
+        Ok(((pte >> PTE_PPN_SHIFT) << PAGE_SHIFT) | (va & (PAGE_SIZE - 1)))
+    }
+
+    // Whether the leaf `pte` allows `access`.
+    fn check(&self, pte: u64, access: AccessType) -> Option<()> {
+        let allowed = pte & access.permission() != 0 && pte & PTE_A != 0 &&
+            (access != AccessType::Store || pte & PTE_D != 0) && (pte & PTE_U != 0) == self.user;
+        allowed.then_some(())
+    }
+
+    // The leaf PTE for `va`, with the PPN of its 4 KB page.
+    fn walk<M: Memory<RegValue = u64>>(&self, va: u64, access: AccessType, bus: &M) -> Result<u64, Exception> {
+        let fault = access.page_fault(va);
+        let mut table = (self.satp & SATP_PPN) << PAGE_SHIFT;
+
+        for level in (0..SV39_LEVELS).rev() {
+            let vpn = (va >> (PAGE_SHIFT as usize + 9 * level)) & 0x1ff;
+            let pte_addr = table + vpn * SV39_PTE_SIZE;
+            let pte = bus.read(&pte_addr, 64).map_err(|_| access.access_fault())?;
+            if pte & PTE_V == 0 || (pte & PTE_R == 0 && pte & PTE_W != 0) {
+                return Err(fault);
+            }
+
+            let ppn = (pte >> PTE_PPN_SHIFT) & SV39_PTE_PPN;
+            if pte & (PTE_R | PTE_X) == 0 {
+                table = ppn << PAGE_SHIFT;
+                continue;
+            }
+
+            // A leaf above the last level is a superpage, whose low PPN
+            // bits must be zero and come from the virtual address instead.
+            let low_bits = 9 * level as u32;
+            let low_mask = (1 << low_bits) - 1;
+            if ppn & low_mask != 0 {
+                return Err(fault);
+            }
+            let ppn = ppn | ((va >> PAGE_SHIFT) & low_mask);
+            return Ok((ppn << PTE_PPN_SHIFT) | (pte & 0x3ff));
+        }
+
+        Err(fault)
+    }
+}
@@ -0,0 +1,87 @@
+use crate::exceptions::Exception;
+use crate::memory::Dram;
+use crate::mmu::{AccessType, PAGE_SHIFT};
+use crate::soft::SoftThread;
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter};
+
+/// What `SoftThread::execute` does with a page fault once the handler
+/// has seen it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PageFaultAction {
+    /// Run the faulting instruction again, e.g. after mapping its page. If
+    /// it faults the same way again, the fault is returned from `execute`.
+    Retry,
+    /// Return the fault from `execute` as if there were no handler.
+    Trap,
+    /// Copy the bytes to the start of the faulting page, map it and retry.
+    Fill(Vec<u8>),
+}
+
+pub type PageFaultHandler<M = Dram> = Box<dyn Fn(u64, AccessType, &mut SoftThread<u64, f64, M>) -> PageFaultAction>;
+
+/// The pages that fault on their next access and the hook that gets the
+/// first look at each fault.
+pub struct PageFaults<M = Dram> {
+    pub handler: Option<PageFaultHandler<M>>,
+    absent: HashSet<u64>,
+}
+
+impl<M> PageFaults<M> {
+    pub fn new() -> PageFaults<M> {
+        PageFaults { handler: None, absent: HashSet::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.absent.is_empty()
+    }
+
+    pub fn unmap(&mut self, addr: u64) {
+        self.absent.insert(addr >> PAGE_SHIFT);
+    }
+
+    pub fn map(&mut self, addr: u64) {
+        self.absent.remove(&(addr >> PAGE_SHIFT));
+    }
+
+    /// Raise the page fault for `access` if the page holding `addr` is
+    /// not present.
+    pub fn check(&self, addr: u64, access: AccessType) -> Result<(), Exception> {
+        match self.absent.contains(&(addr >> PAGE_SHIFT)) {
+            true => Err(access.page_fault(addr)),
+            false => Ok(()),
+        }
+    }
+
+    /// The same pages, without the handler, which cannot be copied.
+    pub fn fork(&self) -> PageFaults<M> {
+        PageFaults { handler: None, absent: self.absent.clone() }
+    }
+}
+
+impl<M> Default for PageFaults<M> {
+    fn default() -> PageFaults<M> {
+        PageFaults::new()
+    }
+}
+
+impl<M> Debug for PageFaults<M> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let mut absent: Vec<u64> = self.absent.iter().map(|page| page << PAGE_SHIFT).collect();
+        absent.sort_unstable();
+        f.debug_struct("PageFaults")
+            .field("handler", &self.handler.is_some())
+            .field("absent", &absent)
+            .finish()
+    }
+}
+
+/// The faulting address and access of a page fault.
+pub fn fault_access(exception: &Exception) -> Option<(u64, AccessType)> {
+    match exception {
+        Exception::InstructionPageFault(addr) => Some((*addr, AccessType::Instruction)),
+        Exception::LoadPageFault(addr) => Some((*addr, AccessType::Load)),
+        Exception::StoreAMOPageFault(addr) => Some((*addr, AccessType::Store)),
+        _ => None,
+    }
+}
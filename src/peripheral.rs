@@ -0,0 +1,389 @@
+use crate::bus::Device;
+use crate::interrupt::InterruptCause;
+use crate::memory::{MemError, Memory};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+
+// Where the CLINT is mapped on the QEMU virt board, and the register
+// offsets of the SiFive compatible CLINT.
+pub const CLINT_BASE: u64 = 0x200_0000;
+pub const CLINT_MSIP: u32 = 0x0;
+pub const CLINT_MTIMECMP: u32 = 0x4000;
+pub const CLINT_MTIME: u32 = 0xbff8;
+pub const CLINT_SIZE: u64 = 0x1_0000;
+
+// The harts a `Clint` has an `mtimecmp` for.
+pub const MAX_HARTS: usize = 8;
+
+// Where the UART is mapped on the QEMU virt board, and the register
+// offsets and line status bits of the 16550 compatible UART.
+pub const UART_BASE: u64 = 0x1000_0000;
+pub const UART_SIZE: u64 = 0x100;
+pub const UART_RBR_THR: u32 = 0;
+pub const UART_LSR: u32 = 5;
+pub const UART_LSR_DR: u64 = 1 << 0;
+pub const UART_LSR_THRE: u64 = 1 << 5;
+
+/// A memory mapped device that advances with the CPU. Offsets are relative
+/// to the device's base address and `size` is the access width in bytes.
+pub trait Peripheral: Debug {
+    fn tick(&mut self, cycles: u64);
+    fn read(&mut self, offset: u32, size: u8) -> u64;
+    fn write(&mut self, offset: u32, val: u64, size: u8);
+
+    /// The `mip` bits this device drives for the hart `hart_id`, and which
+    /// of them are set. Devices that raise no interrupts drive none.
+    fn interrupts(&self, _hart_id: u64) -> (u64, u64) {
+        (0, 0)
+    }
+}
+
+/// A peripheral as memory addressed by register offset, so it can be a
+/// `Device` on a `Bus`. Reading a register may change it, so only `load`
+/// reaches them and `read` faults. A peripheral holds nothing to fetch
+/// and no pages to flag.
+impl<P: Peripheral> Memory for P {
+    type RegValue = u64;
+    type Bytes = Vec<u8>;
+    type Error = MemError;
+
+    fn init(&mut self, _addr: u64, _size: u64, _flags: u8, _source: Option<Vec<u8>>, _offset: u64) -> Result<(), MemError> {
+        Err(MemError::StoreAMOAccessFault)
+    }
+
+    fn get_flag(&mut self, _index: u64) -> Result<u8, MemError> {
+        Err(MemError::OutOfBounds)
+    }
+
+    fn set_flag(&mut self, _index: u64, _flag: u8) -> Result<(), MemError> {
+        Err(MemError::OutOfBounds)
+    }
+
+    fn clear_flag(&mut self, _index: u64, _flag: u8) -> Result<(), MemError> {
+        Err(MemError::OutOfBounds)
+    }
+
+    fn get_indices(&self, _addr: u64, _size: u64) -> Result<(u64, u64), MemError> {
+        Err(MemError::OutOfBounds)
+    }
+
+    fn execute_readhw(&mut self, _addr: u64) -> u64 {
+        0
+    }
+
+    fn execute_readw(&mut self, _addr: u64) -> u64 {
+        0
+    }
+
+    fn read(&self, _addr: &u64, _size: u8) -> Result<u64, MemError> {
+        Err(MemError::LoadAccessFault)
+    }
+
+    fn load(&mut self, addr: &u64, size: u8) -> Result<u64, MemError> {
+        Ok(Peripheral::read(self, *addr as u32, size / 8))
+    }
+
+    fn readb(&self, _addr: &u64) -> u64 {
+        0
+    }
+
+    fn readhw(&self, _addr: &u64) -> u64 {
+        0
+    }
+
+    fn readw(&self, _addr: &u64) -> u64 {
+        0
+    }
+
+    fn readdw(&self, _addr: &u64) -> u64 {
+        0
+    }
+
+    fn write_array(&mut self, addr: u64, val: Vec<u8>) -> Result<(), MemError> {
+        for (idx, byte) in val.into_iter().enumerate() {
+            Peripheral::write(self, addr as u32 + idx as u32, byte as u64, 1);
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, addr: u64, value: u64, size: u8) -> Result<(), MemError> {
+        Peripheral::write(self, addr as u32, value, size / 8);
+        Ok(())
+    }
+
+    fn writeb(&mut self, addr: u64, val: u64) {
+        Peripheral::write(self, addr as u32, val, 1);
+    }
+
+    fn writehw(&mut self, addr: u64, val: u64) {
+        Peripheral::write(self, addr as u32, val, 2);
+    }
+
+    fn writew(&mut self, addr: u64, val: u64) {
+        Peripheral::write(self, addr as u32, val, 4);
+    }
+
+    fn writedw(&mut self, addr: u64, val: u64) {
+        Peripheral::write(self, addr as u32, val, 8);
+    }
+
+    fn into_u64(&self, val: &u64) -> u64 {
+        *val
+    }
+
+    fn into_i64(&self, val: &u64) -> i64 {
+        *val as i64
+    }
+
+    fn into_u32(&self, val: &u64) -> u32 {
+        *val as u32
+    }
+
+    fn into_i32(&self, val: &u64) -> i32 {
+        *val as i32
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        Peripheral::tick(self, cycles);
+    }
+
+    fn interrupts(&self, hart_id: u64) -> (u64, u64) {
+        Peripheral::interrupts(self, hart_id)
+    }
+}
+
+// Read `size` bytes of `reg` starting `byte` bytes into it.
+fn read_reg(reg: u64, byte: u32, size: u8) -> u64 {
+    let val = reg >> (byte * 8);
+    if size >= 8 {
+        val
+    } else {
+        val & ((1 << (size as u64 * 8)) - 1)
+    }
+}
+
+// Replace `size` bytes of `reg` starting `byte` bytes into it.
+fn write_reg(reg: &mut u64, byte: u32, val: u64, size: u8) {
+    let mask = if size >= 8 { u64::MAX } else { (1 << (size as u64 * 8)) - 1 };
+    let shift = byte * 8;
+    *reg = (*reg & !(mask << shift)) | ((val & mask) << shift);
+}
+
+/// The core local interruptor shared by up to `MAX_HARTS` harts, which
+/// `SoftThread::attach_clint` maps at `CLINT_BASE`. `mtime` advances by
+/// `ticks_per_instruction` per instruction, and each hart's `mtimecmp`
+/// starts out at the maximum so no timer fires until one is set. Only bit
+/// 0 of each hart's `msip` is implemented. On a hart's bus it drives
+/// `MTIP` and `MSIP` in that hart's `mip`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clint {
+    pub mtime: u64,
+    pub mtimecmp: [u64; MAX_HARTS],
+    pub msip: [u32; MAX_HARTS],
+    pub ticks_per_instruction: u64,
+}
+
+// A register of a `Clint`, with the hart it belongs to.
+enum ClintReg {
+    Msip(usize),
+    Mtimecmp(usize),
+    Mtime,
+}
+
+impl Clint {
+    pub fn new(ticks_per_instruction: u64) -> Clint {
+        Clint { mtime: 0, mtimecmp: [u64::MAX; MAX_HARTS], msip: [0; MAX_HARTS], ticks_per_instruction }
+    }
+
+    pub fn contains(&self, addr: u64) -> bool {
+        (CLINT_BASE..CLINT_BASE + CLINT_SIZE).contains(&addr)
+    }
+
+    /// True once `mtime` has reached the `mtimecmp` of `hart_id`. Harts
+    /// past `MAX_HARTS` have no timer.
+    pub fn timer_pending(&self, hart_id: u64) -> bool {
+        self.mtimecmp.get(hart_id as usize).is_some_and(|cmp| self.mtime >= *cmp)
+    }
+
+    /// True while the `msip` of `hart_id` is set.
+    pub fn software_pending(&self, hart_id: u64) -> bool {
+        self.msip.get(hart_id as usize).is_some_and(|msip| *msip != 0)
+    }
+
+    /// Read `size` bytes at `offset`. Reads have no side effects, so unlike
+    /// `Peripheral::read` this only needs a shared reference.
+    pub fn load(&self, offset: u32, size: u8) -> u64 {
+        match self.reg_at(offset) {
+            Some((ClintReg::Msip(hart), byte)) => read_reg(self.msip[hart] as u64, byte, size),
+            Some((ClintReg::Mtimecmp(hart), byte)) => read_reg(self.mtimecmp[hart], byte, size),
+            Some((ClintReg::Mtime, byte)) => read_reg(self.mtime, byte, size),
+            None => 0,
+        }
+    }
+
+    // The register at `offset` and the byte offset into it.
+    fn reg_at(&self, offset: u32) -> Option<(ClintReg, u32)> {
+        let msip_end = CLINT_MSIP + 4 * MAX_HARTS as u32;
+        let mtimecmp_end = CLINT_MTIMECMP + 8 * MAX_HARTS as u32;
+        match offset {
+            _ if (CLINT_MSIP..msip_end).contains(&offset) => {
+                let rel = offset - CLINT_MSIP;
+                Some((ClintReg::Msip(rel as usize / 4), rel % 4))
+            },
+            _ if (CLINT_MTIMECMP..mtimecmp_end).contains(&offset) => {
+                let rel = offset - CLINT_MTIMECMP;
+                Some((ClintReg::Mtimecmp(rel as usize / 8), rel % 8))
+            },
+            CLINT_MTIME..=0xbfff => Some((ClintReg::Mtime, offset - CLINT_MTIME)),
+            _ => None,
+        }
+    }
+}
+
+impl Peripheral for Clint {
+    fn tick(&mut self, cycles: u64) {
+        self.mtime = self.mtime.wrapping_add(cycles.wrapping_mul(self.ticks_per_instruction));
+    }
+
+    fn read(&mut self, offset: u32, size: u8) -> u64 {
+        Clint::load(self, offset, size)
+    }
+
+    fn write(&mut self, offset: u32, val: u64, size: u8) {
+        match self.reg_at(offset) {
+            Some((ClintReg::Msip(hart), byte)) => {
+                let mut msip = self.msip[hart] as u64;
+                write_reg(&mut msip, byte, val, size);
+                self.msip[hart] = msip as u32 & 1;
+            },
+            Some((ClintReg::Mtimecmp(hart), byte)) => write_reg(&mut self.mtimecmp[hart], byte, val, size),
+            Some((ClintReg::Mtime, byte)) => write_reg(&mut self.mtime, byte, val, size),
+            None => {},
+        }
+    }
+
+    fn interrupts(&self, hart_id: u64) -> (u64, u64) {
+        let mtip = InterruptCause::MachineTimer.mip_bit();
+        let msip = InterruptCause::MachineSoftware.mip_bit();
+        let timer = if self.timer_pending(hart_id) { mtip } else { 0 };
+        let software = if self.software_pending(hart_id) { msip } else { 0 };
+        (mtip | msip, timer | software)
+    }
+}
+
+impl Device for Clint {
+    fn base_addr(&self) -> u64 {
+        CLINT_BASE
+    }
+
+    fn size(&self) -> u64 {
+        CLINT_SIZE
+    }
+}
+
+/// A 16550 compatible UART, for firmware that prints to a serial console.
+/// It is mapped at `UART_BASE` unless made with `with_base`. Bytes written
+/// to THR go to the `tx_callback` if there is one and are buffered for
+/// `drain_tx` otherwise, and bytes queued with `push_input` or `push_rx`
+/// are received in order. The transmitter is always ready.
+#[derive(Default)]
+pub struct Uart16550 {
+    base: u64,
+    tx_buf: VecDeque<u8>,
+    rx_buf: VecDeque<u8>,
+    pub tx_callback: Option<Box<dyn FnMut(u8)>>,
+}
+
+/// The name the UART was first registered under.
+pub type UartPeripheral = Uart16550;
+
+impl Uart16550 {
+    pub fn new() -> Uart16550 {
+        Uart16550::with_base(UART_BASE)
+    }
+
+    pub fn with_base(base: u64) -> Uart16550 {
+        Uart16550 { base, ..Uart16550::default() }
+    }
+
+    pub fn push_input(&mut self, bytes: &[u8]) {
+        self.rx_buf.extend(bytes);
+    }
+
+    /// Queue `byte` to be received.
+    pub fn push_rx(&mut self, byte: u8) {
+        self.rx_buf.push_back(byte);
+    }
+
+    /// Take the bytes transmitted since the last call.
+    pub fn drain_tx(&mut self) -> Vec<u8> {
+        self.tx_buf.drain(..).collect()
+    }
+}
+
+/// A clone has no `tx_callback`, which cannot be copied, so it buffers
+/// what it transmits.
+impl Clone for Uart16550 {
+    fn clone(&self) -> Uart16550 {
+        Uart16550 {
+            base: self.base,
+            tx_buf: self.tx_buf.clone(),
+            rx_buf: self.rx_buf.clone(),
+            tx_callback: None,
+        }
+    }
+}
+
+/// Two UARTs are equal when their registers and buffers are; the callbacks
+/// cannot be compared.
+impl PartialEq for Uart16550 {
+    fn eq(&self, other: &Uart16550) -> bool {
+        self.base == other.base && self.tx_buf == other.tx_buf && self.rx_buf == other.rx_buf
+    }
+}
+
+impl Debug for Uart16550 {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Uart16550")
+            .field("base", &self.base)
+            .field("tx_buf", &self.tx_buf)
+            .field("rx_buf", &self.rx_buf)
+            .field("tx_callback", &self.tx_callback.is_some())
+            .finish()
+    }
+}
+
+impl Peripheral for Uart16550 {
+    fn tick(&mut self, _cycles: u64) {}
+
+    fn read(&mut self, offset: u32, _size: u8) -> u64 {
+        match offset {
+            UART_RBR_THR => self.rx_buf.pop_front().unwrap_or(0) as u64,
+            UART_LSR => {
+                let ready = if self.rx_buf.is_empty() { 0 } else { UART_LSR_DR };
+                UART_LSR_THRE | ready
+            },
+            _ => 0,
+        }
+    }
+
+    fn write(&mut self, offset: u32, val: u64, _size: u8) {
+        if offset != UART_RBR_THR {
+            return;
+        }
+        match self.tx_callback.as_mut() {
+            Some(callback) => callback(val as u8),
+            None => self.tx_buf.push_back(val as u8),
+        }
+    }
+}
+
+impl Device for Uart16550 {
+    fn base_addr(&self) -> u64 {
+        self.base
+    }
+
+    fn size(&self) -> u64 {
+        UART_SIZE
+    }
+}
@@ -0,0 +1,111 @@
+use crate::csr::{CSR_PMPADDR0, CSR_PMPCFG0, PMPCFG_A};
+use crate::exceptions::Exception;
+use crate::mmu::AccessType;
+use crate::privilege::PrivilegeLevel;
+
+// The entries `Pmp` checks, of the 64 the privileged spec allows.
+pub const PMP_CHECKED_ENTRIES: usize = 16;
+
+// Permission bits and address matching modes in each byte of a pmpcfg CSR.
+pub const PMPCFG_R: u64 = 1 << 0;
+pub const PMPCFG_W: u64 = 1 << 1;
+pub const PMPCFG_X: u64 = 1 << 2;
+pub const PMPCFG_A_SHIFT: u64 = 3;
+pub const PMP_A_OFF: u64 = 0;
+pub const PMP_A_TOR: u64 = 1;
+pub const PMP_A_NA4: u64 = 2;
+pub const PMP_A_NAPOT: u64 = 3;
+
+/// The first 16 PMP entries, as the pmpcfg0-3 and pmpaddr0-15 CSRs hold
+/// them. As on RV64 only the even pmpcfg CSRs are used, each holding the
+/// configuration of 8 entries.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pmp {
+    pub pmpaddr: [u64; PMP_CHECKED_ENTRIES],
+    pub pmpcfg: [u64; 4],
+}
+
+impl Pmp {
+    pub fn new() -> Pmp {
+        Pmp::default()
+    }
+
+    /// Mirror a write of `val` to the CSR at `addr`, if it is one of
+    /// ours.
+    pub fn write_csr(&mut self, addr: u16, val: u64) {
+        if let Some(cfg) = addr.checked_sub(CSR_PMPCFG0).and_then(|idx| self.pmpcfg.get_mut(idx as usize)) {
+            *cfg = val;
+        } else if let Some(pmpaddr) = addr.checked_sub(CSR_PMPADDR0).and_then(|idx| self.pmpaddr.get_mut(idx as usize)) {
+            *pmpaddr = val;
+        }
+    }
+
+    /// True if any entry is on, so that S and U-mode accesses are checked.
+    pub fn is_enabled(&self) -> bool {
+        (0..PMP_CHECKED_ENTRIES).any(|entry| self.range(entry).is_some())
+    }
+
+    // The configuration byte of `entry`.
+    fn cfg(&self, entry: usize) -> u64 {
+        self.pmpcfg[(entry / 8) * 2] >> ((entry % 8) * 8) & 0xff
+    }
+
+    // The bytes `entry` covers, or None when it is off.
+    fn range(&self, entry: usize) -> Option<(u64, u64)> {
+        let pmpaddr = self.pmpaddr[entry];
+        match (self.cfg(entry) & PMPCFG_A) >> PMPCFG_A_SHIFT {
+            PMP_A_TOR => {
+                let start = if entry == 0 { 0 } else { self.pmpaddr[entry - 1] << 2 };
+                Some((start, pmpaddr << 2))
+            },
+            PMP_A_NA4 => Some((pmpaddr << 2, (pmpaddr << 2).wrapping_add(4))),
+            PMP_A_NAPOT => {
+                let ones = pmpaddr.trailing_ones();
+                if ones > 60 {
+                    return Some((0, u64::MAX));
+                }
+                let start = (pmpaddr & !((1 << ones) - 1)) << 2;
+                Some((start, start.saturating_add(1 << (ones + 3))))
+            },
+            _ => None,
+        }
+    }
+
+    /// Check an `access` of `len` bytes at `addr` from `priv_level`. The
+    /// lowest numbered entry that covers any of the bytes decides; it
+    /// must cover all of them and allow the access. S and U-mode
+    /// accesses no entry covers fail, unless every entry is off. M-mode
+    /// accesses always succeed, as the lock bit is not modelled.
+    pub fn check(&self, addr: u64, len: u64, access: AccessType, priv_level: PrivilegeLevel) -> Result<(), Exception> {
+        if priv_level == PrivilegeLevel::Machine {
+            return Ok(());
+        }
+
+        let end = addr.wrapping_add(len);
+        let mut any_on = false;
+        for entry in 0..PMP_CHECKED_ENTRIES {
+            let Some((start, stop)) = self.range(entry) else {
+                continue;
+            };
+            any_on = true;
+            if addr >= stop || end <= start {
+                continue;
+            }
+
+            let permission = match access {
+                AccessType::Instruction => PMPCFG_X,
+                AccessType::Load => PMPCFG_R,
+                AccessType::Store => PMPCFG_W,
+            };
+            if addr < start || end > stop || self.cfg(entry) & permission == 0 {
+                return Err(access.access_fault());
+            }
+            return Ok(());
+        }
+
+        match any_on {
+            true => Err(access.access_fault()),
+            false => Ok(()),
+        }
+    }
+}
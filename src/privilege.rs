@@ -0,0 +1,26 @@
+// The privilege modes a hart can execute in. The discriminants match
+// the encoding used by `mstatus.MPP` and by bits [9:8] of a CSR address.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PrivilegeLevel {
+    User = 0,
+    Supervisor = 1,
+    #[default]
+    Machine = 3,
+}
+
+impl From<u64> for PrivilegeLevel {
+    fn from(bits: u64) -> PrivilegeLevel {
+        match bits & 0b11 {
+            0b00 => PrivilegeLevel::User,
+            0b01 => PrivilegeLevel::Supervisor,
+            _ => PrivilegeLevel::Machine
+        }
+    }
+}
+
+impl From<PrivilegeLevel> for u64 {
+    fn from(level: PrivilegeLevel) -> u64 {
+        level as u64
+    }
+}
+
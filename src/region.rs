@@ -0,0 +1,105 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+use std::ops::BitOr;
+
+/// Permissions of a `MemoryRegion`, combined with `|`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccessFlags(u8);
+
+impl AccessFlags {
+    pub const NONE: AccessFlags = AccessFlags(0);
+    pub const READ: AccessFlags = AccessFlags(1 << 0);
+    pub const WRITE: AccessFlags = AccessFlags(1 << 1);
+    pub const EXECUTE: AccessFlags = AccessFlags(1 << 2);
+    pub const ALL: AccessFlags = AccessFlags(0b111);
+
+    pub fn contains(&self, other: AccessFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+}
+
+impl BitOr for AccessFlags {
+    type Output = AccessFlags;
+
+    fn bitor(self, rhs: AccessFlags) -> AccessFlags {
+        AccessFlags(self.0 | rhs.0)
+    }
+}
+
+impl Display for AccessFlags {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        for (flag, c) in [(AccessFlags::READ, 'R'), (AccessFlags::WRITE, 'W'), (AccessFlags::EXECUTE, 'X')] {
+            write!(f, "{}", if self.contains(flag) { c } else { '-' })?;
+        }
+        Ok(())
+    }
+}
+
+// The name `SoftThread::memory_map` gives a region nobody named.
+pub const UNNAMED_REGION: &str = "region";
+// The name `SoftThread::memory_map` gives the gaps between regions.
+pub const UNMAPPED: &str = "<unmapped>";
+
+/// A range of DRAM with its own access permissions. Regions only restrict
+/// access; the bytes themselves always live in the hart's DRAM, and
+/// addresses outside of every region are accessible without restriction.
+/// `name` is only used to describe the region, e.g. in `memory_map`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryRegion {
+    pub base: u64,
+    pub size: u64,
+    pub flags: AccessFlags,
+    pub name: String,
+}
+
+impl MemoryRegion {
+    pub fn new(base: u64, size: u64, flags: AccessFlags) -> MemoryRegion {
+        MemoryRegion { base, size, flags, name: UNNAMED_REGION.to_string() }
+    }
+
+    pub fn with_name(mut self, name: &str) -> MemoryRegion {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn end(&self) -> u64 {
+        self.base + self.size
+    }
+
+    pub fn contains(&self, addr: u64) -> bool {
+        self.base <= addr && addr < self.end()
+    }
+}
+
+/// One line of `SoftThread::memory_map`: a region, or a gap between two.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MemoryMapEntry<'a> {
+    pub start: u64,
+    pub end: u64,
+    pub name: &'a str,
+    pub flags: AccessFlags,
+}
+
+impl Display for MemoryMapEntry<'_> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:#010x} - {:#010x} {:<14} [{}]", self.start, self.end, self.name, self.flags)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MprotectError {
+    InvalidRange,
+    NotMapped,
+}
+
+impl Display for MprotectError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for MprotectError {}
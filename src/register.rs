@@ -14,6 +14,7 @@ pub trait RegisterValue:
 {
     const BITS: u8;
     const SHIFT_MASK: u8;
+    const XLEN: u32 = Self::BITS as u32;
 
     fn zero() -> Self;
     fn one() -> Self;
@@ -573,4 +574,239 @@ impl RegisterValue for u64 {
         let start = std::cmp::min(*start, 64);
         (((*self << (64 - start)) as i64) >> (64 - start)) as u64 
     }
-}
\ No newline at end of file
+}
+
+impl RegisterValue for u32 {
+    const BITS: u8 = 32;
+    const SHIFT_MASK: u8 = 0x1F;
+
+    fn zero() -> Self { 0 }
+    fn one() -> Self { 1 }
+    fn min_val() -> Self { u32::MIN }
+    fn max_val() -> Self { u32::MAX }
+    
+    fn equal(&self, other: &Self) -> Self { 
+        (self == other).into() 
+    }
+    
+    fn less_than(&self, other: &Self) -> Self { 
+        (self < other).into() 
+    }
+    
+    fn less_than_signed(&self, other: &Self) -> Self { 
+        ((*self as i32) < (*other as i32)).into() 
+    }
+    
+    fn not_equal(&self, rhs: &Self) -> Self { 
+        self.equal(rhs).logical_not() 
+    }
+    
+    fn greater_equal(&self, rhs: &Self) -> Self { 
+        self.less_than(rhs).logical_not() 
+    }
+    
+    fn greater_equal_signed(&self, rhs: &Self) -> Self { 
+        self.less_than_signed(rhs).logical_not() 
+    }
+    
+    fn logical_not(&self) -> Self { 
+        (*self != Self::one()).into() 
+    }
+    
+    fn condition(&self, tval: &Self, fval: &Self) -> Self { 
+        if *self == Self::one() {
+            *tval
+        } else {
+            *fval
+        }
+    }
+    
+    fn oflow_add(&self, rhs: &Self) -> Self { 
+        (*self).overflowing_add(*rhs).0    
+    }
+    
+    fn oflow_sub(&self, rhs: &Self) -> Self { 
+        (*self).overflowing_sub(*rhs).0
+    }
+    
+    fn oflow_mul(&self, rhs: &Self) -> Self {
+        (*self).overflowing_mul(*rhs).0
+    }
+    
+    fn oflow_div(&self, rhs: &Self) -> Self {
+        if *rhs == 0 {
+            Self::max_val()
+        } else {
+            (*self).overflowing_div(*rhs).0
+        }
+    }
+
+    fn oflow_div_euclid(&self, rhs: &Self) -> Self {
+        if *rhs == 0 {
+            Self::max_val()
+        } else {
+            (*self).overflowing_div_euclid(*rhs).0
+        }
+    }
+
+    fn oflow_div_signed(&self, rhs: &Self) -> Self {
+        if *rhs == 0 {
+            (-1i32) as u32
+        } else {
+            let (val, overflow) = (*self as i32).overflowing_div(*rhs as i32);
+            if overflow {
+                ((-1i32) as u32) << (<Self as RegisterValue>::BITS - 1)
+            } else {
+                val as u32
+            }
+        }
+    }
+    
+    fn oflow_rem(&self, rhs: &Self) -> Self {
+        if *rhs == 0 {
+            *self
+        } else {
+            (*self).overflowing_rem(*rhs).0
+        }
+    }
+    
+    fn oflow_rem_euclid(&self, rhs: &Self) -> Self {
+        if *rhs == 0 {
+            *self
+        } else {
+            (*self).overflowing_rem_euclid(*rhs).0
+        }
+    }
+
+    fn oflow_rem_signed(&self, rhs: &Self) -> Self {
+        if *rhs == 0 {
+            *self
+        } else {
+            let (val, overflow) = (*self as i32).overflowing_rem(*rhs as i32);
+            if overflow {
+                0
+            } else {
+                val as u32
+            }
+        }
+    }
+
+    fn oflow_mul_high_signed(&self, rhs: &Self) -> Self {
+        let a = i64::from(*self as i32);
+        let b = i64::from(*rhs as i32);
+        ((a * b) >> 32) as u32
+    }
+
+    fn oflow_mul_high_unsigned(&self, rhs: &Self) -> Self {
+        let a = u64::from(*self);
+        let b = u64::from(*rhs);
+        ((a * b) >> 32) as u32
+    }
+
+    fn oflow_mul_high_signed_unsigned(&self, rhs: &Self) -> Self {
+        let a = i64::from(*self as i32);
+        let b = i64::from(*rhs);
+        ((a * b) >> 32) as u32
+    }
+    
+    fn oflow_neg(&self) -> Self { 
+        (*self).overflowing_neg().0    
+    }
+    
+    fn oflow_pow(&self, exp: u32) -> Self { 
+        (*self).overflowing_pow(exp).0    
+    }
+
+    fn oflow_shl(&self, bits: u32) -> Self {
+        (*self).overflowing_shl(bits).0
+    }
+
+    fn oflow_shr(&self, bits: u32) -> Self {
+        (*self).overflowing_shr(bits).0
+    }
+
+    fn msb_zeros(&self) -> Self { self.leading_zeros() }
+    fn lsb_zeros(&self) -> Self { self.trailing_zeros() }
+    fn n_ones(&self) -> Self { self.count_ones() }
+
+    fn mul_no_carry(&self, rhs: &Self) -> Self { 
+        let mut x: u32 = 0;
+        (0..32).for_each(|i| {
+            if ((rhs >> i) & 1) != 0 {
+                x ^= self << i
+            }
+        });
+
+        x
+    }
+
+    fn mul_no_carry_high(&self, rhs: &Self) -> Self {
+        let mut x: u32 = 0;
+        (1..32).for_each(|i| {
+            if ((rhs >> i) & 1) != 0 {
+                x ^= self >> (32 - i)
+            }
+        });
+
+        x
+    }
+
+    fn mul_no_carry_rev(&self, rhs: &Self) -> Self {
+        let mut x: u32 = 0;
+        (0..32).for_each(|i| {
+            if ((rhs >> i) & 1) != 0 {
+                x ^= self >> (31 - i);
+            }
+        });
+
+        x
+    }
+
+    fn of_no_carry_byte(&self) -> Self {
+        let mut rev_rem = 0;
+        for byte in 0..4 {
+            let mask = 0xffu32 << (byte * 8);
+            if self & mask != 0 {
+                rev_rem |= mask
+            }
+        }
+
+        rev_rem
+    }
+
+    fn revb(&self) -> Self {
+        self.swap_bytes()
+    }
+
+    fn shl_signed(&self, bits: &Self) -> Self {
+        (*self as i32).shl(*bits) as u32
+    }
+
+    fn shr_signed(&self, bits: &Self) -> Self {
+        (*self as i32).shr(*bits) as u32    
+    }
+
+    fn rotatel(&self, rhs: &Self) -> Self {
+        (*self).rotate_left(*rhs)
+    }
+
+    fn rotater(&self, rhs: &Self) -> Self {
+        (*self).rotate_right(*rhs)
+    }
+
+    fn zero_extend(&self, start: &Self) -> Self {
+        let start = std::cmp::min(*start, 32);
+        if start == 32 {
+            return *self;
+        }
+        (*self << (32 - start)) >> (32 - start)
+    }
+
+    fn sign_extend(&self, start: &Self) -> Self {
+        let start = std::cmp::min(*start, 32);
+        if start == 32 {
+            return *self;
+        }
+        (((*self << (32 - start)) as i32) >> (32 - start)) as u32 
+    }
+}
@@ -0,0 +1,150 @@
+use crate::consts::STACK_SIZE;
+use crate::exceptions::Exception;
+use crate::register::Register;
+use std::collections::HashMap;
+
+// Shadow state is tracked per 8 byte word.
+pub const SHADOW_GRANULE: u64 = 8;
+
+// The `a7` values of the allocator calls the sanitizer services.
+pub const SYS_MALLOC: u64 = 0x1_0000;
+pub const SYS_FREE: u64 = 0x1_0001;
+
+// Where the sanitizer's heap lives in DRAM by default.
+pub const SANITIZER_HEAP_BASE: u64 = 0x20_0000;
+pub const SANITIZER_HEAP_SIZE: u64 = 0x10_0000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SanitizerKind {
+    /// A store to a heap word that is not, or is no longer, allocated.
+    WriteToUnallocated,
+    /// `free` of a pointer that is not the start of a live allocation.
+    InvalidFree,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryState {
+    pub allocated: bool,
+    pub initialized: bool,
+}
+
+/// Shadow memory for the sanitizer of a hart. Allocations are carved from
+/// `heap_base..heap_end` by the `malloc` ecall and every store into that
+/// range must land in a live allocation. The stack may not grow more than
+/// `stack_size` bytes below `stack_top`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sanitizer {
+    pub shadow_map: HashMap<u64, MemoryState>,
+    pub heap_base: u64,
+    pub heap_end: u64,
+    pub stack_top: u64,
+    pub stack_size: u64,
+    /// Set once a load reads a heap word that has not been written since
+    /// it was allocated.
+    pub read_uninitialized: bool,
+    heap_next: u64,
+    allocations: HashMap<u64, u64>,
+}
+
+impl Sanitizer {
+    pub fn new(stack_top: u64) -> Sanitizer {
+        Sanitizer {
+            shadow_map: HashMap::new(),
+            heap_base: SANITIZER_HEAP_BASE,
+            heap_end: SANITIZER_HEAP_BASE + SANITIZER_HEAP_SIZE,
+            stack_top,
+            stack_size: STACK_SIZE as u64,
+            read_uninitialized: false,
+            heap_next: SANITIZER_HEAP_BASE,
+            allocations: HashMap::new(),
+        }
+    }
+
+    /// Allocate `len` bytes, returning 0 once the heap is exhausted.
+    pub fn malloc(&mut self, len: u64) -> u64 {
+        let len = (len.max(1) + SHADOW_GRANULE - 1) & !(SHADOW_GRANULE - 1);
+        let ptr = self.heap_next;
+        if ptr.saturating_add(len) > self.heap_end {
+            return 0;
+        }
+
+        self.heap_next += len;
+        self.allocations.insert(ptr, len);
+        self.mark(ptr, len, MemoryState { allocated: true, initialized: false });
+        ptr
+    }
+
+    pub fn free(&mut self, ptr: u64) -> Result<(), Exception> {
+        if ptr == 0 {
+            return Ok(());
+        }
+
+        let len = self.allocations.remove(&ptr).ok_or(Exception::SanitizerError(SanitizerKind::InvalidFree))?;
+        self.mark(ptr, len, MemoryState::default());
+        Ok(())
+    }
+
+    /// Service the allocator call in `a7`, returning false if it is not
+    /// one the sanitizer handles.
+    pub fn handle_call(&mut self, registers: &mut [u64]) -> Result<bool, Exception> {
+        let a0 = registers[Register::X10 as usize];
+        match registers[Register::X17 as usize] {
+            SYS_MALLOC => registers[Register::X10 as usize] = self.malloc(a0),
+            SYS_FREE => self.free(a0)?,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Check a store of `len` bytes at `addr` and mark the words it writes
+    /// as initialized.
+    pub fn check_store(&mut self, addr: u64, len: usize) -> Result<(), Exception> {
+        let granules = Self::granules(addr, len as u64);
+        if !self.in_heap(addr) {
+            return Ok(());
+        }
+
+        if granules.clone().any(|word| !self.state(word).allocated) {
+            return Err(Exception::SanitizerError(SanitizerKind::WriteToUnallocated));
+        }
+
+        for word in granules {
+            self.shadow_map.entry(word).or_default().initialized = true;
+        }
+        Ok(())
+    }
+
+    pub fn check_load(&mut self, addr: u64) {
+        if self.in_heap(addr) && !self.state(addr & !(SHADOW_GRANULE - 1)).initialized {
+            self.read_uninitialized = true;
+        }
+    }
+
+    pub fn check_stack(&self, sp: u64) -> Result<(), Exception> {
+        if sp < self.stack_top.saturating_sub(self.stack_size) {
+            return Err(Exception::StackOverflow);
+        }
+
+        Ok(())
+    }
+
+    fn state(&self, word: u64) -> MemoryState {
+        self.shadow_map.get(&word).copied().unwrap_or_default()
+    }
+
+    fn in_heap(&self, addr: u64) -> bool {
+        (self.heap_base..self.heap_end).contains(&addr)
+    }
+
+    fn mark(&mut self, ptr: u64, len: u64, state: MemoryState) {
+        for word in Self::granules(ptr, len) {
+            self.shadow_map.insert(word, state);
+        }
+    }
+
+    fn granules(addr: u64, len: u64) -> impl Iterator<Item = u64> + Clone {
+        let start = addr & !(SHADOW_GRANULE - 1);
+        (start..addr.saturating_add(len)).step_by(SHADOW_GRANULE as usize)
+    }
+}
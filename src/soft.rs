@@ -1,1488 +1,3874 @@
-#![allow(unused, unused_mut, dead_code)]
-use crate::encoding::{EncodingTable, InstructionDecoder};
-use crate::encoding_types::Inst;
-use crate::extensions::{Base, Extension};
-use crate::exceptions::Exception;
-use crate::instructions::Instruction;
-use crate::register::{Register, RegisterValue};
-use crate::memory::{Dram, MEM_SIZE};
-use crate::machine::{Machine, Support};
-use crate::memory::Memory;
-use std::error::Error;
-
-pub const INST_LEN: u64 = 4u64;
-
-/// The software represeentation of the RISC-V HART aka Hardware Thread
-/// This is separated from the VM itself so that a VM with multiple SOFT's
-/// i.e. a multithread/concurrent/parallel VM can be created and opearted
-/// 
-/// # Example
-/// ```
-/// use trecho::encoding::{EncodingTable, InstructionDecoder};
-/// use trecho::register::{Register, RegisterValue};
-/// use trecho::memory::{Dram, MEM_SIZE, Memory};
-/// use trecho::machine::{Machine, Support};
-/// use trecho::soft::SoftThread;
-///
-/// let mut soft = SoftThread::<u64, f64, Dram>::default();
-/// let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_0011 as u8];
-/// soft.load_program(program);
-/// soft.execute();
-/// ```
-
-#[derive(Debug)]
-pub struct SoftThread<R, F, M> {
-    pub registers: [R; 33],
-    pub f_registers: [F; 33],
-    pub pc: R,
-    pub program: Vec<u8>,
-    pub remainder: u32,
-    eq_flag: bool,
-    enc_table: EncodingTable,
-    pub bus: M,
-    pub csr: [R; 4096],
-    pub res: Vec<u64>,
-}
-
-impl SoftThread<u64, f64, Dram> {
-    pub fn new(enc_table: EncodingTable) -> SoftThread<u64, f64, Dram> {
-        let mut soft = SoftThread {
-            registers: [0; 33],
-            f_registers: [0.0; 33],
-            pc: 0,
-            program: vec![],
-            remainder: 0,
-            eq_flag: false,
-            enc_table,
-            csr: [0; 4096],
-            bus: Dram::default(),
-            res: vec![]
-        };
-
-        soft.registers[2] = MEM_SIZE;
-        soft.registers[0] = 0;
-
-        soft
-    }
-
-    pub(crate) fn read_xreg(&self, idx: usize) -> u64 {
-        self.registers[idx]
-    }
-
-    pub(crate) fn read_freg(&self, idx: usize) -> f64 {
-        self.f_registers[idx]
-    } 
-
-    pub(crate) fn advance(&mut self) {
-        self.pc += INST_LEN;
-    }
-
-    pub(crate) fn fetch(&self) -> Inst {
-        let mut bytes: [u8; 4] = [
-            self.program[(self.pc + 3) as usize],
-            self.program[(self.pc + 2) as usize],
-            self.program[(self.pc + 1) as usize],
-            self.program[self.pc as usize],
-        ];
-        let inst: Inst = u32::from_le_bytes(bytes);
-        return inst;
-    }
-
-    pub fn execute(&mut self) {
-        let instruction: Instruction = Instruction::decode(self.fetch(), &self.enc_table);
-        match instruction {
-            Instruction::Lui { rd, imm } => {
-                //load upper immediate
-                self.registers[rd as usize] = (imm as i64) as u64;
-                self.advance();
-            },
-            Instruction::Auipc { rd, imm } => {
-                //add upper immediate to program counter
-                if let Some(res) = self.pc.checked_add((imm as i64) as u64) {
-                    self.registers[rd as usize] = res
-                }
-                self.advance();
-            },
-            Instruction::Jal { rd, imm } => {
-                // Jump and link
-                self.registers[rd as usize] = self.pc.wrapping_add(4);
-                self.pc = self.pc.wrapping_add((imm as i64) as u64);
-            },
-            Instruction::Jalr { rd, rs1, imm } => {
-                // Jump and link register
-                let t = self.pc.wrapping_add(4);
-                self.pc = (self.registers[rs1 as usize].wrapping_add((imm as i64) as u64) & !1);
-                self.registers[rd as usize] = t;
-            },
-            Instruction::Beq { rs1, rs2, imm, .. } => {
-                // Branch if equal
-                if self.registers[rs1 as usize] == self.registers[rs2 as usize] {
-                    self.pc = self.pc.wrapping_add((imm as i64) as u64);
-                } else {
-                    self.advance();
-                }
-            },
-            Instruction::Bne { rs1, rs2, imm, .. } => {
-                // Branch if not equal
-                if self.registers[rs1 as usize] != self.registers[rs2 as usize] {
-                    self.pc = self.pc.wrapping_add((imm as i64) as u64);
-                } else {
-                    self.advance();
-                }
-            },
-            Instruction::Blt { rs1, rs2, imm, .. } => {
-                // Branch if less than
-                if (self.registers[rs1 as usize] as i64) < (self.registers[rs2 as usize] as i64) {
-                    self.pc = self.pc.wrapping_add((imm as i64) as u64);
-                } else {
-                    self.advance();
-                }
-            },
-            Instruction::Bge { rs1, rs2, imm, .. } => {
-                // Branch if greater or equal
-                if (self.registers[rs1 as usize] as i64) >= (self.registers[rs2 as usize] as i64) {
-                    self.pc = self.pc.wrapping_add((imm as i64) as u64);
-                } else {
-                    self.advance();
-                }
-            },
-            Instruction::Bltu { rs1, rs2, imm, .. } => {
-                // Branch if less than unsigned
-                if self.registers[rs1 as usize] < self.registers[rs2 as usize] {
-                    self.pc = self.pc.wrapping_add((imm as i64) as u64);
-                } else { 
-                    self.advance(); 
-                }
-            },
-            Instruction::Bgeu { rs1, rs2, imm, .. } => {
-                // Branch if greater than unsigned
-                if self.registers[rs1 as usize] >= self.registers[rs2 as usize] {
-                    self.pc = self.pc.wrapping_add((imm as i64) as u64);
-                } else {
-                    self.advance();
-                }
-            },
-            Instruction::Lb { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                if let Ok(val) = self.bus.read(&addr.into(), 8) {
-                    self.registers[rd as usize] = ((self.bus.into_u64(&val)) as i64) as u64;
-                }
-
-                self.advance();
-            },
-            Instruction::Lh { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                if let Ok(val) = self.bus.read(&addr.into(), 16) {
-                    self.registers[rd as usize] = ((self.bus.into_u64(&val)) as i64) as u64;
-                }
-                
-                self.advance();
-            },
-            Instruction::Lw { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                if let Ok(val) = self.bus.read(&addr.into(), 32) {
-                    self.registers[rd as usize] = ((self.bus.into_u64(&val) as i32) as i64) as u64
-                }
-
-                self.advance();
-            },
-            Instruction::Lbu { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                if let Ok(val) = self.bus.read(&addr.into(), 8) {
-                    self.registers[rd as usize] = self.bus.into_u64(&val);
-                }
-
-                self.advance();
-            },
-            Instruction::Lhu { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                if let Ok(val) = self.bus.read(&addr.into(), 16) {
-                    self.registers[rd as usize] = self.bus.into_u64(&val);
-                }
-
-                self.advance();
-            },
-            Instruction::Sb { rs1, rs2, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                let _ = self.bus.write(addr, self.registers[rs2 as usize], 8);
-                self.advance();
-            },
-            Instruction::Sh { rs1, rs2, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                let _ = self.bus.write(addr, self.registers[rs2 as usize], 16);
-                self.advance();
-            },
-            Instruction::Sw { rs1, rs2, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                let _ = self.bus.write(addr, self.registers[rs2 as usize], 32);
-                self.advance();
-            },
-            Instruction::Addi { rd, rs1, imm, .. } => {
-                let imm = (imm as i64) as u64;
-                if let Some(res) = self.registers[rs1 as usize].checked_add(imm) {
-                    self.registers[rd as usize] = res;
-                }
-                self.advance();
-            },
-            Instruction::Slti { rd, rs1, imm, .. } => {
-                self.registers[rd as usize] = if (self.registers[rs1 as usize] as i64) < (imm as i64) {
-                    1
-                } else {
-                    0
-                };
-                self.advance();
-            },
-            Instruction::Sltiu { rd, rs1, imm, .. } => {
-                self.registers[rd as usize] = if self.registers[rs1 as usize] < ((imm as i64) as u64) { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::Xori { rd, rs1, imm, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize] ^ ((imm as i64) as u64);
-                self.advance();
-            },
-            Instruction::Ori { rd, rs1, imm, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize] | ((imm as i64) as u64);
-                self.advance();
-            },
-            Instruction::Andi { rd, rs1, imm, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize] & ((imm as i64) as u64);
-                self.advance();
-            },
-            Instruction::Slli { rd, rs1, shamt, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_shl(shamt);
-                self.advance();
-            },
-            Instruction::Srli { rd, rs1, shamt, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_shr(shamt);
-                self.advance();
-            },
-            Instruction::Srai { rd, rs1, shamt, .. } => {
-                self.registers[rd as usize] = (self.registers[rs1 as usize] as i64).wrapping_shr(shamt) as u64;
-                self.advance();
-            },
-            Instruction::Add { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_add(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Sub { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_sub(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Sll { rd, rs1, rs2, .. } => {
-                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
-                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_shl(shamt);
-                self.advance();
-            },
-            Instruction::Slt { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = if ((self.registers[rs1 as usize] as i64) < (self.registers[rs2 as usize] as i64)) {
-                    1 
-                } else {
-                    0
-                };
-                self.advance();
-            },
-            Instruction::Sltu { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = if self.registers[rs1 as usize] < self.registers[rs2 as usize] {
-                    1
-                } else {
-                    0
-                };
-                self.advance();
-            },
-            Instruction::Xor { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize] ^ self.registers[rs2 as usize];
-                self.advance();
-            },
-            Instruction::Srl { rd, rs1, rs2, .. } => {
-                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
-                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_shr(shamt);
-                self.advance();
-            },
-            Instruction::Sra { rd, rs1, rs2, .. } => {
-                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
-                self.registers[rd as usize] = (self.registers[rs1 as usize] as i64).wrapping_shr(shamt) as u64;
-                self.advance();
-            },
-            Instruction::Or { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize] | self.registers[rs2 as usize];
-                self.advance();
-            },
-            Instruction::And { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize] & self.registers[rs2 as usize];
-                self.advance();
-            },
-            Instruction::Fence { .. } => { todo!() }
-            Instruction::ECall => { 
-                // TODO: Call self.ecall() once machine is impl on SoftThread
-                todo!()
-            },
-            Instruction::EBreak => {
-                // TODO: Call ebreak() on debugger once debugger is added into SoftThread
-                todo!()
-            },
-            Instruction::Lwu { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                if let Ok(val) = self.bus.read(&addr.into(), 32) {
-                    self.registers[rd as usize] = self.bus.into_u64(&val);
-                }
-                self.advance();
-            },
-            Instruction::Ld { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                if let Ok(val) = self.bus.read(&addr.into(), 64) {
-                    self.registers[rd as usize] = self.bus.into_u64(&val);
-                }
-                self.advance();
-            },
-            Instruction::Sd { rs1, rs2, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
-                let _ = self.bus.write(addr, self.registers[rs2 as usize], 64);
-                self.advance();
-            },
-            Instruction::Addiw { rd, rs1, imm, .. } => {
-                self.registers[rd as usize] = ((self.registers[rs1 as usize].wrapping_add(((imm as i64) as u64)) as i32) as i64) as u64;
-                self.advance();
-            },
-            Instruction::Slliw { rd, rs1, shamt, .. } => {
-                self.registers[rd as usize] = ((self.registers[rs1 as usize].wrapping_shl(shamt) as i32) as i64) as u64;
-                self.advance();
-            },
-            Instruction::Sraiw { rd, rs1, shamt, .. } => {
-                self.registers[rd as usize] = ((self.registers[rs1 as usize] as i32).wrapping_shr(shamt) as i64) as u64;
-                self.advance();
-            },
-            Instruction::Addw { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = ((self.registers[rs1 as usize].wrapping_add(self.registers[rs2 as usize]) as i32) as i64) as u64;
-                self.advance();
-            },
-            Instruction::Subw { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = ((self.registers[rs1 as usize].wrapping_sub(self.registers[rs2 as usize]) as i32) as i64) as u64;
-                self.advance()
-            },
-            Instruction::Sllw { rd, rs1, rs2, .. } => {
-                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
-                self.registers[rd as usize] = ((self.registers[rs1 as usize] as u32).wrapping_shl(shamt) as i32) as u64;
-                self.advance();
-            },
-            Instruction::Srlw { rd, rs1, rs2, .. } => {
-                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
-                self.registers[rd as usize] = ((self.registers[rs1 as usize] as u32).wrapping_shr(shamt) as i32) as u64;
-                self.advance();
-            },
-            Instruction::Sraw { rd, rs1, rs2, .. } => {
-                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
-                self.registers[rd as usize] = ((self.registers[rs1 as usize] as i32) >> (shamt as i32)) as u64;
-                self.advance();
-            },
-            Instruction::FenceI { .. } => { todo!() },
-            Instruction::Csrrw { csr, rs1, rd, .. } => {
-                if rd != Register::X0 {
-                    let csr_val = self.csr[csr as usize];
-                    let csr_val = (csr_val as u64).zero_extend(&32);
-                    self.registers[rd as usize] = csr_val;
-                    self.csr[csr as usize] = self.registers[rs1 as usize]
-                }
-                self.advance();
-            },
-            Instruction::Csrrs { csr, rs1, rd, .. } => {
-                if rs1 != Register::X0 {
-                    let csr_val = self.csr[csr as usize];
-                    let csr_val = (csr_val as u64).zero_extend(&32);
-                    self.registers[rd as usize] = csr_val;
-                    self.csr[csr as usize] = self.csr[csr as usize] | self.registers[rs1 as usize];    
-                }
-                self.advance();
-            },
-            Instruction::Csrrc { csr, rs1, rd, .. } => {
-                if rs1 != Register::X0 {
-                    let csr_val = self.csr[csr as usize];
-                    let csr_val = (csr_val as u64).zero_extend(&32);
-                    self.registers[rd as usize] = csr_val;
-                    self.csr[csr as usize] = self.csr[csr as usize] & self.registers[rs1 as usize];
-                }
-                self.advance();
-            },
-            Instruction::Csrrwi { rd, csr, uimm, .. } => {
-                if rd != Register::X0 {
-                    let csr_val = self.csr[csr as usize];
-                    let imm = (uimm as u64).zero_extend(&32);
-                    self.registers[rd as usize] = csr_val;
-                    self.csr[csr as usize] = imm;
-                }
-                self.advance();
-            },
-            Instruction::Csrrsi { rd, csr, uimm, .. } => {
-                if uimm != Register::X0 as u32 {
-                    let csr_val = self.csr[csr as usize];
-                    let imm = (uimm as u64).zero_extend(&32);
-                    self.registers[rd as usize] = csr_val;
-                    self.csr[csr as usize] = self.csr[csr as usize] | imm;
-                }
-                self.advance();
-            },
-            Instruction::Csrrci { rd, csr, uimm, .. } => {
-                if uimm != Register::X0 as u32 {
-                    let csr_val = self.csr[csr as usize];
-                    let imm = (uimm as u64).zero_extend(&32);
-                    self.registers[rd as usize] = csr_val;
-                    self.csr[csr as usize] = self.csr[csr as usize] & imm;
-                }
-                self.advance();
-            },
-            Instruction::Mul { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Mulh { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul_high_signed(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Mulhsu { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul_high_signed_unsigned(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Mulhu { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul_high_unsigned(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Div { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_div_signed(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Divu { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_div(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Rem { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_rem_signed(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Remu { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_rem(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Mulw { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Divw { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_div_signed(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Divuw { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_div(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::Remw { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_rem_signed(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::RemuW { rd, rs1, rs2, .. } => {
-                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_rem(&self.registers[rs2 as usize]);
-                self.advance();
-            },
-            // For W instructions below ALL words being read from
-            // memory must be naturally aligned to 32 bits
-            // i.e. mod 4 == 0;
-            // For D instructions belwo ALL doublewords being
-            // read from memory most be naturally aligned to
-            // 64 bit words, i.e. mod 8 == 0;
-            Instruction::LrW { rd, rs1, .. } => {
-
-                // Test whether vec or hashset is best suited for this.
-                let addr = self.registers[rs1 as usize];
-
-                if addr % 4 != 0 {
-                    // TODO: Reject
-                    todo!();
-                }
-                
-                let res = self.bus.read(&addr, 32);
-                if let Ok(val) = res {
-                    let val = ((val as i32) as i64) as u64;
-                    self.registers[rd as usize] = val;
-                    self.res.push(self.registers[rs1 as usize]);
-                }
-
-                self.advance();
-            },
-            Instruction::ScW { rd, rs1, rs2, .. } => {
-                // if an address reservation is still value
-                // and contains the bytes being written
-                // then write the word in rs2 to addr in
-                // rs1, and set rd to zero.
-                // otherwise write a nonzero value to rd.
-                // Invalidate any reservation held be this
-                // thread.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    //TODO: Reject
-                    todo!();
-                }
-                    
-                if self.res.contains(&addr) {
-                    self.res.retain(|x| *x != addr);
-                    let word = self.registers[rs2 as usize];
-                    self.bus.write(addr, word, 32);
-                    self.registers[rd as usize] = 0;
-                } else {
-                    self.res.retain(|x| *x != addr);
-                    self.registers[rd as usize] = 1;
-                }
-                self.advance();
-            },
-            Instruction::AmoswapW { rd, rs1, rs2, ..} => {
-                // read a word from the address in rs1
-                // write the value in rs2 register to
-                // address in rs1, take value from rs1 and
-                // sign extend then store in rd
-                let addr = self.registers[rs1 as usize];
-
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let temp = ((temp as i32) as i64) as u64;
-                    let val = self.registers[rs2 as usize];                    
-                    let _ = self.bus.write(addr, val, 32);
-                    self.registers[rd as usize] = temp;  
-
-                }
-                
-                self.advance();
-            },
-            Instruction::AmoaddW { rd, rs1, rs2, ..} => {
-                // read word from address in rs1
-                // add the value from rs2 to the word
-                // read at rs1 address and save result
-                // in memory at address in rs1. Write
-                // previous value in address at rs1
-                // to rd.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let temp = ((temp as i32) as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = temp + val;
-                    let _ = self.bus.write(addr, res, 32);
-                    self.registers[rd as usize] = temp; 
-                }
-                self.advance();
-            },
-            Instruction::AmoxorW { rd, rs1, rs2, .. } => {
-                // read word from address in rs1
-                // xor the word against the value in rs2
-                // save the original value found at address
-                // in rs1 to rd. Save the xor value in the
-                // memory at the address from rs1.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let temp = ((temp as i32) as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = temp ^ val;
-                    let _ = self.bus.write(addr, res, 32);
-                    self.registers[rd as usize] = temp;
-                }
-
-                self.advance();
-            },
-            Instruction::AmoandW { rd, rs1, rs2, .. } => {
-                // read word from address in rs1
-                // bitwise and word against the value in rs2
-                // save the original value found at address
-                // in rs1 to rd. Save the bitwise and'd value
-                // in the memory at the address from rs1.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let temp = ((temp as i32) as i64) as u64;     
-                    let val = self.registers[rs2 as usize];
-                    let res = temp & val;
-                    let _ = self.bus.write(addr, res, 32);
-                    self.registers[rd as usize] = temp;
-                }
-
-                self.advance();
-            },
-            Instruction::AmoorW { rd, rs1, rs2, .. } => {
-                // read word from address in rs1
-                // bitwise or word against value in rs2
-                // save the original value found at address
-                // in rs1 to rd. save the bitwise or'd value
-                // in the memory at the address from rs1.
-                let addr = self.registers[rs1 as usize];
-
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let temp = ((temp as i32) as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = temp | val;
-                    let _ = self.bus.write(addr, res, 32);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmominW { rd, rs1, rs2, .. } => {
-                // read word from address in rs1
-                // compare the value of the word to the
-                // value in rs2 and save the lowest value
-                // to memory at the address in rs1.
-                // store the original word at address in rs1
-                // to rd. 
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let temp = ((temp as i32) as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = std::cmp::min(temp, val);   
-                    let _ = self.bus.write(addr, res, 32);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmomaxW { rd, rs1, rs2, .. } => {
-                // read word from address in rs1
-                // compare the value of the word to the
-                // value in rs2. Store the highest value
-                // in memory at the address in rs1.
-                // store the original word atw address in rs1
-                // to rd.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let temp = ((temp as i32) as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = std::cmp::max(temp, val);
-                    let _ = self.bus.write(addr, res, 32);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmominuW { rd, rs1, rs2, .. } => {
-                // read word from address in rs1
-                // compare the unsigned value to an unsigned
-                // value in rs2. Store the lowest value to
-                // memory at the address in rs1
-                // store the original word at address in rs1
-                // to rd.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let temp = temp;
-                    let val = self.registers[rs2 as usize];
-                    let res = std::cmp::min(temp, val);
-                    let _ = self.bus.write(addr, res, 32);
-                    self.registers[rd as usize] = temp;
-                }
-
-                self.advance();
-            },
-            Instruction::AmomaxuW { rd, rs1, rs2, .. } => {
-                // read word from address in rs1
-                // compare the unsigned value to an unsigned
-                // value in rs2. Store the higheste value to
-                // memory at the address in rs1
-                // store the original word at address in rs1
-                // to rd.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-
-                if let Ok(temp) = self.bus.read(&addr, 32) {
-                    let val = self.registers[rs2 as usize];
-                    let res = std::cmp::max(temp, val);
-                    let _ = self.bus.write(addr, res, 32);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::LrD { rd, rs1, .. } => {
-                // See LrD, but instead of reading word
-                // from address at rs1, read double word.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 8 != 0 {
-                    // TODO: Reject
-                    todo!();
-                }
-
-                if let Ok(temp) = self.bus.read(&addr, 64) {
-                    let val = (temp as i64) as u64;    
-                    self.registers[rd as usize] = val;
-                    self.res.push(self.registers[rs1 as usize]);
-                } 
-                self.advance();
-            },
-            Instruction::ScD { rd, rs1, rs2, .. } => {
-                // See ScW, but instead of conditionally
-                // saving a word, save a double word.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 8 != 0 {
-                    //TODO: Reject
-                    todo!();
-                }
-                    
-                if self.res.contains(&addr) {
-                    self.res.retain(|x| *x != addr);
-                    let dword = self.registers[rs2 as usize];
-                    let _ = self.bus.write(addr, dword, 64);
-                    self.registers[rd as usize] = 0;
-                } else {
-                    self.res.retain(|x| *x != addr);
-                    self.registers[rd as usize] = 1;
-                }
-
-                self.advance();
-            },
-            Instruction::AmoswapD { rd, rs1, rs2, ..} => {
-                // read a doubleword from the address in rs1
-                // write the value in rs2 register to
-                // address in rs1, take value from rs1 and
-                // sign extend then store in rd
-                let addr = self.registers[rs1 as usize];
-
-                if addr % 8 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 64) {
-                    let temp: u64 = (temp as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let _ = self.bus.write(addr, val, 64);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmoaddD { rd, rs1, rs2, ..} => {
-                // read doubleword from address in rs1
-                // add the value from rs2 to the doubleword
-                // read at rs1 address and save result
-                // in memory at address in rs1. Write
-                // previous value in address at rs1
-                // to rd.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 8 != 0 {
-                    // Reject
-                    todo!();
-                }
-
-                if let Ok(temp) = self.bus.read(&addr, 64) {
-                    let temp = (temp as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = temp + val;
-                    let _ = self.bus.write(addr, res, 64);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmoxorD { rd, rs1, rs2, .. } => {
-                // read doubleword from address in rs1
-                // xor the doubleword against the value in rs2
-                // save the original value found at address
-                // in rs1 to rd. Save the xor value in the
-                // memory at the address from rs1.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 8 != 0 {
-                    // Reject
-                    todo!();
-                }
-
-                if let Ok(temp) = self.bus.read(&addr, 64) {
-                    let temp = (temp as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = temp ^ val;
-                    let _ = self.bus.write(addr, res, 64);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmoandD { rd, rs1, rs2, .. } => {
-                // read doubleword from address in rs1
-                // bitwise and doubleword against the value in rs2
-                // save the original value found at address
-                // in rs1 to rd. Save the bitwise and'd value
-                // in the memory at the address from rs1.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 8 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 64) {
-                    let temp = (temp as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = temp & val;
-                    let _ = self.bus.write(addr, res, 64);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmoorD { rd, rs1, rs2, .. } => {
-                // read doubleword from address in rs1
-                // bitwise or doubleword against value in rs2
-                // save the original value found at address
-                // in rs1 to rd. save the bitwise or'd value
-                // in the memory at the address from rs1.
-                let addr = self.registers[rs1 as usize];
-
-                if addr % 8 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 64) {
-                    let temp = (temp as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let res = temp | val;
-                    let _ = self.bus.write(addr, res, 64);
-                    self.registers[rd as usize] = temp;                    
-                }
-                self.advance();
-            },
-            Instruction::AmominD { rd, rs1, rs2, .. } => {
-                // read doubleword from address in rs1
-                // compare the value of the doubleword to the
-                // value in rs2 and save the lowest value
-                // to memory at the address in rs1.
-                // store the original doubleword at address in rs1
-                // to rd. 
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 8 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                let res = self.bus.read(&addr, 64);
-                if let Ok(temp) = res {
-                    let temp = (temp as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let fin = std::cmp::min(temp, val);
-                    let _ = self.bus.write(addr, fin, 64);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmomaxD { rd, rs1, rs2, .. } => {
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                let res = self.bus.read(&addr, 64);
-                if let Ok(temp) = res {
-                    let temp = (temp as i64) as u64;
-                    let val = self.registers[rs2 as usize];
-                    let fin = std::cmp::max(temp, val);
-                    let _ = self.bus.write(addr, fin, 64);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmominuD { rd, rs1, rs2, .. } => {
-                // read doubleword from address in rs1
-                // compare the unsigned value to an unsigned
-                // value in rs2. Store the lowest value to
-                // memory at the address in rs1
-                // store the original doubleword at address in rs1
-                // to rd.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 8 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                let res = self.bus.read(&addr, 64);
-                if let Ok(temp) = res {
-                    let val = self.registers[rs2 as usize];
-                    let fin = std::cmp::min(temp, val);
-                    let _ = self.bus.write(addr, fin, 64);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::AmomaxuD { rd, rs1, rs2, .. } => {
-                // read doubleword from address in rs1
-                // compare the unsigned value to an unsigned
-                // value in rs2. Store the higheste value to
-                // memory at the address in rs1
-                // store the original doubleword at address in rs1
-                // to rd.
-                let addr = self.registers[rs1 as usize];
-                
-                if addr % 4 != 0 {
-                    // Reject
-                    todo!();
-                }
-                
-                if let Ok(temp) = self.bus.read(&addr, 64) {
-                    let val = self.registers[rs2 as usize];
-                    let fin = std::cmp::max(temp, val);
-                    let _ = self.bus.write(addr, fin, 64);
-                    self.registers[rd as usize] = temp;
-                }
-                self.advance();
-            },
-            Instruction::Flw { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as u32) as u64);
-                if let Ok(bits) = self.bus.read(&addr, 32) {
-                    let val = f32::from_bits((bits as u32));
-                    self.f_registers[rd as usize] = val as f64;
-                }
-                self.advance();
-            },
-            Instruction::Fsw { rs1, rs2, imm, .. } => {
-                // store value in f_register rs2 as bits into memory at address in rs1 + imm
-                let addr = self.registers[rs1 as usize].wrapping_add((imm as u32) as u64);
-                let val = (self.f_registers[rs2 as usize] as f32).to_bits() as u64;
-                let _ = self.bus.write(addr, val, 32);
-                self.advance();
-            },
-            Instruction::FmaddS { rd, rs1, rs2, rs3, rm, .. } => {
-                // multiply value in f_register[rs1] by value in f_register[rs2]
-                // add value in rs3
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FmsubS { rd, rs1, rs2, rs3, rm, .. } => {
-                // multiply value in f_register[rs1] by value in f_register[rs2]
-                // subtract value in rs3
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = -self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FnmsubS { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = -self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = -self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FnmaddS { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = -self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FaddS { rd, rs1, rs2, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val + rs2_val;
-                self.advance();
-            },
-            Instruction::FsubS { rd, rs1, rs2, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val - rs2_val;
-                self.advance();
-            },
-            Instruction::FmulS { rd, rs1, rs2, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val * rs2_val;
-                self.advance();
-            },
-            Instruction::FdivS { rd, rs1, rs2, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val / rs2_val;
-                self.advance();
-            },
-            Instruction::FsqrtS { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.f_registers[rs1 as usize].sqrt());
-                self.advance();
-            },
-            Instruction::FsgnjS { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val.copysign(rs2_val);
-                self.advance();
-            },
-            Instruction::FsgnjnS { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = -self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val.copysign(rs2_val);
-                self.advance();
-            },
-            Instruction::FsgnjxS { rd, rs1, rs2, .. } => {
-                let sign_1 = (self.f_registers[rs1 as usize] as f32).to_bits() & 0x8000_0000;
-                let sign_2 = (self.f_registers[rs2 as usize] as f32).to_bits() & 0x8000_0000;
-                let other = (self.f_registers[rs1 as usize] as f32).to_bits() & 0x7fff_ffff;
-                self.f_registers[rd as usize] = (f32::from_bits((sign_1 ^ sign_2) | other)) as f64;
-                self.advance();
-            },
-            Instruction::FminS { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val.min(rs2_val);
-                self.advance();
-            },
-            Instruction::FmaxS { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val.max(rs2_val);
-                self.advance();
-            },
-            Instruction::FcvtWS { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize].round() as i32) as u64;
-                self.advance();
-            },
-            Instruction::FcvtWUS { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = ((self.f_registers[rs1 as usize].round() as u32) as i32) as u64;
-                self.advance();
-            },
-            Instruction::FmvXW { rd, rs1, .. } => {
-                let rs1_val = (((self.f_registers[rs1 as usize].to_bits() & 0xffffffff) as i32) as i64) as u64;
-                self.registers[rd as usize] = rs1_val;
-                self.advance();
-            },
-            Instruction::FeqS { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.registers[rd as usize] = if rs1_val == rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FltS { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.registers[rd as usize] = if rs1_val < rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FleS { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                println!("{:?} == {:?}: {:?}", rs1_val, rs2_val, rs1_val <= rs2_val);
-                self.registers[rd as usize] = if rs1_val <= rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FclassS { rd, rs1, .. } => {
-                todo!();
-            },
-            Instruction::FcvtSW { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = ((self.registers[rs1 as usize] as i32) as f32) as f64;
-                self.advance();
-            },
-            Instruction::FcvtSWU { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = ((self.registers[rs1 as usize] as u32) as f32) as f64;
-                self.advance();
-            },
-            Instruction::FmvWX { rd, rs1, .. } => {
-                let rs1_val = self.registers[rs1 as usize];
-                self.f_registers[rd as usize] = f64::from_bits(self.registers[rs1 as usize] & 0xffff_ffff);
-                self.advance();
-            },
-            Instruction::FcvtLS { rd, rs1, rm, ..} => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize] as f32).round() as u64;
-                self.advance();
-            },
-            Instruction::FcvtLUS { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize] as f32).round() as u64;
-                self.advance();
-            },
-            Instruction::FcvtSL { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as f32) as f64;
-                self.advance();
-            },
-            Instruction::FcvtSLU { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = ((self.registers[rs1 as usize] as u64) as f32) as f64;
-                self.advance();
-            },
-            Instruction::Fld { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize];
-                if let Ok(val) = self.bus.read(&addr, 64) {
-                    let f_val = f64::from_bits(val);
-                    self.f_registers[rd as usize] = f_val;
-                }
-                self.advance();
-            },
-            Instruction::Fsd { rs1, rs2, imm, .. } => {
-                let addr = self.registers[rs1 as usize];
-                let val = self.f_registers[rs2 as usize];
-                self.bus.write(addr, val.to_bits() as u64, 64);
-                self.advance();
-            },
-            Instruction::FmaddD { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FmsubD { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = -self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FnmsubD { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = -self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = -self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FnmaddD { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = -self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FaddD { rd, rs1, rs2, rm, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize] + self.f_registers[rs2 as usize]; 
-                self.advance();
-            },
-            Instruction::FsubD { rd, rs1, rs2, rm, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize] - self.f_registers[rs2 as usize];
-                self.advance();
-            },
-            Instruction::FmulD { rd, rs1, rs2, rm, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize] * self.f_registers[rs2 as usize];
-                self.advance();
-            },
-            Instruction::FdivD { rd, rs1, rs2, rm, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize] / self.f_registers[rs2 as usize];
-                self.advance();
-            },
-            Instruction::FsqrtD { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].sqrt();
-                self.advance();
-            },
-            Instruction::FsgnjD { rd, rs1, rs2, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].copysign(self.f_registers[rs2 as usize]);
-                self.advance();   
-            },
-            Instruction::FsgnjnD { rd, rs1, rs2, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].copysign(-self.f_registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::FsgnjxD { rd, rs1, rs2, .. } => {
-                let sign_1 = self.f_registers[rs1 as usize].to_bits() & 0x8000_0000_0000_0000;
-                let sign_2 = self.f_registers[rs2 as usize].to_bits() & 0x8000_0000_0000_0000;
-                let other = self.f_registers[rs1 as usize].to_bits() & 0x7fff_ffff_ffff_ffff;
-                self.f_registers[rd as usize] = f64::from_bits((sign_1 ^ sign_2) | other);
-                self.advance();
-            },
-            Instruction::FminD { rd, rs1, rs2, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].min(self.f_registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::FmaxD { rd, rs1, rs2, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].max(self.f_registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::FcvtSD { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize];
-                self.advance();
-            },
-            Instruction::FcvtDS { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.f_registers[rs1 as usize] as f32) as f64;
-                self.advance();
-            },
-            Instruction::FeqD { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.registers[rd as usize] = if rs1_val == rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FltD { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.registers[rd as usize] = if  rs1_val < rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FleD { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.registers[rd as usize] = if  rs1_val <= rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FclassD { rd, rs1, ..} => {},
-            Instruction::FcvtWD { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize].round() as i32) as u64;
-                self.advance();
-            },
-            Instruction::FcvtWUD { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = ((self.f_registers[rs1 as usize].round() as u32) as i32) as u64;
-                self.advance();
-            },
-            Instruction::FcvtDW { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as i32) as f64;
-                self.advance();
-            },
-            Instruction::FcvtDWU { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as u32) as f64;
-                self.advance();
-            },
-            Instruction::FcvtLD { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize].round()) as u64;
-                self.advance();
-            },
-            Instruction::FcvtLUD { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize].round()) as u64;
-                self.advance();
-            },
-            Instruction::FmvXD { rd, rs1, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize].to_bits());
-                self.advance();
-            },
-            Instruction::FcvtDL { rd, rs1, .. } => {
-                self.f_registers[rd as usize] = self.registers[rs1 as usize] as f64;
-                self.advance();
-            },
-            Instruction::FcvtDLU { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = self.registers[rs1 as usize] as f64;
-                self.advance();
-            },
-            Instruction::FmvDX { rd, rs1, .. } => {
-                self.registers[rd as usize] = self.f_registers[rs1 as usize].to_bits();
-                self.advance();
-            },
-            Instruction::Flq { rd, rs1, imm, .. } => {
-                let addr = self.registers[rs1 as usize];
-                if let Ok(val) = self.bus.read(&addr, 64) {
-                    let val = f64::from_bits(val);
-                    self.f_registers[rd as usize] = val;
-                }
-                self.advance();
-            },
-            Instruction::Fsq { rs1, rs2, imm, .. } => {
-                let addr = self.registers[rs1 as usize];
-                let val = self.f_registers[rs2 as usize].to_bits() as u64;
-                self.bus.write(addr, val, 64);
-                self.advance();
-            },
-            Instruction::FmaddQ { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FmsubQ { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = -self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FnmsubQ { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = -self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = -self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FnmaddQ { rd, rs1, rs2, rs3, rm, .. } => {
-                let rs1_val = -self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                let rs3_val = self.f_registers[rs3 as usize];
-                self.f_registers[rd as usize] = rs1_val.mul_add(rs2_val, rs3_val);
-                self.advance();
-            },
-            Instruction::FaddQ { rd, rs1, rs2, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val + rs2_val;
-                self.advance();
-            },
-            Instruction::FsubQ { rd, rs1, rs2, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val - rs2_val;
-                self.advance();
-            },
-            Instruction::FmulQ { rd, rs1, rs2, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val * rs2_val;
-                self.advance();
-            },
-            Instruction::FdivQ { rd, rs1, rs2, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val / rs2_val;
-                self.advance();
-            },
-            Instruction::FsqrtQ { rd, rs1, rm, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                self.f_registers[rd as usize] = rs1_val.sqrt();
-                self.advance();
-            },
-            Instruction::FsgnjQ { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val.copysign(rs2_val);
-                self.advance();
-            },
-            Instruction::FsgnjnQ { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = -self.f_registers[rs2 as usize];
-                self.f_registers[rd as usize] = rs1_val.copysign(rs2_val);
-                self.advance();
-            },
-            Instruction::FsgnjxQ { rd, rs1, rs2, .. } => {
-                let sign_1 = self.f_registers[rs1 as usize].to_bits() & 0x8000_0000_0000_0000;
-                let sign_2 = self.f_registers[rs2 as usize].to_bits() & 0x8000_0000_0000_0000;
-                let other = self.f_registers[rs1 as usize].to_bits() & 0x7fff_ffff_ffff_ffff;
-                self.f_registers[rd as usize] = f64::from_bits((sign_1 ^ sign_2) | other);
-                self.advance();
-            },
-            Instruction::FminQ { rd, rs1, rs2, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].min(self.f_registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::FmaxQ { rd, rs1, rs2, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].max(self.f_registers[rs2 as usize]);
-                self.advance();
-            },
-            Instruction::FcvtSQ { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = self.f_registers[rs1 as usize];
-                self.advance();
-            },
-            Instruction::FcvtQS { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.f_registers[rs1 as usize]);
-                self.advance();
-            },
-            Instruction::FcvtDQ { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.f_registers[rs1 as usize] as f32) as f64;
-                self.advance();
-            },
-            Instruction::FcvtQD { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.f_registers[rs1 as usize] as f32) as f64;
-                self.advance();
-            },
-            Instruction::FeqQ { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.registers[rd as usize] = if rs1_val == rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FltQ { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.registers[rd as usize] = if rs1_val < rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FleQ { rd, rs1, rs2, .. } => {
-                let rs1_val = self.f_registers[rs1 as usize];
-                let rs2_val = self.f_registers[rs2 as usize];
-                self.registers[rd as usize] = if rs1_val <= rs2_val { 1 } else { 0 };
-                self.advance();
-            },
-            Instruction::FclassQ { rd, rs1, .. } => {
-                //TODO: Need to add classes enum and class logic execution
-                self.advance();
-            },
-            Instruction::FcvtWQ { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize].round() as i32) as u64;
-                self.advance();
-            },
-            Instruction::FcvtWUQ { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = ((self.f_registers[rs1 as usize].round() as u32) as i32) as u64;
-                self.advance();
-            },
-            Instruction::FcvtQW { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as i32) as f64;
-                self.advance();
-            },
-            Instruction::FcvtQWU { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as u32) as f64;
-                self.advance();
-            },
-            Instruction::FcvtLQ { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize].round()) as u64;
-                self.advance();
-            },
-            Instruction::FcvtLUQ { rd, rs1, rm, .. } => {
-                self.registers[rd as usize] = (self.f_registers[rs1 as usize].round()) as u64;
-                self.advance();    
-            },
-            Instruction::FcvtQL { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = self.registers[rs1 as usize] as f64;
-                self.advance();
-            },
-            Instruction::FcvtQLU { rd, rs1, rm, .. } => {
-                self.f_registers[rd as usize] = self.registers[rs1 as usize] as f64;
-                self.advance();
-            },
-            _ => { /* Return an error here, and some other places */ }
-        }
-    }
-
-    pub fn load_program(&mut self, code: Vec<u8>) -> Result<(), Exception> {
-        if code.len() > 4096usize {
-            return Err(Exception::StackSizeExceeded);
-        }
-
-        self.program = code;
-        
-        Ok(())
-    }
-}
-
-
-
-
-impl Default for SoftThread<u64, f64, Dram> {
-    fn default() -> SoftThread<u64, f64, Dram> {
-        let enc_table = EncodingTable::default();
-        SoftThread::<u64, f64, Dram>::new(enc_table)
-    }
-}
+#![allow(unused, unused_mut, dead_code)]
+use crate::encoding::{EncodingTable, InstructionDecoder};
+use crate::encoding_types::Inst;
+use crate::extensions::{Base, Extension};
+use crate::exceptions::{Exception, StepError};
+use crate::instructions::Instruction;
+use crate::register::{Register, RegisterValue};
+use crate::memory::{self, Dram, UnalignedMode, MEM_SIZE};
+use crate::machine::{Machine, Support};
+use crate::memory::Memory;
+use crate::csr;
+use crate::csr::{CSR_FCSR, CSR_FFLAGS, CSR_FRM, CSR_MCAUSE, CSR_MEPC, CSR_MHARTID, CSR_MIE, CSR_MIP, CSR_MISA, CSR_MSTATUS, CSR_MTVAL, CSR_MTVEC, CSR_SEPC, FS_DIRTY, MSTATUS_MIE, MSTATUS_MPP, MSTATUS_MPP_SHIFT, Mstatus};
+use crate::csr::{CSR_CYCLE, CSR_CYCLEH, CSR_INSTRET, CSR_INSTRETH, CSR_MCYCLE, CSR_MINSTRET, CSR_PMPADDR0, CSR_PMPCFG0, CSR_SATP, CSR_STVEC, PMPCFG_A, PMP_ENTRIES, SATP_MODE_SHIFT, SATP_PPN};
+use crate::privilege::PrivilegeLevel;
+use crate::float::{add_flags, add_flags_f32, box_f32, canonicalize, canonicalize_f32, classify_f32, classify_f64, div_flags, div_flags_f32, fma_flags, fma_flags_f32, fmax_rv, fmax_rv_f32, fmin_rv, fmin_rv_f32, int_cvt_flags, is_signaling_nan, is_signaling_nan_f32, mul_flags, mul_flags_f32, narrow_f32, round_rm, sqrt_flags, sqrt_flags_f32, unbox_f32, FFLAGS_NV, RM_DYN, RM_RMM};
+use crate::speed::SimSpeed;
+use crate::elf::{self, CoreSegment, Elf, PF_R, PF_W, PF_X};
+use crate::linux::{self, ExitStatus, LinuxSyscalls, RunError, SyscallResult};
+use crate::consts::STACK_SIZE;
+use crate::mmu::{self, AccessType, Sv39Mmu};
+use crate::region::{AccessFlags, MemoryMapEntry, MemoryRegion, MprotectError, UNMAPPED};
+use crate::trace::{self, BinaryTraceLogger, ExecutionEvent, ExecutionTrace, RingBuffer, TraceEntry, TraceHook};
+use crate::dtb::{self, DtbError};
+use crate::jit::{FenceICallback, FenceIHook, JitCache, NativeBlock};
+use crate::invariants::InvariantViolation;
+use crate::peripheral::Clint;
+use crate::disasm;
+use crate::watch::RegisterWatches;
+use crate::timing::CycleAccurateModel;
+use crate::bus::Bus;
+use crate::pmp::Pmp;
+use crate::interrupt::{InterruptCause, WfiCallback, WfiHook, MCAUSE_INTERRUPT, WFI_WAKE_MASK};
+use crate::branch::{BranchStats, BranchType};
+use crate::validate::{ValidationKind, ValidationWarning};
+use crate::memory_model::{self, MemoryBarrierCallback, MemoryBarrierHook};
+use crate::sanitizer::Sanitizer;
+use crate::strace::{self, Strace};
+use crate::breakpoint::{BreakpointCondition, BreakpointId, ConditionalBreakpoint, DebuggerCallback, DebuggerHook, EBREAK};
+use crate::page_fault::{self, PageFaultAction, PageFaultHandler, PageFaults};
+use crate::coverage::InstructionHistogram;
+use crate::compressed::inst_len;
+use crate::bitmanip;
+use crate::history::{RegisterSnapshot, StepHistory};
+use crate::ecall::{EcallHandler, EcallResult, SbiCallback, SbiHook, SyscallHandler, SyscallRouter};
+use std::collections::HashMap;
+use std::error::Error;
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use strum::EnumProperty;
+
+pub const INST_LEN: u64 = 4u64;
+pub const COMPRESSED_INST_LEN: u64 = 2u64;
+// `jal x0, 0`, a jump to itself.
+pub const SELF_JUMP: u32 = 0x0000_006f;
+// `addi x0, x0, 0`, the canonical NOP.
+pub const NOP: u32 = 0x0000_0013;
+// The longest basic block the JIT will compile.
+pub const MAX_BLOCK_LEN: usize = 256;
+// The most frames `backtrace` follows, in case the frame chain is corrupt.
+pub const BACKTRACE_DEPTH: usize = 64;
+// Defaults for `max_stack_depth` and `average_frame_size`.
+pub const MAX_STACK_DEPTH: usize = 1024;
+pub const AVERAGE_FRAME_SIZE: u64 = 64;
+// The most instructions `run_until_tohost` executes before giving up.
+pub const TOHOST_MAX_STEPS: u64 = 1 << 24;
+
+/// What the instruction `SoftThread::step` executed did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The hart moved on to the next instruction.
+    Continue,
+    /// A branch or jump was taken to the address given.
+    BranchTaken(u64),
+    /// A `wfi` stalled the hart until an interrupt arrives. The pc stays
+    /// at the `wfi`, which checks again when the hart is next stepped.
+    WaitForInterrupt,
+    /// An `ebreak` or a breakpoint stopped the hart short of the
+    /// instruction at the pc.
+    Breakpoint,
+    /// The hart halted, see `is_halted`. That is only checked after an
+    /// exit ecall, a jump to itself or a store to `tohost`, so a hart that
+    /// reaches a `j 0` halts once it runs it.
+    Halted,
+}
+
+// Forks are numbered from here up so they never share an mhartid with the
+// harts of a `Cpu`.
+static NEXT_FORK_HART_ID: AtomicU64 = AtomicU64::new(1 << 16);
+
+/// The software represeentation of the RISC-V HART aka Hardware Thread
+/// This is separated from the VM itself so that a VM with multiple SOFT's
+/// i.e. a multithread/concurrent/parallel VM can be created and opearted
+///
+/// Only a `SoftThread<u64, f64, M>` can execute; `u32` implements
+/// `RegisterValue` but there is no RV32I `execute` yet.
+/// 
+/// # Example
+/// ```
+/// use trecho::encoding::{EncodingTable, InstructionDecoder};
+/// use trecho::register::{Register, RegisterValue};
+/// use trecho::memory::{Dram, MEM_SIZE, Memory};
+/// use trecho::machine::{Machine, Support};
+/// use trecho::soft::SoftThread;
+///
+/// let mut soft = SoftThread::<u64, f64, Dram>::default();
+/// let program = vec![0b1100_1100 as u8, 0b1100_1010 as u8, 0b1000_0101 as u8, 0b1001_0011 as u8];
+/// soft.load_program(program);
+/// soft.execute();
+/// ```
+
+#[derive(Debug)]
+pub struct SoftThread<R, F, M> {
+    pub registers: [R; 33],
+    pub f_registers: [F; 33],
+    pub pc: R,
+    pub program: Vec<u8>,
+    pub remainder: u32,
+    pub(crate) eq_flag: bool,
+    enc_table: Arc<EncodingTable>,
+    pub bus: M,
+    pub csr: [R; 4096],
+    pub res: Vec<u64>,
+    pub(crate) priv_level: PrivilegeLevel,
+    pub(crate) image: Range<u64>,
+    pub speed: Option<SimSpeed>,
+    pub regions: Vec<MemoryRegion>,
+    pub trace: Option<Box<dyn TraceHook>>,
+    pub jit: JitCache,
+    /// Bytes of stack `load_binary` reserves above the binary.
+    pub stack_size: u64,
+    pub sanitizer: Option<Sanitizer>,
+    pub branch_stats: Option<BranchStats>,
+    pub timing: Option<CycleAccurateModel>,
+    watches: RegisterWatches,
+    halted: bool,
+    /// The `tohost` address `is_halted` watches, if any.
+    pub tohost: Option<u64>,
+    // Set by a store that overlaps `tohost`, so `step` knows to check it.
+    tohost_stored: bool,
+    symbols: HashMap<u64, String>,
+    /// The estimated call depth past which `execute` reports a stack
+    /// overflow. See `call_stack_depth`.
+    pub max_stack_depth: usize,
+    /// Bytes of stack `call_stack_depth` assumes each call uses.
+    pub average_frame_size: u64,
+    initial_sp: Option<u64>,
+    strace: Option<Strace>,
+    breakpoints: Vec<ConditionalBreakpoint<M>>,
+    // The breakpoint that last stopped execution, so resuming from it runs
+    // the saved instruction instead of checking the condition again.
+    resume_breakpoint: Option<u64>,
+    pub unaligned: UnalignedMode,
+    panic_trace: bool,
+    page_faults: PageFaults<M>,
+    pub histogram: Option<InstructionHistogram>,
+    max_nops: Option<u64>,
+    // The address and length of the run of NOPs just executed.
+    nop_run: (u64, u64),
+    pub history: Option<StepHistory>,
+    syscalls: SyscallRouter<M>,
+    sbi_hook: Option<SbiHook<M>>,
+    debugger_hook: Option<DebuggerHook<M>>,
+    barrier_hook: Option<MemoryBarrierHook>,
+    fence_i_hook: Option<FenceIHook>,
+    wfi_hook: Option<WfiHook>,
+    // Whether the instruction being executed took a branch or jump.
+    branch_taken: bool,
+    // Whether the hart is stalled in a `wfi`.
+    waiting: bool,
+    execution_trace: Option<ExecutionTrace>,
+    // Breakpoints `step` stops at without patching the program, which
+    // outlive loading new code.
+    pc_breakpoints: HashMap<u64, BreakpointId>,
+    next_breakpoint_id: u64,
+    /// Devices mapped into the physical address space, which loads and
+    /// stores reach before DRAM. They are ticked once per instruction and
+    /// drive the `mip` bits they raise, such as a CLINT's `MTIP`. A hart
+    /// whose `bus` is itself a `Bus` can map them there instead.
+    pub mmio: Bus,
+    pub mmu: Sv39Mmu,
+    /// The PMP entries, kept in step with their CSRs.
+    pub pmp: Pmp,
+    /// The cycles each retired instruction adds to `mcycle`.
+    pub cpi: u64,
+    // The length of the instruction being executed, which `advance` moves
+    // the pc past.
+    inst_len: u64,
+}
+
+impl<M: Memory<RegValue = u64, Bytes = Vec<u8>>> SoftThread<u64, f64, M> {
+    /// A hart whose loads and stores go to `bus`, in M-mode at pc 0. With
+    /// a `Bus`, attach a `Dram` at 0 for it to run from alongside the
+    /// devices.
+    pub fn with_memory(bus: M, enc_table: EncodingTable) -> SoftThread<u64, f64, M> {
+        let mut soft = SoftThread {
+            registers: [0; 33],
+            f_registers: [0.0; 33],
+            pc: 0,
+            program: vec![],
+            remainder: 0,
+            eq_flag: false,
+            enc_table: Arc::new(enc_table),
+            csr: [0; 4096],
+            bus,
+            res: vec![],
+            priv_level: PrivilegeLevel::Machine,
+            image: 0..0,
+            speed: None,
+            regions: vec![],
+            trace: None,
+            jit: JitCache::new(),
+            stack_size: STACK_SIZE as u64,
+            sanitizer: None,
+            branch_stats: None,
+            timing: None,
+            watches: RegisterWatches::new(),
+            halted: false,
+            tohost: None,
+            tohost_stored: false,
+            symbols: HashMap::new(),
+            max_stack_depth: MAX_STACK_DEPTH,
+            average_frame_size: AVERAGE_FRAME_SIZE,
+            initial_sp: None,
+            strace: None,
+            breakpoints: vec![],
+            resume_breakpoint: None,
+            unaligned: UnalignedMode::default(),
+            panic_trace: false,
+            page_faults: PageFaults::new(),
+            histogram: None,
+            max_nops: None,
+            nop_run: (0, 0),
+            history: None,
+            syscalls: SyscallRouter::new(),
+            sbi_hook: None,
+            debugger_hook: None,
+            barrier_hook: None,
+            fence_i_hook: None,
+            wfi_hook: None,
+            branch_taken: false,
+            waiting: false,
+            execution_trace: None,
+            pc_breakpoints: HashMap::new(),
+            next_breakpoint_id: 0,
+            mmio: Bus::new(),
+            mmu: Sv39Mmu::default(),
+            pmp: Pmp::new(),
+            cpi: 1,
+            inst_len: INST_LEN,
+        };
+
+        soft.registers[2] = MEM_SIZE;
+        soft.registers[0] = 0;
+        soft.csr[CSR_MISA as usize] = csr::misa(soft.enc_table.get_base(), soft.enc_table.get_ext());
+
+        soft
+    }
+
+    pub(crate) fn read_xreg(&self, idx: usize) -> u64 {
+        self.registers[idx]
+    }
+
+    pub(crate) fn read_freg(&self, idx: usize) -> f64 {
+        self.f_registers[idx]
+    } 
+
+    // The single precision value in `reg`, which reads as the canonical
+    // NaN unless it is NaN-boxed.
+    fn read_f32(&self, reg: Register) -> f32 {
+        unbox_f32(self.f_registers[reg as usize])
+    }
+
+    // NaN-box the single precision `val` into `reg`.
+    fn write_f32(&mut self, reg: Register, val: f32) {
+        self.f_registers[reg as usize] = box_f32(val);
+    }
+
+    pub(crate) fn advance(&mut self) {
+        self.pc = self.pc.wrapping_add(self.inst_len);
+    }
+
+    // Move the pc from outside the instruction stream, as a debugger, a
+    // loader or a trap does. This ends a `wfi` stall, whose wake-up would
+    // otherwise step past whatever the pc now points at.
+    pub(crate) fn set_pc(&mut self, pc: u64) {
+        self.waiting = false;
+        self.pc = pc;
+    }
+
+    /// Move the pc past the instruction at it without executing it.
+    pub(crate) fn skip(&mut self) {
+        self.pc = self.pc.wrapping_add(inst_len(self.fetch()));
+    }
+
+    pub(crate) fn fetch(&self) -> Inst {
+        self.fetch_at(self.pc)
+    }
+
+    pub(crate) fn fetch_at(&self, pc: u64) -> Inst {
+        if self.program.is_empty() {
+            return self.fetch_from_bus(pc);
+        }
+
+        let mut bytes: [u8; 4] = [
+            self.program[(pc + 3) as usize],
+            self.program[(pc + 2) as usize],
+            self.program[(pc + 1) as usize],
+            self.program[pc as usize],
+        ];
+        let inst: Inst = u32::from_le_bytes(bytes);
+        return inst;
+    }
+
+    /// Decode the instruction at the pc without executing it.
+    pub(crate) fn peek(&self) -> Instruction {
+        Instruction::decode(self.fetch(), &self.enc_table)
+    }
+
+    // Instructions of an image loaded into DRAM are stored little endian.
+    // A pc outside of DRAM fetches 0, which decodes as an illegal instruction.
+    // Compressed instructions are fetched as their 2 bytes, so that one at
+    // the end of DRAM can be fetched.
+    fn fetch_from_bus(&self, pc: u64) -> Inst {
+        match self.bus.read(&pc, memory::HALFWORD) {
+            Ok(half) if half & 0b11 != 0b11 => half as Inst,
+            _ => self.bus.read(&pc, memory::WORD).unwrap_or(0) as Inst,
+        }
+    }
+
+    /// Whether the pc is still inside the loaded code, either the program
+    /// buffer or, when that is empty, the image loaded into DRAM.
+    pub fn in_program(&self) -> bool {
+        self.in_code(self.pc)
+    }
+
+    fn in_code(&self, pc: u64) -> bool {
+        if self.program.is_empty() {
+            return self.image.contains(&pc);
+        }
+
+        pc < (self.program.len() as u64)
+    }
+
+    pub(crate) fn read_csr_raw(&self, addr: u16) -> u64 {
+        self.csr[addr as usize]
+    }
+
+    pub(crate) fn write_csr_raw(&mut self, addr: u16, val: u64) {
+        self.csr[addr as usize] = val;
+        self.pmp.write_csr(addr, val);
+    }
+
+    // Read a CSR as an instruction would. `fcsr` is `frm` and `fflags`
+    // side by side rather than a register of its own, and `cycleh` and
+    // `instreth` are the upper halves of the 64-bit counters for RV32.
+    fn read_csr(&self, addr: u16) -> u64 {
+        match addr {
+            CSR_FCSR => self.read_csr_raw(CSR_FRM) << 5 | self.read_csr_raw(CSR_FFLAGS),
+            CSR_CYCLEH => self.read_csr_raw(CSR_CYCLE) >> 32,
+            CSR_INSTRETH => self.read_csr_raw(CSR_INSTRET) >> 32,
+            _ => self.read_csr_raw(addr),
+        }
+    }
+
+    // Write a CSR as an instruction would, which leaves `misa` alone: the
+    // extensions are fixed by the encoding table. Writes to `fcsr` go to
+    // `frm` and `fflags`, which keep only their own bits.
+    fn write_csr(&mut self, addr: u16, val: u64) {
+        match addr {
+            CSR_MISA => {},
+            CSR_FCSR => {
+                self.write_csr_raw(CSR_FRM, (val >> 5) & 0b111);
+                self.write_csr_raw(CSR_FFLAGS, val & 0x1f);
+            },
+            CSR_FRM => self.write_csr_raw(CSR_FRM, val & 0b111),
+            CSR_FFLAGS => self.write_csr_raw(CSR_FFLAGS, val & 0x1f),
+            _ => self.write_csr_raw(addr, val),
+        }
+    }
+
+    // Accrue the floating point exception `flags` in `fflags`.
+    fn set_fflags(&mut self, flags: u8) {
+        let fflags = self.read_csr_raw(CSR_FFLAGS) | flags as u64;
+        self.write_csr_raw(CSR_FFLAGS, fflags);
+    }
+
+    // The rounding mode an instruction's `rm` field selects, taking `RM_DYN`
+    // from `frm`. None when that mode is reserved, which every instruction
+    // with an `rm` field raises as illegal. Single precision results and
+    // the conversions honour it; double and quad arithmetic rounds as the
+    // host does, and doing better needs a soft-float backend.
+    fn effective_rm(&self, rm: u32) -> Option<u32> {
+        let rm = if rm == RM_DYN { (self.read_csr(CSR_FCSR) >> 5 & 0b111) as u32 } else { rm };
+        (rm <= RM_RMM).then_some(rm)
+    }
+
+    /// Count `retired` instructions in `mcycle` and `minstret`, taking
+    /// `cpi` cycles each, and mirror the counters into the read-only `cycle`
+    /// and `instret` that `rdcycle` and `rdinstret` read.
+    pub fn emulate_csr_counter_increment(&mut self, retired: u64) {
+        let mcycle = self.read_csr_raw(CSR_MCYCLE).wrapping_add(retired.wrapping_mul(self.cpi));
+        let minstret = self.read_csr_raw(CSR_MINSTRET).wrapping_add(retired);
+        self.write_csr_raw(CSR_MCYCLE, mcycle);
+        self.write_csr_raw(CSR_MINSTRET, minstret);
+        self.write_csr_raw(CSR_CYCLE, mcycle);
+        self.write_csr_raw(CSR_INSTRET, minstret);
+    }
+
+    fn count_stalled_cycle(&mut self) {
+        let mcycle = self.read_csr_raw(CSR_MCYCLE).wrapping_add(self.cpi);
+        self.write_csr_raw(CSR_MCYCLE, mcycle);
+        self.write_csr_raw(CSR_CYCLE, mcycle);
+    }
+
+    /// The index of this hart, as reported by the read-only `mhartid` CSR.
+    pub fn hart_id(&self) -> u64 {
+        self.read_csr_raw(CSR_MHARTID)
+    }
+
+    /// Disassemble the code in `func_start..func_end`. Branch and `jal`
+    /// lines are annotated with their target's offset from `func_start`
+    /// and indirect jumps with `<+??>`. Targets are shown by symbol when
+    /// the symbol table has one at or below them. The targets of backward
+    /// branches inside the function are potential loop headers and are
+    /// prefixed with `<loop_back:>`.
+    pub fn disassemble_function(&self, func_start: u64, func_end: u64) -> Vec<(u64, String)> {
+        let mut lines = vec![];
+        let mut loop_heads = vec![];
+        let mut addr = func_start;
+        while addr < func_end {
+            if !self.program.is_empty() && addr + INST_LEN > self.program.len() as u64 {
+                break;
+            }
+
+            let inst = self.fetch_at(addr);
+            let instruction = Instruction::decode(inst, &self.enc_table);
+            let mut text = disasm::disassemble(&instruction, addr);
+            match instruction.expand() {
+                Instruction::Jal { imm, .. } | Instruction::Beq { imm, .. } | Instruction::Bne { imm, .. } |
+                Instruction::Blt { imm, .. } | Instruction::Bge { imm, .. } | Instruction::Bltu { imm, .. } |
+                Instruction::Bgeu { imm, .. } => {
+                    let target = addr.wrapping_add(imm as i64 as u64);
+                    if let Some(name) = self.symbolize(target) {
+                        let len = text.len() - format!("{:#x}", target).len();
+                        text.truncate(len);
+                        text.push_str(&name);
+                    }
+                    let offset = target.wrapping_sub(func_start) as i64;
+                    let sign = if offset < 0 { '-' } else { '+' };
+                    text.push_str(&format!(" ; <{}{:#x}>", sign, offset.unsigned_abs()));
+                    if target <= addr && target >= func_start {
+                        loop_heads.push(target);
+                    }
+                },
+                Instruction::Jalr { .. } => text.push_str(" ; <+??>"),
+                _ => {},
+            }
+
+            lines.push((addr, text));
+            addr += inst_len(inst);
+        }
+
+        for (addr, text) in lines.iter_mut() {
+            if loop_heads.contains(addr) {
+                text.insert_str(0, "<loop_back:> ");
+            }
+        }
+
+        lines
+    }
+
+    /// Replace the symbol table used to name addresses in disassembly and
+    /// backtraces. `load_elf` loads the table of the executable.
+    pub fn load_symbol_table(&mut self, symbols: HashMap<u64, String>) {
+        self.symbols = symbols;
+    }
+
+    /// The nearest symbol at or below `addr` and the offset of `addr` from it.
+    pub fn symbol_at(&self, addr: u64) -> Option<(&str, u64)> {
+        self.symbols.iter()
+            .filter(|(start, _)| **start <= addr)
+            .max_by_key(|(start, _)| **start)
+            .map(|(start, name)| (name.as_str(), addr - start))
+    }
+
+    // `name` for the start of a symbol and `name + 0x10` inside it.
+    fn symbolize(&self, addr: u64) -> Option<String> {
+        self.symbol_at(addr).map(|(name, offset)| match offset {
+            0 => name.to_string(),
+            _ => format!("{} + {:#x}", name, offset),
+        })
+    }
+
+    /// The return addresses of the active calls, innermost first, starting
+    /// with the `pc`. The frames are found by following the frame pointer
+    /// in `s0`, which the standard prologue leaves pointing above the saved
+    /// `ra` and caller `s0`, so code built without frame pointers only shows
+    /// the `pc`. Each address is named by symbol where possible.
+    pub fn backtrace(&self) -> Vec<(u64, String)> {
+        let mut frames = vec![self.pc];
+        let mut fp = self.registers[Register::X8 as usize];
+        while frames.len() < BACKTRACE_DEPTH {
+            let mut frame = [0u8; 16];
+            if fp < 16 || self.store_raw(fp - 16, &mut frame).is_err() {
+                break;
+            }
+
+            let prev = u64::from_le_bytes(frame[..8].try_into().unwrap());
+            let ra = u64::from_le_bytes(frame[8..].try_into().unwrap());
+            if ra == 0 {
+                break;
+            }
+
+            frames.push(ra);
+            if prev <= fp {
+                break;
+            }
+            fp = prev;
+        }
+
+        frames.into_iter()
+            .map(|addr| (addr, self.symbolize(addr).unwrap_or_else(|| format!("{:#x}", addr))))
+            .collect()
+    }
+
+    /// Scan the loaded code for likely problems without running it: direct
+    /// jumps and branches that leave the code, stores to absolute addresses
+    /// in regions without write permission, accesses to reserved CSRs,
+    /// instructions straight after an unconditional jump that nothing
+    /// branches to, and `addi sp, sp` adjustments that add up to more than
+    /// `stack_size`. Jumping to the end of the code, which finishes the
+    /// run, is allowed.
+    pub fn validate_program(&self) -> Vec<(u64, ValidationWarning)> {
+        let code = if self.program.is_empty() { self.image.clone() } else { 0..self.program.len() as u64 };
+        let instrs: Vec<(u64, Instruction)> = (code.start..code.end).step_by(INST_LEN as usize)
+            .filter(|&pc| pc + INST_LEN <= code.end)
+            .map(|pc| (pc, Instruction::decode(self.fetch_at(pc), &self.enc_table)))
+            .collect();
+
+        let target = |pc: u64, instruction: &Instruction| match *instruction {
+            Instruction::Jal { imm, .. } | Instruction::Beq { imm, .. } | Instruction::Bne { imm, .. } |
+            Instruction::Blt { imm, .. } | Instruction::Bge { imm, .. } | Instruction::Bltu { imm, .. } |
+            Instruction::Bgeu { imm, .. } => Some(pc.wrapping_add(imm as i64 as u64)),
+            _ => None,
+        };
+        let targets: Vec<u64> = instrs.iter().filter_map(|(pc, instruction)| target(*pc, instruction)).collect();
+
+        let mut warnings = vec![];
+        let mut warn = |pc: u64, instruction: Instruction, kind: ValidationKind, description: String| {
+            warnings.push((pc, ValidationWarning::new(pc, instruction, kind, description)));
+        };
+        let mut after_jump = false;
+        let mut stack_used = 0i64;
+        for (pc, instruction) in instrs {
+            if after_jump && !targets.contains(&pc) {
+                warn(pc, instruction, ValidationKind::Unreachable, "unreachable after an unconditional jump".to_string());
+            }
+            after_jump = matches!(instruction,
+                Instruction::Jal { rd: Register::X0, .. } | Instruction::Jalr { rd: Register::X0, .. });
+
+            if let Some(target) = target(pc, &instruction) {
+                if target < code.start || target > code.end {
+                    warn(pc, instruction, ValidationKind::JumpOutOfRange, format!("jump to {:#x} outside of the code", target));
+                }
+            }
+
+            match instruction {
+                Instruction::Sb { rs1: Register::X0, imm, .. } | Instruction::Sh { rs1: Register::X0, imm, .. } |
+                Instruction::Sw { rs1: Register::X0, imm, .. } | Instruction::Sd { rs1: Register::X0, imm, .. } => {
+                    let addr = imm as i64 as u64;
+                    if self.check_access(addr, AccessType::Store).is_err() {
+                        warn(pc, instruction, ValidationKind::StoreToReadOnly, format!("store to read-only address {:#x}", addr));
+                    }
+                },
+                Instruction::Csrrw { csr, .. } | Instruction::Csrrs { csr, .. } | Instruction::Csrrc { csr, .. } |
+                Instruction::Csrrwi { csr, .. } | Instruction::Csrrsi { csr, .. } | Instruction::Csrrci { csr, .. }
+                    if !csr::is_known(csr as u16) => {
+                    warn(pc, instruction, ValidationKind::ReservedCsr, format!("access to reserved CSR {:#x}", csr));
+                },
+                Instruction::Addi { rd: Register::X2, rs1: Register::X2, imm, .. } => {
+                    stack_used -= imm as i64;
+                    if stack_used > self.stack_size as i64 {
+                        warn(pc, instruction, ValidationKind::StackUnderflow, format!("{} bytes of stack used, more than the {} available", stack_used, self.stack_size));
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        warnings
+    }
+
+    /// Check the architectural state for things no valid execution can
+    /// produce: a non-zero x0, a misaligned pc, a reserved MPP encoding,
+    /// non-zero reserved CSRs and a stack pointer outside of DRAM.
+    pub fn verify_risc_v_invariants(&self) -> Vec<InvariantViolation> {
+        let mut violations = vec![];
+        if self.registers[0] != 0 {
+            violations.push(InvariantViolation::new("x0 is not zero", 0, self.registers[0]));
+        }
+
+        if self.pc & 1 != 0 {
+            violations.push(InvariantViolation::new("pc is not 2-byte aligned", self.pc, self.pc));
+        }
+
+        let mstatus = self.read_csr_raw(CSR_MSTATUS);
+        let mpp = (mstatus & MSTATUS_MPP) >> MSTATUS_MPP_SHIFT;
+        if mpp == 2 {
+            violations.push(InvariantViolation::new("mstatus.MPP holds the reserved value 2", CSR_MSTATUS as u64, mstatus));
+        }
+
+        for addr in 0..self.csr.len() as u16 {
+            if !csr::is_known(addr) && self.csr[addr as usize] != 0 {
+                violations.push(InvariantViolation::new("reserved CSR is non-zero", addr as u64, self.csr[addr as usize]));
+            }
+        }
+
+        let sp = self.registers[Register::X2 as usize];
+        if sp != 0 && self.bus.read(&(sp - 1), memory::BYTE).is_err() {
+            violations.push(InvariantViolation::new("sp is outside of DRAM", Register::X2 as u64, sp));
+        }
+
+        violations
+    }
+
+    /// Check that a CSR may be accessed from privilege level `mode`. The
+    /// access is rejected with an illegal instruction exception if the
+    /// address is reserved, if it is a write to a read-only CSR, or if the
+    /// CSR belongs to a more privileged level. `execute()` replaces the
+    /// trap value with the offending instruction.
+    pub fn validate_csr_access(&self, addr: u16, write: bool, mode: PrivilegeLevel) -> Result<(), Exception> {
+        if !csr::is_known(addr) {
+            return Err(Exception::Invalid(0));
+        }
+
+        if write && csr::is_read_only(addr) {
+            return Err(Exception::Invalid(0));
+        }
+
+        if csr::min_privilege(addr) > u64::from(mode) {
+            return Err(Exception::Invalid(0));
+        }
+
+        Ok(())
+    }
+
+    /// The value of the CSR at `addr` with its name and, for `mstatus`,
+    /// `sstatus`, `misa`, `mip` and `mie`, its fields decoded.
+    pub fn dump_csr(&self, addr: u16) -> String {
+        csr::dump(addr, self.csr.get(addr as usize).copied().unwrap_or(0))
+    }
+
+    /// `dump_csr` of every CSR that is not zero, one per line.
+    pub fn dump_all_csrs(&self) -> String {
+        self.dump_csrs(false)
+    }
+
+    /// `dump_csr` of every CSR, one per line, leaving out those that are
+    /// zero unless `verbose` is set.
+    pub fn dump_csrs(&self, verbose: bool) -> String {
+        (0..self.csr.len() as u16)
+            .filter(|addr| verbose || self.csr[*addr as usize] != 0)
+            .map(|addr| self.dump_csr(addr))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Read a CSR with the checks of `csrrs x0, csr, x0` at the current
+    /// privilege level.
+    pub fn get_csr(&self, addr: u16) -> Result<u64, Exception> {
+        self.validate_csr_access(addr, false, self.priv_level)?;
+        Ok(self.read_csr(addr))
+    }
+
+    /// Write a CSR with the checks of `csrrw x0, csr, rs1` at the current
+    /// privilege level.
+    pub fn set_csr(&mut self, addr: u16, val: u64) -> Result<(), Exception> {
+        self.validate_csr_access(addr, true, self.priv_level)?;
+        self.write_csr(addr, val);
+        Ok(())
+    }
+
+    /// Deliver an exception to the machine mode trap handler at `mtvec`.
+    /// The faulting pc is saved to `mepc`, the previous privilege level and
+    /// interrupt enable are stacked in `mstatus`, and the hart continues in
+    /// machine mode. Exceptions without an architectural cause are ignored.
+    pub fn take_trap(&mut self, exception: Exception) {
+        let cause = match exception.cause() {
+            Some(cause) => cause,
+            None => return,
+        };
+
+        self.enter_trap(cause, exception.tval());
+        self.set_pc(self.read_csr_raw(CSR_MTVEC) & !0b11);
+    }
+
+    /// Mark `cause` as pending in `mip`. It is taken before the next
+    /// instruction once it is enabled in `mie` and interrupts are enabled
+    /// for machine mode.
+    pub fn inject_interrupt(&mut self, cause: InterruptCause) {
+        let mip = self.read_csr_raw(CSR_MIP);
+        self.write_csr_raw(CSR_MIP, mip | cause.mip_bit());
+    }
+
+    pub fn clear_interrupt(&mut self, cause: InterruptCause) {
+        let mip = self.read_csr_raw(CSR_MIP);
+        self.write_csr_raw(CSR_MIP, mip & !cause.mip_bit());
+    }
+
+    /// The highest priority interrupt the hart would take now. Interrupts
+    /// are not delegated, so all of them are taken in machine mode, which
+    /// masks them with `mstatus.MIE`.
+    pub fn pending_interrupt(&self) -> Option<InterruptCause> {
+        let pending = self.read_csr_raw(CSR_MIP) & self.read_csr_raw(CSR_MIE);
+        if pending == 0 {
+            return None;
+        }
+
+        if self.priv_level == PrivilegeLevel::Machine && self.read_csr_raw(CSR_MSTATUS) & MSTATUS_MIE == 0 {
+            return None;
+        }
+
+        InterruptCause::PRIORITY.into_iter().find(|cause| pending & cause.mip_bit() != 0)
+    }
+
+    // Trap to the machine mode handler for `cause`, honouring vectored
+    // `mtvec`. The interrupt stays pending until software clears it.
+    fn take_interrupt(&mut self, cause: InterruptCause) {
+        self.enter_trap(MCAUSE_INTERRUPT | cause.code(), 0);
+        let mtvec = self.read_csr_raw(CSR_MTVEC);
+        let vector = if mtvec & 0b11 == 1 { 4 * cause.code() } else { 0 };
+        self.pc = (mtvec & !0b11).wrapping_add(vector);
+    }
+
+    pub fn mstatus(&self) -> Mstatus {
+        Mstatus(self.read_csr_raw(CSR_MSTATUS))
+    }
+
+    pub fn set_mstatus(&mut self, mstatus: Mstatus) {
+        self.write_csr_raw(CSR_MSTATUS, mstatus.0);
+    }
+
+    // Save the trap state and switch to machine mode. The caller sets
+    // the pc.
+    fn enter_trap(&mut self, cause: u64, tval: u64) {
+        let mut mstatus = self.mstatus();
+        mstatus.set_mpie(mstatus.mie());
+        mstatus.set_mie(false);
+        mstatus.set_mpp(self.priv_level);
+
+        self.set_mstatus(mstatus);
+        self.write_csr_raw(CSR_MEPC, self.pc);
+        self.write_csr_raw(CSR_MCAUSE, cause);
+        self.write_csr_raw(CSR_MTVAL, tval);
+        self.priv_level = PrivilegeLevel::Machine;
+    }
+
+    /// Roughly how many calls deep the program is: how far the stack
+    /// pointer has moved below where it was when execution started, which
+    /// is `MEM_SIZE` for a fresh thread, in units of `average_frame_size`.
+    pub fn call_stack_depth(&self) -> usize {
+        self.stack_depth_at(self.registers[Register::X2 as usize])
+    }
+
+    // `call_stack_depth` with the stack pointer at `sp`.
+    fn stack_depth_at(&self, sp: u64) -> usize {
+        let initial_sp = self.initial_sp.unwrap_or(MEM_SIZE);
+        (initial_sp.saturating_sub(sp) / self.average_frame_size.max(1)) as usize
+    }
+
+    /// Make `execute` fail with `Exception::StackOverflow` when an
+    /// `addi sp, sp` that allocates a frame takes `call_stack_depth` past
+    /// `depth`.
+    pub fn set_max_stack_depth(&mut self, depth: usize) {
+        self.max_stack_depth = depth;
+    }
+
+    /// Patch an `ebreak` over the instruction at `addr` that makes `execute`
+    /// return `Exception::Breakpoint` when it is reached with `condition`
+    /// holding, without halting the hart. The next `execute` resumes with
+    /// the replaced instruction. When `condition` does not hold, the
+    /// replaced instruction runs as if there were no breakpoint. Setting a
+    /// breakpoint where there already is one replaces its condition.
+    /// Loading new code removes all breakpoints.
+    pub fn set_conditional_breakpoint(&mut self, addr: u64, condition: BreakpointCondition<M>) {
+        if let Some(breakpoint) = self.breakpoints.iter_mut().find(|bp| bp.addr == addr) {
+            breakpoint.condition = condition;
+            return;
+        }
+
+        let saved_inst = self.fetch_at(addr);
+        self.store_inst(addr, EBREAK);
+        self.breakpoints.push(ConditionalBreakpoint { addr, condition, saved_inst });
+    }
+
+    /// Remove the breakpoint at `addr`, restoring the instruction it
+    /// replaced. Returns whether there was one.
+    pub fn remove_conditional_breakpoint(&mut self, addr: u64) -> bool {
+        let Some(idx) = self.breakpoints.iter().position(|bp| bp.addr == addr) else {
+            return false;
+        };
+
+        let breakpoint = self.breakpoints.remove(idx);
+        self.store_inst(addr, breakpoint.saved_inst);
+        true
+    }
+
+    /// Make `step` stop with `StepOutcome::Breakpoint` before executing the
+    /// instruction at `pc`. Stepping again runs it. Unlike conditional
+    /// breakpoints these leave the program alone and are kept when new code
+    /// is loaded. Setting a breakpoint where there already is one returns
+    /// its id.
+    pub fn set_breakpoint(&mut self, pc: u64) -> BreakpointId {
+        if let Some(id) = self.pc_breakpoints.get(&pc) {
+            return *id;
+        }
+
+        let id = BreakpointId(self.next_breakpoint_id);
+        self.next_breakpoint_id += 1;
+        self.pc_breakpoints.insert(pc, id);
+        id
+    }
+
+    /// Remove the breakpoint `set_breakpoint` returned `id` for. Returns
+    /// whether there was one.
+    pub fn clear_breakpoint(&mut self, id: BreakpointId) -> bool {
+        let len = self.pc_breakpoints.len();
+        self.pc_breakpoints.retain(|_, bp| *bp != id);
+        self.pc_breakpoints.len() != len
+    }
+
+    pub fn clear_all_breakpoints(&mut self) {
+        self.pc_breakpoints.clear();
+    }
+
+    // Overwrite the instruction at `addr`, in the byte order `fetch_at`
+    // reads it in, and drop any compiled blocks that may contain it.
+    fn store_inst(&mut self, addr: u64, inst: u32) {
+        if self.program.is_empty() {
+            let _ = self.load_raw(addr, &inst.to_le_bytes());
+        } else if let Some(bytes) = self.program.get_mut(addr as usize..addr as usize + 4) {
+            bytes.copy_from_slice(&inst.to_be_bytes());
+        }
+        self.jit.clear();
+    }
+
+    /// Stop the hart: `is_halted` reports true from now on, until new code
+    /// is loaded.
+    pub fn halt(&mut self) {
+        self.halted = true;
+    }
+
+    /// Whether the hart has stopped for good. That is when any of these
+    /// holds:
+    /// - the pc is on a `j 0` that would spin forever,
+    /// - the last `execute` hit an `ebreak`,
+    /// - the last `execute` was an `exit` or `exit_group` ecall from user
+    ///   mode, with the exit code in `a0`,
+    /// - `halt` was called,
+    /// - a `tohost` address is set and the doubleword there is nonzero, as
+    ///   riscv-tests do to report their result.
+    pub fn is_halted(&self) -> bool {
+        let spinning = (self.program.is_empty() || self.in_program()) && self.fetch() == SELF_JUMP;
+        if self.halted || spinning {
+            return true;
+        }
+
+        match self.tohost {
+            Some(addr) => matches!(self.bus.read(&addr, 64), Ok(val) if val != 0),
+            None => false,
+        }
+    }
+
+    /// Run the loaded program until the pc leaves the loaded code or the
+    /// hart halts, see `is_halted`. Syscalls with a registered handler are
+    /// serviced by `execute`, and environment calls from supervisor mode by
+    /// the handler `set_sbi_handler` installs, if any. The run resumes
+    /// after either. Other environment calls that do not halt are trapped
+    /// into the handler installed at `mtvec`, which is responsible for
+    /// servicing the call and resuming execution. Any other exception
+    /// stops the run and is returned to the caller.
+    pub fn run_until_halt(&mut self) -> Result<(), Exception> {
+        while self.in_program() && !self.is_halted() {
+            let result = self.execute();
+            if let Some(speed) = self.speed.as_mut() {
+                speed.tick(self.csr[CSR_MHARTID as usize]);
+            }
+
+            match result {
+                Ok(()) => {},
+                Err(_) if self.halted => {},
+                Err(Exception::EnvironmentCallFromSMode) if self.sbi_hook.is_some() => self.sbi_call(),
+                Err(e @ Exception::EnvironmentCallFromUMode) |
+                Err(e @ Exception::EnvironmentCallFromSMode) |
+                Err(e @ Exception::EnvironmentCallFromMMode) => self.take_trap(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a riscv-tests style program until it writes a nonzero value to
+    /// the doubleword at `tohost_addr`, which also becomes the `tohost` that
+    /// `is_halted` watches. A write of 1 means every test passed and is
+    /// returned as is, as are even values, which are host requests rather
+    /// than results. Any other odd value means test `val >> 1` failed and
+    /// is returned as `Exception::TestFailed`. Environment calls are
+    /// trapped as in `run_until_halt`, and the run stops with
+    /// `Exception::StepLimitExceeded` after `TOHOST_MAX_STEPS`
+    /// instructions.
+    pub fn run_until_tohost(&mut self, tohost_addr: u64) -> Result<u64, Exception> {
+        self.tohost = Some(tohost_addr);
+        for _ in 0..TOHOST_MAX_STEPS {
+            match self.bus.read(&tohost_addr, 64) {
+                Ok(val) if val != 0 && val != 1 && val & 1 == 1 => return Err(Exception::TestFailed(val >> 1)),
+                Ok(val) if val != 0 => return Ok(val),
+                _ => {},
+            }
+
+            match self.execute() {
+                Ok(()) => {},
+                Err(e @ Exception::EnvironmentCallFromUMode) |
+                Err(e @ Exception::EnvironmentCallFromSMode) |
+                Err(e @ Exception::EnvironmentCallFromMMode) => self.take_trap(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(Exception::StepLimitExceeded)
+    }
+
+    /// Execute exactly `n` instructions. Unlike `run_until_halt` nothing is
+    /// treated as a halt, so leaving the loaded code does not stop it. On
+    /// an exception, returns it with the number of instructions that had
+    /// completed before it.
+    pub fn step_n(&mut self, n: u64) -> Result<u64, (u64, Exception)> {
+        for count in 0..n {
+            self.execute().map_err(|e| (count, e))?;
+        }
+        Ok(n)
+    }
+
+    /// Execute until the pc reaches `target`, as GDB's `advance` does, and
+    /// return the number of instructions that took. Gives up after
+    /// `max_steps` instructions, and fails at once if `target` is not
+    /// 2-byte aligned, as compressed instructions may be.
+    pub fn execute_until_pc(&mut self, target: u64, max_steps: u64) -> Result<u64, StepError> {
+        if !target.is_multiple_of(COMPRESSED_INST_LEN) {
+            return Err(StepError::MisalignedTarget);
+        }
+
+        for steps in 0..max_steps {
+            if self.pc == target {
+                return Ok(steps);
+            }
+            self.execute()?;
+        }
+
+        if self.pc == target {
+            return Ok(max_steps);
+        }
+        Err(StepError::StepLimitReached)
+    }
+
+    /// Report the simulation speed of `run_until_halt` every `interval`.
+    pub fn enable_speed_monitor(&mut self, interval: Duration) {
+        self.speed = Some(SimSpeed::new(interval));
+    }
+
+    /// Route speed reports to `callback` instead of stderr. Has no effect
+    /// until the speed monitor is enabled.
+    pub fn set_speed_callback(&mut self, callback: Box<dyn Fn(f64)>) {
+        if let Some(speed) = self.speed.as_mut() {
+            speed.set_callback(callback);
+        }
+    }
+
+    /// Record the last `N` executed instructions in a ring buffer,
+    /// replacing any trace hook already installed.
+    pub fn enable_ring_trace<const N: usize>(&mut self) {
+        self.trace = Some(Box::new(RingBuffer::<TraceEntry, N>::new()));
+    }
+
+    /// Stream a binary trace of every executed instruction to `path`,
+    /// replacing any trace hook already installed. The file is flushed and
+    /// closed when the hook is dropped, e.g. by setting `trace` to `None`.
+    pub fn enable_binary_trace(&mut self, path: &Path) -> io::Result<()> {
+        self.trace = Some(Box::new(BinaryTraceLogger::open(path)?));
+        Ok(())
+    }
+
+    /// The traced instructions, oldest first.
+    pub fn ring_trace(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.trace.iter().flat_map(|trace| trace.entries())
+    }
+
+    /// Record the pc, raw instruction and registers before each of the
+    /// next instructions executed, keeping the last `capacity` of them.
+    /// `trace::EXECUTION_TRACE_LEN` is a reasonable capacity. Replaces any
+    /// events already recorded.
+    pub fn enable_trace(&mut self, capacity: usize) {
+        self.execution_trace = Some(ExecutionTrace::new(capacity));
+    }
+
+    /// Stop recording execution events and drop those recorded.
+    pub fn disable_trace(&mut self) {
+        self.execution_trace = None;
+    }
+
+    /// The events recorded since tracing was enabled or last taken, oldest
+    /// first. Tracing carries on into an empty trace of the same capacity.
+    pub fn take_trace(&mut self) -> ExecutionTrace {
+        match self.execution_trace.as_mut() {
+            Some(trace) => std::mem::replace(trace, ExecutionTrace::new(trace.capacity())),
+            None => ExecutionTrace::new(0),
+        }
+    }
+
+    /// Keep the last `PANIC_TRACE_LEN` executed instructions and print them
+    /// to stderr when the thread panics, or with the trapped pc and the
+    /// exception when `execute` fails. Environment calls and breakpoints
+    /// are not reported, as the guest raises those on purpose.
+    pub fn print_trace_on_panic(&mut self, enabled: bool) {
+        self.panic_trace = enabled;
+        trace::clear_panic_trace();
+        if enabled {
+            trace::set_panic_hook();
+        }
+    }
+
+    /// What `print_trace_on_panic` prints when `execute` fails with
+    /// `exception`.
+    pub fn crash_report(&self, exception: &Exception) -> String {
+        format!("trap at {:#x}: {}\n{}", self.pc, exception, trace::panic_trace())
+    }
+
+    /// Add a region with its own access permissions. Regions must not
+    /// overlap.
+    pub fn add_region(&mut self, region: MemoryRegion) {
+        self.regions.push(region);
+    }
+
+    /// The regions by start address, with the gaps between them as
+    /// `UNMAPPED` entries without permissions.
+    pub fn memory_map(&self) -> Vec<MemoryMapEntry<'_>> {
+        let mut regions: Vec<&MemoryRegion> = self.regions.iter().collect();
+        regions.sort_by_key(|region| region.base);
+
+        let mut map: Vec<MemoryMapEntry> = vec![];
+        for region in regions {
+            if let Some(prev) = map.last() {
+                if prev.end < region.base {
+                    map.push(MemoryMapEntry { start: prev.end, end: region.base, name: UNMAPPED, flags: AccessFlags::NONE });
+                }
+            }
+            map.push(MemoryMapEntry { start: region.base, end: region.end(), name: &region.name, flags: region.flags });
+        }
+        map
+    }
+
+    /// Print `memory_map` to stdout, one entry per line, e.g.
+    /// `0x80000000 - 0x88000000 DRAM           [RWX]`.
+    pub fn print_memory_map(&self) {
+        for entry in self.memory_map() {
+            println!("{}", entry);
+        }
+    }
+
+    /// Change the permissions of `len` bytes at `addr`. The range must lie
+    /// within a single existing region, which is split if needed.
+    pub fn mprotect(&mut self, addr: u64, len: u64, flags: AccessFlags) -> Result<(), MprotectError> {
+        let end = match addr.checked_add(len) {
+            Some(end) if len > 0 => end,
+            _ => return Err(MprotectError::InvalidRange),
+        };
+
+        let idx = self.regions.iter()
+            .position(|region| region.base <= addr && end <= region.end())
+            .ok_or(MprotectError::NotMapped)?;
+        let region = self.regions.remove(idx);
+
+        let mut parts = vec![];
+        if region.base < addr {
+            parts.push(MemoryRegion::new(region.base, addr - region.base, region.flags).with_name(&region.name));
+        }
+        parts.push(MemoryRegion::new(addr, len, flags).with_name(&region.name));
+        if end < region.end() {
+            parts.push(MemoryRegion::new(end, region.end() - end, region.flags).with_name(&region.name));
+        }
+        self.regions.splice(idx..idx, parts);
+
+        Ok(())
+    }
+
+    /// Check `addr` against the permissions of the region containing it.
+    pub fn check_access(&self, addr: u64, access: AccessType) -> Result<(), Exception> {
+        let required = match access {
+            AccessType::Instruction => AccessFlags::EXECUTE,
+            AccessType::Load => AccessFlags::READ,
+            AccessType::Store => AccessFlags::WRITE,
+        };
+
+        match self.regions.iter().find(|region| region.contains(addr)) {
+            Some(region) if !region.flags.contains(required) => Err(access.access_fault()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Give `handler` the first look at every page fault `execute` raises,
+    /// with the faulting address and access. The handler can resolve the
+    /// fault and have the instruction retried, or let `execute` return it.
+    pub fn page_fault_handler(&mut self, handler: PageFaultHandler<M>) {
+        self.page_faults.handler = Some(handler);
+    }
+
+    /// Mark the page holding `addr` not present, so the next access to it
+    /// raises a page fault. Its contents are left as they are.
+    pub fn unmap_page(&mut self, addr: u64) {
+        self.page_faults.unmap(addr);
+    }
+
+    pub fn map_page(&mut self, addr: u64) {
+        self.page_faults.map(addr);
+    }
+
+    // Pass a page fault to the handler. Returns whether the faulting
+    // instruction should be run again.
+    fn resolve_page_fault(&mut self, exception: &Exception) -> Result<bool, Exception> {
+        let Some((addr, access)) = page_fault::fault_access(exception) else {
+            return Ok(false);
+        };
+        let Some(handler) = self.page_faults.handler.take() else {
+            return Ok(false);
+        };
+
+        let action = handler(addr, access, self);
+        self.page_faults.handler.get_or_insert(handler);
+        match action {
+            PageFaultAction::Retry => Ok(true),
+            PageFaultAction::Trap => Ok(false),
+            PageFaultAction::Fill(mut data) => {
+                data.truncate(mmu::PAGE_SIZE as usize);
+                let page = addr & !(mmu::PAGE_SIZE - 1);
+                self.load_raw(page, &data)?;
+                self.map_page(page);
+                Ok(true)
+            },
+        }
+    }
+
+    /// Check memory accesses against a shadow map of the heap and the stack
+    /// pointer against the initial one, from the current stack pointer on.
+    /// Heap blocks are handed out by ecalls with `a7` set to
+    /// `sanitizer::SYS_MALLOC`, or released with `sanitizer::SYS_FREE`.
+    pub fn enable_sanitizer(&mut self) {
+        self.sanitizer = Some(Sanitizer::new(self.registers[Register::X2 as usize]));
+    }
+
+    fn sanitize(&mut self, instruction: &Instruction) -> Result<(), Exception> {
+        let store = memory_model::store_footprint(instruction, &self.registers);
+        let access = self.data_access(instruction);
+        let Some(sanitizer) = self.sanitizer.as_mut() else {
+            return Ok(());
+        };
+
+        match (store, access) {
+            (Some((addr, len)), _) => sanitizer.check_store(addr, len),
+            (None, Some((addr, AccessType::Load))) => {
+                sanitizer.check_load(addr);
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    // The address and kind of the memory access `instruction` makes, if any.
+    fn data_access(&self, instruction: &Instruction) -> Option<(u64, AccessType)> {
+        let offset = |rs1: &Register, imm: &i32| self.registers[*rs1 as usize].wrapping_add(*imm as i64 as u64);
+        match instruction {
+            Instruction::Lb { rs1, imm, .. } | Instruction::Lh { rs1, imm, .. } |
+            Instruction::Lw { rs1, imm, .. } | Instruction::Ld { rs1, imm, .. } |
+            Instruction::Lbu { rs1, imm, .. } | Instruction::Lhu { rs1, imm, .. } |
+            Instruction::Lwu { rs1, imm, .. } | Instruction::Flw { rs1, imm, .. } |
+            Instruction::Fld { rs1, imm, .. } | Instruction::Flq { rs1, imm, .. } => {
+                Some((offset(rs1, imm), AccessType::Load))
+            },
+            Instruction::Sb { rs1, imm, .. } | Instruction::Sh { rs1, imm, .. } |
+            Instruction::Sw { rs1, imm, .. } | Instruction::Sd { rs1, imm, .. } |
+            Instruction::Fsw { rs1, imm, .. } | Instruction::Fsd { rs1, imm, .. } |
+            Instruction::Fsq { rs1, imm, .. } => {
+                Some((offset(rs1, imm), AccessType::Store))
+            },
+            Instruction::LrW { rs1, .. } | Instruction::LrD { rs1, .. } => {
+                Some((self.registers[*rs1 as usize], AccessType::Load))
+            },
+            Instruction::ScW { rs1, .. } | Instruction::ScD { rs1, .. } |
+            Instruction::AmoswapW { rs1, .. } | Instruction::AmoaddW { rs1, .. } |
+            Instruction::AmoxorW { rs1, .. } | Instruction::AmoandW { rs1, .. } |
+            Instruction::AmoorW { rs1, .. } | Instruction::AmominW { rs1, .. } |
+            Instruction::AmomaxW { rs1, .. } | Instruction::AmominuW { rs1, .. } |
+            Instruction::AmomaxuW { rs1, .. } | Instruction::AmoswapD { rs1, .. } |
+            Instruction::AmoaddD { rs1, .. } | Instruction::AmoxorD { rs1, .. } |
+            Instruction::AmoandD { rs1, .. } | Instruction::AmoorD { rs1, .. } |
+            Instruction::AmominD { rs1, .. } | Instruction::AmomaxD { rs1, .. } |
+            Instruction::AmominuD { rs1, .. } | Instruction::AmomaxuD { rs1, .. } => {
+                Some((self.registers[*rs1 as usize], AccessType::Store))
+            },
+            _ => None,
+        }
+    }
+
+    /// Execute the next instruction, or the whole basic block starting at
+    /// the pc if it has been compiled to native code. Blocks are compiled
+    /// once they are hot. Guest stores to compiled code are not detected,
+    /// and the interpreter is used whenever anything watches individual
+    /// instructions, see `jit_allowed`.
+    pub fn execute_jit(&mut self) -> Result<(), Exception> {
+        if self.jit_allowed() {
+            if let Some(block) = self.jit.get(self.pc) {
+                // Safety: the block only touches the 33 registers it is given.
+                unsafe { block.call(self.registers.as_mut_ptr()) };
+                self.pc += block.len * INST_LEN;
+                let len = block.len;
+                self.tick_devices(len);
+                self.emulate_csr_counter_increment(len);
+                return Ok(());
+            }
+
+            if self.jit.visit(self.pc) {
+                if let Some(block) = self.compile_block_at(self.pc) {
+                    self.jit.insert(self.pc, block);
+                    return self.execute_jit();
+                }
+            }
+        }
+
+        self.execute()
+    }
+
+    // Whether native blocks can run in place of the interpreter. They skip
+    // everything `execute` does around an instruction, so tracing,
+    // memory regions, breakpoints, register watches, the timing model,
+    // PMP checks and address translation all need the interpreter, and so
+    // do harts below M-mode, whose fetches may fault.
+    fn jit_allowed(&self) -> bool {
+        self.trace.is_none() && !self.panic_trace && self.histogram.is_none() && self.max_nops.is_none() &&
+            self.history.is_none() && self.execution_trace.is_none() && self.regions.is_empty() && self.page_faults.is_empty() &&
+            self.pc_breakpoints.is_empty() && self.watches.is_empty() && self.timing.is_none() &&
+            !self.pmp.is_enabled() && self.priv_level == PrivilegeLevel::Machine
+    }
+
+    fn compile_block_at(&self, pc: u64) -> Option<NativeBlock> {
+        let mut instrs = vec![];
+        let mut at = pc;
+        while self.in_code(at) && instrs.len() < MAX_BLOCK_LEN {
+            let instruction = Instruction::decode(self.fetch_at(at), &self.enc_table);
+            if !JitCache::is_supported(&instruction) {
+                break;
+            }
+            instrs.push(instruction);
+            at += INST_LEN;
+        }
+
+        if instrs.is_empty() {
+            return None;
+        }
+
+        Some(JitCache::compile_block(&instrs, pc))
+    }
+
+    /// Decode and execute `instrs` in order as if they were fetched from
+    /// the pc onwards, returning how many were executed. Execution stops
+    /// after the first instruction that sends the pc anywhere but the next
+    /// instruction, such as a taken branch or jump, so that the caller can
+    /// fetch from the new pc.
+    pub fn execute_block(&mut self, instrs: &[u32]) -> Result<u64, Exception> {
+        let mut count = 0;
+        for inst in instrs {
+            let next = self.pc.wrapping_add(inst_len(*inst));
+            self.execute_inst(*inst)?;
+            count += 1;
+            if self.pc != next {
+                break;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Call `callback` with the old and new value each time an instruction
+    /// changes `reg`, replacing any callback already watching it.
+    pub fn watch_register(&mut self, reg: Register, callback: Box<dyn FnMut(u64, u64)>) {
+        self.watches.watch(reg, callback);
+    }
+
+    pub fn unwatch_register(&mut self, reg: Register) {
+        self.watches.unwatch(reg);
+    }
+
+    /// Like `watch_register`, for the float register `reg`.
+    pub fn watch_freg(&mut self, reg: Register, callback: Box<dyn FnMut(f64, f64)>) {
+        self.watches.watch_freg(reg, callback);
+    }
+
+    pub fn unwatch_freg(&mut self, reg: Register) {
+        self.watches.unwatch_freg(reg);
+    }
+
+    /// Charge every instruction executed from now on to `model`, replacing
+    /// any model already installed.
+    pub fn enable_cycle_model(&mut self, model: CycleAccurateModel) {
+        self.timing = Some(model);
+    }
+
+    /// Count the outcome of every branch and jump from now on, replacing
+    /// any counts already collected.
+    pub fn enable_branch_profiling(&mut self) {
+        self.branch_stats = Some(BranchStats::default());
+    }
+
+    /// The branch outcomes counted since profiling was enabled, which are
+    /// empty if it never was.
+    pub fn measure_branch_accuracy(&self) -> BranchStats {
+        self.branch_stats.clone().unwrap_or_default()
+    }
+
+    /// Count every instruction executed by type from now on, replacing any
+    /// counts already collected.
+    pub fn enable_instruction_histogram(&mut self) {
+        self.histogram = Some(InstructionHistogram::default());
+    }
+
+    /// Write the table `InstructionHistogram::write_report` makes of the
+    /// instructions executed since the histogram was enabled.
+    pub fn coverage_report(&self, output: &mut dyn Write) -> io::Result<()> {
+        match self.histogram.as_ref() {
+            Some(histogram) => histogram.write_report(output),
+            None => Err(io::Error::other("the instruction histogram is not enabled")),
+        }
+    }
+
+    fn record_branch(&mut self, kind: BranchType, taken: bool) {
+        self.branch_taken |= taken;
+        if let Some(stats) = self.branch_stats.as_mut() {
+            stats.record(kind, taken);
+        }
+    }
+
+    /// Have `execute` service syscall `number` with `handler` instead of
+    /// raising an environment call exception for it, replacing any handler
+    /// registered for it before.
+    pub fn register_ecall_handler(&mut self, number: u64, handler: EcallHandler<M>) {
+        self.syscalls.register(number, handler);
+    }
+
+    /// Have `execute` pass every syscall without a handler of its own to
+    /// `handler`, with its number, instead of raising an environment call
+    /// exception. The pc moves past the `ecall` if the handler succeeds.
+    pub fn set_syscall_handler(&mut self, handler: SyscallHandler<M>) {
+        self.syscalls.fallback = Some(handler);
+    }
+
+    /// Have `run_until_halt` service the environment calls supervisor code
+    /// makes with `handler` instead of trapping them, as an SBI
+    /// implementation would.
+    pub fn set_sbi_handler(&mut self, handler: SbiCallback<M>) {
+        self.sbi_hook = Some(SbiHook(handler));
+    }
+
+    // Perform the SBI call in `a7` and `a6`, write its error and value to
+    // `a0` and `a1` and move past the `ecall`.
+    fn sbi_call(&mut self) {
+        let Some(mut hook) = self.sbi_hook.take() else {
+            return;
+        };
+        let (eid, fid) = (self.registers[Register::X17 as usize], self.registers[Register::X16 as usize]);
+        let result = (hook.0)(self, eid, fid);
+        self.sbi_hook.get_or_insert(hook);
+
+        self.registers[Register::X10 as usize] = result.error as u64;
+        self.registers[Register::X11 as usize] = result.value as u64;
+        self.advance();
+    }
+
+    /// Run `hook` with the `fm`, `pred` and `succ` fields of every `fence`
+    /// the hart executes, after the host barrier the fence needs.
+    pub fn set_memory_barrier_hook(&mut self, hook: MemoryBarrierCallback) {
+        self.barrier_hook = Some(MemoryBarrierHook(hook));
+    }
+
+    /// Run `hook` with the pc of every `fence.i` the hart executes, before
+    /// moving past it. The hart drops its own compiled blocks either way.
+    pub fn set_fence_i_hook(&mut self, hook: FenceICallback) {
+        self.fence_i_hook = Some(FenceIHook(hook));
+    }
+
+    /// Run `hook` each time a `wfi` stalls the hart because no enabled
+    /// machine interrupt is pending, before `step` reports
+    /// `StepOutcome::WaitForInterrupt`.
+    pub fn set_wfi_hook(&mut self, hook: WfiCallback) {
+        self.wfi_hook = Some(WfiHook(hook));
+    }
+
+    /// Run `hook` for every `ebreak` the program executes instead of
+    /// raising `Exception::Breakpoint`, e.g. to service semihosting calls.
+    /// Execution continues after the `ebreak` unless the hook moves the pc.
+    /// Breakpoints set with `set_conditional_breakpoint` still stop.
+    pub fn attach_debugger(&mut self, hook: DebuggerCallback<M>) {
+        self.debugger_hook = Some(DebuggerHook(hook));
+    }
+
+    /// Perform the syscall in `a7` with the registered handlers and write
+    /// its result to `a0`. Syscalls without a handler fail with `ENOSYS`.
+    /// The pc is not advanced.
+    pub fn ecall(&mut self) -> EcallResult {
+        let mut router = std::mem::take(&mut self.syscalls);
+        let result = router.dispatch(self.registers[Register::X17 as usize], self);
+        // Keep any handlers registered by the handler itself.
+        router.extend(std::mem::take(&mut self.syscalls));
+        self.syscalls = router;
+
+        self.registers[Register::X10 as usize] = result.a0() as u64;
+        result
+    }
+
+    /// Remember how to undo each of the next `depth` instructions `execute`
+    /// runs, so `step_back` can walk back over them.
+    pub fn enable_step_history(&mut self, depth: usize) {
+        self.history = Some(StepHistory::new(depth));
+    }
+
+    /// Undo the last instruction executed, restoring the pc, registers and
+    /// CSRs it changed but not memory. Returns false once the history is
+    /// empty or if it was never enabled.
+    pub fn step_back(&mut self) -> bool {
+        let Some(mut history) = self.history.take() else {
+            return false;
+        };
+
+        let snapshot = history.pop(self);
+        if let Some(snapshot) = snapshot.as_ref() {
+            snapshot.restore(self);
+        }
+        self.history = Some(history);
+        snapshot.is_some()
+    }
+
+    /// Copy the pc and the integer and float registers.
+    pub fn snapshot(&self) -> RegisterSnapshot {
+        RegisterSnapshot { xregs: self.registers, fregs: self.f_registers, pc: self.pc }
+    }
+
+    /// Put back the registers and pc `snapshot` took. CSRs and memory are
+    /// left as they are.
+    pub fn restore_snapshot(&mut self, snap: &RegisterSnapshot) {
+        self.registers = snap.xregs;
+        self.f_registers = snap.fregs;
+        self.set_pc(snap.pc);
+    }
+
+    /// Stop with `Exception::NopSledDetected` instead of executing a NOP
+    /// that follows `max_nops` others in a row, which usually means the
+    /// program jumped into memory it never wrote.
+    pub fn emulate_nop_sled_detection(&mut self, max_nops: u64) {
+        self.max_nops = Some(max_nops);
+        self.nop_run = (0, 0);
+    }
+
+    fn count_nop(&mut self, inst: Inst, max_nops: u64) -> Result<(), Exception> {
+        if inst != NOP {
+            self.nop_run = (0, 0);
+            return Ok(());
+        }
+
+        let (start_pc, len) = self.nop_run;
+        let start_pc = if len == 0 { self.pc } else { start_pc };
+        if len >= max_nops {
+            return Err(Exception::NopSledDetected { start_pc });
+        }
+        self.nop_run = (start_pc, len + 1);
+        Ok(())
+    }
+
+    /// Choose whether misaligned loads and stores trap or are split into
+    /// byte accesses. They are emulated by default.
+    pub fn emulate_unaligned_access(&mut self, mode: UnalignedMode) {
+        self.unaligned = mode;
+    }
+
+    /// Map a `Clint` at `CLINT_BASE` on `mmio` whose `mtime` advances by
+    /// `ticks_per_instruction` per instruction, returning its index there.
+    /// Loads and stores there reach its registers instead of DRAM, and
+    /// `MTIP` and `MSIP` in `mip` follow whether `mtime` has reached this
+    /// hart's `mtimecmp` and whether its `msip` is set.
+    pub fn attach_clint(&mut self, ticks_per_instruction: u64) -> usize {
+        self.mmio.attach(Clint::new(ticks_per_instruction))
+    }
+
+    // Advance the devices on `mmio`, and those of `bus` when it is a `Bus`,
+    // by `instructions` and update the `mip` bits they drive.
+    fn tick_devices(&mut self, instructions: u64) {
+        let hart_id = self.read_csr_raw(CSR_MHARTID);
+        self.bus.tick(instructions);
+        let (mut driven, mut pending) = self.bus.interrupts(hart_id);
+        if !self.mmio.is_empty() {
+            self.mmio.tick(instructions);
+            let (mmio_driven, mmio_pending) = self.mmio.interrupts(hart_id);
+            driven |= mmio_driven;
+            pending |= mmio_pending;
+        }
+
+        if driven != 0 {
+            let mip = self.read_csr_raw(CSR_MIP);
+            self.write_csr_raw(CSR_MIP, mip & !driven | pending);
+        }
+    }
+
+    // Whether loads, stores and fetches go through `mmu`: outside M-mode,
+    // once satp selects Sv39.
+    fn translating(&self) -> bool {
+        self.priv_level != PrivilegeLevel::Machine && self.read_csr_raw(CSR_SATP) >> SATP_MODE_SHIFT == mmu::SATP_MODE_SV39
+    }
+
+    // The physical address of `vaddr`, which is `vaddr` itself unless
+    // `translating`.
+    fn translate(&mut self, vaddr: u64, access: AccessType) -> Result<u64, Exception> {
+        if !self.translating() {
+            return Ok(vaddr);
+        }
+
+        self.mmu.satp = self.read_csr_raw(CSR_SATP);
+        self.mmu.user = self.priv_level == PrivilegeLevel::User;
+        self.mmu.translate(vaddr, access, &self.bus)
+    }
+
+    // Fetch the instruction at the pc, through the MMU and PMP when DRAM
+    // holds the program.
+    fn fetch_translated(&mut self) -> Result<Inst, Exception> {
+        if !self.program.is_empty() {
+            return Ok(self.fetch());
+        }
+
+        let paddr = self.translate(self.pc, AccessType::Instruction)?;
+        let inst = self.fetch_from_bus(paddr);
+        self.pmp.check(paddr, inst_len(inst), AccessType::Instruction, self.priv_level)?;
+        Ok(inst)
+    }
+
+    // Read the `size` bit value at `addr`, zero extended.
+    fn load_unsigned(&mut self, addr: u64, size: u8) -> Result<u64, Exception> {
+        let addr = self.translate(addr, AccessType::Load)?;
+        self.pmp.check(addr, size as u64 / 8, AccessType::Load, self.priv_level)?;
+        self.load_physical(addr, size)
+    }
+
+    // Read the `size` bit value at the physical address `addr`, from a
+    // device or DRAM, zero extended.
+    fn load_physical(&mut self, addr: u64, size: u8) -> Result<u64, Exception> {
+        if self.mmio.contains(addr) {
+            return self.mmio.load(&addr, size);
+        }
+
+        let val = if addr.is_multiple_of(size as u64 / 8) {
+            self.bus.load(&addr, size).map_err(|_| Exception::LoadAccessFault)?
+        } else {
+            match self.unaligned {
+                UnalignedMode::Trap => return Err(Exception::LoadAddressMisaligned),
+                UnalignedMode::Emulate => {
+                    let mut val = 0;
+                    for idx in 0..size as u64 / 8 {
+                        let byte = self.bus.load(&addr.wrapping_add(idx), memory::BYTE).map_err(|_| Exception::LoadAccessFault)?;
+                        val |= byte << (8 * idx);
+                    }
+                    val
+                },
+            }
+        };
+
+        Ok(memory::zero_extend(val, size))
+    }
+
+    // Read the `size` bit value at `addr`, sign extended.
+    fn load_signed(&mut self, addr: u64, size: u8) -> Result<u64, Exception> {
+        Ok(memory::sign_extend(self.load_unsigned(addr, size)?, size))
+    }
+
+    // Write the low `size` bits of `val` to `addr`.
+    fn store(&mut self, addr: u64, val: u64, size: u8) -> Result<(), Exception> {
+        let addr = self.translate(addr, AccessType::Store)?;
+        self.pmp.check(addr, size as u64 / 8, AccessType::Store, self.priv_level)?;
+        self.store_physical(addr, val, size)
+    }
+
+    // Write the low `size` bits of `val` to the physical address `addr`.
+    fn store_physical(&mut self, addr: u64, val: u64, size: u8) -> Result<(), Exception> {
+        if let Some(tohost) = self.tohost {
+            self.tohost_stored |= addr < tohost.wrapping_add(8) && tohost < addr.wrapping_add(size as u64 / 8);
+        }
+        if self.mmio.contains(addr) {
+            return self.mmio.write(addr, val, size);
+        }
+
+        if addr.is_multiple_of(size as u64 / 8) {
+            return self.bus.write(addr, val, size).map_err(|_| Exception::StoreAMOAccessFault);
+        }
+
+        match self.unaligned {
+            UnalignedMode::Trap => Err(Exception::StoreAMOAddressMisaligned),
+            UnalignedMode::Emulate => {
+                // Check the last byte first so a store off the end of DRAM
+                // writes nothing.
+                let bytes = size as u64 / 8;
+                self.bus.read(&addr.wrapping_add(bytes - 1), memory::BYTE).map_err(|_| Exception::StoreAMOAccessFault)?;
+                for idx in 0..bytes {
+                    self.bus.write(addr.wrapping_add(idx), val >> (8 * idx), memory::BYTE).map_err(|_| Exception::StoreAMOAccessFault)?;
+                }
+                Ok(())
+            },
+        }
+    }
+
+    // The physical address of the `size` bit word at `vaddr` an LR, SC or
+    // AMO accesses. These are never split, so a misaligned one traps
+    // whatever `unaligned` says. An SC or AMO is checked as a store, and
+    // an AMO also needs read permission.
+    fn atomic_address(&mut self, vaddr: u64, size: u8, access: AccessType) -> Result<u64, Exception> {
+        let bytes = size as u64 / 8;
+        if !vaddr.is_multiple_of(bytes) {
+            return Err(match access {
+                AccessType::Load => Exception::LoadAddressMisaligned,
+                _ => Exception::StoreAMOAddressMisaligned,
+            });
+        }
+
+        let addr = self.translate(vaddr, access)?;
+        self.pmp.check(addr, bytes, access, self.priv_level)?;
+        Ok(addr)
+    }
+
+    // Replace the `size` bit word at `vaddr` with `op` of it, as an AMO
+    // does, and return the old word sign extended.
+    fn amo(&mut self, vaddr: u64, size: u8, op: impl FnOnce(u64) -> u64) -> Result<u64, Exception> {
+        let addr = self.atomic_address(vaddr, size, AccessType::Store)?;
+        self.pmp.check(addr, size as u64 / 8, AccessType::Load, self.priv_level)
+            .map_err(|_| Exception::StoreAMOAccessFault)?;
+        let old = match self.load_physical(addr, size) {
+            Err(Exception::LoadAccessFault) => return Err(Exception::StoreAMOAccessFault),
+            result => memory::sign_extend(result?, size),
+        };
+        self.store_physical(addr, op(old), size)?;
+        Ok(old)
+    }
+
+    // Take a conditional branch to pc + `imm`, or fall through.
+    fn branch(&mut self, kind: BranchType, taken: bool, imm: i32) {
+        self.record_branch(kind, taken);
+        if taken {
+            self.pc = self.pc.wrapping_add((imm as i64) as u64);
+        } else {
+            self.advance();
+        }
+    }
+
+    pub fn execute(&mut self) -> Result<(), Exception> {
+        match self.step()? {
+            StepOutcome::Breakpoint => Err(Exception::Breakpoint),
+            _ => Ok(()),
+        }
+    }
+
+    /// Execute the next instruction, like `execute`, and report what it
+    /// did. An `ebreak` is reported as `StepOutcome::Breakpoint` rather than
+    /// as an exception.
+    pub fn step(&mut self) -> Result<StepOutcome, Exception> {
+        self.branch_taken = false;
+        if self.pc_breakpoints.contains_key(&self.pc) && self.resume_breakpoint != Some(self.pc) {
+            self.resume_breakpoint = Some(self.pc);
+            return Ok(StepOutcome::Breakpoint);
+        }
+        let pc = self.pc;
+        match self.execute_reported() {
+            Err(Exception::Breakpoint) => Ok(StepOutcome::Breakpoint),
+            Err(e) => Err(e),
+            Ok(()) if self.halt_pending(pc) => Ok(StepOutcome::Halted),
+            Ok(()) if self.waiting => Ok(StepOutcome::WaitForInterrupt),
+            Ok(()) if self.branch_taken => Ok(StepOutcome::BranchTaken(self.pc)),
+            Ok(()) => Ok(StepOutcome::Continue),
+        }
+    }
+
+    // Whether the instruction that ran from `pc` halted the hart. Only an
+    // exit ecall, a jump to itself or a store to `tohost` can, so
+    // `is_halted` and the fetch and load it does are skipped otherwise.
+    fn halt_pending(&mut self, pc: u64) -> bool {
+        let stored = std::mem::take(&mut self.tohost_stored);
+        (self.halted || stored || self.pc == pc && !self.waiting) && self.is_halted()
+    }
+
+    // Execute the next instruction, reporting a crash if `print_trace_on_panic`
+    // is on.
+    fn execute_reported(&mut self) -> Result<(), Exception> {
+        if let Some(mut history) = self.history.take() {
+            history.save(self);
+            self.history = Some(history);
+        }
+
+        // A fault that comes back after the handler asked for a retry is
+        // returned, so a handler that does not map the page cannot hang
+        // the hart.
+        let mut result = self.execute_unreported();
+        let mut retried = None;
+        while let Err(exception) = &result {
+            let fault = page_fault::fault_access(exception);
+            if fault.is_some() && fault == retried {
+                break;
+            }
+            match self.resolve_page_fault(exception) {
+                Ok(true) => {
+                    retried = fault;
+                    result = self.execute_unreported();
+                },
+                Ok(false) => break,
+                Err(e) => result = Err(e),
+            }
+        }
+
+        if let Err(exception) = &result {
+            let deliberate = matches!(exception, Exception::Breakpoint | Exception::EnvironmentCallFromUMode |
+                Exception::EnvironmentCallFromSMode | Exception::EnvironmentCallFromMMode);
+            if self.panic_trace && !deliberate {
+                eprint!("{}", self.crash_report(exception));
+            }
+        }
+        result
+    }
+
+    fn execute_unreported(&mut self) -> Result<(), Exception> {
+        self.initial_sp.get_or_insert(self.registers[Register::X2 as usize]);
+        if let Some(cause) = self.pending_interrupt() {
+            // An interrupt that ends a stall returns past the `wfi`.
+            if std::mem::take(&mut self.waiting) {
+                self.pc = self.pc.wrapping_add(INST_LEN);
+            }
+            self.take_interrupt(cause);
+            return Ok(());
+        }
+
+        if !self.regions.is_empty() && self.program.is_empty() {
+            self.check_access(self.pc, AccessType::Instruction)?;
+        }
+        self.page_faults.check(self.pc, AccessType::Instruction)?;
+
+        let mut inst = self.fetch_translated()?;
+        if let Some(breakpoint) = self.breakpoints.iter().find(|bp| bp.addr == self.pc && inst == EBREAK) {
+            if self.resume_breakpoint != Some(self.pc) && (breakpoint.condition)(self) {
+                self.resume_breakpoint = Some(self.pc);
+                return Err(Exception::Breakpoint);
+            }
+            inst = breakpoint.saved_inst;
+        }
+        self.resume_breakpoint = None;
+        if let Some(trace) = self.execution_trace.as_mut() {
+            trace.push(ExecutionEvent { pc: self.pc, raw_inst: inst, registers_before: self.registers });
+        }
+
+        if let Some(max_nops) = self.max_nops {
+            self.count_nop(inst, max_nops)?;
+        }
+
+        // A stalled `wfi` has not retired, so only the cycle counters move.
+        let result = self.execute_inst(inst);
+        if result.is_ok() && self.waiting {
+            self.count_stalled_cycle();
+        } else if result.is_ok() {
+            self.emulate_csr_counter_increment(1);
+        }
+        let syscall = self.registers[Register::X17 as usize];
+        match result {
+            Err(Exception::Breakpoint) => self.halted = true,
+            Err(Exception::EnvironmentCallFromUMode) if syscall == linux::SYS_EXIT || syscall == linux::SYS_EXIT_GROUP => {
+                self.halted = true;
+            },
+            _ => {},
+        }
+        result
+    }
+
+    fn execute_inst(&mut self, inst: Inst) -> Result<(), Exception> {
+        let illegal = |_| Exception::Invalid(inst as u64);
+        let decoded: Instruction = Instruction::decode(inst, &self.enc_table);
+        // Compressed instructions run as the instruction they expand to,
+        // but are traced and counted as themselves.
+        let instruction = decoded.expand();
+        self.inst_len = inst_len(inst);
+        self.waiting = false;
+        if !self.regions.is_empty() || !self.page_faults.is_empty() {
+            if let Some((addr, access)) = self.data_access(&instruction) {
+                self.check_access(addr, access)?;
+                self.page_faults.check(addr, access)?;
+            }
+        }
+
+        if self.sanitizer.is_some() {
+            self.sanitize(&instruction)?;
+        }
+
+        // Frame allocations are checked against the stack limits before sp
+        // is written, so a stack overflow leaves the hart as it was. Only
+        // they are checked for the depth limit, so code that uses sp as a
+        // scratch register does not trip it.
+        if let Instruction::Addi { rd: Register::X2, rs1: Register::X2, imm, .. } = instruction {
+            let sp = self.registers[Register::X2 as usize].wrapping_add(imm as i64 as u64);
+            if let Some(sanitizer) = self.sanitizer.as_ref() {
+                sanitizer.check_stack(sp)?;
+            }
+            if imm < 0 && self.stack_depth_at(sp) > self.max_stack_depth {
+                return Err(Exception::StackOverflow);
+            }
+        }
+        let (pc, sp) = (self.pc, self.registers[Register::X2 as usize]);
+
+        if let Some(trace) = self.trace.as_mut() {
+            trace.record(TraceEntry { pc: self.pc, raw: inst, decoded });
+        }
+        if self.panic_trace {
+            trace::record_panic_trace(TraceEntry { pc: self.pc, raw: inst, decoded });
+        }
+        if let Some(histogram) = self.histogram.as_mut() {
+            histogram.record(&decoded);
+        }
+        let before = self.trace.is_some().then_some(self.registers);
+        let watched = (!self.watches.is_empty()).then_some((self.registers, self.f_registers));
+        let timed = self.timing.is_some().then(|| (self.pc, self.data_access(&instruction)));
+
+        self.tick_devices(1);
+
+        match instruction {
+            Instruction::Lui { rd, imm } => {
+                //load upper immediate
+                self.registers[rd as usize] = (imm as i64) as u64;
+                self.advance();
+            },
+            Instruction::Auipc { rd, imm } => {
+                //add upper immediate to program counter
+                self.registers[rd as usize] = self.pc.wrapping_add((imm as i64) as u64);
+                self.advance();
+            },
+            Instruction::Jal { rd, imm } => {
+                // Jump and link
+                self.record_branch(BranchType::Jal, true);
+                self.registers[rd as usize] = self.pc.wrapping_add(self.inst_len);
+                self.pc = self.pc.wrapping_add((imm as i64) as u64);
+            },
+            Instruction::Jalr { rd, rs1, imm } => {
+                // Jump and link register
+                self.record_branch(BranchType::Jalr, true);
+                let t = self.pc.wrapping_add(self.inst_len);
+                self.pc = (self.registers[rs1 as usize].wrapping_add((imm as i64) as u64) & !1);
+                self.registers[rd as usize] = t;
+            },
+            Instruction::Beq { rs1, rs2, imm, .. } => {
+                // Branch if equal
+                let taken = self.registers[rs1 as usize] == self.registers[rs2 as usize];
+                self.branch(BranchType::Beq, taken, imm);
+            },
+            Instruction::Bne { rs1, rs2, imm, .. } => {
+                // Branch if not equal
+                let taken = self.registers[rs1 as usize] != self.registers[rs2 as usize];
+                self.branch(BranchType::Bne, taken, imm);
+            },
+            Instruction::Blt { rs1, rs2, imm, .. } => {
+                // Branch if less than
+                let taken = (self.registers[rs1 as usize] as i64) < (self.registers[rs2 as usize] as i64);
+                self.branch(BranchType::Blt, taken, imm);
+            },
+            Instruction::Bge { rs1, rs2, imm, .. } => {
+                // Branch if greater or equal
+                let taken = (self.registers[rs1 as usize] as i64) >= (self.registers[rs2 as usize] as i64);
+                self.branch(BranchType::Bge, taken, imm);
+            },
+            Instruction::Bltu { rs1, rs2, imm, .. } => {
+                // Branch if less than unsigned
+                let taken = self.registers[rs1 as usize] < self.registers[rs2 as usize];
+                self.branch(BranchType::Bltu, taken, imm);
+            },
+            Instruction::Bgeu { rs1, rs2, imm, .. } => {
+                // Branch if greater than unsigned
+                let taken = self.registers[rs1 as usize] >= self.registers[rs2 as usize];
+                self.branch(BranchType::Bgeu, taken, imm);
+            },
+            Instruction::Lb { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.registers[rd as usize] = self.load_signed(addr, 8)?;
+                self.advance();
+            },
+            Instruction::Lh { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.registers[rd as usize] = self.load_signed(addr, 16)?;
+                self.advance();
+            },
+            Instruction::Lw { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.registers[rd as usize] = self.load_signed(addr, 32)?;
+                self.advance();
+            },
+            Instruction::Lbu { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.registers[rd as usize] = self.load_unsigned(addr, 8)?;
+                self.advance();
+            },
+            Instruction::Lhu { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.registers[rd as usize] = self.load_unsigned(addr, 16)?;
+                self.advance();
+            },
+            Instruction::Sb { rs1, rs2, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.store(addr, self.registers[rs2 as usize], 8)?;
+                self.advance();
+            },
+            Instruction::Sh { rs1, rs2, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.store(addr, self.registers[rs2 as usize], 16)?;
+                self.advance();
+            },
+            Instruction::Sw { rs1, rs2, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.store(addr, self.registers[rs2 as usize], 32)?;
+                self.advance();
+            },
+            Instruction::Addi { rd, rs1, imm, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.advance();
+            },
+            Instruction::Slti { rd, rs1, imm, .. } => {
+                self.registers[rd as usize] = if (self.registers[rs1 as usize] as i64) < (imm as i64) {
+                    1
+                } else {
+                    0
+                };
+                self.advance();
+            },
+            Instruction::Sltiu { rd, rs1, imm, .. } => {
+                self.registers[rd as usize] = if self.registers[rs1 as usize] < ((imm as i64) as u64) { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::Xori { rd, rs1, imm, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] ^ ((imm as i64) as u64);
+                self.advance();
+            },
+            Instruction::Ori { rd, rs1, imm, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] | ((imm as i64) as u64);
+                self.advance();
+            },
+            Instruction::Andi { rd, rs1, imm, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] & ((imm as i64) as u64);
+                self.advance();
+            },
+            Instruction::Slli { rd, rs1, shamt, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_shl(shamt);
+                self.advance();
+            },
+            Instruction::Srli { rd, rs1, shamt, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_shr(shamt);
+                self.advance();
+            },
+            Instruction::Srai { rd, rs1, shamt, .. } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as i64).wrapping_shr(shamt) as u64;
+                self.advance();
+            },
+            Instruction::Add { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_add(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Sub { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_sub(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Sll { rd, rs1, rs2, .. } => {
+                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
+                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_shl(shamt);
+                self.advance();
+            },
+            Instruction::Slt { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = if ((self.registers[rs1 as usize] as i64) < (self.registers[rs2 as usize] as i64)) {
+                    1 
+                } else {
+                    0
+                };
+                self.advance();
+            },
+            Instruction::Sltu { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = if self.registers[rs1 as usize] < self.registers[rs2 as usize] {
+                    1
+                } else {
+                    0
+                };
+                self.advance();
+            },
+            Instruction::Xor { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] ^ self.registers[rs2 as usize];
+                self.advance();
+            },
+            Instruction::Srl { rd, rs1, rs2, .. } => {
+                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
+                self.registers[rd as usize] = self.registers[rs1 as usize].wrapping_shr(shamt);
+                self.advance();
+            },
+            Instruction::Sra { rd, rs1, rs2, .. } => {
+                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as i64).wrapping_shr(shamt) as u64;
+                self.advance();
+            },
+            Instruction::Or { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] | self.registers[rs2 as usize];
+                self.advance();
+            },
+            Instruction::And { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] & self.registers[rs2 as usize];
+                self.advance();
+            },
+            // Harts of a `Cpu` order their stores in `Cpu::run`. The host
+            // barrier orders the emulator's own accesses, and the decoded
+            // fields are already in the trace entry recorded above.
+            Instruction::Fence { fm, pred, succ, .. } => {
+                if let Some(ordering) = memory_model::fence_ordering(fm, pred, succ) {
+                    std::sync::atomic::fence(ordering);
+                }
+                if let Some(hook) = self.barrier_hook.as_mut() {
+                    (hook.0)(fm, pred, succ);
+                }
+                self.advance()
+            },
+            Instruction::ECall => {
+                if let Some(sanitizer) = self.sanitizer.as_mut() {
+                    if sanitizer.handle_call(&mut self.registers)? {
+                        self.advance();
+                        return Ok(());
+                    }
+                }
+
+                let number = self.registers[Register::X17 as usize];
+                if self.syscalls.handles(number) {
+                    self.ecall();
+                    self.advance();
+                    return Ok(());
+                }
+
+                if let Some(mut handler) = self.syscalls.fallback.take() {
+                    let result = handler(self, number);
+                    self.syscalls.fallback.get_or_insert(handler);
+                    result?;
+                    self.advance();
+                    return Ok(());
+                }
+
+                // The pc is left on the ecall so that it is saved to
+                // mepc when the call is delivered as a trap.
+                return Err(match self.priv_level {
+                    PrivilegeLevel::User => Exception::EnvironmentCallFromUMode,
+                    PrivilegeLevel::Supervisor => Exception::EnvironmentCallFromSMode,
+                    PrivilegeLevel::Machine => Exception::EnvironmentCallFromMMode,
+                });
+            },
+            Instruction::EBreak => {
+                if let Some(mut hook) = self.debugger_hook.take() {
+                    let pc = self.pc;
+                    (hook.0)(self);
+                    self.debugger_hook.get_or_insert(hook);
+                    if self.pc == pc {
+                        self.advance();
+                    }
+                    return Ok(());
+                }
+
+                // The pc is left on the ebreak, as for ecall.
+                return Err(Exception::Breakpoint);
+            },
+            Instruction::Lwu { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.registers[rd as usize] = self.load_unsigned(addr, 32)?;
+                self.advance();
+            },
+            Instruction::Ld { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.registers[rd as usize] = self.load_unsigned(addr, 64)?;
+                self.advance();
+            },
+            Instruction::Sd { rs1, rs2, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as i64) as u64);
+                self.store(addr, self.registers[rs2 as usize], 64)?;
+                self.advance();
+            },
+            Instruction::Addiw { rd, rs1, imm, .. } => {
+                self.registers[rd as usize] = ((self.registers[rs1 as usize].wrapping_add(((imm as i64) as u64)) as i32) as i64) as u64;
+                self.advance();
+            },
+            Instruction::Slliw { rd, rs1, shamt, .. } => {
+                self.registers[rd as usize] = ((self.registers[rs1 as usize].wrapping_shl(shamt) as i32) as i64) as u64;
+                self.advance();
+            },
+            Instruction::Srliw { rd, rs1, shamt, .. } => {
+                let word = (self.registers[rs1 as usize] & 0xffff_ffff) as u32;
+                self.registers[rd as usize] = (word.wrapping_shr(shamt) as i32) as u64;
+                self.advance();
+            },
+            Instruction::Sraiw { rd, rs1, shamt, .. } => {
+                self.registers[rd as usize] = ((self.registers[rs1 as usize] as i32).wrapping_shr(shamt) as i64) as u64;
+                self.advance();
+            },
+            Instruction::Addw { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = ((self.registers[rs1 as usize].wrapping_add(self.registers[rs2 as usize]) as i32) as i64) as u64;
+                self.advance();
+            },
+            Instruction::Subw { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = ((self.registers[rs1 as usize].wrapping_sub(self.registers[rs2 as usize]) as i32) as i64) as u64;
+                self.advance()
+            },
+            Instruction::Sllw { rd, rs1, rs2, .. } => {
+                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
+                self.registers[rd as usize] = ((self.registers[rs1 as usize] as u32).wrapping_shl(shamt) as i32) as u64;
+                self.advance();
+            },
+            Instruction::Srlw { rd, rs1, rs2, .. } => {
+                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
+                self.registers[rd as usize] = ((self.registers[rs1 as usize] as u32).wrapping_shr(shamt) as i32) as u64;
+                self.advance();
+            },
+            Instruction::Sraw { rd, rs1, rs2, .. } => {
+                let shamt = ((self.registers[rs2 as usize] & 0x3f) as u64) as u32;
+                self.registers[rd as usize] = ((self.registers[rs1 as usize] as i32) >> (shamt as i32)) as u64;
+                self.advance();
+            },
+            Instruction::FenceI { .. } => {
+                // The program may have rewritten code the JIT compiled.
+                self.jit.clear();
+                if let Some(hook) = self.fence_i_hook.as_ref() {
+                    (hook.0)(self.pc);
+                }
+                self.advance();
+            },
+            // Return from a trap to the privilege level it was taken from,
+            // saved in MPP or SPP, which is then reset to U-mode.
+            Instruction::Mret => {
+                if self.priv_level != PrivilegeLevel::Machine {
+                    return Err(Exception::Invalid(inst as u64));
+                }
+                let mut mstatus = self.mstatus();
+                self.priv_level = mstatus.mpp();
+                mstatus.set_mie(mstatus.mpie());
+                mstatus.set_mpie(true);
+                mstatus.set_mpp(PrivilegeLevel::User);
+                // Returning below M-mode ends any MPRV accesses.
+                if self.priv_level != PrivilegeLevel::Machine {
+                    mstatus.set_mprv(false);
+                }
+                self.set_mstatus(mstatus);
+                self.pc = self.read_csr_raw(CSR_MEPC);
+            },
+            Instruction::Sret => {
+                let mut mstatus = self.mstatus();
+                if self.priv_level < PrivilegeLevel::Supervisor ||
+                    (self.priv_level == PrivilegeLevel::Supervisor && mstatus.tsr()) {
+                    return Err(Exception::Invalid(inst as u64));
+                }
+                self.priv_level = mstatus.spp();
+                mstatus.set_sie(mstatus.spie());
+                mstatus.set_spie(true);
+                mstatus.set_spp(PrivilegeLevel::User);
+                mstatus.set_mprv(false);
+                self.set_mstatus(mstatus);
+                self.pc = self.read_csr_raw(CSR_SEPC);
+            },
+            Instruction::Wfi => {
+                if self.read_csr_raw(CSR_MIP) & self.read_csr_raw(CSR_MIE) & WFI_WAKE_MASK == 0 {
+                    self.waiting = true;
+                    if let Some(hook) = self.wfi_hook.as_mut() {
+                        (hook.0)();
+                    }
+                } else {
+                    self.advance();
+                }
+            },
+            Instruction::SfenceVma { rs1, .. } => {
+                self.mmu.flush((rs1 != Register::X0).then(|| self.registers[rs1 as usize]));
+                self.advance();
+            },
+            // rs1 is read before rd is written, so rd may be rs1. csrrw
+            // always writes and only skips the read for rd == x0, as in
+            // csrw. The set and clear forms always read and only write
+            // when rs1 or uimm is non-zero, as in csrr.
+            Instruction::Csrrw { csr, rs1, rd, .. } => {
+                self.validate_csr_access(csr as u16, true, self.priv_level).map_err(illegal)?;
+                let rs1_val = self.registers[rs1 as usize];
+                if rd != Register::X0 {
+                    self.registers[rd as usize] = self.read_csr(csr as u16);
+                }
+                self.write_csr(csr as u16, rs1_val);
+                self.advance();
+            },
+            Instruction::Csrrs { csr, rs1, rd, .. } => {
+                self.validate_csr_access(csr as u16, rs1 != Register::X0, self.priv_level).map_err(illegal)?;
+                let csr_val = self.read_csr(csr as u16);
+                let rs1_val = self.registers[rs1 as usize];
+                self.registers[rd as usize] = csr_val;
+                if rs1 != Register::X0 {
+                    self.write_csr(csr as u16, csr_val | rs1_val);
+                }
+                self.advance();
+            },
+            Instruction::Csrrc { csr, rs1, rd, .. } => {
+                self.validate_csr_access(csr as u16, rs1 != Register::X0, self.priv_level).map_err(illegal)?;
+                let csr_val = self.read_csr(csr as u16);
+                let rs1_val = self.registers[rs1 as usize];
+                self.registers[rd as usize] = csr_val;
+                if rs1 != Register::X0 {
+                    self.write_csr(csr as u16, csr_val & !rs1_val);
+                }
+                self.advance();
+            },
+            Instruction::Csrrwi { rd, csr, uimm, .. } => {
+                self.validate_csr_access(csr as u16, true, self.priv_level).map_err(illegal)?;
+                if rd != Register::X0 {
+                    self.registers[rd as usize] = self.read_csr(csr as u16);
+                }
+                self.write_csr(csr as u16, uimm as u64);
+                self.advance();
+            },
+            Instruction::Csrrsi { rd, csr, uimm, .. } => {
+                self.validate_csr_access(csr as u16, uimm != 0, self.priv_level).map_err(illegal)?;
+                let csr_val = self.read_csr(csr as u16);
+                self.registers[rd as usize] = csr_val;
+                if uimm != 0 {
+                    self.write_csr(csr as u16, csr_val | uimm as u64);
+                }
+                self.advance();
+            },
+            Instruction::Csrrci { rd, csr, uimm, .. } => {
+                self.validate_csr_access(csr as u16, uimm != 0, self.priv_level).map_err(illegal)?;
+                let csr_val = self.read_csr(csr as u16);
+                self.registers[rd as usize] = csr_val;
+                if uimm != 0 {
+                    self.write_csr(csr as u16, csr_val & !(uimm as u64));
+                }
+                self.advance();
+            },
+            Instruction::Mul { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Mulh { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul_high_signed(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Mulhsu { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul_high_signed_unsigned(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Mulhu { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_mul_high_unsigned(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Div { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_div_signed(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Divu { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_div(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Rem { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_rem_signed(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Remu { rd, rs1, rs2, .. } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].oflow_rem(&self.registers[rs2 as usize]);
+                self.advance();
+            },
+            // The W forms operate on the low 32 bits of each operand and
+            // sign extend the 32 bit result.
+            Instruction::Mulw { rd, rs1, rs2, .. } => {
+                let (lhs, rhs) = (self.registers[rs1 as usize] as u32, self.registers[rs2 as usize] as u32);
+                self.registers[rd as usize] = (lhs.wrapping_mul(rhs) as i32) as u64;
+                self.advance();
+            },
+            Instruction::Divw { rd, rs1, rs2, .. } => {
+                let (lhs, rhs) = (self.registers[rs1 as usize] as u32, self.registers[rs2 as usize] as u32);
+                self.registers[rd as usize] = (lhs.oflow_div_signed(&rhs) as i32) as u64;
+                self.advance();
+            },
+            Instruction::Divuw { rd, rs1, rs2, .. } => {
+                let (lhs, rhs) = (self.registers[rs1 as usize] as u32, self.registers[rs2 as usize] as u32);
+                self.registers[rd as usize] = (lhs.oflow_div(&rhs) as i32) as u64;
+                self.advance();
+            },
+            Instruction::Remw { rd, rs1, rs2, .. } => {
+                let (lhs, rhs) = (self.registers[rs1 as usize] as u32, self.registers[rs2 as usize] as u32);
+                self.registers[rd as usize] = (lhs.oflow_rem_signed(&rhs) as i32) as u64;
+                self.advance();
+            },
+            Instruction::RemuW { rd, rs1, rs2, .. } => {
+                let (lhs, rhs) = (self.registers[rs1 as usize] as u32, self.registers[rs2 as usize] as u32);
+                self.registers[rd as usize] = (lhs.oflow_rem(&rhs) as i32) as u64;
+                self.advance();
+            },
+            // For W instructions below ALL words being read from
+            // memory must be naturally aligned to 32 bits
+            // i.e. mod 4 == 0;
+            // For D instructions belwo ALL doublewords being
+            // read from memory most be naturally aligned to
+            // 64 bit words, i.e. mod 8 == 0;
+            Instruction::LrW { rd, rs1, .. } => {
+                let addr = self.atomic_address(self.registers[rs1 as usize], 32, AccessType::Load)?;
+                let val = self.load_physical(addr, 32)?;
+                self.registers[rd as usize] = memory::sign_extend(val, 32);
+                self.res.push(addr);
+                self.advance();
+            },
+            Instruction::ScW { rd, rs1, rs2, .. } => {
+                // if an address reservation is still value
+                // and contains the bytes being written
+                // then write the word in rs2 to addr in
+                // rs1, and set rd to zero.
+                // otherwise write a nonzero value to rd.
+                // Invalidate any reservation held be this
+                // thread.
+                let addr = self.atomic_address(self.registers[rs1 as usize], 32, AccessType::Store)?;
+                if self.res.contains(&addr) {
+                    self.res.retain(|x| *x != addr);
+                    self.store_physical(addr, self.registers[rs2 as usize], 32)?;
+                    self.registers[rd as usize] = 0;
+                } else {
+                    self.res.retain(|x| *x != addr);
+                    self.registers[rd as usize] = 1;
+                }
+                self.advance();
+            },
+            Instruction::AmoswapW { rd, rs1, rs2, ..} => {
+                // read a word from the address in rs1
+                // write the value in rs2 register to
+                // address in rs1, take value from rs1 and
+                // sign extend then store in rd
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |_| val)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmoaddW { rd, rs1, rs2, ..} => {
+                // read word from address in rs1
+                // add the value from rs2 to the word
+                // read at rs1 address and save result
+                // in memory at address in rs1. Write
+                // previous value in address at rs1
+                // to rd. The add wraps on overflow, and like
+                // every AMO it needs no reservation from lr.w.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |old| old.wrapping_add(val))?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmoxorW { rd, rs1, rs2, .. } => {
+                // read word from address in rs1
+                // xor the word against the value in rs2
+                // save the original value found at address
+                // in rs1 to rd. Save the xor value in the
+                // memory at the address from rs1.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |old| old ^ val)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmoandW { rd, rs1, rs2, .. } => {
+                // read word from address in rs1
+                // bitwise and word against the value in rs2
+                // save the original value found at address
+                // in rs1 to rd. Save the bitwise and'd value
+                // in the memory at the address from rs1.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |old| old & val)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmoorW { rd, rs1, rs2, .. } => {
+                // read word from address in rs1
+                // bitwise or word against value in rs2
+                // save the original value found at address
+                // in rs1 to rd. save the bitwise or'd value
+                // in the memory at the address from rs1.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |old| old | val)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmominW { rd, rs1, rs2, .. } => {
+                // read word from address in rs1
+                // compare the value of the word to the
+                // value in rs2 and save the lowest value
+                // to memory at the address in rs1.
+                // store the original word at address in rs1
+                // to rd.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |old| if (old as i32) < (val as i32) { old } else { val })?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmomaxW { rd, rs1, rs2, .. } => {
+                // read word from address in rs1
+                // compare the value of the word to the
+                // value in rs2. Store the highest value
+                // in memory at the address in rs1.
+                // store the original word atw address in rs1
+                // to rd.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |old| if (old as i32) > (val as i32) { old } else { val })?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmominuW { rd, rs1, rs2, .. } => {
+                // read word from address in rs1
+                // compare the unsigned value to an unsigned
+                // value in rs2. Store the lowest value to
+                // memory at the address in rs1
+                // store the original word at address in rs1
+                // to rd.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |old| std::cmp::min(old as u32, val as u32) as u64)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmomaxuW { rd, rs1, rs2, .. } => {
+                // read word from address in rs1
+                // compare the unsigned value to an unsigned
+                // value in rs2. Store the higheste value to
+                // memory at the address in rs1
+                // store the original word at address in rs1
+                // to rd.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 32, |old| std::cmp::max(old as u32, val as u32) as u64)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::LrD { rd, rs1, .. } => {
+                // See LrD, but instead of reading word
+                // from address at rs1, read double word.
+                let addr = self.atomic_address(self.registers[rs1 as usize], 64, AccessType::Load)?;
+                let val = self.load_physical(addr, 64)?;
+                self.registers[rd as usize] = memory::sign_extend(val, 64);
+                self.res.push(addr);
+                self.advance();
+            },
+            Instruction::ScD { rd, rs1, rs2, .. } => {
+                // See ScW, but instead of conditionally
+                // saving a word, save a double word.
+                let addr = self.atomic_address(self.registers[rs1 as usize], 64, AccessType::Store)?;
+                if self.res.contains(&addr) {
+                    self.res.retain(|x| *x != addr);
+                    self.store_physical(addr, self.registers[rs2 as usize], 64)?;
+                    self.registers[rd as usize] = 0;
+                } else {
+                    self.res.retain(|x| *x != addr);
+                    self.registers[rd as usize] = 1;
+                }
+                self.advance();
+            },
+            Instruction::AmoswapD { rd, rs1, rs2, ..} => {
+                // read a doubleword from the address in rs1
+                // write the value in rs2 register to
+                // address in rs1, take value from rs1 and
+                // sign extend then store in rd
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |_| val)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmoaddD { rd, rs1, rs2, ..} => {
+                // read doubleword from address in rs1
+                // add the value from rs2 to the doubleword
+                // read at rs1 address and save result
+                // in memory at address in rs1. Write
+                // previous value in address at rs1
+                // to rd. The add wraps on overflow, and like
+                // every AMO it needs no reservation from lr.d.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |old| old.wrapping_add(val))?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmoxorD { rd, rs1, rs2, .. } => {
+                // read doubleword from address in rs1
+                // xor the doubleword against the value in rs2
+                // save the original value found at address
+                // in rs1 to rd. Save the xor value in the
+                // memory at the address from rs1.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |old| old ^ val)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmoandD { rd, rs1, rs2, .. } => {
+                // read doubleword from address in rs1
+                // bitwise and doubleword against the value in rs2
+                // save the original value found at address
+                // in rs1 to rd. Save the bitwise and'd value
+                // in the memory at the address from rs1.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |old| old & val)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmoorD { rd, rs1, rs2, .. } => {
+                // read doubleword from address in rs1
+                // bitwise or doubleword against value in rs2
+                // save the original value found at address
+                // in rs1 to rd. save the bitwise or'd value
+                // in the memory at the address from rs1.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |old| old | val)?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmominD { rd, rs1, rs2, .. } => {
+                // read doubleword from address in rs1
+                // compare the value of the doubleword to the
+                // value in rs2 and save the lowest value
+                // to memory at the address in rs1.
+                // store the original doubleword at address in rs1
+                // to rd.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |old| if (old as i64) < (val as i64) { old } else { val })?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmomaxD { rd, rs1, rs2, .. } => {
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |old| if (old as i64) > (val as i64) { old } else { val })?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmominuD { rd, rs1, rs2, .. } => {
+                // read doubleword from address in rs1
+                // compare the unsigned value to an unsigned
+                // value in rs2. Store the lowest value to
+                // memory at the address in rs1
+                // store the original doubleword at address in rs1
+                // to rd.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |old| std::cmp::min(old, val))?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::AmomaxuD { rd, rs1, rs2, .. } => {
+                // read doubleword from address in rs1
+                // compare the unsigned value to an unsigned
+                // value in rs2. Store the higheste value to
+                // memory at the address in rs1
+                // store the original doubleword at address in rs1
+                // to rd.
+                let val = self.registers[rs2 as usize];
+                let old = self.amo(self.registers[rs1 as usize], 64, |old| std::cmp::max(old, val))?;
+                self.registers[rd as usize] = old;
+                self.advance();
+            },
+            Instruction::Flw { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as u32) as u64);
+                let val = f32::from_bits(self.load_unsigned(addr, 32)? as u32);
+                self.write_f32(rd, val);
+                self.advance();
+            },
+            Instruction::Fsw { rs1, rs2, imm, .. } => {
+                // store value in f_register rs2 as bits into memory at address in rs1 + imm
+                let addr = self.registers[rs1 as usize].wrapping_add((imm as u32) as u64);
+                let val = self.read_f32(rs2).to_bits() as u64;
+                self.store(addr, val, 32)?;
+                self.advance();
+            },
+            Instruction::FmaddS { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                // multiply value in f_register[rs1] by value in f_register[rs2]
+                // add value in rs3
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let rs3_val = self.read_f32(rs3);
+                let res = narrow_f32((rs1_val as f64).mul_add(rs2_val as f64, rs3_val as f64), rm);
+                self.set_fflags(fma_flags_f32(rs1_val, rs2_val, rs3_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            Instruction::FmsubS { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                // multiply value in f_register[rs1] by value in f_register[rs2]
+                // subtract value in rs3
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let rs3_val = -self.read_f32(rs3);
+                let res = narrow_f32((rs1_val as f64).mul_add(rs2_val as f64, rs3_val as f64), rm);
+                self.set_fflags(fma_flags_f32(rs1_val, rs2_val, rs3_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            Instruction::FnmsubS { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = -self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let rs3_val = -self.read_f32(rs3);
+                let res = narrow_f32((rs1_val as f64).mul_add(rs2_val as f64, rs3_val as f64), rm);
+                self.set_fflags(fma_flags_f32(rs1_val, rs2_val, rs3_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            Instruction::FnmaddS { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = -self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let rs3_val = self.read_f32(rs3);
+                let res = narrow_f32((rs1_val as f64).mul_add(rs2_val as f64, rs3_val as f64), rm);
+                self.set_fflags(fma_flags_f32(rs1_val, rs2_val, rs3_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            Instruction::FaddS { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let res = narrow_f32(rs1_val as f64 + rs2_val as f64, rm);
+                self.set_fflags(add_flags_f32(rs1_val, rs2_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            Instruction::FsubS { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let res = narrow_f32(rs1_val as f64 - rs2_val as f64, rm);
+                self.set_fflags(add_flags_f32(rs1_val, -rs2_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            Instruction::FmulS { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let res = narrow_f32(rs1_val as f64 * rs2_val as f64, rm);
+                self.set_fflags(mul_flags_f32(rs1_val, rs2_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            Instruction::FdivS { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let res = narrow_f32(rs1_val as f64 / rs2_val as f64, rm);
+                self.set_fflags(div_flags_f32(rs1_val, rs2_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            Instruction::FsqrtS { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.read_f32(rs1);
+                let res = narrow_f32((rs1_val as f64).sqrt(), rm);
+                self.set_fflags(sqrt_flags_f32(rs1_val, res));
+                self.write_f32(rd, canonicalize_f32(res));
+                self.advance();
+            },
+            // The sign injections work on the bits, so NaN payloads pass
+            // through untouched.
+            Instruction::FsgnjS { rd, rs1, rs2, .. } => {
+                let sign = self.read_f32(rs2).to_bits() & 0x8000_0000;
+                let other = self.read_f32(rs1).to_bits() & 0x7fff_ffff;
+                self.write_f32(rd, f32::from_bits(sign | other));
+                self.advance();
+            },
+            Instruction::FsgnjnS { rd, rs1, rs2, .. } => {
+                let sign = !self.read_f32(rs2).to_bits() & 0x8000_0000;
+                let other = self.read_f32(rs1).to_bits() & 0x7fff_ffff;
+                self.write_f32(rd, f32::from_bits(sign | other));
+                self.advance();
+            },
+            Instruction::FsgnjxS { rd, rs1, rs2, .. } => {
+                let sign_1 = self.read_f32(rs1).to_bits() & 0x8000_0000;
+                let sign_2 = self.read_f32(rs2).to_bits() & 0x8000_0000;
+                let other = self.read_f32(rs1).to_bits() & 0x7fff_ffff;
+                self.write_f32(rd, f32::from_bits((sign_1 ^ sign_2) | other));
+                self.advance();
+            },
+            Instruction::FminS { rd, rs1, rs2, .. } => {
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let mut fflags = self.read_csr_raw(CSR_FFLAGS) as u8;
+                self.write_f32(rd, fmin_rv_f32(rs1_val, rs2_val, &mut fflags));
+                self.write_csr_raw(CSR_FFLAGS, fflags as u64);
+                self.advance();
+            },
+            Instruction::FmaxS { rd, rs1, rs2, .. } => {
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                let mut fflags = self.read_csr_raw(CSR_FFLAGS) as u8;
+                self.write_f32(rd, fmax_rv_f32(rs1_val, rs2_val, &mut fflags));
+                self.write_csr_raw(CSR_FFLAGS, fflags as u64);
+                self.advance();
+            },
+            Instruction::FcvtWS { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.read_f32(rs1) as f64;
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, -2f64.powi(31), 2f64.powi(31)));
+                self.registers[rd as usize] = (rounded as i32) as u64;
+                self.advance();
+            },
+            Instruction::FcvtWUS { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.read_f32(rs1) as f64;
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, 0.0, 2f64.powi(32)));
+                self.registers[rd as usize] = ((rounded as u32) as i32) as u64;
+                self.advance();
+            },
+            Instruction::FmvXW { rd, rs1, .. } => {
+                // The low half of the register, whether or not it is
+                // NaN-boxed.
+                let rs1_val = (((self.f_registers[rs1 as usize].to_bits() & 0xffffffff) as i32) as i64) as u64;
+                self.registers[rd as usize] = rs1_val;
+                self.advance();
+            },
+            Instruction::FeqS { rd, rs1, rs2, .. } => {
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                if is_signaling_nan_f32(rs1_val) || is_signaling_nan_f32(rs2_val) {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if rs1_val == rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FltS { rd, rs1, rs2, .. } => {
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                if rs1_val.is_nan() || rs2_val.is_nan() {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if rs1_val < rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FleS { rd, rs1, rs2, .. } => {
+                let rs1_val = self.read_f32(rs1);
+                let rs2_val = self.read_f32(rs2);
+                if rs1_val.is_nan() || rs2_val.is_nan() {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if rs1_val <= rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FclassS { rd, rs1, .. } => {
+                self.registers[rd as usize] = classify_f32(self.read_f32(rs1));
+                self.advance();
+            },
+            Instruction::FcvtSW { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.write_f32(rd, narrow_f32((self.registers[rs1 as usize] as i32) as f64, rm));
+                self.advance();
+            },
+            Instruction::FcvtSWU { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.write_f32(rd, narrow_f32((self.registers[rs1 as usize] as u32) as f64, rm));
+                self.advance();
+            },
+            Instruction::FmvWX { rd, rs1, .. } => {
+                self.write_f32(rd, f32::from_bits(self.registers[rs1 as usize] as u32));
+                self.advance();
+            },
+            Instruction::FcvtLS { rd, rs1, rm, ..} => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.read_f32(rs1) as f64;
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, -2f64.powi(63), 2f64.powi(63)));
+                self.registers[rd as usize] = rounded as i64 as u64;
+                self.advance();
+            },
+            Instruction::FcvtLUS { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.read_f32(rs1) as f64;
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, 0.0, 2f64.powi(64)));
+                self.registers[rd as usize] = rounded as u64;
+                self.advance();
+            },
+            Instruction::FcvtSL { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.write_f32(rd, narrow_f32((self.registers[rs1 as usize] as i64) as f64, rm));
+                self.advance();
+            },
+            Instruction::FcvtSLU { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.write_f32(rd, narrow_f32(self.registers[rs1 as usize] as f64, rm));
+                self.advance();
+            },
+            Instruction::Fld { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize];
+                self.f_registers[rd as usize] = f64::from_bits(self.load_unsigned(addr, 64)?);
+                self.advance();
+            },
+            Instruction::Fsd { rs1, rs2, imm, .. } => {
+                let addr = self.registers[rs1 as usize];
+                let val = self.f_registers[rs2 as usize];
+                self.store(addr, val.to_bits(), 64)?;
+                self.advance();
+            },
+            Instruction::FmaddD { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                let rs3_val = self.f_registers[rs3 as usize];
+                self.set_fflags(fma_flags(rs1_val, rs2_val, rs3_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.mul_add(rs2_val, rs3_val));
+                self.advance();
+            },
+            Instruction::FmsubD { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                let rs3_val = -self.f_registers[rs3 as usize];
+                self.set_fflags(fma_flags(rs1_val, rs2_val, rs3_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.mul_add(rs2_val, rs3_val));
+                self.advance();
+            },
+            Instruction::FnmsubD { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = -self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                let rs3_val = -self.f_registers[rs3 as usize];
+                self.set_fflags(fma_flags(rs1_val, rs2_val, rs3_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.mul_add(rs2_val, rs3_val));
+                self.advance();
+            },
+            Instruction::FnmaddD { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = -self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                let rs3_val = self.f_registers[rs3 as usize];
+                self.set_fflags(fma_flags(rs1_val, rs2_val, rs3_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.mul_add(rs2_val, rs3_val));
+                self.advance();
+            },
+            Instruction::FaddD { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.set_fflags(add_flags(self.f_registers[rs1 as usize], self.f_registers[rs2 as usize]));
+                self.f_registers[rd as usize] = canonicalize(self.f_registers[rs1 as usize] + self.f_registers[rs2 as usize]); 
+                self.advance();
+            },
+            Instruction::FsubD { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.set_fflags(add_flags(self.f_registers[rs1 as usize], -self.f_registers[rs2 as usize]));
+                self.f_registers[rd as usize] = canonicalize(self.f_registers[rs1 as usize] - self.f_registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::FmulD { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.set_fflags(mul_flags(self.f_registers[rs1 as usize], self.f_registers[rs2 as usize]));
+                self.f_registers[rd as usize] = canonicalize(self.f_registers[rs1 as usize] * self.f_registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::FdivD { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.set_fflags(div_flags(self.f_registers[rs1 as usize], self.f_registers[rs2 as usize]));
+                self.f_registers[rd as usize] = canonicalize(self.f_registers[rs1 as usize] / self.f_registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::FsqrtD { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.set_fflags(sqrt_flags(self.f_registers[rs1 as usize]));
+                self.f_registers[rd as usize] = canonicalize(self.f_registers[rs1 as usize].sqrt());
+                self.advance();
+            },
+            Instruction::FsgnjD { rd, rs1, rs2, .. } => {
+                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].copysign(self.f_registers[rs2 as usize]);
+                self.advance();   
+            },
+            Instruction::FsgnjnD { rd, rs1, rs2, .. } => {
+                self.f_registers[rd as usize] = self.f_registers[rs1 as usize].copysign(-self.f_registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::FsgnjxD { rd, rs1, rs2, .. } => {
+                let sign_1 = self.f_registers[rs1 as usize].to_bits() & 0x8000_0000_0000_0000;
+                let sign_2 = self.f_registers[rs2 as usize].to_bits() & 0x8000_0000_0000_0000;
+                let other = self.f_registers[rs1 as usize].to_bits() & 0x7fff_ffff_ffff_ffff;
+                self.f_registers[rd as usize] = f64::from_bits((sign_1 ^ sign_2) | other);
+                self.advance();
+            },
+            Instruction::FminD { rd, rs1, rs2, .. } => {
+                let mut fflags = self.read_csr_raw(CSR_FFLAGS) as u8;
+                self.f_registers[rd as usize] = fmin_rv(self.f_registers[rs1 as usize], self.f_registers[rs2 as usize], &mut fflags);
+                self.write_csr_raw(CSR_FFLAGS, fflags as u64);
+                self.advance();
+            },
+            Instruction::FmaxD { rd, rs1, rs2, .. } => {
+                let mut fflags = self.read_csr_raw(CSR_FFLAGS) as u8;
+                self.f_registers[rd as usize] = fmax_rv(self.f_registers[rs1 as usize], self.f_registers[rs2 as usize], &mut fflags);
+                self.write_csr_raw(CSR_FFLAGS, fflags as u64);
+                self.advance();
+            },
+            Instruction::FcvtSD { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.write_f32(rd, narrow_f32(self.f_registers[rs1 as usize], rm));
+                self.advance();
+            },
+            Instruction::FcvtDS { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = canonicalize(self.read_f32(rs1) as f64);
+                self.advance();
+            },
+            Instruction::FeqD { rd, rs1, rs2, .. } => {
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                if is_signaling_nan(rs1_val) || is_signaling_nan(rs2_val) {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if rs1_val == rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FltD { rd, rs1, rs2, .. } => {
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                if rs1_val.is_nan() || rs2_val.is_nan() {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if  rs1_val < rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FleD { rd, rs1, rs2, .. } => {
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                if rs1_val.is_nan() || rs2_val.is_nan() {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if  rs1_val <= rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FclassD { rd, rs1, ..} => {
+                self.registers[rd as usize] = classify_f64(self.f_registers[rs1 as usize]);
+                self.advance();
+            },
+            Instruction::FcvtWD { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.f_registers[rs1 as usize];
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, -2f64.powi(31), 2f64.powi(31)));
+                self.registers[rd as usize] = (rounded as i32) as u64;
+                self.advance();
+            },
+            Instruction::FcvtWUD { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.f_registers[rs1 as usize];
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, 0.0, 2f64.powi(32)));
+                self.registers[rd as usize] = ((rounded as u32) as i32) as u64;
+                self.advance();
+            },
+            Instruction::FcvtDW { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as i32) as f64;
+                self.advance();
+            },
+            Instruction::FcvtDWU { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as u32) as f64;
+                self.advance();
+            },
+            Instruction::FcvtLD { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.f_registers[rs1 as usize];
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, -2f64.powi(63), 2f64.powi(63)));
+                self.registers[rd as usize] = rounded as i64 as u64;
+                self.advance();
+            },
+            Instruction::FcvtLUD { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.f_registers[rs1 as usize];
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, 0.0, 2f64.powi(64)));
+                self.registers[rd as usize] = rounded as u64;
+                self.advance();
+            },
+            Instruction::FmvXD { rd, rs1, .. } => {
+                self.registers[rd as usize] = (self.f_registers[rs1 as usize].to_bits());
+                self.advance();
+            },
+            Instruction::FcvtDL { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = self.registers[rs1 as usize] as f64;
+                self.advance();
+            },
+            Instruction::FcvtDLU { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = self.registers[rs1 as usize] as f64;
+                self.advance();
+            },
+            Instruction::FmvDX { rd, rs1, .. } => {
+                self.f_registers[rd as usize] = f64::from_bits(self.registers[rs1 as usize]);
+                self.advance();
+            },
+            Instruction::Flq { rd, rs1, imm, .. } => {
+                let addr = self.registers[rs1 as usize];
+                self.f_registers[rd as usize] = f64::from_bits(self.load_unsigned(addr, 64)?);
+                self.advance();
+            },
+            Instruction::Fsq { rs1, rs2, imm, .. } => {
+                let addr = self.registers[rs1 as usize];
+                let val = self.f_registers[rs2 as usize].to_bits();
+                self.store(addr, val, 64)?;
+                self.advance();
+            },
+            Instruction::FmaddQ { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                let rs3_val = self.f_registers[rs3 as usize];
+                self.set_fflags(fma_flags(rs1_val, rs2_val, rs3_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.mul_add(rs2_val, rs3_val));
+                self.advance();
+            },
+            Instruction::FmsubQ { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                let rs3_val = -self.f_registers[rs3 as usize];
+                self.set_fflags(fma_flags(rs1_val, rs2_val, rs3_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.mul_add(rs2_val, rs3_val));
+                self.advance();
+            },
+            Instruction::FnmsubQ { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = -self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                let rs3_val = -self.f_registers[rs3 as usize];
+                self.set_fflags(fma_flags(rs1_val, rs2_val, rs3_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.mul_add(rs2_val, rs3_val));
+                self.advance();
+            },
+            Instruction::FnmaddQ { rd, rs1, rs2, rs3, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = -self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                let rs3_val = self.f_registers[rs3 as usize];
+                self.set_fflags(fma_flags(rs1_val, rs2_val, rs3_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.mul_add(rs2_val, rs3_val));
+                self.advance();
+            },
+            Instruction::FaddQ { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                self.set_fflags(add_flags(rs1_val, rs2_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val + rs2_val);
+                self.advance();
+            },
+            Instruction::FsubQ { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                self.set_fflags(add_flags(rs1_val, -rs2_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val - rs2_val);
+                self.advance();
+            },
+            Instruction::FmulQ { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                self.set_fflags(mul_flags(rs1_val, rs2_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val * rs2_val);
+                self.advance();
+            },
+            Instruction::FdivQ { rd, rs1, rs2, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                self.set_fflags(div_flags(rs1_val, rs2_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val / rs2_val);
+                self.advance();
+            },
+            Instruction::FsqrtQ { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let rs1_val = self.f_registers[rs1 as usize];
+                self.set_fflags(sqrt_flags(rs1_val));
+                self.f_registers[rd as usize] = canonicalize(rs1_val.sqrt());
+                self.advance();
+            },
+            Instruction::FsgnjQ { rd, rs1, rs2, .. } => {
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                self.f_registers[rd as usize] = rs1_val.copysign(rs2_val);
+                self.advance();
+            },
+            Instruction::FsgnjnQ { rd, rs1, rs2, .. } => {
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = -self.f_registers[rs2 as usize];
+                self.f_registers[rd as usize] = rs1_val.copysign(rs2_val);
+                self.advance();
+            },
+            Instruction::FsgnjxQ { rd, rs1, rs2, .. } => {
+                let sign_1 = self.f_registers[rs1 as usize].to_bits() & 0x8000_0000_0000_0000;
+                let sign_2 = self.f_registers[rs2 as usize].to_bits() & 0x8000_0000_0000_0000;
+                let other = self.f_registers[rs1 as usize].to_bits() & 0x7fff_ffff_ffff_ffff;
+                self.f_registers[rd as usize] = f64::from_bits((sign_1 ^ sign_2) | other);
+                self.advance();
+            },
+            Instruction::FminQ { rd, rs1, rs2, .. } => {
+                let mut fflags = self.read_csr_raw(CSR_FFLAGS) as u8;
+                self.f_registers[rd as usize] = fmin_rv(self.f_registers[rs1 as usize], self.f_registers[rs2 as usize], &mut fflags);
+                self.write_csr_raw(CSR_FFLAGS, fflags as u64);
+                self.advance();
+            },
+            Instruction::FmaxQ { rd, rs1, rs2, .. } => {
+                let mut fflags = self.read_csr_raw(CSR_FFLAGS) as u8;
+                self.f_registers[rd as usize] = fmax_rv(self.f_registers[rs1 as usize], self.f_registers[rs2 as usize], &mut fflags);
+                self.write_csr_raw(CSR_FFLAGS, fflags as u64);
+                self.advance();
+            },
+            // Quads are held as f64, so only narrowing to single rounds and
+            // the conversions to and from double are copies.
+            Instruction::FcvtSQ { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.write_f32(rd, narrow_f32(self.f_registers[rs1 as usize], rm));
+                self.advance();
+            },
+            Instruction::FcvtQS { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = canonicalize(self.read_f32(rs1) as f64);
+                self.advance();
+            },
+            Instruction::FcvtDQ { rd, rs1, rm, .. } | Instruction::FcvtQD { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = self.f_registers[rs1 as usize];
+                self.advance();
+            },
+            Instruction::FeqQ { rd, rs1, rs2, .. } => {
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                if is_signaling_nan(rs1_val) || is_signaling_nan(rs2_val) {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if rs1_val == rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FltQ { rd, rs1, rs2, .. } => {
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                if rs1_val.is_nan() || rs2_val.is_nan() {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if rs1_val < rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FleQ { rd, rs1, rs2, .. } => {
+                let rs1_val = self.f_registers[rs1 as usize];
+                let rs2_val = self.f_registers[rs2 as usize];
+                if rs1_val.is_nan() || rs2_val.is_nan() {
+                    self.set_fflags(FFLAGS_NV);
+                }
+                self.registers[rd as usize] = if rs1_val <= rs2_val { 1 } else { 0 };
+                self.advance();
+            },
+            Instruction::FclassQ { rd, rs1, .. } => {
+                self.registers[rd as usize] = classify_f64(self.f_registers[rs1 as usize]);
+                self.advance();
+            },
+            Instruction::FcvtWQ { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.f_registers[rs1 as usize];
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, -2f64.powi(31), 2f64.powi(31)));
+                self.registers[rd as usize] = (rounded as i32) as u64;
+                self.advance();
+            },
+            Instruction::FcvtWUQ { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.f_registers[rs1 as usize];
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, 0.0, 2f64.powi(32)));
+                self.registers[rd as usize] = ((rounded as u32) as i32) as u64;
+                self.advance();
+            },
+            Instruction::FcvtQW { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as i32) as f64;
+                self.advance();
+            },
+            Instruction::FcvtQWU { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = (self.registers[rs1 as usize] as u32) as f64;
+                self.advance();
+            },
+            Instruction::FcvtLQ { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.f_registers[rs1 as usize];
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, -2f64.powi(63), 2f64.powi(63)));
+                self.registers[rd as usize] = rounded as i64 as u64;
+                self.advance();
+            },
+            Instruction::FcvtLUQ { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                let val = self.f_registers[rs1 as usize];
+                let rounded = round_rm(val, rm);
+                self.set_fflags(int_cvt_flags(val, rounded, 0.0, 2f64.powi(64)));
+                self.registers[rd as usize] = rounded as u64;
+                self.advance();    
+            },
+            Instruction::FcvtQL { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = self.registers[rs1 as usize] as f64;
+                self.advance();
+            },
+            Instruction::FcvtQLU { rd, rs1, rm, .. } => {
+                let rm = self.effective_rm(rm).ok_or(Exception::Invalid(inst as u64))?;
+                self.f_registers[rd as usize] = self.registers[rs1 as usize] as f64;
+                self.advance();
+            },
+            // Zba: address generation. The .uw forms zero extend the low word
+            // of rs1 first.
+            Instruction::Sh1add { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] << 1).wrapping_add(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Sh2add { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] << 2).wrapping_add(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Sh3add { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] << 3).wrapping_add(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::AddUw { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as u32 as u64).wrapping_add(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Sh1addUw { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = ((self.registers[rs1 as usize] as u32 as u64) << 1).wrapping_add(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Sh2addUw { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = ((self.registers[rs1 as usize] as u32 as u64) << 2).wrapping_add(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Sh3addUw { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = ((self.registers[rs1 as usize] as u32 as u64) << 3).wrapping_add(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::SlliUw { rd, rs1, shamt } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as u32 as u64) << shamt;
+                self.advance();
+            },
+            // Zbb: basic bit manipulation. The W forms work on the low word
+            // and sign extend the result.
+            Instruction::Andn { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] & !self.registers[rs2 as usize];
+                self.advance();
+            },
+            Instruction::Orn { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] | !self.registers[rs2 as usize];
+                self.advance();
+            },
+            Instruction::Xnor { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = !(self.registers[rs1 as usize] ^ self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Clz { rd, rs1 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].leading_zeros() as u64;
+                self.advance();
+            },
+            Instruction::Ctz { rd, rs1 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].trailing_zeros() as u64;
+                self.advance();
+            },
+            Instruction::Cpop { rd, rs1 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].count_ones() as u64;
+                self.advance();
+            },
+            Instruction::Clzw { rd, rs1 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as u32).leading_zeros() as u64;
+                self.advance();
+            },
+            Instruction::Ctzw { rd, rs1 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as u32).trailing_zeros() as u64;
+                self.advance();
+            },
+            Instruction::Cpopw { rd, rs1 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as u32).count_ones() as u64;
+                self.advance();
+            },
+            Instruction::Max { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as i64).max(self.registers[rs2 as usize] as i64) as u64;
+                self.advance();
+            },
+            Instruction::Maxu { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].max(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::Min { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as i64).min(self.registers[rs2 as usize] as i64) as u64;
+                self.advance();
+            },
+            Instruction::Minu { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].min(self.registers[rs2 as usize]);
+                self.advance();
+            },
+            Instruction::SextB { rd, rs1 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] as i8 as u64;
+                self.advance();
+            },
+            Instruction::SextH { rd, rs1 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] as i16 as u64;
+                self.advance();
+            },
+            Instruction::ZextH { rd, rs1 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] as u16 as u64;
+                self.advance();
+            },
+            Instruction::Rol { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].rotate_left(self.registers[rs2 as usize] as u32 & 0b111111);
+                self.advance();
+            },
+            Instruction::Ror { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].rotate_right(self.registers[rs2 as usize] as u32 & 0b111111);
+                self.advance();
+            },
+            Instruction::Rori { rd, rs1, shamt } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].rotate_right(shamt);
+                self.advance();
+            },
+            Instruction::Rolw { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as u32).rotate_left(self.registers[rs2 as usize] as u32 & 0b11111) as i32 as u64;
+                self.advance();
+            },
+            Instruction::Rorw { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as u32).rotate_right(self.registers[rs2 as usize] as u32 & 0b11111) as i32 as u64;
+                self.advance();
+            },
+            Instruction::Roriw { rd, rs1, shamt } => {
+                self.registers[rd as usize] = (self.registers[rs1 as usize] as u32).rotate_right(shamt) as i32 as u64;
+                self.advance();
+            },
+            Instruction::OrcB { rd, rs1 } => {
+                self.registers[rd as usize] = bitmanip::orc_b(self.registers[rs1 as usize]);
+                self.advance();
+            },
+            Instruction::Rev8 { rd, rs1 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize].swap_bytes();
+                self.advance();
+            },
+            // Zbc: carry-less multiplication.
+            Instruction::Clmul { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = bitmanip::clmul(self.registers[rs1 as usize], self.registers[rs2 as usize]) as u64;
+                self.advance();
+            },
+            Instruction::Clmulh { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (bitmanip::clmul(self.registers[rs1 as usize], self.registers[rs2 as usize]) >> 64) as u64;
+                self.advance();
+            },
+            Instruction::Clmulr { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = (bitmanip::clmul(self.registers[rs1 as usize], self.registers[rs2 as usize]) >> 63) as u64;
+                self.advance();
+            },
+            // Zbs: single bit instructions. The bit index is taken modulo 64.
+            Instruction::Bclr { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] & !(1 << (self.registers[rs2 as usize] & 0b111111));
+                self.advance();
+            },
+            Instruction::Bclri { rd, rs1, shamt } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] & !(1 << shamt);
+                self.advance();
+            },
+            Instruction::Bext { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] >> (self.registers[rs2 as usize] & 0b111111) & 1;
+                self.advance();
+            },
+            Instruction::Bexti { rd, rs1, shamt } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] >> shamt & 1;
+                self.advance();
+            },
+            Instruction::Binv { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] ^ 1 << (self.registers[rs2 as usize] & 0b111111);
+                self.advance();
+            },
+            Instruction::Binvi { rd, rs1, shamt } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] ^ 1 << shamt;
+                self.advance();
+            },
+            Instruction::Bset { rd, rs1, rs2 } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] | 1 << (self.registers[rs2 as usize] & 0b111111);
+                self.advance();
+            },
+            Instruction::Bseti { rd, rs1, shamt } => {
+                self.registers[rd as usize] = self.registers[rs1 as usize] | 1 << shamt;
+                self.advance();
+            },
+            _ => { /* Return an error here, and some other places */ }
+        }
+
+        // x0 is hardwired to zero, so whatever an instruction wrote to it,
+        // as the expanded `c.j` and `c.jr` do, is discarded.
+        self.registers[Register::X0 as usize] = 0;
+
+        // Other writes to sp can only be checked once made, so they are
+        // undone.
+        if let Some(sanitizer) = self.sanitizer.as_ref() {
+            if let Err(e) = sanitizer.check_stack(self.registers[Register::X2 as usize]) {
+                self.registers[Register::X2 as usize] = sp;
+                self.pc = pc;
+                return Err(e);
+            }
+        }
+
+        // Any F, D or Q instruction but a store may have written a float
+        // register or fflags, so the float state is conservatively dirty.
+        let float = matches!(instruction.get_str("Ext"), Some("F" | "D" | "Q"));
+        if float && !matches!(instruction, Instruction::Fsw { .. } | Instruction::Fsd { .. } | Instruction::Fsq { .. }) {
+            let mut mstatus = self.mstatus();
+            mstatus.set_fs(FS_DIRTY);
+            self.set_mstatus(mstatus);
+        }
+
+        if let (Some(trace), Some(before)) = (self.trace.as_mut(), before) {
+            let rd = (1..32).find(|&idx| self.registers[idx] != before[idx]).unwrap_or(0);
+            trace.retire(rd as u8, self.registers[rd]);
+        }
+
+        if let Some((x, f)) = watched {
+            self.watches.fire((&x, &f), (&self.registers, &self.f_registers));
+        }
+
+        if let (Some(model), Some((pc, access))) = (self.timing.as_mut(), timed) {
+            let conditional = matches!(instruction,
+                Instruction::Beq { .. } | Instruction::Bne { .. } | Instruction::Blt { .. } |
+                Instruction::Bge { .. } | Instruction::Bltu { .. } | Instruction::Bgeu { .. });
+            let taken = conditional.then(|| self.pc != pc.wrapping_add(self.inst_len));
+            let load = match access {
+                Some((addr, AccessType::Load)) => Some(addr),
+                _ => None,
+            };
+            model.retire(pc, &instruction, taken, load);
+        }
+
+        Ok(())
+    }
+
+    pub fn load_program(&mut self, code: Vec<u8>) -> Result<(), Exception> {
+        if code.len() > 4096usize {
+            return Err(Exception::StackSizeExceeded);
+        }
+
+        self.program = code;
+        self.jit.clear();
+        self.halted = false;
+        self.initial_sp = None;
+        self.breakpoints.clear();
+        self.resume_breakpoint = None;
+        
+        Ok(())
+    }
+
+    /// Load `code` into DRAM at `base` and start executing it from there.
+    /// The program buffer is cleared so that instructions are fetched from
+    /// DRAM, and the run ends once the pc leaves the image.
+    pub fn load_image(&mut self, code: &[u8], base: u64) -> Result<(), Exception> {
+        self.load_raw(base, code)?;
+        self.program.clear();
+        self.jit.clear();
+        self.halted = false;
+        self.initial_sp = None;
+        self.breakpoints.clear();
+        self.resume_breakpoint = None;
+        self.image = base..(base + code.len() as u64);
+        self.set_pc(base);
+
+        Ok(())
+    }
+
+    /// Load a flat binary into DRAM at `base` and start executing it from
+    /// `entry`. A zeroed stack of `stack_size` bytes is placed directly
+    /// above the binary, starting at the next 8 byte boundary, and the
+    /// stack pointer is set to its top.
+    pub fn load_binary(&mut self, data: &[u8], base: u64, entry: u64) -> Result<(), Exception> {
+        let stack_base = (base + data.len() as u64 + 7) & !7;
+        let sp = stack_base + self.stack_size;
+        self.load_raw(base, data)?;
+        self.load_raw(stack_base, &vec![0u8; self.stack_size as usize])?;
+        self.program.clear();
+        self.jit.clear();
+        self.halted = false;
+        self.initial_sp = None;
+        self.breakpoints.clear();
+        self.resume_breakpoint = None;
+        self.image = base..(base + data.len() as u64);
+        self.registers[Register::X2 as usize] = sp;
+        self.set_pc(entry);
+
+        Ok(())
+    }
+
+    /// Load the segments of `elf` into DRAM, zero filling past the end of
+    /// each segment's file data, and point the pc at the entry point. Each
+    /// segment becomes a region with the permissions from its flags.
+    /// Returns the end of the highest segment.
+    pub fn load_elf(&mut self, elf: &Elf) -> Result<u64, Exception> {
+        let mut end = 0u64;
+        let (mut text_start, mut text_end) = (u64::MAX, 0u64);
+        for segment in elf.segments.iter() {
+            // Check the whole segment fits before allocating its zeros.
+            let seg_end = segment.vaddr.checked_add(segment.memsz).ok_or(Exception::InvalidAddr)?;
+            self.raw_range(segment.vaddr, segment.memsz as usize)?;
+            self.load_raw(segment.vaddr, &segment.data)?;
+            let zeros = (segment.memsz as usize).saturating_sub(segment.data.len());
+            self.load_raw(segment.vaddr + segment.data.len() as u64, &vec![0u8; zeros])?;
+
+            let mut flags = AccessFlags::NONE;
+            for (bit, flag) in [(PF_R, AccessFlags::READ), (PF_W, AccessFlags::WRITE), (PF_X, AccessFlags::EXECUTE)] {
+                if segment.flags & bit != 0 {
+                    flags = flags | flag;
+                }
+            }
+            self.add_region(MemoryRegion::new(segment.vaddr, segment.memsz, flags));
+
+            if segment.flags & PF_X != 0 {
+                text_start = text_start.min(segment.vaddr);
+                text_end = text_end.max(seg_end);
+            }
+            end = end.max(seg_end);
+        }
+
+        self.program.clear();
+        self.jit.clear();
+        self.halted = false;
+        self.initial_sp = None;
+        self.breakpoints.clear();
+        self.resume_breakpoint = None;
+        self.image = text_start..text_end;
+        self.set_pc(elf.entry);
+        self.load_symbol_table(elf.symbols.clone());
+
+        Ok(end)
+    }
+
+    // Perform the syscall at the pc, logging it if `strace_mode` is on,
+    // and step past the `ecall` unless the program exited.
+    fn service_syscall(&mut self, syscalls: &mut LinuxSyscalls) -> Result<SyscallResult, Exception> {
+        let call = self.strace.is_some().then(|| strace::format_call(self));
+        let result = syscalls.handle(self)?;
+        if let (Some(strace), Some(call)) = (self.strace.as_mut(), call) {
+            let ret = match result {
+                SyscallResult::Continue => (self.registers[Register::X10 as usize] as i64).to_string(),
+                SyscallResult::Exit(_) => "?".to_string(),
+            };
+            let _ = writeln!(strace.output, "{} = {}", call, ret);
+        }
+
+        if result == SyscallResult::Continue {
+            self.advance();
+        }
+        Ok(result)
+    }
+
+    /// Log every syscall `run_elf` services to `output`, one line per call
+    /// in the style of `strace(1)`, e.g.
+    /// `[0x100b0] write(fd=1, buf=0x100d4 "hi\n", count=3) = 3`.
+    pub fn strace_mode(&mut self, output: Box<dyn Write>) {
+        self.strace = Some(Strace { output });
+    }
+
+    /// Place a device tree blob in DRAM at `addr` and pass its address in
+    /// `a1`, as the RISC-V Linux boot protocol expects. The blob must not
+    /// overlap the loaded code.
+    pub fn load_dtb(&mut self, dtb: &[u8], addr: u64) -> Result<(), DtbError> {
+        if !dtb::has_magic(dtb) {
+            return Err(DtbError::BadMagic);
+        }
+
+        let end = addr.checked_add(dtb.len() as u64).ok_or(DtbError::OutOfBounds)?;
+        if addr < self.image.end && self.image.start < end {
+            return Err(DtbError::OverlapsCode);
+        }
+
+        self.load_raw(addr, dtb)?;
+        self.registers[Register::X11 as usize] = addr;
+
+        Ok(())
+    }
+
+    pub fn load_dtb_from_path(&mut self, path: impl AsRef<Path>, addr: u64) -> Result<(), DtbError> {
+        let dtb = std::fs::read(path)?;
+        self.load_dtb(&dtb, addr)
+    }
+
+    /// Copy `data` into DRAM starting at `addr` in a single bulk copy,
+    /// without going through the sized accesses of the `Memory` trait.
+    pub fn load_raw(&mut self, addr: u64, data: &[u8]) -> Result<(), Exception> {
+        self.raw_range(addr, data.len())?;
+        self.bus.write_array(addr, data.to_vec()).map_err(|_| Exception::InvalidAddr)
+    }
+
+    /// Fill `buf` with the bytes of DRAM starting at `addr`.
+    pub fn store_raw(&self, addr: u64, buf: &mut [u8]) -> Result<(), Exception> {
+        self.raw_range(addr, buf.len())?;
+        self.bus.read_array(addr, buf).map_err(|_| Exception::InvalidAddr)
+    }
+
+    // Check that the `len` bytes from `addr` are all in the memory.
+    pub(crate) fn raw_range(&self, addr: u64, len: usize) -> Result<(), Exception> {
+        addr.checked_add(len as u64).ok_or(Exception::InvalidAddr)?;
+        match len {
+            0 => Ok(()),
+            _ => self.bus.get_indices(addr, len as u64).map(|_| ()).map_err(|_| Exception::InvalidAddr),
+        }
+    }
+}
+
+
+
+
+impl SoftThread<u64, f64, Dram> {
+    pub fn new(enc_table: EncodingTable) -> SoftThread<u64, f64, Dram> {
+        SoftThread::with_memory(Dram::default(), enc_table)
+    }
+
+    /// Snapshot this hart into a new, independent one for speculative
+    /// execution. Registers, CSRs, the reservation set, the pc and DRAM
+    /// are copied and the encoding table is shared. The fork gets a fresh
+    /// `mhartid`, no trace hook, speed monitor, branch profile, timing
+    /// model or register watches, and an empty JIT cache. Only the shared
+    /// devices of `mmio`, such as the CLINT of a `Cpu`, are mapped in the
+    /// fork too; the others, like a UART or a CLINT from `attach_clint`,
+    /// are dropped, see `Bus::fork`.
+    pub fn fork(&self) -> SoftThread<u64, f64, Dram> {
+        let mut fork = SoftThread {
+            registers: self.registers,
+            f_registers: self.f_registers,
+            pc: self.pc,
+            program: self.program.clone(),
+            remainder: self.remainder,
+            eq_flag: self.eq_flag,
+            enc_table: Arc::clone(&self.enc_table),
+            csr: self.csr,
+            bus: self.bus.clone(),
+            res: self.res.clone(),
+            priv_level: self.priv_level,
+            image: self.image.clone(),
+            speed: None,
+            regions: self.regions.clone(),
+            trace: None,
+            jit: JitCache::new(),
+            stack_size: self.stack_size,
+            sanitizer: self.sanitizer.clone(),
+            branch_stats: None,
+            timing: None,
+            watches: RegisterWatches::new(),
+            halted: self.halted,
+            tohost: self.tohost,
+            tohost_stored: self.tohost_stored,
+            symbols: self.symbols.clone(),
+            max_stack_depth: self.max_stack_depth,
+            average_frame_size: self.average_frame_size,
+            initial_sp: self.initial_sp,
+            strace: None,
+            breakpoints: vec![],
+            resume_breakpoint: None,
+            unaligned: self.unaligned,
+            panic_trace: false,
+            page_faults: self.page_faults.fork(),
+            histogram: None,
+            max_nops: self.max_nops,
+            nop_run: self.nop_run,
+            history: None,
+            syscalls: SyscallRouter::new(),
+            sbi_hook: None,
+            debugger_hook: None,
+            barrier_hook: None,
+            fence_i_hook: None,
+            wfi_hook: None,
+            branch_taken: false,
+            waiting: false,
+            execution_trace: None,
+            pc_breakpoints: self.pc_breakpoints.clone(),
+            next_breakpoint_id: self.next_breakpoint_id,
+            mmio: self.mmio.fork(),
+            mmu: self.mmu.clone(),
+            pmp: self.pmp.clone(),
+            cpi: self.cpi,
+            inst_len: self.inst_len,
+        };
+
+        // The conditions cannot be copied, so the fork gets the original
+        // code back instead.
+        for breakpoint in self.breakpoints.iter() {
+            fork.store_inst(breakpoint.addr, breakpoint.saved_inst);
+        }
+        fork.write_csr_raw(CSR_MHARTID, NEXT_FORK_HART_ID.fetch_add(1, Ordering::Relaxed));
+        fork
+    }
+
+    /// Write the registers and memory to `path` as an ELF core file that
+    /// GDB can open alongside the guest executable. Each memory region
+    /// becomes a segment with the region's permissions, or all of DRAM a
+    /// single read, write and execute segment if there are no regions.
+    pub fn generate_core_dump(&self, path: &Path) -> io::Result<()> {
+        let mut gregs = [0u64; 32];
+        gregs[0] = self.pc;
+        gregs[1..].copy_from_slice(&self.registers[1..32]);
+        let fregs: [u64; 32] = std::array::from_fn(|idx| self.f_registers[idx].to_bits());
+        let fcsr = self.read_csr(CSR_FCSR) as u32;
+
+        let mem = &self.bus.mem[..];
+        let segments: Vec<CoreSegment> = if self.regions.is_empty() {
+            vec![CoreSegment { vaddr: 0, flags: PF_R | PF_W | PF_X, data: mem }]
+        } else {
+            self.regions.iter().filter(|region| region.base < mem.len() as u64).map(|region| {
+                let flags = [(AccessFlags::READ, PF_R), (AccessFlags::WRITE, PF_W), (AccessFlags::EXECUTE, PF_X)]
+                    .iter()
+                    .filter(|(access, _)| region.flags.contains(*access))
+                    .fold(0, |flags, (_, pf)| flags | pf);
+                let end = region.end().min(mem.len() as u64);
+                CoreSegment { vaddr: region.base, flags, data: &mem[region.base as usize..end as usize] }
+            }).collect()
+        };
+
+        let mut out = BufWriter::new(File::create(path)?);
+        elf::write_core(&mut out, &gregs, &fregs, fcsr, &segments)?;
+        out.flush()
+    }
+
+    /// Load the ELF executable at `path`, start it with `args` as its argv
+    /// using stdin and stdout for I/O, and run it until it calls `exit()`.
+    /// Returns the exit code.
+    pub fn run_elf(&mut self, path: impl AsRef<Path>, args: &[&str]) -> Result<i32, RunError> {
+        let stdin = io::stdin();
+        let stdout = io::stdout();
+        self.run_elf_with_io(path, args, &mut stdin.lock(), &mut stdout.lock())
+    }
+
+    /// Like `run_elf`, with the program's stdin and stdout redirected.
+    pub fn run_elf_with_io(&mut self, path: impl AsRef<Path>, args: &[&str], stdin: &mut dyn Read, stdout: &mut dyn Write) -> Result<i32, RunError> {
+        let (brk, mmap_top) = self.start_elf(path, args)?;
+        let mut syscalls = LinuxSyscalls::new(stdin, stdout, brk, mmap_top);
+        loop {
+            match self.execute() {
+                Ok(()) => {},
+                Err(Exception::EnvironmentCallFromUMode) |
+                Err(Exception::EnvironmentCallFromSMode) |
+                Err(Exception::EnvironmentCallFromMMode) => {
+                    if let SyscallResult::Exit(code) = self.service_syscall(&mut syscalls)? {
+                        return Ok(code);
+                    }
+                },
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Load the ELF executable at `path`, start it with `args` as its argv
+    /// and run it until it calls `exit()` or `exit_group()`, with its stdin
+    /// empty and everything it writes to stdout and stderr captured in the
+    /// returned `ExitStatus`. A program still running after
+    /// `max_instructions` instructions stops with `Exception::Timeout`,
+    /// which unlike a wall clock limit gives the same result on every host.
+    /// Any other exception it raises stops it too. Only RV64 executables
+    /// can be run, so an RV32 one fails to load with
+    /// `ElfError::UnsupportedClass`.
+    pub fn load_elf_and_run(&mut self, path: &Path, args: &[&str], max_instructions: u64) -> Result<ExitStatus, RunError> {
+        let (brk, mmap_top) = self.start_elf(path, args)?;
+        let (instret, cycles) = (self.csr[CSR_MINSTRET as usize], self.csr[CSR_MCYCLE as usize]);
+
+        let mut output = vec![];
+        let mut stderr = vec![];
+        let mut stdin = io::empty();
+        let mut syscalls = LinuxSyscalls::new(&mut stdin, &mut output, brk, mmap_top).with_stderr(&mut stderr);
+
+        let mut steps = 0u64;
+        let (code, exception) = loop {
+            if steps == max_instructions {
+                break (-1, Some(Exception::Timeout));
+            }
+            steps += 1;
+
+            match self.execute() {
+                Ok(()) => {},
+                Err(Exception::EnvironmentCallFromUMode) |
+                Err(Exception::EnvironmentCallFromSMode) |
+                Err(Exception::EnvironmentCallFromMMode) => match self.service_syscall(&mut syscalls) {
+                    Ok(SyscallResult::Exit(code)) => break (code, None),
+                    Ok(SyscallResult::Continue) => {},
+                    Err(e) => break (-1, Some(e)),
+                },
+                Err(e) => break (-1, Some(e)),
+            }
+        };
+
+        Ok(ExitStatus {
+            code,
+            instructions: self.csr[CSR_MINSTRET as usize].wrapping_sub(instret),
+            cycles: self.csr[CSR_MCYCLE as usize].wrapping_sub(cycles),
+            exception,
+            stdout: output,
+            stderr,
+        })
+    }
+
+    // Load the executable at `path` and lay out its stack for `args`.
+    // Returns the initial program break and the top of the mmap area.
+    fn start_elf(&mut self, path: impl AsRef<Path>, args: &[&str]) -> Result<(u64, u64), RunError> {
+        let bytes = std::fs::read(path)?;
+        let elf = Elf::parse(&bytes)?;
+        let end = self.load_elf(&elf)?;
+
+        let top = self.bus.mem.len() as u64;
+        self.registers[Register::X2 as usize] = linux::setup_stack(self, args, &[], elf.entry, top)?;
+
+        let brk = (end + linux::PAGE_SIZE - 1) & !(linux::PAGE_SIZE - 1);
+        Ok((brk, top - STACK_SIZE as u64))
+    }
+
+    /// Grow or shrink DRAM to `new_size` bytes, keeping the contents that
+    /// fit and zeroing any new memory. Shrinking fails with `AddressInUse`
+    /// if the trap vectors, the root page table or an active PMP entry
+    /// point into the removed memory. With `update_sp` the stack pointer
+    /// moves to the new top of memory.
+    pub fn set_memory_size(&mut self, new_size: usize, update_sp: bool) -> Result<(), Exception> {
+        if new_size < self.bus.mem.len() {
+            let removed = new_size as u64..self.bus.mem.len() as u64;
+            if self.live_addresses().iter().any(|addr| removed.contains(addr)) {
+                return Err(Exception::AddressInUse);
+            }
+        }
+
+        self.bus.resize(new_size).map_err(|_| Exception::General)?;
+        if update_sp {
+            self.registers[Register::X2 as usize] = new_size as u64;
+        }
+
+        Ok(())
+    }
+
+    // Addresses that CSRs currently direct the hart to.
+    fn live_addresses(&self) -> Vec<u64> {
+        let mut addrs = vec![];
+        for tvec in [CSR_MTVEC, CSR_STVEC] {
+            let base = self.read_csr_raw(tvec) & !0b11;
+            if base != 0 {
+                addrs.push(base);
+            }
+        }
+
+        let satp = self.read_csr_raw(CSR_SATP);
+        if satp >> SATP_MODE_SHIFT != 0 {
+            addrs.push((satp & SATP_PPN) << 12);
+        }
+
+        for entry in 0..PMP_ENTRIES {
+            let cfg = self.read_csr_raw(CSR_PMPCFG0 + (entry / 8) * 2) >> ((entry % 8) * 8);
+            if cfg & PMPCFG_A != 0 {
+                addrs.push(self.read_csr_raw(CSR_PMPADDR0 + entry) << 2);
+            }
+        }
+
+        addrs
+    }
+}
+
+impl Default for SoftThread<u64, f64, Dram> {
+    fn default() -> SoftThread<u64, f64, Dram> {
+        let enc_table = EncodingTable::default();
+        SoftThread::<u64, f64, Dram>::new(enc_table)
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::{Debug, Formatter};
+use std::time::{Duration, Instant};
+
+// Only look at the clock once every this many instructions.
+pub const CHECK_EVERY: u64 = 1000;
+
+/// Measures how many million instructions per second a hart retires in
+/// real time, reporting the rate once every `interval`. Reports go to
+/// stderr unless a callback has been set.
+pub struct SimSpeed {
+    pub interval: Duration,
+    pub last_report: Instant,
+    pub last_count: u64,
+    count: u64,
+    mips: f64,
+    callback: Option<Box<dyn Fn(f64)>>,
+}
+
+impl SimSpeed {
+    pub fn new(interval: Duration) -> SimSpeed {
+        SimSpeed {
+            interval,
+            last_report: Instant::now(),
+            last_count: 0,
+            count: 0,
+            mips: 0.0,
+            callback: None,
+        }
+    }
+
+    /// The average rate over the last completed interval.
+    pub fn current_mips(&self) -> f64 {
+        self.mips
+    }
+
+    /// The number of instructions retired since the monitor was enabled.
+    pub fn instructions(&self) -> u64 {
+        self.count
+    }
+
+    pub fn set_callback(&mut self, callback: Box<dyn Fn(f64)>) {
+        self.callback = Some(callback);
+    }
+
+    pub(crate) fn tick(&mut self, hart: u64) {
+        self.count += 1;
+        if !self.count.is_multiple_of(CHECK_EVERY) {
+            return;
+        }
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_report);
+        if elapsed < self.interval {
+            return;
+        }
+
+        self.mips = (self.count - self.last_count) as f64 / elapsed.as_secs_f64() / 1_000_000.0;
+        self.last_report = now;
+        self.last_count = self.count;
+
+        match &self.callback {
+            Some(callback) => callback(self.mips),
+            None => eprintln!(
+                "[trecho] hart {}: {:.1} MIPS, {:.3}B instructions total",
+                hart, self.mips, self.count as f64 / 1_000_000_000.0
+            ),
+        }
+    }
+}
+
+impl Debug for SimSpeed {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("SimSpeed")
+            .field("interval", &self.interval)
+            .field("last_report", &self.last_report)
+            .field("last_count", &self.last_count)
+            .field("count", &self.count)
+            .field("mips", &self.mips)
+            .finish()
+    }
+}
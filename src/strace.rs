@@ -0,0 +1,153 @@
+use crate::linux;
+use crate::memory::Memory;
+use crate::register::Register;
+use crate::soft::SoftThread;
+use std::fmt::{Debug, Formatter};
+use std::io::Write;
+use ArgKind::*;
+
+// The most bytes of a buffer or path shown in a traced call.
+pub const SNIPPET_LEN: usize = 32;
+
+/// What a syscall argument means, which decides its name and how it is
+/// shown in a traced call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArgKind {
+    Fd,
+    /// A buffer whose length is the `Count` argument after it.
+    Buf,
+    Count,
+    Path,
+    Flags,
+    Mode,
+    Addr,
+    Len,
+    Prot,
+    Offset,
+    Status,
+    Int,
+}
+
+impl ArgKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArgKind::Fd => "fd",
+            ArgKind::Buf => "buf",
+            ArgKind::Count => "count",
+            ArgKind::Path => "path",
+            ArgKind::Flags => "flags",
+            ArgKind::Mode => "mode",
+            ArgKind::Addr => "addr",
+            ArgKind::Len => "len",
+            ArgKind::Prot => "prot",
+            ArgKind::Offset => "offset",
+            ArgKind::Status => "status",
+            ArgKind::Int => "arg",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SyscallMeta {
+    pub name: &'static str,
+    pub arg_kinds: &'static [ArgKind],
+}
+
+const fn meta(name: &'static str, arg_kinds: &'static [ArgKind]) -> SyscallMeta {
+    SyscallMeta { name, arg_kinds }
+}
+
+/// The common syscalls of the generic Linux ABI used by RISC-V, by number.
+pub const SYSCALLS: [(u64, SyscallMeta); 32] = [
+    (17, meta("getcwd", &[Buf, Count])),
+    (23, meta("dup", &[Fd])),
+    (25, meta("fcntl", &[Fd, Int, Int])),
+    (linux::SYS_IOCTL, meta("ioctl", &[Fd, Int, Addr])),
+    (35, meta("unlinkat", &[Fd, Path, Flags])),
+    (48, meta("faccessat", &[Fd, Path, Mode])),
+    (56, meta("openat", &[Fd, Path, Flags, Mode])),
+    (57, meta("close", &[Fd])),
+    (61, meta("getdents64", &[Fd, Addr, Count])),
+    (62, meta("lseek", &[Fd, Offset, Int])),
+    (linux::SYS_READ, meta("read", &[Fd, Addr, Count])),
+    (linux::SYS_WRITE, meta("write", &[Fd, Buf, Count])),
+    (65, meta("readv", &[Fd, Addr, Count])),
+    (linux::SYS_WRITEV, meta("writev", &[Fd, Addr, Count])),
+    (78, meta("readlinkat", &[Fd, Path, Addr, Len])),
+    (79, meta("newfstatat", &[Fd, Path, Addr, Flags])),
+    (80, meta("fstat", &[Fd, Addr])),
+    (linux::SYS_EXIT, meta("exit", &[Status])),
+    (linux::SYS_EXIT_GROUP, meta("exit_group", &[Status])),
+    (linux::SYS_SET_TID_ADDRESS, meta("set_tid_address", &[Addr])),
+    (98, meta("futex", &[Addr, Int, Int, Addr])),
+    (113, meta("clock_gettime", &[Int, Addr])),
+    (124, meta("sched_yield", &[])),
+    (134, meta("rt_sigaction", &[Int, Addr, Addr])),
+    (135, meta("rt_sigprocmask", &[Int, Addr, Addr])),
+    (160, meta("uname", &[Addr])),
+    (172, meta("getpid", &[])),
+    (178, meta("gettid", &[])),
+    (linux::SYS_BRK, meta("brk", &[Addr])),
+    (linux::SYS_MUNMAP, meta("munmap", &[Addr, Len])),
+    (linux::SYS_MMAP, meta("mmap", &[Addr, Len, Prot, Flags, Fd, Offset])),
+    (226, meta("mprotect", &[Addr, Len, Prot])),
+];
+
+pub fn lookup(nr: u64) -> Option<&'static SyscallMeta> {
+    SYSCALLS.iter().find(|(num, _)| *num == nr).map(|(_, meta)| meta)
+}
+
+/// Where `SoftThread::strace_mode` writes its log.
+pub struct Strace {
+    pub output: Box<dyn Write>,
+}
+
+impl Debug for Strace {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Strace").finish_non_exhaustive()
+    }
+}
+
+/// The syscall `soft` is about to make, as `[pc] name(arg=val, ..)`.
+/// Buffers and paths in DRAM are shown as quoted snippets after their
+/// address. Calls missing from `SYSCALLS` are shown as `syscall_<nr>`
+/// with all six argument registers.
+pub fn format_call<M: Memory<RegValue = u64, Bytes = Vec<u8>>>(soft: &SoftThread<u64, f64, M>) -> String {
+    let arg = |idx: usize| soft.registers[Register::X10 as usize + idx];
+    let nr = soft.registers[Register::X17 as usize];
+    let Some(meta) = lookup(nr) else {
+        let args: Vec<String> = (0..6).map(|idx| format!("{:#x}", arg(idx))).collect();
+        return format!("[{:#x}] syscall_{}({})", soft.pc, nr, args.join(", "));
+    };
+
+    let args: Vec<String> = meta.arg_kinds.iter().enumerate().map(|(idx, kind)| {
+        let val = arg(idx);
+        let shown = match kind {
+            Fd | Int | Status => format!("{}", val as i64),
+            Count | Len => format!("{}", val),
+            Mode => format!("{:#o}", val),
+            Buf => snippet(soft, val, arg(idx + 1) as usize, false),
+            Path => snippet(soft, val, SNIPPET_LEN, true),
+            Flags | Addr | Prot | Offset => format!("{:#x}", val),
+        };
+        format!("{}={}", kind.name(), shown)
+    }).collect();
+
+    format!("[{:#x}] {}({})", soft.pc, meta.name, args.join(", "))
+}
+
+// `addr` followed by up to `SNIPPET_LEN` of the `len` bytes there, or just
+// `addr` if they are not in DRAM. A path ends at its NUL.
+fn snippet<M: Memory<RegValue = u64, Bytes = Vec<u8>>>(soft: &SoftThread<u64, f64, M>, addr: u64, len: usize, path: bool) -> String {
+    let mut buf = vec![0u8; len.min(SNIPPET_LEN)];
+    if soft.store_raw(addr, &mut buf).is_err() {
+        return format!("{:#x}", addr);
+    }
+
+    if path {
+        let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+        buf.truncate(end);
+    }
+    let dots = if !path && len > SNIPPET_LEN { "..." } else { "" };
+    format!("{:#x} {:?}{}", addr, String::from_utf8_lossy(&buf), dots)
+}
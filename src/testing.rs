@@ -0,0 +1,87 @@
+use crate::memory::Dram;
+use crate::privilege::PrivilegeLevel;
+use crate::register::Register;
+use crate::soft::SoftThread;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+
+/// A failed check of a hart's state, carrying what was expected and what
+/// was actually found.
+#[derive(Debug, PartialEq)]
+pub enum AssertionError {
+    Register { register: Register, expected: u64, actual: u64 },
+    FloatRegister { register: Register, expected: f64, actual: f64, epsilon: f64 },
+    Csr { addr: u16, expected: u64, actual: u64 },
+    Memory { addr: u64, expected: Vec<u8>, actual: Vec<u8> },
+}
+
+impl Display for AssertionError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            AssertionError::Register { register, expected, actual } => {
+                write!(f, "{:?}: expected {:#x}, found {:#x}", register, expected, actual)
+            },
+            AssertionError::FloatRegister { register, expected, actual, epsilon } => {
+                write!(f, "f{}: expected {} (+/- {}), found {}", *register as usize, expected, epsilon, actual)
+            },
+            AssertionError::Csr { addr, expected, actual } => {
+                write!(f, "csr {:#x}: expected {:#x}, found {:#x}", addr, expected, actual)
+            },
+            AssertionError::Memory { addr, expected, actual } => {
+                write!(f, "memory at {:#x}: expected {:02x?}, found {:02x?}", addr, expected, actual)
+            },
+        }
+    }
+}
+
+impl Error for AssertionError {}
+
+impl SoftThread<u64, f64, Dram> {
+    /// Switch the hart straight to `mode`, without going through a trap
+    /// and `mret`. Only available to tests, since guest code must never be
+    /// able to raise its own privilege.
+    pub fn set_privilege_mode(&mut self, mode: PrivilegeLevel) {
+        self.priv_level = mode;
+    }
+
+    pub fn assert_register(&self, reg: Register, expected: u64) -> std::result::Result<(), AssertionError> {
+        let actual = self.registers[reg as usize];
+        if actual != expected {
+            return Err(AssertionError::Register { register: reg, expected, actual });
+        }
+        Ok(())
+    }
+
+    /// Check float register `reg` is within `epsilon` of `expected`. NaN
+    /// matches NaN.
+    pub fn assert_float_register(&self, reg: Register, expected: f64, epsilon: f64) -> std::result::Result<(), AssertionError> {
+        let actual = self.f_registers[reg as usize];
+        let matches = if expected.is_nan() { actual.is_nan() } else { (actual - expected).abs() <= epsilon };
+        if !matches {
+            return Err(AssertionError::FloatRegister { register: reg, expected, actual, epsilon });
+        }
+        Ok(())
+    }
+
+    pub fn assert_csr(&self, addr: u16, expected: u64) -> std::result::Result<(), AssertionError> {
+        let actual = self.read_csr_raw(addr);
+        if actual != expected {
+            return Err(AssertionError::Csr { addr, expected, actual });
+        }
+        Ok(())
+    }
+
+    /// Check DRAM at `addr` holds `expected`. A range outside of DRAM is
+    /// reported with an empty `actual`.
+    pub fn assert_memory(&self, addr: u64, expected: &[u8]) -> std::result::Result<(), AssertionError> {
+        let mut actual = vec![0u8; expected.len()];
+        if self.store_raw(addr, &mut actual).is_err() {
+            actual.clear();
+        }
+
+        if actual != expected {
+            return Err(AssertionError::Memory { addr, expected: expected.to_vec(), actual });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use crate::instructions::Instruction;
+
+// Defaults for `CycleAccurateModel::new`.
+pub const BHT_ENTRIES: usize = 256;
+pub const CACHE_LINES: usize = 512;
+pub const CACHE_LINE_SIZE: u64 = 64;
+pub const BRANCH_PENALTY: u64 = 3;
+pub const L1_LATENCY: u64 = 1;
+pub const DRAM_LATENCY: u64 = 100;
+pub const MUL_LATENCY: u64 = 3;
+pub const DIV_LATENCY: u64 = 20;
+
+/// A direct mapped cache that only tracks which line each set holds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirectMappedCache {
+    pub line_size: u64,
+    tags: Vec<Option<u64>>,
+}
+
+impl DirectMappedCache {
+    pub fn new(lines: usize, line_size: u64) -> DirectMappedCache {
+        DirectMappedCache { line_size, tags: vec![None; lines] }
+    }
+
+    /// Access `addr`, filling its line on a miss. Returns true on a hit.
+    pub fn access(&mut self, addr: u64) -> bool {
+        let line = addr / self.line_size;
+        let set = (line % self.tags.len() as u64) as usize;
+        let hit = self.tags[set] == Some(line);
+        self.tags[set] = Some(line);
+        hit
+    }
+}
+
+/// A timing model that charges each instruction its latency, plus
+/// `branch_penalty` cycles for a conditional branch the one bit predictor
+/// in `branch_history_table` gets wrong, plus `l1_latency` or
+/// `dram_latency` cycles for a load that hits or misses in `cache_state`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CycleAccurateModel {
+    pub branch_history_table: Vec<bool>,
+    pub cache_state: DirectMappedCache,
+    pub branch_penalty: u64,
+    pub l1_latency: u64,
+    pub dram_latency: u64,
+    pub cycle_count: u64,
+    pub instructions: u64,
+}
+
+impl CycleAccurateModel {
+    pub fn new() -> CycleAccurateModel {
+        CycleAccurateModel {
+            branch_history_table: vec![false; BHT_ENTRIES],
+            cache_state: DirectMappedCache::new(CACHE_LINES, CACHE_LINE_SIZE),
+            branch_penalty: BRANCH_PENALTY,
+            l1_latency: L1_LATENCY,
+            dram_latency: DRAM_LATENCY,
+            cycle_count: 0,
+            instructions: 0,
+        }
+    }
+
+    /// The cycles `instruction` takes to execute once its operands are
+    /// ready, not counting branch or memory penalties.
+    pub fn latency(instruction: &Instruction) -> u64 {
+        match instruction {
+            Instruction::Mul { .. } | Instruction::Mulh { .. } | Instruction::Mulhsu { .. } |
+            Instruction::Mulhu { .. } | Instruction::Mulw { .. } => MUL_LATENCY,
+            Instruction::Div { .. } | Instruction::Divu { .. } | Instruction::Rem { .. } |
+            Instruction::Remu { .. } | Instruction::Divw { .. } | Instruction::Divuw { .. } |
+            Instruction::Remw { .. } | Instruction::RemuW { .. } => DIV_LATENCY,
+            _ => 1,
+        }
+    }
+
+    /// Account for `instruction` at `pc` having executed. `taken` is set
+    /// for a conditional branch and `load` is the address of a load.
+    pub fn retire(&mut self, pc: u64, instruction: &Instruction, taken: Option<bool>, load: Option<u64>) {
+        self.instructions += 1;
+        self.cycle_count += Self::latency(instruction);
+
+        if let Some(taken) = taken {
+            let entry = ((pc >> 2) % self.branch_history_table.len() as u64) as usize;
+            if self.branch_history_table[entry] != taken {
+                self.cycle_count += self.branch_penalty;
+            }
+            self.branch_history_table[entry] = taken;
+        }
+
+        if let Some(addr) = load {
+            self.cycle_count += if self.cache_state.access(addr) { self.l1_latency } else { self.dram_latency };
+        }
+    }
+
+    /// Instructions per cycle so far, or 0 before anything has executed.
+    pub fn ipc(&self) -> f64 {
+        if self.cycle_count == 0 {
+            return 0.0;
+        }
+
+        self.instructions as f64 / self.cycle_count as f64
+    }
+}
+
+impl Default for CycleAccurateModel {
+    fn default() -> CycleAccurateModel {
+        CycleAccurateModel::new()
+    }
+}
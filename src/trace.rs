@@ -0,0 +1,373 @@
+use crate::disasm;
+use crate::instructions::Instruction;
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Once;
+
+// Size of one record of a binary trace: pc, raw instruction, rd, rd value.
+pub const TRACE_RECORD_LEN: usize = 21;
+// Default capacity of an `ExecutionTrace`.
+pub const EXECUTION_TRACE_LEN: usize = 1024;
+// How many instructions `SoftThread::print_trace_on_panic` keeps.
+pub const PANIC_TRACE_LEN: usize = 16;
+
+thread_local! {
+    // The last instructions executed on this thread by harts with
+    // `print_trace_on_panic` enabled. Thread local so the panic hook can
+    // find them without a reference to the hart.
+    static PANIC_TRACE: RefCell<RingBuffer<TraceEntry, PANIC_TRACE_LEN>> = RefCell::new(RingBuffer::new());
+}
+
+/// A fixed capacity circular buffer. Once `N` entries have been pushed,
+/// each push overwrites the oldest entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RingBuffer<T, const N: usize> {
+    ring: DynRingBuffer<T>,
+}
+
+impl<T, const N: usize> RingBuffer<T, N> {
+    pub fn new() -> RingBuffer<T, N> {
+        RingBuffer { ring: DynRingBuffer::new(N) }
+    }
+
+    pub fn push(&mut self, val: T) {
+        self.ring.push(val);
+    }
+
+    /// Entries from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.ring.iter()
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.ring.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn clear(&mut self) {
+        self.ring.clear();
+    }
+}
+
+impl<T, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> RingBuffer<T, N> {
+        RingBuffer::new()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for RingBuffer<T, N> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ring.into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a RingBuffer<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::iter::Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&self.ring).into_iter()
+    }
+}
+
+/// A `RingBuffer` whose capacity is chosen at run time.
+#[derive(Clone, Debug)]
+pub struct DynRingBuffer<T> {
+    buf: Vec<T>,
+    head: usize,
+    capacity: usize,
+}
+
+impl<T> DynRingBuffer<T> {
+    pub fn new(capacity: usize) -> DynRingBuffer<T> {
+        DynRingBuffer { buf: Vec::with_capacity(capacity), head: 0, capacity }
+    }
+
+    pub fn push(&mut self, val: T) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.buf.len() < self.capacity {
+            self.buf.push(val);
+        } else {
+            self.buf[self.head] = val;
+            self.head = (self.head + 1) % self.capacity;
+        }
+    }
+
+    /// Entries from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.into_iter()
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.iter().last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.head = 0;
+    }
+}
+
+impl<T: PartialEq> PartialEq for DynRingBuffer<T> {
+    fn eq(&self, other: &DynRingBuffer<T>) -> bool {
+        self.capacity == other.capacity && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> IntoIterator for DynRingBuffer<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.buf.rotate_left(self.head);
+        self.buf.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DynRingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = std::iter::Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.buf[self.head..].iter().chain(self.buf[..self.head].iter())
+    }
+}
+
+/// One executed instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TraceEntry {
+    pub pc: u64,
+    pub raw: u32,
+    pub decoded: Instruction,
+}
+
+/// The state of a hart just before it executed the instruction at `pc`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExecutionEvent {
+    pub pc: u64,
+    pub raw_inst: u32,
+    pub registers_before: [u64; 33],
+}
+
+/// The last instructions executed, oldest first, with the registers each
+/// one started from.
+pub type ExecutionTrace = DynRingBuffer<ExecutionEvent>;
+
+impl Default for ExecutionTrace {
+    fn default() -> ExecutionTrace {
+        ExecutionTrace::new(EXECUTION_TRACE_LEN)
+    }
+}
+
+/// Somewhere to record executed instructions. Lets a `SoftThread` hold a
+/// ring of any capacity.
+pub trait TraceHook: Debug {
+    fn record(&mut self, entry: TraceEntry);
+    fn entries(&self) -> Box<dyn Iterator<Item = &TraceEntry> + '_>;
+
+    /// Called once the recorded instruction has executed with the integer
+    /// register it changed, or 0 if it changed none, and its new value.
+    fn retire(&mut self, _rd: u8, _value: u64) {}
+}
+
+impl<const N: usize> TraceHook for RingBuffer<TraceEntry, N> {
+    fn record(&mut self, entry: TraceEntry) {
+        self.push(entry);
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = &TraceEntry> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+pub fn record_panic_trace(entry: TraceEntry) {
+    PANIC_TRACE.with(|trace| trace.borrow_mut().push(entry));
+}
+
+pub fn clear_panic_trace() {
+    PANIC_TRACE.with(|trace| trace.borrow_mut().clear());
+}
+
+/// The instructions recorded for `print_trace_on_panic` on this thread,
+/// disassembled one per line from oldest to newest, or an empty string if
+/// there are none.
+pub fn panic_trace() -> String {
+    let Ok(Some(lines)) = PANIC_TRACE.try_with(|trace| {
+        let trace = trace.try_borrow().ok()?;
+        let lines: Vec<String> = trace.iter()
+            .map(|entry| format!("  {:#010x}: {}\n", entry.pc, disasm::disassemble(&entry.decoded, entry.pc)))
+            .collect();
+        Some(lines)
+    }) else {
+        return String::new();
+    };
+
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!("last {} instructions:\n{}", lines.len(), lines.concat())
+}
+
+/// Chain a panic hook that prints `panic_trace` for the panicking thread
+/// before running the hook it replaces. Only the first call installs it.
+pub fn set_panic_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            eprint!("{}", panic_trace());
+            previous(info);
+        }));
+    });
+}
+
+/// One executed instruction of a binary trace and the register it wrote.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TraceRecord {
+    pub pc: u64,
+    pub raw_inst: u32,
+    pub rd_written: u8,
+    pub rd_value: u64,
+}
+
+impl TraceRecord {
+    pub fn to_bytes(&self) -> [u8; TRACE_RECORD_LEN] {
+        let mut bytes = [0u8; TRACE_RECORD_LEN];
+        bytes[0..8].copy_from_slice(&self.pc.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.raw_inst.to_le_bytes());
+        bytes[12] = self.rd_written;
+        bytes[13..21].copy_from_slice(&self.rd_value.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; TRACE_RECORD_LEN]) -> TraceRecord {
+        TraceRecord {
+            pc: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            raw_inst: u32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            rd_written: bytes[12],
+            rd_value: u64::from_le_bytes(bytes[13..21].try_into().unwrap()),
+        }
+    }
+}
+
+/// A trace hook that streams fixed size little endian `TraceRecord`s to
+/// a file. The first write error stops the trace and is returned by
+/// `flush_and_close`.
+#[derive(Debug)]
+pub struct BinaryTraceLogger {
+    out: BufWriter<File>,
+    pending: Option<TraceRecord>,
+    error: Option<io::Error>,
+}
+
+impl BinaryTraceLogger {
+    pub fn open(path: &Path) -> io::Result<BinaryTraceLogger> {
+        Ok(BinaryTraceLogger { out: BufWriter::new(File::create(path)?), pending: None, error: None })
+    }
+
+    pub fn write_record(&mut self, record: &TraceRecord) {
+        if self.error.is_none() {
+            if let Err(e) = self.out.write_all(&record.to_bytes()) {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    pub fn flush_and_close(mut self) -> io::Result<()> {
+        if let Some(record) = self.pending.take() {
+            self.write_record(&record);
+        }
+
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => self.out.flush(),
+        }
+    }
+}
+
+impl Drop for BinaryTraceLogger {
+    fn drop(&mut self) {
+        if let Some(record) = self.pending.take() {
+            self.write_record(&record);
+        }
+        let _ = self.out.flush();
+    }
+}
+
+impl TraceHook for BinaryTraceLogger {
+    // The record is held back until the instruction retires, unless it
+    // never does because it raised an exception.
+    fn record(&mut self, entry: TraceEntry) {
+        if let Some(record) = self.pending.take() {
+            self.write_record(&record);
+        }
+        self.pending = Some(TraceRecord { pc: entry.pc, raw_inst: entry.raw, ..TraceRecord::default() });
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = &TraceEntry> + '_> {
+        Box::new(std::iter::empty())
+    }
+
+    fn retire(&mut self, rd: u8, value: u64) {
+        if let Some(mut record) = self.pending.take() {
+            record.rd_written = rd;
+            record.rd_value = value;
+            self.write_record(&record);
+        }
+    }
+}
+
+/// Reads back the records of a trace written by `BinaryTraceLogger`,
+/// stopping at the end of the file or the first read error.
+#[derive(Debug)]
+pub struct BinaryTraceReader {
+    input: BufReader<File>,
+}
+
+impl BinaryTraceReader {
+    pub fn open(path: &Path) -> io::Result<BinaryTraceReader> {
+        Ok(BinaryTraceReader { input: BufReader::new(File::open(path)?) })
+    }
+}
+
+impl Iterator for BinaryTraceReader {
+    type Item = TraceRecord;
+
+    fn next(&mut self) -> Option<TraceRecord> {
+        let mut bytes = [0u8; TRACE_RECORD_LEN];
+        self.input.read_exact(&mut bytes).ok()?;
+        Some(TraceRecord::from_bytes(&bytes))
+    }
+}
@@ -0,0 +1,33 @@
+use crate::instructions::Instruction;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationKind {
+    JumpOutOfRange,
+    StoreToReadOnly,
+    ReservedCsr,
+    Unreachable,
+    StackUnderflow,
+}
+
+/// A likely problem `SoftThread::validate_program` found in the loaded
+/// code. The checks are heuristics, so a warning is not necessarily a bug.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationWarning {
+    pub pc: u64,
+    pub instruction: Instruction,
+    pub kind: ValidationKind,
+    pub description: String,
+}
+
+impl ValidationWarning {
+    pub fn new(pc: u64, instruction: Instruction, kind: ValidationKind, description: String) -> ValidationWarning {
+        ValidationWarning { pc, instruction, kind, description }
+    }
+}
+
+impl Display for ValidationWarning {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:#x}: {}", self.pc, self.description)
+    }
+}
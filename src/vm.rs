@@ -3,8 +3,15 @@ use crate::soft::SoftThread;
 use crate::extensions::{Extension};
 use crate::exceptions::Exception;
 use crate::memory::{Memory, Dram};
-use crate::register::RegisterValue;
+use crate::register::{Register, RegisterValue};
 use crate::state::StateObject;
+use crate::csr::CSR_MHARTID;
+use crate::instructions::Instruction;
+use crate::memory_model::{self, MemoryModel, MemoryOrdering, StoreBuffer, FENCE_W};
+use crate::interrupt::InterruptCause;
+use crate::peripheral::{Clint, CLINT_BASE, CLINT_MSIP};
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::fmt::{Display, Formatter};
 use std::error::Error;
 use std::hash::Hash;
@@ -15,6 +22,8 @@ use std::io::Read;
 pub const STACKSIZE: u64 = 4096u64;
 pub const INST_LEN: u64 = 4u64;
 pub type CpuResult = Result<(), Exception>;
+/// The index of a hart in `Cpu::cores`, which is also its `mhartid`.
+pub type HartId = usize;
 
 #[derive(Debug)]
 pub struct ProgramBuffer {
@@ -24,11 +33,21 @@ pub struct ProgramBuffer {
 
 #[derive(Debug)]
 pub struct Cpu {
-    pub core: SoftThread<u64, f64, Dram>,
+    pub cores: Vec<SoftThread<u64, f64, Dram>>,
+    /// How stores propagate between harts. `None` keeps each hart's DRAM
+    /// private.
+    pub memory_model: Option<MemoryModel>,
+    buffers: Vec<StoreBuffer>,
+    /// Where the shared CLINT is mapped. Stores by any hart to the `msip`
+    /// register of a hart, at this base plus 4 times its id, raise or
+    /// clear that hart's machine software interrupt. Harts with a `Clint`
+    /// attached store to it instead.
+    pub clint_base: u64,
+    /// The CLINT every hart shares, once `attach_clint` has mapped it.
+    pub clint: Option<Rc<RefCell<Clint>>>,
     ext: Extension,
     pb: ProgramBuffer,
     //TODO: Add queue so that the VM can run programs sequentially.
-    //TODO: Replace core with multi core structure
     //TODO: Add task scheduler to communicate tasks to multiple cores from queue.
 }
 
@@ -37,12 +56,218 @@ impl Cpu {
         Cpu::default()    
     }
 
+    /// Create a `Cpu` with `harts` hardware threads. Each hart's index is
+    /// written to its `mhartid` CSR. Hart 0 is the boot hart that programs
+    /// are loaded into.
+    pub fn with_harts(harts: usize) -> Cpu {
+        let cores = (0..harts).map(|id| {
+            let mut soft = SoftThread::<u64, f64, Dram>::default();
+            soft.write_csr_raw(CSR_MHARTID, id as u64);
+            soft
+        }).collect();
+
+        Cpu {
+            cores,
+            memory_model: None,
+            buffers: (0..harts).map(|_| StoreBuffer::new()).collect(),
+            clint_base: CLINT_BASE,
+            clint: None,
+            ext: Extension::G,
+            pb: ProgramBuffer::default()
+        }
+    }
+
+    /// The boot hart.
+    pub fn core(&mut self) -> &mut SoftThread<u64, f64, Dram> {
+        &mut self.cores[0]
+    }
+
+    /// Load the same image at `base` into every hart and point each hart's
+    /// pc at it. Harts have private DRAM, so the image is copied to each one,
+    /// and each hart's stack pointer starts at the top of its own DRAM.
+    pub fn load_program_all_harts(&mut self, code: &[u8], base: u64) -> CpuResult {
+        for core in self.cores.iter_mut() {
+            core.load_image(code, base)?;
+            core.registers[Register::X2 as usize] = core.bus.mem.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Make stores of each hart visible to the others under `model`. The
+    /// harts' DRAM is kept in step by copying each store to the other
+    /// harts as it drains from the storing hart's buffer.
+    pub fn set_memory_model(&mut self, model: MemoryModel) {
+        self.memory_model = Some(model);
+    }
+
+    /// Map one `Clint` that every hart shares, as `SoftThread::attach_clint`
+    /// does for a single hart. Its `mtime` advances with each instruction
+    /// of any hart.
+    pub fn attach_clint(&mut self, ticks_per_instruction: u64) {
+        let clint = Rc::new(RefCell::new(Clint::new(ticks_per_instruction)));
+        for core in self.cores.iter_mut() {
+            core.mmio.attach(Rc::clone(&clint));
+        }
+        self.clint = Some(clint);
+    }
+
+    /// Raise `cause` on every hart except `from`, the hart sending it.
+    pub fn broadcast_interrupt(&mut self, from: HartId, cause: InterruptCause) {
+        for hart in 0..self.cores.len() {
+            if hart != from {
+                self.send_ipi(from, hart, cause);
+            }
+        }
+    }
+
+    /// Send an inter-processor interrupt from hart `from` to hart `to`,
+    /// setting only `cause`'s bit in the destination's `mip`. Usually
+    /// `cause` is `MachineSoftware`, which also sets the destination's
+    /// `msip` once `attach_clint` has mapped a CLINT, so the interrupt
+    /// stays pending until the hart clears it there.
+    pub fn send_ipi(&mut self, from: HartId, to: HartId, cause: InterruptCause) {
+        if let Some(core) = self.cores.get_mut(to) {
+            if let (Some(clint), InterruptCause::MachineSoftware) = (self.clint.as_ref(), cause) {
+                if let Some(msip) = clint.borrow_mut().msip.get_mut(to) {
+                    *msip = 1;
+                }
+            }
+            core.inject_interrupt(cause);
+        }
+    }
+
+    /// Step every hart in turn, one instruction at a time, until each has
+    /// run off the end of its loaded code.
     pub fn run(&mut self) -> CpuResult {
-        while self.core.pc < (self.core.program.len() as u64) {
-            self.core.execute();
+        while self.cores.iter().any(|core| core.in_program()) {
+            for hart in 0..self.cores.len() {
+                if self.cores[hart].in_program() {
+                    self.step_hart(hart)?;
+                    if !self.cores[hart].in_program() {
+                        self.drain(hart, usize::MAX);
+                    }
+                }
+            }
         }
         Ok(())
     }
+
+    /// Step every hart until all of them have reached `barrier_addr`,
+    /// holding back the ones that get there first, then make every buffered
+    /// store visible to all harts, like `pthread_barrier_wait`. The harts
+    /// are left at `barrier_addr` for `run` to continue from. Fails with
+    /// `Exception::BarrierNotReached` if a hart runs off its code or halts
+    /// before it gets there.
+    pub fn barrier_sync(&mut self, barrier_addr: u64) -> CpuResult {
+        let mut arrived = 0;
+        while arrived < self.cores.len() {
+            arrived = 0;
+            for hart in 0..self.cores.len() {
+                let core = &self.cores[hart];
+                if core.pc == barrier_addr {
+                    arrived += 1;
+                } else if !core.in_program() || core.is_halted() {
+                    return Err(Exception::BarrierNotReached);
+                } else {
+                    self.step_hart(hart)?;
+                }
+            }
+        }
+
+        for hart in 0..self.cores.len() {
+            self.drain(hart, usize::MAX);
+        }
+        Ok(())
+    }
+
+    fn step_hart(&mut self, hart: usize) -> CpuResult {
+        // A hart about to take an interrupt does not execute its next
+        // instruction this step.
+        if self.cores[hart].pending_interrupt().is_some() {
+            return self.cores[hart].execute();
+        }
+
+        let instruction = self.cores[hart].peek().expand();
+        if let Some((to, val)) = self.msip_write(hart, &instruction) {
+            // The store goes to the CLINT instead of the hart's DRAM.
+            if val & 1 != 0 {
+                self.send_ipi(hart, to, InterruptCause::MachineSoftware);
+            } else {
+                self.cores[to].clear_interrupt(InterruptCause::MachineSoftware);
+            }
+            self.cores[hart].skip();
+            return Ok(());
+        }
+
+        match self.memory_model {
+            Some(model) if self.cores.len() > 1 => self.step_ordered(hart, instruction, model),
+            _ => self.cores[hart].execute(),
+        }
+    }
+
+    // The hart whose CLINT `msip` register `instruction` stores to, if it
+    // does, and the value stored. A hart with a device mapped there, such
+    // as the `Clint` of `attach_clint`, stores to it like any other.
+    fn msip_write(&self, hart: usize, instruction: &Instruction) -> Option<(HartId, u64)> {
+        let core = &self.cores[hart];
+        let rs2 = match *instruction {
+            Instruction::Sb { rs2, .. } | Instruction::Sh { rs2, .. } |
+            Instruction::Sw { rs2, .. } | Instruction::Sd { rs2, .. } => rs2,
+            _ => return None,
+        };
+        let (addr, _) = memory_model::store_footprint(instruction, &core.registers)?;
+        if core.mmio.contains(addr) {
+            return None;
+        }
+        let offset = addr.checked_sub(self.clint_base + CLINT_MSIP as u64)?;
+        let to = (offset / 4) as usize;
+
+        (offset % 4 == 0 && to < self.cores.len()).then(|| (to, core.registers[rs2 as usize]))
+    }
+
+    fn step_ordered(&mut self, hart: usize, instruction: Instruction, model: MemoryModel) -> CpuResult {
+        let core = &self.cores[hart];
+        // A store-conditional without a reservation writes nothing.
+        let store = memory_model::store_footprint(&instruction, &core.registers).filter(|(addr, _)| {
+            !matches!(instruction, Instruction::ScW { .. } | Instruction::ScD { .. }) || core.res.contains(addr)
+        });
+        self.cores[hart].execute()?;
+        self.buffers[hart].tick();
+
+        if let Some((addr, len)) = store {
+            let mut bytes = vec![0u8; len];
+            if self.cores[hart].store_raw(addr, &mut bytes).is_ok() {
+                self.buffers[hart].push(addr, bytes);
+            }
+        }
+
+        let excess = match (model.ordering, instruction) {
+            (MemoryOrdering::SequentiallyConsistent, _) => usize::MAX,
+            (_, Instruction::Fence { pred, .. }) if pred & FENCE_W != 0 => usize::MAX,
+            _ => self.buffers[hart].len().saturating_sub(model.store_buffer_depth),
+        };
+        self.drain(hart, excess);
+        while self.buffers[hart].overdue(model.store_buffer_latency) {
+            self.drain(hart, 1);
+        }
+        Ok(())
+    }
+
+    // Make up to `count` of `hart`'s buffered stores visible to the others.
+    fn drain(&mut self, hart: usize, count: usize) {
+        let ordering = self.memory_model.map(|model| model.ordering).unwrap_or_default();
+        for _ in 0..count {
+            let Some((addr, bytes)) = self.buffers[hart].pop(ordering) else {
+                break;
+            };
+
+            for (idx, core) in self.cores.iter_mut().enumerate() {
+                if idx != hart {
+                    let _ = core.load_raw(addr, &bytes);
+                }
+            }
+        }
+    }
     
     pub fn load_from_file(&mut self, path: String) -> CpuResult {
         let mut f = File::open(&path).expect("file not found");
@@ -56,7 +281,7 @@ impl Cpu {
         } else {
             let mut buffer = vec![0; meta.len() as usize];
             f.read(&mut buffer).expect("buffer overflow");
-            self.core.load_program(buffer)?;
+            self.core().load_program(buffer)?;
         }
         Ok(())
     }
@@ -65,13 +290,13 @@ impl Cpu {
         if let Ok(program) = state.get_code(&addr) {
             let program: Vec<u8> = program.into();
             if program.len() > ((STACKSIZE * INST_LEN) as usize) {
-                self.core.load_program(program[..(STACKSIZE * INST_LEN) as usize].into());
+                self.core().load_program(program[..(STACKSIZE * INST_LEN) as usize].into());
                 self.pb.buf = program[((STACKSIZE * INST_LEN) as usize)..].to_vec();
                 self.pb.cursor = (STACKSIZE * INST_LEN) as usize;
                 return Err(Exception::LoadFromBuffer);
             }
 
-            self.core.load_program(program.into())?;
+            self.core().load_program(program.into())?;
         }
 
         return Err(Exception::InvalidAddr)
@@ -90,12 +315,7 @@ impl Default for ProgramBuffer {
 
 impl Default for Cpu {
     fn default() -> Cpu {
-        let mut softs = SoftThread::<u64, f64, Dram>::default(); 
-        Cpu {
-            core: softs,
-            ext: Extension::G,
-            pb: ProgramBuffer::default()
-        }
+        Cpu::with_harts(1)
     }
 }
 
@@ -0,0 +1,78 @@
+use crate::register::Register;
+use std::fmt::{Debug, Formatter};
+
+pub type WatchCallback = Box<dyn FnMut(u64, u64)>;
+pub type FloatWatchCallback = Box<dyn FnMut(f64, f64)>;
+
+/// Callbacks fired with `(old, new)` when an instruction changes the value
+/// of a watched register. There is at most one callback per register.
+pub struct RegisterWatches {
+    x: [Option<WatchCallback>; 33],
+    f: [Option<FloatWatchCallback>; 33],
+    count: usize,
+}
+
+impl RegisterWatches {
+    pub fn new() -> RegisterWatches {
+        RegisterWatches { x: std::array::from_fn(|_| None), f: std::array::from_fn(|_| None), count: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn watch(&mut self, reg: Register, callback: WatchCallback) {
+        Self::set(&mut self.x[reg as usize], Some(callback), &mut self.count);
+    }
+
+    pub fn unwatch(&mut self, reg: Register) {
+        Self::set(&mut self.x[reg as usize], None, &mut self.count);
+    }
+
+    pub fn watch_freg(&mut self, reg: Register, callback: FloatWatchCallback) {
+        Self::set(&mut self.f[reg as usize], Some(callback), &mut self.count);
+    }
+
+    pub fn unwatch_freg(&mut self, reg: Register) {
+        Self::set(&mut self.f[reg as usize], None, &mut self.count);
+    }
+
+    /// Fire the callbacks of the registers that differ between `before`
+    /// and `after`. Float registers are compared bit for bit.
+    pub fn fire(&mut self, before: (&[u64; 33], &[f64; 33]), after: (&[u64; 33], &[f64; 33])) {
+        for (idx, callback) in self.x.iter_mut().enumerate() {
+            if let Some(callback) = callback {
+                if before.0[idx] != after.0[idx] {
+                    callback(before.0[idx], after.0[idx]);
+                }
+            }
+        }
+
+        for (idx, callback) in self.f.iter_mut().enumerate() {
+            if let Some(callback) = callback {
+                if before.1[idx].to_bits() != after.1[idx].to_bits() {
+                    callback(before.1[idx], after.1[idx]);
+                }
+            }
+        }
+    }
+
+    fn set<T>(slot: &mut Option<T>, val: Option<T>, count: &mut usize) {
+        *count = *count + val.is_some() as usize - slot.is_some() as usize;
+        *slot = val;
+    }
+}
+
+impl Default for RegisterWatches {
+    fn default() -> RegisterWatches {
+        RegisterWatches::new()
+    }
+}
+
+impl Debug for RegisterWatches {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let x: Vec<usize> = (0..33).filter(|idx| self.x[*idx].is_some()).collect();
+        let fl: Vec<usize> = (0..33).filter(|idx| self.f[*idx].is_some()).collect();
+        f.debug_struct("RegisterWatches").field("x", &x).field("f", &fl).finish()
+    }
+}
@@ -0,0 +1,136 @@
+// Runs the riscv-tests ISA suite against the emulator.
+//
+// Only a small smoke test in `tests/riscv-tests` is checked in, so the
+// harness always has something to run. Point `TRECHO_RISCV_TESTS` at a
+// directory of riscv-tests ELF files (e.g. the `isa` directory of a
+// riscv-tests build) to run the full suite as well. Each test signals the
+// end of the run with an `ecall` where `a7 == 93`, passing if `a0 == 0`.
+//
+// The tests are linked at the start of DRAM, 0x80000000, while the
+// emulator's memory is indexed from 0, so each image is moved down by
+// `memory::BASE` before it is loaded. The tests only address their own
+// code and data pc-relatively, which keeps working after the move.
+use std::fs;
+use std::path::{Path, PathBuf};
+use trecho::elf::Elf;
+use trecho::exceptions::Exception;
+use trecho::memory::{Dram, BASE};
+use trecho::register::Register;
+use trecho::soft::SoftThread;
+
+const TEST_DIR_VAR: &str = "TRECHO_RISCV_TESTS";
+const CHECKED_IN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/riscv-tests");
+const TEST_SETS: [&str; 5] = ["rv64ui", "rv64um", "rv64ua", "rv64uf", "rv64ud"];
+const MAX_STEPS: u64 = 10_000_000;
+const SYS_EXIT: u64 = 93;
+
+#[derive(Debug)]
+struct RiscvTestResult {
+    name: String,
+    pass: bool,
+    fail_address: Option<u64>,
+    dump: String,
+}
+
+fn dump(soft: &SoftThread<u64, f64, Dram>) -> String {
+    let mut out = format!("pc: {:#018x}\n", soft.pc);
+    for (idx, val) in soft.registers[..32].iter().enumerate() {
+        out += &format!("x{:<2}: {:#018x}\n", idx, val);
+    }
+    out
+}
+
+// Move everything `elf` places in DRAM down by the DRAM base.
+fn relocate(elf: &mut Elf) {
+    let rebase = |addr: u64| if addr >= BASE { addr - BASE } else { addr };
+    elf.entry = rebase(elf.entry);
+    for segment in elf.segments.iter_mut() {
+        segment.vaddr = rebase(segment.vaddr);
+    }
+    elf.symbols = elf.symbols.drain().map(|(addr, name)| (rebase(addr), name)).collect();
+}
+
+fn run_test(path: &Path) -> RiscvTestResult {
+    let name = path.file_name().unwrap().to_string_lossy().into_owned();
+    let mut soft = SoftThread::<u64, f64, Dram>::default();
+    let fail = |soft: &SoftThread<u64, f64, Dram>, why: String| RiscvTestResult {
+        name: name.clone(),
+        pass: false,
+        fail_address: Some(soft.pc),
+        dump: format!("{}\n{}", why, dump(soft)),
+    };
+
+    let mut elf = match fs::read(path).map_err(|e| e.to_string()).and_then(|bytes| Elf::parse(&bytes).map_err(|e| e.to_string())) {
+        Ok(elf) => elf,
+        Err(e) => return fail(&soft, e),
+    };
+
+    relocate(&mut elf);
+    if let Err(e) = soft.load_elf(&elf) {
+        return fail(&soft, format!("load failed: {}", e));
+    }
+
+    for _ in 0..MAX_STEPS {
+        match soft.execute() {
+            Ok(()) => {},
+            Err(e @ Exception::EnvironmentCallFromUMode) |
+            Err(e @ Exception::EnvironmentCallFromSMode) |
+            Err(e @ Exception::EnvironmentCallFromMMode) => {
+                if soft.registers[Register::X17 as usize] == SYS_EXIT {
+                    let code = soft.registers[Register::X10 as usize];
+                    if code == 0 {
+                        return RiscvTestResult { name, pass: true, fail_address: None, dump: String::new() };
+                    }
+                    return fail(&soft, format!("exit code {}", code));
+                }
+                soft.take_trap(e);
+            },
+            Err(e) => return fail(&soft, format!("exception: {}", e)),
+        }
+    }
+
+    fail(&soft, format!("no exit after {} steps", MAX_STEPS))
+}
+
+fn test_binaries(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .expect("unable to read the riscv-tests directory")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            let in_set = TEST_SETS.iter().any(|set| name.starts_with(&format!("{}-p-", set)));
+            in_set && (path.extension().map_or(true, |ext| ext == "elf")) && !name.ends_with(".dump")
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn riscv_tests_isa() {
+    let mut dirs = vec![PathBuf::from(CHECKED_IN_DIR)];
+    match std::env::var_os(TEST_DIR_VAR) {
+        Some(dir) => dirs.push(PathBuf::from(dir)),
+        None => eprintln!("{} is not set, running only the checked-in ISA tests", TEST_DIR_VAR),
+    }
+
+    let mut results = vec![];
+    for dir in dirs.iter() {
+        let binaries = test_binaries(dir);
+        assert!(!binaries.is_empty(), "no riscv-tests binaries found in {}", dir.display());
+        results.extend(binaries.iter().map(|path| run_test(path)));
+    }
+
+    println!("{:<32} {:<6} fail address", "test", "result");
+    for result in results.iter() {
+        let address = result.fail_address.map_or(String::new(), |addr| format!("{:#x}", addr));
+        println!("{:<32} {:<6} {}", result.name, if result.pass { "pass" } else { "FAIL" }, address);
+    }
+
+    let failed: Vec<&RiscvTestResult> = results.iter().filter(|result| !result.pass).collect();
+    for result in failed.iter() {
+        println!("\n{}:\n{}", result.name, result.dump);
+    }
+
+    assert!(failed.is_empty(), "{} of {} ISA tests failed", failed.len(), results.len());
+}